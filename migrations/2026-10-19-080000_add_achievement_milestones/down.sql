@@ -0,0 +1,10 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX achievements_goal_early_completion_idx;
+DROP INDEX achievements_goal_milestone_idx;
+
+ALTER TABLE achievements
+    DROP COLUMN seen_at,
+    DROP COLUMN milestone,
+    DROP COLUMN kind;
+
+DROP TYPE achievement_kind;
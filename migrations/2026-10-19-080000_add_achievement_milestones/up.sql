@@ -0,0 +1,16 @@
+-- Your SQL goes here
+CREATE TYPE achievement_kind AS ENUM ('manual', 'milestone', 'early_completion');
+
+ALTER TABLE achievements
+    ADD COLUMN kind achievement_kind NOT NULL DEFAULT 'manual',
+    ADD COLUMN milestone INTEGER,
+    ADD COLUMN seen_at TIMESTAMP;
+
+-- A goal can earn each milestone and the early completion award only once.
+CREATE UNIQUE INDEX achievements_goal_milestone_idx
+    ON achievements (goal_id, milestone)
+    WHERE kind = 'milestone';
+
+CREATE UNIQUE INDEX achievements_goal_early_completion_idx
+    ON achievements (goal_id)
+    WHERE kind = 'early_completion';
@@ -0,0 +1,20 @@
+-- Your SQL goes here
+CREATE TABLE user_badges (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    badge_code VARCHAR(64) NOT NULL,
+    progress INTEGER NOT NULL,
+    earned_at TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL,
+    UNIQUE (user_id, badge_code)
+);
+
+CREATE TABLE user_streaks (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    streak_code VARCHAR(64) NOT NULL,
+    current_length INTEGER NOT NULL,
+    longest_length INTEGER NOT NULL,
+    updated_at TIMESTAMP NOT NULL,
+    UNIQUE (user_id, streak_code)
+);
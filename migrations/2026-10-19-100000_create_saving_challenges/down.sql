@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE saving_challenges;
+DROP TYPE challenge_type;
+DROP TABLE goal_contributions;
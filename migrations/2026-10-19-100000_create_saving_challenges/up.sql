@@ -0,0 +1,25 @@
+-- Your SQL goes here
+CREATE TABLE goal_contributions (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    goal_id UUID NOT NULL REFERENCES saving_goals(id) ON DELETE CASCADE,
+    amount DOUBLE PRECISION NOT NULL,
+    contributed_at TIMESTAMP NOT NULL,
+    note VARCHAR(255)
+);
+
+CREATE INDEX goal_contributions_goal_id_idx ON goal_contributions (goal_id, contributed_at);
+
+CREATE TYPE challenge_type AS ENUM ('fifty_two_week', 'no_spend_month', 'income_percentage');
+
+CREATE TABLE saving_challenges (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    challenge_type challenge_type NOT NULL,
+    goal_id UUID NOT NULL REFERENCES saving_goals(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    start_date DATE NOT NULL,
+    end_date DATE NOT NULL,
+    unit_amount DOUBLE PRECISION,
+    percentage DOUBLE PRECISION,
+    category_ids UUID[] NOT NULL DEFAULT '{}',
+    created_at TIMESTAMP NOT NULL
+);
@@ -0,0 +1,10 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE round_up_rules;
+
+DROP INDEX goal_contributions_transaction_id_idx;
+
+ALTER TABLE goal_contributions
+    DROP COLUMN transaction_id,
+    DROP COLUMN source;
+
+DROP TYPE contribution_source;
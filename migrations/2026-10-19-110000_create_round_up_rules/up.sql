@@ -0,0 +1,17 @@
+-- Your SQL goes here
+CREATE TYPE contribution_source AS ENUM ('manual', 'round_up');
+
+ALTER TABLE goal_contributions
+    ADD COLUMN source contribution_source NOT NULL DEFAULT 'manual',
+    ADD COLUMN transaction_id UUID REFERENCES transactions(id) ON DELETE SET NULL;
+
+CREATE INDEX goal_contributions_transaction_id_idx ON goal_contributions (transaction_id);
+
+CREATE TABLE round_up_rules (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    account_id UUID NOT NULL UNIQUE REFERENCES accounts(id) ON DELETE CASCADE,
+    goal_id UUID NOT NULL REFERENCES saving_goals(id) ON DELETE CASCADE,
+    round_to INTEGER NOT NULL CHECK (round_to IN (1, 5, 10)),
+    created_at TIMESTAMP NOT NULL
+);
@@ -0,0 +1,16 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE allocations;
+DROP TABLE allocation_rule_targets;
+DROP TABLE allocation_rules;
+DROP TYPE allocation_amount_type;
+DROP TYPE allocation_target_type;
+
+-- Postgres can't drop a single enum value, so rebuild the type without it.
+ALTER TABLE goal_contributions ALTER COLUMN source DROP DEFAULT;
+ALTER TABLE goal_contributions ALTER COLUMN source TYPE TEXT;
+UPDATE goal_contributions SET source = 'manual' WHERE source = 'allocation';
+DROP TYPE contribution_source;
+CREATE TYPE contribution_source AS ENUM ('manual', 'round_up');
+ALTER TABLE goal_contributions
+    ALTER COLUMN source TYPE contribution_source USING source::contribution_source;
+ALTER TABLE goal_contributions ALTER COLUMN source SET DEFAULT 'manual';
@@ -0,0 +1,38 @@
+-- Your SQL goes here
+ALTER TYPE contribution_source ADD VALUE 'allocation';
+
+CREATE TYPE allocation_target_type AS ENUM ('saving_goal', 'budget', 'account');
+CREATE TYPE allocation_amount_type AS ENUM ('fixed', 'percentage');
+
+CREATE TABLE allocation_rules (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    name VARCHAR(255) NOT NULL,
+    match_title VARCHAR(255),
+    match_account_id UUID REFERENCES accounts(id) ON DELETE CASCADE,
+    priority INTEGER NOT NULL DEFAULT 0,
+    auto_apply BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMP NOT NULL
+);
+
+CREATE TABLE allocation_rule_targets (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    rule_id UUID NOT NULL REFERENCES allocation_rules(id) ON DELETE CASCADE,
+    position INTEGER NOT NULL,
+    target_type allocation_target_type NOT NULL,
+    target_id UUID NOT NULL,
+    amount_type allocation_amount_type NOT NULL,
+    value DOUBLE PRECISION NOT NULL CHECK (value > 0)
+);
+
+CREATE TABLE allocations (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    source_transaction_id UUID NOT NULL REFERENCES transactions(id) ON DELETE CASCADE,
+    rule_id UUID REFERENCES allocation_rules(id) ON DELETE SET NULL,
+    target_type allocation_target_type NOT NULL,
+    target_id UUID NOT NULL,
+    amount DOUBLE PRECISION NOT NULL,
+    created_at TIMESTAMP NOT NULL
+);
+
+CREATE INDEX allocations_source_transaction_id_idx ON allocations (source_transaction_id);
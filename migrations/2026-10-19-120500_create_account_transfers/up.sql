@@ -0,0 +1,14 @@
+-- Money an allocation moved from the account that received the income into
+-- another account. Balances are updated when the transfer is made; these rows
+-- let balance history, running balances and cash flow see the movement.
+CREATE TABLE account_transfers (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    allocation_id UUID NOT NULL UNIQUE REFERENCES allocations(id) ON DELETE CASCADE,
+    from_account_id UUID NOT NULL REFERENCES accounts(id) ON DELETE CASCADE,
+    to_account_id UUID NOT NULL REFERENCES accounts(id) ON DELETE CASCADE,
+    amount NUMERIC(19, 4) NOT NULL,
+    transferred_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX account_transfers_from_account_id_idx ON account_transfers (from_account_id);
+CREATE INDEX account_transfers_to_account_id_idx ON account_transfers (to_account_id);
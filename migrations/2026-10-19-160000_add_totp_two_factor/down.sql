@@ -0,0 +1,7 @@
+DROP TABLE recovery_codes;
+
+ALTER TABLE sessions DROP COLUMN two_factor_verified;
+
+ALTER TABLE users DROP COLUMN totp_last_step;
+ALTER TABLE users DROP COLUMN totp_enabled;
+ALTER TABLE users DROP COLUMN totp_secret;
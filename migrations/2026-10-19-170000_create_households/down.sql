@@ -0,0 +1,12 @@
+DROP FUNCTION can_edit_record(UUID, UUID, UUID);
+DROP FUNCTION can_view_record(UUID, UUID, UUID);
+
+ALTER TABLE saving_goals DROP COLUMN household_id;
+ALTER TABLE categories DROP COLUMN household_id;
+ALTER TABLE budgets DROP COLUMN household_id;
+ALTER TABLE accounts DROP COLUMN household_id;
+
+DROP TABLE household_members;
+DROP TABLE households;
+
+DROP TYPE household_role;
@@ -0,0 +1,41 @@
+CREATE TYPE household_role AS ENUM ('owner', 'editor', 'viewer');
+
+CREATE TABLE households (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    name VARCHAR NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT current_timestamp
+);
+
+CREATE TABLE household_members (
+    household_id UUID NOT NULL REFERENCES households(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    role household_role NOT NULL,
+    joined_at TIMESTAMP NOT NULL DEFAULT current_timestamp,
+    PRIMARY KEY (household_id, user_id)
+);
+
+CREATE INDEX household_members_user_id_idx ON household_members (user_id);
+
+ALTER TABLE accounts ADD COLUMN household_id UUID REFERENCES households(id) ON DELETE SET NULL;
+ALTER TABLE budgets ADD COLUMN household_id UUID REFERENCES households(id) ON DELETE SET NULL;
+ALTER TABLE categories ADD COLUMN household_id UUID REFERENCES households(id) ON DELETE SET NULL;
+ALTER TABLE saving_goals ADD COLUMN household_id UUID REFERENCES households(id) ON DELETE SET NULL;
+
+-- A record is visible to its owner and to every member of the household it
+-- is shared with; owners and editors of that household may also change it.
+CREATE FUNCTION can_view_record(owner_id UUID, record_household_id UUID, caller_id UUID)
+RETURNS BOOLEAN AS $$
+    SELECT owner_id = caller_id OR EXISTS (
+        SELECT 1 FROM household_members m
+        WHERE m.household_id = record_household_id AND m.user_id = caller_id
+    )
+$$ LANGUAGE SQL STABLE;
+
+CREATE FUNCTION can_edit_record(owner_id UUID, record_household_id UUID, caller_id UUID)
+RETURNS BOOLEAN AS $$
+    SELECT owner_id = caller_id OR EXISTS (
+        SELECT 1 FROM household_members m
+        WHERE m.household_id = record_household_id AND m.user_id = caller_id
+          AND m.role IN ('owner', 'editor')
+    )
+$$ LANGUAGE SQL STABLE;
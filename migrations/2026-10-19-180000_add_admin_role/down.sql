@@ -0,0 +1,8 @@
+DROP TABLE impersonation_log;
+
+ALTER TABLE sessions DROP COLUMN impersonator_id;
+
+ALTER TABLE users DROP COLUMN suspended_at;
+ALTER TABLE users DROP COLUMN role;
+
+DROP TYPE user_role;
@@ -0,0 +1,25 @@
+-- The first admin is promoted directly in the database:
+--   UPDATE users SET role = 'admin' WHERE email = '...';
+CREATE TYPE user_role AS ENUM ('user', 'admin');
+
+ALTER TABLE users ADD COLUMN role user_role NOT NULL DEFAULT 'user';
+ALTER TABLE users ADD COLUMN suspended_at TIMESTAMP;
+
+-- Set on sessions an admin opened as another user.
+ALTER TABLE sessions ADD COLUMN impersonator_id UUID REFERENCES users(id) ON DELETE CASCADE;
+
+-- Every request made while impersonating. No foreign keys, so the trail
+-- outlives the users involved.
+CREATE TABLE impersonation_log (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    impersonator_id UUID NOT NULL,
+    user_id UUID NOT NULL,
+    session_id UUID NOT NULL,
+    method VARCHAR NOT NULL,
+    path VARCHAR NOT NULL,
+    status INTEGER NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT current_timestamp
+);
+
+CREATE INDEX impersonation_log_user_id_idx ON impersonation_log (user_id);
+CREATE INDEX impersonation_log_impersonator_id_idx ON impersonation_log (impersonator_id);
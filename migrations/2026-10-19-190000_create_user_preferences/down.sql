@@ -0,0 +1,3 @@
+DROP TABLE user_preferences;
+
+DROP TYPE week_day;
@@ -0,0 +1,14 @@
+CREATE TYPE week_day AS ENUM ('monday', 'tuesday', 'wednesday', 'thursday', 'friday', 'saturday', 'sunday');
+
+-- Rows are created with these defaults the first time a user's preferences
+-- are read.
+CREATE TABLE user_preferences (
+    user_id UUID PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    base_currency VARCHAR(3) NOT NULL DEFAULT 'USD',
+    locale VARCHAR NOT NULL DEFAULT 'en-US',
+    timezone VARCHAR NOT NULL DEFAULT 'UTC',
+    week_start week_day NOT NULL DEFAULT 'monday',
+    fiscal_year_start_month INTEGER NOT NULL DEFAULT 1 CHECK (fiscal_year_start_month BETWEEN 1 AND 12),
+    date_format VARCHAR NOT NULL DEFAULT '%Y-%m-%d',
+    updated_at TIMESTAMP
+);
@@ -0,0 +1,14 @@
+CREATE FUNCTION goal_owner_timezone(goal UUID) RETURNS VARCHAR AS $$
+    SELECT user_timezone((SELECT user_id FROM saving_goals WHERE id = goal))
+$$ LANGUAGE SQL STABLE;
+
+ALTER TABLE achievements
+    ALTER COLUMN date_achieved TYPE TIMESTAMP USING date_achieved AT TIME ZONE goal_owner_timezone(goal_id);
+ALTER TABLE budgets
+    ALTER COLUMN start_date TYPE TIMESTAMP USING start_date AT TIME ZONE user_timezone(user_id),
+    ALTER COLUMN end_date TYPE TIMESTAMP USING end_date AT TIME ZONE user_timezone(user_id);
+ALTER TABLE transactions
+    ALTER COLUMN date TYPE TIMESTAMP USING date AT TIME ZONE user_timezone(user_id);
+
+DROP FUNCTION goal_owner_timezone(UUID);
+DROP FUNCTION user_timezone(UUID);
@@ -0,0 +1,19 @@
+-- The timezone a user's dates are bucketed in.
+CREATE FUNCTION user_timezone(owner UUID) RETURNS VARCHAR AS $$
+    SELECT COALESCE((SELECT timezone FROM user_preferences WHERE user_id = owner), 'UTC')
+$$ LANGUAGE SQL STABLE;
+
+CREATE FUNCTION goal_owner_timezone(goal UUID) RETURNS VARCHAR AS $$
+    SELECT user_timezone((SELECT user_id FROM saving_goals WHERE id = goal))
+$$ LANGUAGE SQL STABLE;
+
+-- Existing values are wall-clock times in their owner's timezone.
+ALTER TABLE transactions
+    ALTER COLUMN date TYPE TIMESTAMPTZ USING date AT TIME ZONE user_timezone(user_id);
+ALTER TABLE budgets
+    ALTER COLUMN start_date TYPE TIMESTAMPTZ USING start_date AT TIME ZONE user_timezone(user_id),
+    ALTER COLUMN end_date TYPE TIMESTAMPTZ USING end_date AT TIME ZONE user_timezone(user_id);
+ALTER TABLE achievements
+    ALTER COLUMN date_achieved TYPE TIMESTAMPTZ USING date_achieved AT TIME ZONE goal_owner_timezone(goal_id);
+
+DROP FUNCTION goal_owner_timezone(UUID);
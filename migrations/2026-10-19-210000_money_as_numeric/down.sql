@@ -0,0 +1,15 @@
+DROP FUNCTION user_base_currency(UUID);
+
+ALTER TABLE allocations ALTER COLUMN amount TYPE DOUBLE PRECISION;
+ALTER TABLE allocation_rule_targets ALTER COLUMN value TYPE DOUBLE PRECISION;
+ALTER TABLE saving_challenges
+    ALTER COLUMN unit_amount TYPE DOUBLE PRECISION,
+    ALTER COLUMN percentage TYPE DOUBLE PRECISION;
+ALTER TABLE goal_contributions ALTER COLUMN amount TYPE DOUBLE PRECISION;
+ALTER TABLE achievements ALTER COLUMN amount_saved TYPE DOUBLE PRECISION;
+ALTER TABLE saving_goals
+    ALTER COLUMN target_amount TYPE DOUBLE PRECISION,
+    ALTER COLUMN current_amount TYPE DOUBLE PRECISION;
+ALTER TABLE budgets ALTER COLUMN amount TYPE DOUBLE PRECISION;
+ALTER TABLE transactions ALTER COLUMN amount TYPE DOUBLE PRECISION;
+ALTER TABLE accounts ALTER COLUMN balance TYPE DOUBLE PRECISION;
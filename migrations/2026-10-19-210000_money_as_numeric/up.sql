@@ -0,0 +1,21 @@
+-- Four decimals cover the minor unit of every ISO 4217 currency. Existing
+-- values are rounded to that scale on the way over.
+ALTER TABLE accounts ALTER COLUMN balance TYPE NUMERIC(19, 4);
+ALTER TABLE transactions ALTER COLUMN amount TYPE NUMERIC(19, 4);
+ALTER TABLE budgets ALTER COLUMN amount TYPE NUMERIC(19, 4);
+ALTER TABLE saving_goals
+    ALTER COLUMN target_amount TYPE NUMERIC(19, 4),
+    ALTER COLUMN current_amount TYPE NUMERIC(19, 4);
+ALTER TABLE achievements ALTER COLUMN amount_saved TYPE NUMERIC(19, 4);
+ALTER TABLE goal_contributions ALTER COLUMN amount TYPE NUMERIC(19, 4);
+ALTER TABLE saving_challenges
+    ALTER COLUMN unit_amount TYPE NUMERIC(19, 4),
+    ALTER COLUMN percentage TYPE NUMERIC(7, 4);
+-- Either a fixed amount or a percentage, depending on amount_type.
+ALTER TABLE allocation_rule_targets ALTER COLUMN value TYPE NUMERIC(19, 4);
+ALTER TABLE allocations ALTER COLUMN amount TYPE NUMERIC(19, 4);
+
+-- The currency a user's amounts are in until records carry their own.
+CREATE FUNCTION user_base_currency(owner UUID) RETURNS VARCHAR AS $$
+    SELECT COALESCE((SELECT base_currency FROM user_preferences WHERE user_id = owner), 'USD')
+$$ LANGUAGE SQL STABLE;
@@ -0,0 +1,5 @@
+ALTER TABLE transactions
+    DROP COLUMN original_currency,
+    DROP COLUMN original_amount,
+    DROP COLUMN currency;
+ALTER TABLE accounts DROP COLUMN currency;
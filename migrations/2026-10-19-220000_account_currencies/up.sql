@@ -0,0 +1,21 @@
+-- Existing accounts are taken to be in their owner's base currency.
+ALTER TABLE accounts ADD COLUMN currency VARCHAR(3);
+UPDATE accounts SET currency = user_base_currency(user_id);
+ALTER TABLE accounts
+    ALTER COLUMN currency SET NOT NULL,
+    ADD CONSTRAINT accounts_currency_code CHECK (currency ~ '^[A-Z]{3}$');
+
+-- A transaction's amount is always in its account's currency. Purchases made
+-- in another currency keep what was originally charged alongside.
+ALTER TABLE transactions
+    ADD COLUMN currency VARCHAR(3),
+    ADD COLUMN original_amount NUMERIC(19, 4),
+    ADD COLUMN original_currency VARCHAR(3);
+UPDATE transactions t SET currency = a.currency FROM accounts a WHERE a.id = t.account_id;
+ALTER TABLE transactions
+    ALTER COLUMN currency SET NOT NULL,
+    ADD CONSTRAINT transactions_currency_code CHECK (currency ~ '^[A-Z]{3}$'),
+    ADD CONSTRAINT transactions_original_amount CHECK (
+        (original_amount IS NULL) = (original_currency IS NULL)
+        AND original_currency IS DISTINCT FROM currency
+    );
@@ -0,0 +1,13 @@
+-- Daily reference rates: one unit of base_currency buys `rate` units of
+-- quote_currency. They aren't tied to a user.
+CREATE TABLE exchange_rates (
+    base_currency VARCHAR(3) NOT NULL CHECK (base_currency ~ '^[A-Z]{3}$'),
+    quote_currency VARCHAR(3) NOT NULL CHECK (quote_currency ~ '^[A-Z]{3}$'),
+    rate_date DATE NOT NULL,
+    rate NUMERIC(24, 10) NOT NULL CHECK (rate > 0),
+    imported_at TIMESTAMP NOT NULL,
+    PRIMARY KEY (base_currency, quote_currency, rate_date),
+    CHECK (base_currency <> quote_currency)
+);
+
+CREATE INDEX exchange_rates_rate_date_idx ON exchange_rates (rate_date);
@@ -0,0 +1,13 @@
+-- An account's balance as it stood on a day in its owner's timezone. Taking
+-- another snapshot on the same day replaces the earlier one.
+CREATE TABLE account_balance_snapshots (
+    account_id UUID NOT NULL REFERENCES accounts(id) ON DELETE CASCADE,
+    snapshot_date DATE NOT NULL,
+    account_type account_type NOT NULL,
+    balance NUMERIC(19, 4) NOT NULL,
+    currency VARCHAR(3) NOT NULL,
+    taken_at TIMESTAMP NOT NULL,
+    PRIMARY KEY (account_id, snapshot_date)
+);
+
+CREATE INDEX account_balance_snapshots_snapshot_date_idx ON account_balance_snapshots (snapshot_date);
@@ -0,0 +1,2 @@
+DROP TABLE recurring_transactions;
+DROP TYPE recurrence_frequency;
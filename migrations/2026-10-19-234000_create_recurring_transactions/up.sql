@@ -0,0 +1,20 @@
+CREATE TYPE recurrence_frequency AS ENUM ('daily', 'weekly', 'monthly', 'yearly');
+
+-- Income and bills that come back on a schedule: every `every` days, weeks,
+-- months or years from start_date, until end_date if there is one.
+CREATE TABLE recurring_transactions (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    account_id UUID NOT NULL REFERENCES accounts(id) ON DELETE CASCADE,
+    category_id UUID NOT NULL REFERENCES categories(id) ON DELETE CASCADE,
+    title VARCHAR(255) NOT NULL,
+    amount NUMERIC(19, 4) NOT NULL CHECK (amount > 0),
+    transaction_type transaction_type NOT NULL,
+    frequency recurrence_frequency NOT NULL,
+    every INTEGER NOT NULL DEFAULT 1 CHECK (every BETWEEN 1 AND 366),
+    start_date DATE NOT NULL,
+    end_date DATE CHECK (end_date >= start_date),
+    created_at TIMESTAMP NOT NULL
+);
+
+CREATE INDEX recurring_transactions_user_id_idx ON recurring_transactions (user_id);
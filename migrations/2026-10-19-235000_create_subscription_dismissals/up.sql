@@ -0,0 +1,9 @@
+-- Charges a user said aren't a subscription, by account and normalised payee,
+-- so the detector stops suggesting them.
+CREATE TABLE subscription_dismissals (
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    account_id UUID NOT NULL REFERENCES accounts(id) ON DELETE CASCADE,
+    payee VARCHAR(255) NOT NULL,
+    dismissed_at TIMESTAMP NOT NULL,
+    PRIMARY KEY (user_id, account_id, payee)
+);
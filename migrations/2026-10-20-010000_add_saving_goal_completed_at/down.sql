@@ -0,0 +1,3 @@
+DROP TRIGGER set_completed_at ON saving_goals;
+DROP FUNCTION set_saving_goal_completed_at();
+ALTER TABLE saving_goals DROP COLUMN completed_at;
@@ -0,0 +1,32 @@
+ALTER TABLE saving_goals ADD COLUMN completed_at TIMESTAMPTZ;
+
+-- Goals that already reached their target are dated by their first
+-- full-target or early completion achievement, or by their last update (a
+-- wall-clock time in their owner's timezone) when they have neither.
+UPDATE saving_goals g
+SET completed_at = COALESCE(
+    (
+        SELECT MIN(a.date_achieved)
+        FROM achievements a
+        WHERE a.goal_id = g.id
+            AND (a.kind = 'early_completion' OR a.milestone >= 100)
+    ),
+    g.updated_at AT TIME ZONE user_timezone(g.user_id)
+)
+WHERE g.target_amount > 0 AND g.current_amount >= g.target_amount;
+
+-- Stamps a goal when it first reaches its target and clears the stamp when it
+-- falls below it again, however its amounts were changed.
+CREATE FUNCTION set_saving_goal_completed_at() RETURNS trigger AS $$
+BEGIN
+    IF NEW.target_amount > 0 AND NEW.current_amount >= NEW.target_amount THEN
+        NEW.completed_at := COALESCE(NEW.completed_at, NOW());
+    ELSE
+        NEW.completed_at := NULL;
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER set_completed_at BEFORE INSERT OR UPDATE ON saving_goals
+    FOR EACH ROW EXECUTE PROCEDURE set_saving_goal_completed_at();
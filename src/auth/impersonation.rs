@@ -0,0 +1,63 @@
+use rocket::error;
+use rocket::fairing::AdHoc;
+use rocket::http::Status;
+use rocket::request::{FromRequest, Outcome, Request};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::operations::admin_ops::record_impersonated_request;
+
+pub const IMPERSONATOR_SESSION_COOKIE: &str = "impersonator_session_id";
+
+// Who is really behind a request made in an impersonation session. The
+// `AuthUser` guard caches it on the request once the session is resolved.
+#[derive(Debug, Clone, Copy)]
+pub struct Impersonation {
+    pub impersonator_id: Uuid,
+    pub user_id: Uuid,
+    pub session_id: Uuid,
+}
+
+pub fn current(req: &Request<'_>) -> Option<Impersonation> {
+    *req.local_cache(|| None::<Impersonation>)
+}
+
+// Guard for routes that only make sense while impersonating; it has to run
+// after `AuthUser` in the handler's argument list.
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for Impersonation {
+    type Error = ();
+
+    async fn from_request(req: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        match current(req) {
+            Some(impersonation) => Outcome::Success(impersonation),
+            None => Outcome::Failure((Status::BadRequest, ())),
+        }
+    }
+}
+
+// Writes every request made while impersonating to `impersonation_log`,
+// whatever its outcome.
+pub fn impersonation_auditor() -> AdHoc {
+    AdHoc::on_response("Impersonation auditor", |req, res| {
+        Box::pin(async move {
+            let impersonation = match current(req) {
+                Some(impersonation) => impersonation,
+                None => return,
+            };
+            if let Some(pool) = req.rocket().state::<PgPool>() {
+                let result = record_impersonated_request(
+                    pool,
+                    &impersonation,
+                    req.method().as_str(),
+                    req.uri().path().as_str(),
+                    res.status().code,
+                )
+                .await;
+                if let Err(e) = result {
+                    error!("Failed to record impersonated request: {}", e);
+                }
+            }
+        })
+    })
+}
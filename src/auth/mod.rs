@@ -0,0 +1,160 @@
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::Argon2;
+use rand_core::OsRng;
+use rocket::http::{Cookie, CookieJar, SameSite, Status};
+use rocket::request::{FromRequest, Outcome, Request};
+use sqlx::PgPool;
+use std::{env, str::FromStr};
+use uuid::Uuid;
+
+use crate::models::user::User;
+use crate::operations::admin_ops::is_admin;
+use crate::operations::api_token_ops::find_api_token_user;
+use crate::operations::session_ops::find_session_user;
+
+pub mod impersonation;
+pub mod oidc;
+pub mod tokens;
+pub mod totp;
+
+pub const SESSION_COOKIE: &str = "session_id";
+pub const MIN_PASSWORD_LENGTH: usize = 8;
+
+const DEFAULT_SESSION_TTL_HOURS: i64 = 14 * 24;
+
+// Checked against when a login names no user with a password, so the reply
+// takes as long as a wrong password does.
+pub const DUMMY_PASSWORD_HASH: &str =
+    "$argon2id$v=19$m=19456,t=2,p=1$0q4DcamNp604o86EiDZrSQ$zmvQfzrGIwObZi0pvVJQGlTf/SvZNZP9bVxDTihh1d4";
+
+pub fn hash_password(password: &str) -> Result<String, argon2::password_hash::Error> {
+    let salt = SaltString::generate(&mut OsRng);
+    let hash = Argon2::default().hash_password(password.as_bytes(), &salt)?;
+    Ok(hash.to_string())
+}
+
+pub fn verify_password(password: &str, password_hash: &str) -> bool {
+    PasswordHash::new(password_hash)
+        .map(|hash| {
+            Argon2::default()
+                .verify_password(password.as_bytes(), &hash)
+                .is_ok()
+        })
+        .unwrap_or(false)
+}
+
+pub fn session_ttl() -> chrono::Duration {
+    let hours = env::var("SESSION_TTL_HOURS")
+        .ok()
+        .and_then(|value| value.parse::<i64>().ok())
+        .filter(|hours| *hours > 0)
+        .unwrap_or(DEFAULT_SESSION_TTL_HOURS);
+    chrono::Duration::hours(hours)
+}
+
+// The cookie only carries the session id; it is encrypted and signed by
+// Rocket's secret key, and the row in `sessions` is what makes it valid.
+pub fn set_session_cookie(cookies: &CookieJar<'_>, session_id: Uuid) {
+    let cookie = Cookie::build(SESSION_COOKIE, session_id.to_string())
+        .http_only(true)
+        .same_site(SameSite::Lax)
+        .max_age(rocket::time::Duration::hours(session_ttl().num_hours()))
+        .finish();
+    cookies.add_private(cookie);
+}
+
+pub fn session_id_from_cookies(cookies: &CookieJar<'_>) -> Option<Uuid> {
+    cookies
+        .get_private(SESSION_COOKIE)
+        .and_then(|cookie| Uuid::from_str(cookie.value()).ok())
+}
+
+// Request guard for the signed-in user. Handlers take the user from here
+// instead of trusting a `user_id` sent by the client. Requests may also
+// authenticate with `Authorization: Bearer <token>`, in which case the token
+// must hold the scope the route requires.
+pub struct AuthUser(pub User);
+
+fn bearer_token<'r>(req: &'r Request<'_>) -> Option<&'r str> {
+    req.headers()
+        .get_one("Authorization")
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(str::trim)
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for AuthUser {
+    type Error = ();
+
+    async fn from_request(req: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        let pool = match req.rocket().state::<PgPool>() {
+            Some(pool) => pool,
+            None => return Outcome::Failure((Status::InternalServerError, ())),
+        };
+
+        if let Some(token) = bearer_token(req) {
+            return match find_api_token_user(pool, &tokens::hash_token(token)).await {
+                Ok(Some((user, scopes))) => {
+                    match tokens::required_scope(req.method(), req.uri().path().as_str()) {
+                        Some(scope) if scopes.contains(&scope) => Outcome::Success(AuthUser(user)),
+                        _ => Outcome::Failure((Status::Forbidden, ())),
+                    }
+                }
+                Ok(None) => Outcome::Failure((Status::Unauthorized, ())),
+                Err(_) => Outcome::Failure((Status::InternalServerError, ())),
+            };
+        }
+
+        let session_id = match session_id_from_cookies(req.cookies()) {
+            Some(session_id) => session_id,
+            None => return Outcome::Failure((Status::Unauthorized, ())),
+        };
+
+        match find_session_user(pool, session_id).await {
+            Ok(Some((user, impersonator_id))) => {
+                if let Some(impersonator_id) = impersonator_id {
+                    req.local_cache(|| {
+                        Some(impersonation::Impersonation {
+                            impersonator_id,
+                            user_id: user.id,
+                            session_id,
+                        })
+                    });
+                }
+                Outcome::Success(AuthUser(user))
+            }
+            Ok(None) => Outcome::Failure((Status::Unauthorized, ())),
+            Err(_) => Outcome::Failure((Status::InternalServerError, ())),
+        }
+    }
+}
+
+// Request guard for admin-only routes. Sessions an admin opened as another
+// user never pass, so impersonation can't be used to reach these routes.
+pub struct AdminUser(pub User);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for AdminUser {
+    type Error = ();
+
+    async fn from_request(req: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        let user = match req.guard::<AuthUser>().await {
+            Outcome::Success(AuthUser(user)) => user,
+            Outcome::Failure(failure) => return Outcome::Failure(failure),
+            Outcome::Forward(forward) => return Outcome::Forward(forward),
+        };
+        if impersonation::current(req).is_some() {
+            return Outcome::Failure((Status::Forbidden, ()));
+        }
+        let pool = match req.rocket().state::<PgPool>() {
+            Some(pool) => pool,
+            None => return Outcome::Failure((Status::InternalServerError, ())),
+        };
+
+        match is_admin(pool, user.id).await {
+            Ok(true) => Outcome::Success(AdminUser(user)),
+            Ok(false) => Outcome::Failure((Status::Forbidden, ())),
+            Err(_) => Outcome::Failure((Status::InternalServerError, ())),
+        }
+    }
+}
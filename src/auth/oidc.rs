@@ -0,0 +1,250 @@
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use jsonwebtoken::jwk::JwkSet;
+use jsonwebtoken::{decode, decode_header, Algorithm, DecodingKey, Validation};
+use rand_core::{OsRng, RngCore};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{env, time::Duration};
+
+pub const OIDC_FLOW_COOKIE: &str = "oidc_flow";
+
+const DEFAULT_SCOPES: &str = "openid email profile";
+const HTTP_TIMEOUT_SECS: u64 = 10;
+const ACCEPTED_ALGORITHMS: &[Algorithm] = &[
+    Algorithm::RS256,
+    Algorithm::RS384,
+    Algorithm::RS512,
+    Algorithm::PS256,
+    Algorithm::PS384,
+    Algorithm::PS512,
+    Algorithm::ES256,
+    Algorithm::ES384,
+];
+
+#[derive(Debug)]
+pub enum OidcError {
+    // The identity provider could not be reached or answered unexpectedly.
+    Provider(String),
+    // The provider answered, but what it returned cannot be trusted.
+    InvalidToken(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct OidcConfig {
+    pub issuer: String,
+    pub client_id: String,
+    pub client_secret: Option<String>,
+    pub redirect_uri: String,
+    pub scopes: String,
+}
+
+impl OidcConfig {
+    // OIDC login is only enabled when the issuer, client id and redirect URI
+    // are all set; the client secret is optional for public clients.
+    pub fn from_env() -> Option<Self> {
+        Some(OidcConfig {
+            issuer: env::var("OIDC_ISSUER").ok()?,
+            client_id: env::var("OIDC_CLIENT_ID").ok()?,
+            client_secret: env::var("OIDC_CLIENT_SECRET").ok(),
+            redirect_uri: env::var("OIDC_REDIRECT_URI").ok()?,
+            scopes: env::var("OIDC_SCOPES").unwrap_or_else(|_| DEFAULT_SCOPES.to_string()),
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ProviderMetadata {
+    pub issuer: String,
+    pub authorization_endpoint: String,
+    pub token_endpoint: String,
+    pub jwks_uri: String,
+}
+
+// What we need to remember between redirecting to the provider and handling
+// its callback. Kept in a private cookie so no server-side state is needed.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AuthorizationFlow {
+    pub state: String,
+    pub nonce: String,
+    pub code_verifier: String,
+}
+
+impl AuthorizationFlow {
+    pub fn new() -> Self {
+        AuthorizationFlow {
+            state: random_url_safe(),
+            nonce: random_url_safe(),
+            code_verifier: random_url_safe(),
+        }
+    }
+}
+
+impl Default for AuthorizationFlow {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct IdTokenClaims {
+    pub iss: String,
+    pub sub: String,
+    pub nonce: Option<String>,
+    pub email: Option<String>,
+    pub email_verified: Option<bool>,
+    pub preferred_username: Option<String>,
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    id_token: String,
+}
+
+fn random_url_safe() -> String {
+    let mut bytes = [0u8; 32];
+    OsRng.fill_bytes(&mut bytes);
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+// PKCE S256 challenge (RFC 7636, section 4.2).
+pub fn code_challenge(code_verifier: &str) -> String {
+    URL_SAFE_NO_PAD.encode(Sha256::digest(code_verifier.as_bytes()))
+}
+
+fn http_client() -> Result<reqwest::Client, OidcError> {
+    reqwest::Client::builder()
+        .timeout(Duration::from_secs(HTTP_TIMEOUT_SECS))
+        .build()
+        .map_err(|e| OidcError::Provider(e.to_string()))
+}
+
+async fn get_json<T: for<'de> Deserialize<'de>>(url: &str) -> Result<T, OidcError> {
+    http_client()?
+        .get(url)
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(|e| OidcError::Provider(e.to_string()))?
+        .json::<T>()
+        .await
+        .map_err(|e| OidcError::Provider(e.to_string()))
+}
+
+pub async fn discover(config: &OidcConfig) -> Result<ProviderMetadata, OidcError> {
+    let url = format!(
+        "{}/.well-known/openid-configuration",
+        config.issuer.trim_end_matches('/')
+    );
+    let metadata = get_json::<ProviderMetadata>(&url).await?;
+    if metadata.issuer != config.issuer {
+        return Err(OidcError::Provider(format!(
+            "Discovery document is for issuer {}",
+            metadata.issuer
+        )));
+    }
+
+    Ok(metadata)
+}
+
+pub fn authorization_url(
+    metadata: &ProviderMetadata,
+    config: &OidcConfig,
+    flow: &AuthorizationFlow,
+) -> Result<String, OidcError> {
+    let challenge = code_challenge(&flow.code_verifier);
+    let url = reqwest::Url::parse_with_params(
+        &metadata.authorization_endpoint,
+        &[
+            ("response_type", "code"),
+            ("client_id", config.client_id.as_str()),
+            ("redirect_uri", config.redirect_uri.as_str()),
+            ("scope", config.scopes.as_str()),
+            ("state", flow.state.as_str()),
+            ("nonce", flow.nonce.as_str()),
+            ("code_challenge", challenge.as_str()),
+            ("code_challenge_method", "S256"),
+        ],
+    )
+    .map_err(|e| OidcError::Provider(e.to_string()))?;
+
+    Ok(url.to_string())
+}
+
+pub async fn exchange_code(
+    metadata: &ProviderMetadata,
+    config: &OidcConfig,
+    code: &str,
+    code_verifier: &str,
+) -> Result<String, OidcError> {
+    let mut form = vec![
+        ("grant_type", "authorization_code"),
+        ("code", code),
+        ("redirect_uri", config.redirect_uri.as_str()),
+        ("client_id", config.client_id.as_str()),
+        ("code_verifier", code_verifier),
+    ];
+    if let Some(client_secret) = &config.client_secret {
+        form.push(("client_secret", client_secret.as_str()));
+    }
+
+    let response = http_client()?
+        .post(&metadata.token_endpoint)
+        .form(&form)
+        .send()
+        .await
+        .map_err(|e| OidcError::Provider(e.to_string()))?;
+    if !response.status().is_success() {
+        return Err(OidcError::InvalidToken(format!(
+            "Token endpoint answered {}",
+            response.status()
+        )));
+    }
+    let token_response = response
+        .json::<TokenResponse>()
+        .await
+        .map_err(|e| OidcError::Provider(e.to_string()))?;
+
+    Ok(token_response.id_token)
+}
+
+// Checks the ID token's signature against the provider's published keys, then
+// its issuer, audience, expiry and the nonce we sent with the request.
+pub async fn verify_id_token(
+    metadata: &ProviderMetadata,
+    config: &OidcConfig,
+    id_token: &str,
+    nonce: &str,
+) -> Result<IdTokenClaims, OidcError> {
+    let header = decode_header(id_token).map_err(|e| OidcError::InvalidToken(e.to_string()))?;
+    if !ACCEPTED_ALGORITHMS.contains(&header.alg) {
+        return Err(OidcError::InvalidToken(format!(
+            "Unsupported signing algorithm {:?}",
+            header.alg
+        )));
+    }
+
+    let jwks = get_json::<JwkSet>(&metadata.jwks_uri).await?;
+    let jwk = match &header.kid {
+        Some(kid) => jwks.find(kid),
+        None if jwks.keys.len() == 1 => jwks.keys.first(),
+        None => None,
+    }
+    .ok_or_else(|| OidcError::InvalidToken("No matching signing key".to_string()))?;
+    let key = DecodingKey::from_jwk(jwk).map_err(|e| OidcError::InvalidToken(e.to_string()))?;
+
+    let mut validation = Validation::new(header.alg);
+    validation.set_issuer(&[&config.issuer]);
+    validation.set_audience(&[&config.client_id]);
+    validation.set_required_spec_claims(&["exp", "iss", "aud", "sub"]);
+
+    let claims = decode::<IdTokenClaims>(id_token, &key, &validation)
+        .map_err(|e| OidcError::InvalidToken(e.to_string()))?
+        .claims;
+    if claims.nonce.as_deref() != Some(nonce) {
+        return Err(OidcError::InvalidToken("Nonce mismatch".to_string()));
+    }
+
+    Ok(claims)
+}
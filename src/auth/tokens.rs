@@ -0,0 +1,59 @@
+use rand_core::{OsRng, RngCore};
+use rocket::http::Method;
+use sha2::{Digest, Sha256};
+
+pub const TOKEN_PREFIX: &str = "pft_";
+pub const TOKEN_SCOPES: &[&str] = &[
+    "accounts:read",
+    "accounts:write",
+    "budgets:read",
+    "budgets:write",
+    "categories:read",
+    "categories:write",
+    "saving_goals:read",
+    "saving_goals:write",
+    "transactions:read",
+    "transactions:write",
+    "reports:read",
+];
+
+const TOKEN_BYTES: usize = 32;
+const DISPLAYED_PREFIX_LENGTH: usize = 12;
+
+pub fn generate_token() -> String {
+    let mut bytes = [0u8; TOKEN_BYTES];
+    OsRng.fill_bytes(&mut bytes);
+    format!("{}{}", TOKEN_PREFIX, hex::encode(bytes))
+}
+
+// Tokens carry 256 random bits, so a fast digest is enough and keeps the
+// per-request lookup cheap; Argon2 is reserved for user-chosen passwords.
+pub fn hash_token(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
+
+pub fn displayed_prefix(token: &str) -> String {
+    token.chars().take(DISPLAYED_PREFIX_LENGTH).collect()
+}
+
+pub fn is_known_scope(scope: &str) -> bool {
+    TOKEN_SCOPES.contains(&scope)
+}
+
+// The scope a token needs for a request: the first path segment names the
+// resource and the method decides between read and write. Paths outside the
+// scoped resources (token management, auth, users) get `None` and are never
+// reachable with a token.
+pub fn required_scope(method: Method, path: &str) -> Option<String> {
+    let resource = path.trim_start_matches('/').split('/').next()?;
+    let access = match method {
+        Method::Get | Method::Head => "read",
+        _ => "write",
+    };
+    let scope = format!("{}:{}", resource, access);
+    if is_known_scope(&scope) {
+        Some(scope)
+    } else {
+        None
+    }
+}
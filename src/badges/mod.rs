@@ -0,0 +1,254 @@
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc, Weekday};
+use chrono_tz::Tz;
+use std::collections::HashMap;
+
+use crate::{
+    calendar::{local_date, week_start},
+    enums::custom_enums::TransactionType,
+    models::{budget::Budget, saving_goals::SavingGoal, transactions::Transaction},
+    money::Money,
+};
+
+pub const WEEKLY_SAVING_STREAK: &str = "weekly_saving";
+pub const MONTHLY_BUDGET_STREAK: &str = "monthly_under_budget";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BadgeMetric {
+    NoSpendWeekends,
+    MonthsUnderBudget,
+    WeeksSaved,
+    CompletedGoals,
+}
+
+#[derive(Debug)]
+pub struct BadgeDefinition {
+    pub code: &'static str,
+    pub name: &'static str,
+    pub description: &'static str,
+    pub metric: BadgeMetric,
+    pub target: i32,
+}
+
+// Every badge a user can earn. Adding a badge only needs a new entry here as
+// long as it is measured by one of the existing metrics.
+pub const BADGES: &[BadgeDefinition] = &[
+    BadgeDefinition {
+        code: "no_spend_weekend",
+        name: "No-spend weekend",
+        description: "Get through a whole weekend without a single expense.",
+        metric: BadgeMetric::NoSpendWeekends,
+        target: 1,
+    },
+    BadgeDefinition {
+        code: "three_months_under_budget",
+        name: "3 months under budget",
+        description: "Stay within every budget for three months in a row.",
+        metric: BadgeMetric::MonthsUnderBudget,
+        target: 3,
+    },
+    BadgeDefinition {
+        code: "weekly_saver",
+        name: "Weekly saver",
+        description: "Save money every week for ten weeks in a row.",
+        metric: BadgeMetric::WeeksSaved,
+        target: 10,
+    },
+    BadgeDefinition {
+        code: "goal_getter",
+        name: "Goal getter",
+        description: "Reach the target of a saving goal.",
+        metric: BadgeMetric::CompletedGoals,
+        target: 1,
+    },
+];
+
+pub fn find_badge(code: &str) -> Option<&'static BadgeDefinition> {
+    BADGES.iter().find(|badge| badge.code == code)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Streak {
+    pub current: i32,
+    pub longest: i32,
+}
+
+pub struct BadgeContext<'a> {
+    pub transactions: &'a [Transaction],
+    pub budgets: &'a [Budget],
+    pub saving_goals: &'a [SavingGoal],
+    pub today: NaiveDate,
+    pub week_start: Weekday,
+    pub timezone: Tz,
+    // Savings and budgets are measured in this currency only.
+    pub currency: &'a str,
+}
+
+impl<'a> BadgeContext<'a> {
+    fn day(&self, instant: DateTime<Utc>) -> NaiveDate {
+        local_date(instant, self.timezone)
+    }
+
+    pub fn progress(&self, badge: &BadgeDefinition) -> i32 {
+        match badge.metric {
+            BadgeMetric::NoSpendWeekends => self.no_spend_weekends(),
+            BadgeMetric::MonthsUnderBudget => self.monthly_budget_streak().longest,
+            BadgeMetric::WeeksSaved => self.weekly_saving_streak().longest,
+            BadgeMetric::CompletedGoals => self
+                .saving_goals
+                .iter()
+                .filter(|goal| {
+                    goal.target_amount.is_positive() && goal.current_amount >= goal.target_amount
+                })
+                .count() as i32,
+        }
+    }
+
+    pub fn streaks(&self) -> Vec<(&'static str, Streak)> {
+        vec![
+            (WEEKLY_SAVING_STREAK, self.weekly_saving_streak()),
+            (MONTHLY_BUDGET_STREAK, self.monthly_budget_streak()),
+        ]
+    }
+
+    fn in_currency(&self) -> impl Iterator<Item = &'a Transaction> + '_ {
+        self.transactions
+            .iter()
+            .filter(|t| t.currency == self.currency)
+    }
+
+    fn first_activity(&self) -> Option<NaiveDate> {
+        self.transactions.iter().map(|t| self.day(t.date)).min()
+    }
+
+    // Completed weekends, starting with the first one after the user's first
+    // transaction, that have no expenses on either day.
+    pub fn no_spend_weekends(&self) -> i32 {
+        let Some(first) = self.first_activity() else {
+            return 0;
+        };
+
+        let mut saturday = first
+            + Duration::days(i64::from(
+                (7 + 5 - first.weekday().num_days_from_monday()) % 7,
+            ));
+        let mut count = 0;
+        while saturday + Duration::days(1) < self.today {
+            let sunday = saturday + Duration::days(1);
+            let spent = self.transactions.iter().any(|t| {
+                t.transaction_type == TransactionType::Expense
+                    && (self.day(t.date) == saturday || self.day(t.date) == sunday)
+            });
+            if !spent {
+                count += 1;
+            }
+            saturday += Duration::days(7);
+        }
+        count
+    }
+
+    // A week counts as saved when its income exceeds its expenses. The week in
+    // progress only extends the current streak, it never breaks it.
+    pub fn weekly_saving_streak(&self) -> Streak {
+        let Some(first) = self.first_activity() else {
+            return Streak::default();
+        };
+
+        let mut net_by_week: HashMap<NaiveDate, Money> = HashMap::new();
+        for transaction in self.in_currency() {
+            let net = net_by_week
+                .entry(week_start(self.day(transaction.date), self.week_start))
+                .or_default();
+            match transaction.transaction_type {
+                TransactionType::Income => *net += transaction.amount,
+                TransactionType::Expense => *net -= transaction.amount,
+            }
+        }
+
+        let current_week = week_start(self.today, self.week_start);
+        let mut weeks = Vec::new();
+        let mut week = week_start(first, self.week_start);
+        while week <= current_week {
+            let saved = net_by_week.get(&week).is_some_and(|net| net.is_positive());
+            weeks.push((week, saved));
+            week += Duration::days(7);
+        }
+
+        streak_from_periods(&weeks, current_week)
+    }
+
+    // A finished month counts when it has at least one active budget and none
+    // of them had been overspent by the end of that month.
+    pub fn monthly_budget_streak(&self) -> Streak {
+        let Some(first) = self.budgets.iter().map(|b| self.day(b.start_date)).min() else {
+            return Streak::default();
+        };
+
+        let current_month = month_start(self.today);
+        let mut months = Vec::new();
+        let mut month = month_start(first);
+        while month < current_month {
+            let month_end = next_month(month) - Duration::days(1);
+            let active: Vec<&Budget> = self
+                .budgets
+                .iter()
+                .filter(|b| self.day(b.start_date) <= month_end && self.day(b.end_date) >= month)
+                .collect();
+            let kept = !active.is_empty()
+                && active
+                    .iter()
+                    .all(|budget| self.spent_within(budget, month_end) <= budget.amount);
+            months.push((month, kept));
+            month = next_month(month);
+        }
+
+        streak_from_periods(&months, current_month)
+    }
+
+    fn spent_within(&self, budget: &Budget, until: NaiveDate) -> Money {
+        let end = self.day(budget.end_date).min(until);
+        self.in_currency()
+            .filter(|t| t.transaction_type == TransactionType::Expense)
+            .filter(|t| budget.category_id.is_none_or(|c| c == t.category_id))
+            .filter(|t| self.day(t.date) >= self.day(budget.start_date) && self.day(t.date) <= end)
+            .map(|t| t.amount)
+            .sum()
+    }
+}
+
+pub fn progress_percentage(progress: i32, target: i32) -> f64 {
+    if target <= 0 {
+        return 100.0;
+    }
+    f64::from(progress.clamp(0, target)) / f64::from(target) * 100.0
+}
+
+fn month_start(date: NaiveDate) -> NaiveDate {
+    date.with_day(1).expect("every month has a first day")
+}
+
+fn next_month(month: NaiveDate) -> NaiveDate {
+    if month.month() == 12 {
+        NaiveDate::from_ymd_opt(month.year() + 1, 1, 1)
+    } else {
+        NaiveDate::from_ymd_opt(month.year(), month.month() + 1, 1)
+    }
+    .expect("first day of the next month is valid")
+}
+
+fn streak_from_periods(periods: &[(NaiveDate, bool)], in_progress: NaiveDate) -> Streak {
+    let mut longest = 0;
+    let mut run = 0;
+    for (_, kept) in periods {
+        run = if *kept { run + 1 } else { 0 };
+        longest = longest.max(run);
+    }
+
+    let current = periods
+        .iter()
+        .rev()
+        .skip_while(|(period, kept)| *period == in_progress && !kept)
+        .take_while(|(_, kept)| *kept)
+        .count() as i32;
+
+    Streak { current, longest }
+}
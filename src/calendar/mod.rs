@@ -0,0 +1,68 @@
+use chrono::{
+    DateTime, Datelike, Duration, Local, NaiveDate, NaiveDateTime, TimeZone, Utc, Weekday,
+};
+use chrono_tz::Tz;
+
+// The first day of the week that contains `date`.
+pub fn week_start(date: NaiveDate, first_day: Weekday) -> NaiveDate {
+    let offset = (7 + date.weekday().num_days_from_monday() - first_day.num_days_from_monday()) % 7;
+    date - Duration::days(i64::from(offset))
+}
+
+// The first day of the fiscal year that contains `date`, for a fiscal year
+// starting on the first of `start_month`.
+pub fn fiscal_year_start(date: NaiveDate, start_month: u32) -> NaiveDate {
+    let year = if date.month() >= start_month {
+        date.year()
+    } else {
+        date.year() - 1
+    };
+    NaiveDate::from_ymd_opt(year, start_month, 1).expect("start month is between 1 and 12")
+}
+
+// The wall-clock time in the given timezone.
+pub fn now_in(timezone: Tz) -> NaiveDateTime {
+    Utc::now().with_timezone(&timezone).naive_local()
+}
+
+// The calendar day an instant falls on in the given timezone.
+pub fn local_date(instant: DateTime<Utc>, timezone: Tz) -> NaiveDate {
+    instant.with_timezone(&timezone).date_naive()
+}
+
+// The calendar day in the given timezone of a wall-clock time recorded in the
+// server's timezone. Times skipped by a clock change are read as UTC.
+pub fn local_date_of_server_time(time: NaiveDateTime, timezone: Tz) -> NaiveDate {
+    let instant = Local
+        .from_local_datetime(&time)
+        .earliest()
+        .map_or_else(|| Utc.from_utc_datetime(&time), |t| t.with_timezone(&Utc));
+    local_date(instant, timezone)
+}
+
+// Month names for the languages the app is most used in, January first.
+// Other languages get English until a full locale database is available.
+const MONTH_NAMES: [(&str, &str); 9] = [
+    ("en", "January,February,March,April,May,June,July,August,September,October,November,December"),
+    ("de", "Januar,Februar,März,April,Mai,Juni,Juli,August,September,Oktober,November,Dezember"),
+    ("fr", "janvier,février,mars,avril,mai,juin,juillet,août,septembre,octobre,novembre,décembre"),
+    ("es", "enero,febrero,marzo,abril,mayo,junio,julio,agosto,septiembre,octubre,noviembre,diciembre"),
+    ("it", "gennaio,febbraio,marzo,aprile,maggio,giugno,luglio,agosto,settembre,ottobre,novembre,dicembre"),
+    ("pt", "janeiro,fevereiro,março,abril,maio,junho,julho,agosto,setembro,outubro,novembro,dezembro"),
+    ("nl", "januari,februari,maart,april,mei,juni,juli,augustus,september,oktober,november,december"),
+    ("sv", "januari,februari,mars,april,maj,juni,juli,augusti,september,oktober,november,december"),
+    ("pl", "styczeń,luty,marzec,kwiecień,maj,czerwiec,lipiec,sierpień,wrzesień,październik,listopad,grudzień"),
+];
+
+// The name of the month `date` falls in, in the language of a BCP 47 locale.
+pub fn month_name(date: NaiveDate, locale: &str) -> &'static str {
+    let language = locale.split('-').next().unwrap_or_default();
+    let (_, names) = MONTH_NAMES
+        .iter()
+        .find(|(code, _)| code.eq_ignore_ascii_case(language))
+        .unwrap_or(&MONTH_NAMES[0]);
+    names
+        .split(',')
+        .nth(date.month0() as usize)
+        .expect("every language names twelve months")
+}
@@ -0,0 +1,230 @@
+use chrono::{Duration, Months, NaiveDate};
+use chrono_tz::Tz;
+use rust_decimal::Decimal;
+
+use crate::{
+    calendar::{local_date, local_date_of_server_time},
+    dtos::challenge_dtos::{ChallengePeriodOutDTO, ChallengeReportOutDTO},
+    enums::custom_enums::{ChallengeStatus, ChallengeType, TransactionType},
+    exchange_rates::{MissingRate, RateTable},
+    models::{
+        challenge::SavingChallenge, contribution::GoalContribution, transactions::Transaction,
+    },
+    money::Money,
+};
+
+pub const FIFTY_TWO_WEEKS: i64 = 52;
+// Ten years, the longest an income percentage challenge can run.
+pub const MAX_DURATION_WEEKS: i64 = 520;
+pub const DEFAULT_UNIT_AMOUNT: Money = Money::new(Decimal::ONE);
+
+// `None` when the challenge would end past the last date chrono can hold.
+pub fn challenge_end_date(
+    challenge_type: ChallengeType,
+    start_date: NaiveDate,
+    duration_weeks: Option<i64>,
+) -> Option<NaiveDate> {
+    let end = match challenge_type {
+        ChallengeType::FiftyTwoWeek => {
+            start_date.checked_add_signed(Duration::weeks(FIFTY_TWO_WEEKS))
+        }
+        ChallengeType::NoSpendMonth => start_date.checked_add_months(Months::new(1)),
+        ChallengeType::IncomePercentage => start_date
+            .checked_add_signed(Duration::weeks(duration_weeks.unwrap_or(FIFTY_TWO_WEEKS))),
+    };
+    end?.checked_sub_signed(Duration::days(1))
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChallengePeriod {
+    pub week: u32,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub expected_amount: Money,
+    pub contributed_amount: Money,
+    pub spent_amount: Money,
+    // None while the week is still running and nothing has decided it yet.
+    pub met: Option<bool>,
+}
+
+impl ChallengePeriod {
+    pub fn to_challenge_period_out_dto(&self) -> ChallengePeriodOutDTO {
+        ChallengePeriodOutDTO {
+            week: self.week,
+            start_date: self.start_date,
+            end_date: self.end_date,
+            expected_amount: self.expected_amount,
+            contributed_amount: self.contributed_amount,
+            spent_amount: self.spent_amount,
+            met: self.met,
+        }
+    }
+}
+
+// Splits the challenge into seven day periods counted from its start date and
+// compares what was expected in each one with what actually happened.
+// Contributions and transactions are placed on the day they happened in the
+// user's timezone. Transactions are converted into `currency` at that day's
+// rate and percentage based targets are rounded to its minor unit.
+pub fn build_schedule(
+    challenge: &SavingChallenge,
+    contributions: &[GoalContribution],
+    transactions: &[Transaction],
+    rates: &RateTable,
+    today: NaiveDate,
+    timezone: Tz,
+    currency: &str,
+) -> Result<Vec<ChallengePeriod>, MissingRate> {
+    let mut schedule = Vec::new();
+    let mut start = challenge.start_date;
+    let mut week = 1;
+
+    while start <= challenge.end_date {
+        let end = start
+            .checked_add_signed(Duration::days(6))
+            .map_or(challenge.end_date, |end| end.min(challenge.end_date));
+        let in_period = |date: NaiveDate| date >= start && date <= end;
+        let has_ended = end < today;
+
+        let contributed_amount: Money = contributions
+            .iter()
+            .filter(|c| in_period(local_date_of_server_time(c.contributed_at, timezone)))
+            .map(|c| c.amount)
+            .sum();
+        let matching: Vec<&Transaction> = transactions
+            .iter()
+            .filter(|t| in_period(local_date(t.date, timezone)))
+            .filter(|t| match challenge.challenge_type {
+                ChallengeType::NoSpendMonth => {
+                    t.transaction_type == TransactionType::Expense
+                        && challenge.category_ids.contains(&t.category_id)
+                }
+                _ => t.transaction_type == TransactionType::Income,
+            })
+            .collect();
+        let matched_amount = matching
+            .iter()
+            .map(|t| {
+                rates.convert(
+                    t.amount,
+                    &t.currency,
+                    currency,
+                    local_date(t.date, timezone),
+                )
+            })
+            .sum::<Result<Money, MissingRate>>()?;
+
+        let (expected_amount, spent_amount, met) = match challenge.challenge_type {
+            ChallengeType::FiftyTwoWeek => {
+                let expected =
+                    challenge.unit_amount.unwrap_or(DEFAULT_UNIT_AMOUNT) * Decimal::from(week);
+                (
+                    expected,
+                    Money::ZERO,
+                    contribution_met(contributed_amount, expected, has_ended),
+                )
+            }
+            ChallengeType::IncomePercentage => {
+                let expected = matched_amount
+                    .percent(challenge.percentage.unwrap_or_default())
+                    .round_to(currency);
+                (
+                    expected,
+                    Money::ZERO,
+                    contribution_met(contributed_amount, expected, has_ended),
+                )
+            }
+            ChallengeType::NoSpendMonth => {
+                let met = if matching.iter().any(|t| t.amount.is_positive()) {
+                    Some(false)
+                } else if has_ended {
+                    Some(true)
+                } else {
+                    None
+                };
+                (Money::ZERO, matched_amount, met)
+            }
+        };
+
+        schedule.push(ChallengePeriod {
+            week,
+            start_date: start,
+            end_date: end,
+            expected_amount,
+            contributed_amount,
+            spent_amount,
+            met,
+        });
+
+        match end.checked_add_signed(Duration::days(1)) {
+            Some(next) => start = next,
+            None => break,
+        }
+        week += 1;
+    }
+
+    Ok(schedule)
+}
+
+fn contribution_met(contributed: Money, expected: Money, has_ended: bool) -> Option<bool> {
+    if expected.is_positive() && contributed >= expected {
+        Some(true)
+    } else if has_ended {
+        Some(contributed >= expected)
+    } else {
+        None
+    }
+}
+
+pub fn challenge_status(
+    challenge: &SavingChallenge,
+    schedule: &[ChallengePeriod],
+    today: NaiveDate,
+) -> ChallengeStatus {
+    if schedule.iter().all(|period| period.met == Some(true)) {
+        ChallengeStatus::Completed
+    } else if challenge.end_date < today
+        || (challenge.challenge_type == ChallengeType::NoSpendMonth
+            && schedule.iter().any(|period| period.met == Some(false)))
+    {
+        ChallengeStatus::Failed
+    } else {
+        ChallengeStatus::InProgress
+    }
+}
+
+pub fn build_report(
+    challenge: &SavingChallenge,
+    contributions: &[GoalContribution],
+    transactions: &[Transaction],
+    rates: &RateTable,
+    today: NaiveDate,
+    timezone: Tz,
+    currency: &str,
+) -> Result<ChallengeReportOutDTO, MissingRate> {
+    let schedule = build_schedule(
+        challenge,
+        contributions,
+        transactions,
+        rates,
+        today,
+        timezone,
+        currency,
+    )?;
+
+    Ok(ChallengeReportOutDTO {
+        challenge: challenge.to_challenge_out_dto(),
+        status: challenge_status(challenge, &schedule, today),
+        expected_total: schedule.iter().map(|p| p.expected_amount).sum(),
+        contributed_total: schedule.iter().map(|p| p.contributed_amount).sum(),
+        missed_weeks: schedule
+            .iter()
+            .filter(|period| period.met == Some(false))
+            .map(|period| period.week)
+            .collect(),
+        schedule: schedule
+            .iter()
+            .map(|period| period.to_challenge_period_out_dto())
+            .collect(),
+    })
+}
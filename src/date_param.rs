@@ -0,0 +1,13 @@
+use chrono::NaiveDate;
+use rocket::form::{self, FromFormField, ValueField};
+
+// A calendar day in a query string, written as 2024-01-31.
+pub struct DateParam(pub NaiveDate);
+
+impl<'r> FromFormField<'r> for DateParam {
+    fn from_value(field: ValueField<'r>) -> form::Result<'r, Self> {
+        NaiveDate::parse_from_str(field.value, "%Y-%m-%d")
+            .map(DateParam)
+            .map_err(|e| form::Error::validation(e.to_string()).into())
+    }
+}
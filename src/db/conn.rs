@@ -19,7 +19,7 @@ pub async fn establish_connection(is_test: bool) -> PgPool {
         "DATABASE_URL"
     };
     let database_url =
-        env::var(database_url_key).expect(&format!("{} must be set", database_url_key));
+        env::var(database_url_key).unwrap_or_else(|_| panic!("{} must be set", database_url_key));
 
     // let database_url = env::var("DATABASE_URL").expect("DATABASE_URL must be set");
 
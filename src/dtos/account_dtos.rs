@@ -1,21 +1,84 @@
+use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::enums::custom_enums::AccountType;
+use crate::money::{is_currency_code, Money};
 
 #[derive(Debug, Deserialize, Serialize)]
 pub struct AccountInDTO {
     pub name: String,
-    pub balance: f64,
+    pub balance: Money,
     pub account_type: AccountType,
-    pub user_id: Uuid,
+    // Defaults to the user's base currency when creating and to the current
+    // currency when updating.
+    pub currency: Option<String>,
+}
+
+impl AccountInDTO {
+    pub fn validate(&self) -> Result<(), &'static str> {
+        if self
+            .currency
+            .as_deref()
+            .is_some_and(|currency| !is_currency_code(currency))
+        {
+            return Err("Currency must be a three letter ISO 4217 code.");
+        }
+
+        Ok(())
+    }
+
+    // `currency` is the one the account will be kept in.
+    pub fn validate_balance(&self, currency: &str) -> Result<(), &'static str> {
+        if self.balance.round_to(currency) != self.balance {
+            return Err("Balance has more decimals than its currency allows.");
+        }
+
+        Ok(())
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct AccountOutDTO {
     pub id: Uuid,
     pub name: String,
-    pub balance: f64,
+    pub balance: Money,
+    pub currency: String,
     pub account_type: AccountType,
     pub user_id: Uuid,
+    pub household_id: Option<Uuid>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CurrencyBalanceOutDTO {
+    pub currency: String,
+    pub balance: Money,
+    pub accounts: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ConvertedBalanceOutDTO {
+    pub currency: String,
+    pub balance: Money,
+    pub accounts: i64,
+    pub converted_balance: Money,
+}
+
+// Every balance restated in one currency at the rates of `date`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct NetWorthOutDTO {
+    pub currency: String,
+    pub date: NaiveDate,
+    pub net_worth: Money,
+    pub balances: Vec<ConvertedBalanceOutDTO>,
+}
+
+// What an account held at the end of a day, worked back from its current
+// balance by taking out every transaction booked after that day.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AccountBalanceOutDTO {
+    pub account_id: Uuid,
+    pub date: NaiveDate,
+    pub balance: Money,
+    pub currency: String,
 }
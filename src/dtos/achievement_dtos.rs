@@ -1,17 +1,28 @@
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::enums::custom_enums::AchievementKind;
+use crate::money::Money;
+
 #[derive(Debug, Deserialize)]
 pub struct AchievementInDTO {
     pub goal_id: Uuid,
-    pub date_achieved: chrono::NaiveDateTime,
-    pub amount_saved: f64,
+    pub date_achieved: chrono::DateTime<chrono::Utc>,
+    pub amount_saved: Money,
+}
+
+// Achievements from the feed that the client has shown to the user.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct AchievementsSeenInDTO {
+    pub achievement_ids: Vec<Uuid>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct AchievementOutDTO {
     pub id: Uuid,
     pub goal_id: Uuid,
-    pub date_achieved: chrono::NaiveDateTime,
-    pub amount_saved: f64,
+    pub date_achieved: chrono::DateTime<chrono::Utc>,
+    pub amount_saved: Money,
+    pub kind: AchievementKind,
+    pub milestone: Option<i32>,
 }
@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::enums::custom_enums::UserRole;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UserOverviewOutDTO {
+    pub id: Uuid,
+    pub username: String,
+    pub email: String,
+    pub role: UserRole,
+    pub created_at: chrono::NaiveDateTime,
+    pub suspended_at: Option<chrono::NaiveDateTime>,
+    pub last_seen_at: Option<chrono::NaiveDateTime>,
+    pub active_sessions: i64,
+    pub account_count: i64,
+    pub transaction_count: i64,
+    pub saving_goal_count: i64,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct UserRoleInDTO {
+    pub role: UserRole,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ImpersonationLogEntryOutDTO {
+    pub id: Uuid,
+    pub impersonator_id: Uuid,
+    pub user_id: Uuid,
+    pub session_id: Uuid,
+    pub method: String,
+    pub path: String,
+    pub status: i32,
+    pub created_at: chrono::NaiveDateTime,
+}
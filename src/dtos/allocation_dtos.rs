@@ -0,0 +1,110 @@
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::enums::custom_enums::{AllocationAmountType, AllocationTargetType};
+use crate::money::Money;
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct AllocationTargetInDTO {
+    pub target_type: AllocationTargetType,
+    pub target_id: Uuid,
+    pub amount_type: AllocationAmountType,
+    pub value: Decimal,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct AllocationRuleInDTO {
+    pub name: String,
+    pub match_title: Option<String>,
+    pub match_account_id: Option<Uuid>,
+    #[serde(default)]
+    pub priority: i32,
+    #[serde(default = "default_auto_apply")]
+    pub auto_apply: bool,
+    pub targets: Vec<AllocationTargetInDTO>,
+}
+
+fn default_auto_apply() -> bool {
+    true
+}
+
+impl AllocationRuleInDTO {
+    pub fn validate(&self) -> Result<(), &'static str> {
+        if self.match_title.as_deref().is_none_or(str::is_empty) && self.match_account_id.is_none()
+        {
+            return Err("A rule needs a title or an account to match on.");
+        }
+        if self.targets.is_empty() {
+            return Err("A rule needs at least one target.");
+        }
+        if self
+            .targets
+            .iter()
+            .any(|target| target.value <= Decimal::ZERO)
+        {
+            return Err("Allocation values must be positive.");
+        }
+
+        let percentage: Decimal = self
+            .targets
+            .iter()
+            .filter(|target| target.amount_type == AllocationAmountType::Percentage)
+            .map(|target| target.value)
+            .sum();
+        if percentage > Decimal::ONE_HUNDRED {
+            return Err("Percentages can't add up to more than 100.");
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AllocationTargetOutDTO {
+    pub target_type: AllocationTargetType,
+    pub target_id: Uuid,
+    pub amount_type: AllocationAmountType,
+    pub value: Decimal,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AllocationRuleOutDTO {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub name: String,
+    pub match_title: Option<String>,
+    pub match_account_id: Option<Uuid>,
+    pub priority: i32,
+    pub auto_apply: bool,
+    pub targets: Vec<AllocationTargetOutDTO>,
+    pub created_at: chrono::NaiveDateTime,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PlannedAllocationOutDTO {
+    pub target_type: AllocationTargetType,
+    pub target_id: Uuid,
+    pub amount: Money,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AllocationPlanOutDTO {
+    pub transaction_id: Uuid,
+    pub rule_id: Uuid,
+    pub rule_name: String,
+    pub income_amount: Money,
+    pub allocations: Vec<PlannedAllocationOutDTO>,
+    pub unallocated: Money,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AllocationOutDTO {
+    pub id: Uuid,
+    pub source_transaction_id: Uuid,
+    pub rule_id: Option<Uuid>,
+    pub target_type: AllocationTargetType,
+    pub target_id: Uuid,
+    pub amount: Money,
+    pub created_at: chrono::NaiveDateTime,
+}
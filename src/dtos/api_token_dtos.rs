@@ -0,0 +1,28 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ApiTokenInDTO {
+    pub name: String,
+    pub scopes: Vec<String>,
+    pub expires_at: chrono::NaiveDateTime,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ApiTokenOutDTO {
+    pub id: Uuid,
+    pub name: String,
+    pub token_prefix: String,
+    pub scopes: Vec<String>,
+    pub expires_at: chrono::NaiveDateTime,
+    pub last_used_at: Option<chrono::NaiveDateTime>,
+    pub created_at: chrono::NaiveDateTime,
+}
+
+// Returned once, on creation; only the hash is kept afterwards.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ApiTokenCreatedOutDTO {
+    pub token: String,
+    #[serde(flatten)]
+    pub api_token: ApiTokenOutDTO,
+}
@@ -0,0 +1,27 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BadgeOutDTO {
+    pub code: String,
+    pub name: String,
+    pub description: String,
+    pub progress: i32,
+    pub target: i32,
+    pub progress_percentage: f64,
+    pub earned: bool,
+    pub earned_at: Option<chrono::NaiveDateTime>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StreakOutDTO {
+    pub code: String,
+    pub current: i32,
+    pub longest: i32,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UserBadgesOutDTO {
+    pub earned: Vec<BadgeOutDTO>,
+    pub in_progress: Vec<BadgeOutDTO>,
+    pub streaks: Vec<StreakOutDTO>,
+}
@@ -1,24 +1,37 @@
-use chrono::NaiveDateTime;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::money::Money;
+
 #[derive(Debug, Deserialize)]
 pub struct BudgetInDTO {
     pub name: String,
-    pub amount: f64,
-    pub start_date: NaiveDateTime,
-    pub end_date: NaiveDateTime,
-    pub user_id: Uuid,
+    pub amount: Money,
+    pub start_date: DateTime<Utc>,
+    pub end_date: DateTime<Utc>,
     pub category_id: Option<Uuid>,
 }
 
+impl BudgetInDTO {
+    // Budgets are kept in their owner's base currency.
+    pub fn validate(&self, currency: &str) -> Result<(), &'static str> {
+        if self.amount.round_to(currency) != self.amount {
+            return Err("Amount has more decimals than its currency allows.");
+        }
+
+        Ok(())
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct BudgetOutDTO {
     pub id: Uuid,
     pub name: String,
-    pub amount: f64,
-    pub start_date: NaiveDateTime,
-    pub end_date: NaiveDateTime,
+    pub amount: Money,
+    pub start_date: DateTime<Utc>,
+    pub end_date: DateTime<Utc>,
     pub user_id: Uuid,
     pub category_id: Option<Uuid>,
+    pub household_id: Option<Uuid>,
 }
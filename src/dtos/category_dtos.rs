@@ -4,7 +4,6 @@ use uuid::Uuid;
 #[derive(Debug, Deserialize)]
 pub struct CategoryInDTO {
     pub name: String,
-    pub user_id: Uuid,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -12,4 +11,5 @@ pub struct CategoryOutDTO {
     pub id: Uuid,
     pub name: String,
     pub user_id: Uuid,
+    pub household_id: Option<Uuid>,
 }
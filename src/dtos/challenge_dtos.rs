@@ -0,0 +1,91 @@
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::challenges::{challenge_end_date, MAX_DURATION_WEEKS};
+use crate::enums::custom_enums::{ChallengeStatus, ChallengeType};
+use crate::money::Money;
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ChallengeInDTO {
+    pub challenge_type: ChallengeType,
+    pub goal_id: Uuid,
+    pub start_date: NaiveDate,
+    pub unit_amount: Option<Money>,
+    pub percentage: Option<Decimal>,
+    #[serde(default)]
+    pub category_ids: Vec<Uuid>,
+    pub duration_weeks: Option<i64>,
+}
+
+impl ChallengeInDTO {
+    pub fn validate(&self) -> Result<(), &'static str> {
+        self.validate_type()?;
+        match challenge_end_date(self.challenge_type, self.start_date, self.duration_weeks) {
+            Some(_) => Ok(()),
+            None => Err("The challenge would end too far in the future."),
+        }
+    }
+
+    fn validate_type(&self) -> Result<(), &'static str> {
+        match self.challenge_type {
+            ChallengeType::FiftyTwoWeek => match self.unit_amount {
+                Some(unit) if !unit.is_positive() => Err("unit_amount must be positive."),
+                _ => Ok(()),
+            },
+            ChallengeType::NoSpendMonth if self.category_ids.is_empty() => {
+                Err("A no-spend month needs at least one category.")
+            }
+            ChallengeType::IncomePercentage => match self.percentage {
+                Some(percentage)
+                    if percentage > Decimal::ZERO && percentage <= Decimal::ONE_HUNDRED =>
+                {
+                    match self.duration_weeks {
+                        Some(weeks) if !(1..=MAX_DURATION_WEEKS).contains(&weeks) => {
+                            Err("duration_weeks must be between 1 and 520.")
+                        }
+                        _ => Ok(()),
+                    }
+                }
+                _ => Err("percentage must be between 0 and 100."),
+            },
+            _ => Ok(()),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ChallengeOutDTO {
+    pub id: Uuid,
+    pub challenge_type: ChallengeType,
+    pub goal_id: Uuid,
+    pub user_id: Uuid,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub unit_amount: Option<Money>,
+    pub percentage: Option<Decimal>,
+    pub category_ids: Vec<Uuid>,
+    pub created_at: chrono::NaiveDateTime,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ChallengePeriodOutDTO {
+    pub week: u32,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub expected_amount: Money,
+    pub contributed_amount: Money,
+    pub spent_amount: Money,
+    pub met: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ChallengeReportOutDTO {
+    pub challenge: ChallengeOutDTO,
+    pub status: ChallengeStatus,
+    pub expected_total: Money,
+    pub contributed_total: Money,
+    pub missed_weeks: Vec<u32>,
+    pub schedule: Vec<ChallengePeriodOutDTO>,
+}
@@ -0,0 +1,23 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::enums::custom_enums::ContributionSource;
+use crate::money::Money;
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ContributionInDTO {
+    pub amount: Money,
+    pub contributed_at: Option<chrono::NaiveDateTime>,
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ContributionOutDTO {
+    pub id: Uuid,
+    pub goal_id: Uuid,
+    pub amount: Money,
+    pub contributed_at: chrono::NaiveDateTime,
+    pub note: Option<String>,
+    pub source: ContributionSource,
+    pub transaction_id: Option<Uuid>,
+}
@@ -0,0 +1,28 @@
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExchangeRateOutDTO {
+    pub base_currency: String,
+    pub quote_currency: String,
+    pub rate_date: NaiveDate,
+    pub rate: Decimal,
+}
+
+// The rate that conversions use for a day, which may come from an earlier day
+// or be derived from other rates.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExchangeRateQuoteOutDTO {
+    pub from: String,
+    pub to: String,
+    pub date: NaiveDate,
+    pub rate: Decimal,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExchangeRateImportOutDTO {
+    pub imported: u64,
+    pub first_date: Option<NaiveDate>,
+    pub last_date: Option<NaiveDate>,
+}
@@ -0,0 +1,49 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::enums::custom_enums::{HouseholdRole, SharedRecordType};
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct HouseholdInDTO {
+    pub name: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HouseholdOutDTO {
+    pub id: Uuid,
+    pub name: String,
+    pub created_at: chrono::NaiveDateTime,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HouseholdDetailOutDTO {
+    #[serde(flatten)]
+    pub household: HouseholdOutDTO,
+    pub members: Vec<HouseholdMemberOutDTO>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct HouseholdMemberInDTO {
+    pub email: String,
+    pub role: HouseholdRole,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct HouseholdRoleInDTO {
+    pub role: HouseholdRole,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HouseholdMemberOutDTO {
+    pub user_id: Uuid,
+    pub username: String,
+    pub email: String,
+    pub role: HouseholdRole,
+    pub joined_at: chrono::NaiveDateTime,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct HouseholdShareInDTO {
+    pub record_type: SharedRecordType,
+    pub record_id: Uuid,
+}
@@ -0,0 +1,51 @@
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::enums::custom_enums::AnomalyKind;
+use crate::money::Money;
+
+// Unusual expenses between `from` and `to`, judged against what was spent
+// from `baseline_from` up to the day before `from`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AnomalyReportOutDTO {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub baseline_from: NaiveDate,
+    pub anomalies: Vec<AnomalyOutDTO>,
+}
+
+// Outliers and new payees point at one transaction; a spike covers all of a
+// category's spending in the period, so it has no transaction or date.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AnomalyOutDTO {
+    pub kind: AnomalyKind,
+    pub transaction_id: Option<Uuid>,
+    pub date: Option<NaiveDate>,
+    pub category_id: Uuid,
+    pub category_name: String,
+    pub payee: Option<String>,
+    pub amount: Money,
+    pub currency: String,
+    pub explanation: String,
+    pub statistics: AnomalyStatisticsOutDTO,
+}
+
+// What the flag was decided on. Only the figures the kind of anomaly uses
+// are set; `threshold` is what the amount had to exceed.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AnomalyStatisticsOutDTO {
+    pub sample_size: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub median: Option<Money>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maximum: Option<Money>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub percentile_90: Option<Money>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expected: Option<Money>,
+    pub threshold: Money,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ratio: Option<Decimal>,
+}
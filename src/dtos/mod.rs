@@ -1,7 +1,23 @@
 pub mod account_dtos;
 pub mod achievement_dtos;
+pub mod admin_dtos;
+pub mod allocation_dtos;
+pub mod api_token_dtos;
+pub mod auth_dtos;
+pub mod badge_dtos;
 pub mod budget_dtos;
 pub mod category_dtos;
+pub mod challenge_dtos;
+pub mod contribution_dtos;
+pub mod exchange_rate_dtos;
+pub mod household_dtos;
+pub mod insight_dtos;
+pub mod recurring_transaction_dtos;
+pub mod report_dtos;
+pub mod round_up_dtos;
 pub mod saving_goal_dtos;
+pub mod subscription_dtos;
 pub mod transaction_dtos;
+pub mod two_factor_dtos;
 pub mod user_dtos;
+pub mod user_preferences_dtos;
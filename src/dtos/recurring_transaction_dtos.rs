@@ -0,0 +1,56 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::enums::custom_enums::{RecurrenceFrequency, TransactionType};
+use crate::money::Money;
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct RecurringTransactionInDTO {
+    pub title: String,
+    // In the account's currency.
+    pub amount: Money,
+    pub transaction_type: TransactionType,
+    pub account_id: Uuid,
+    pub category_id: Uuid,
+    pub frequency: RecurrenceFrequency,
+    // How many days, weeks, months or years apart occurrences are; 1 unless
+    // given.
+    pub every: Option<i32>,
+    pub start_date: NaiveDate,
+    pub end_date: Option<NaiveDate>,
+}
+
+impl RecurringTransactionInDTO {
+    pub fn validate(&self) -> Result<(), &'static str> {
+        if self.title.trim().is_empty() {
+            return Err("title can't be empty.");
+        }
+        if !self.amount.is_positive() {
+            return Err("amount must be positive.");
+        }
+        if self.every.is_some_and(|every| !(1..=366).contains(&every)) {
+            return Err("every must be between 1 and 366.");
+        }
+        if self.end_date.is_some_and(|end| end < self.start_date) {
+            return Err("end_date can't be before start_date.");
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RecurringTransactionOutDTO {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub account_id: Uuid,
+    pub category_id: Uuid,
+    pub title: String,
+    pub amount: Money,
+    pub transaction_type: TransactionType,
+    pub frequency: RecurrenceFrequency,
+    pub every: i32,
+    pub start_date: NaiveDate,
+    pub end_date: Option<NaiveDate>,
+    pub created_at: chrono::NaiveDateTime,
+}
@@ -0,0 +1,254 @@
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    enums::custom_enums::{AccountType, ReportGranularity, TransactionType},
+    money::Money,
+};
+
+// Income and spending between two days, both included, with every day's
+// transactions converted at that day's rate.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SummaryReportOutDTO {
+    pub currency: String,
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub granularity: ReportGranularity,
+    pub income: Money,
+    pub expenses: Money,
+    pub net: Money,
+    pub savings_rate: Option<Decimal>,
+    pub transaction_count: i64,
+    pub periods: Vec<SummaryPeriodOutDTO>,
+}
+
+// `start` and `end` are clipped to the report range, so the first and last
+// periods may be partial.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SummaryPeriodOutDTO {
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+    pub income: Money,
+    pub expenses: Money,
+    pub net: Money,
+    pub savings_rate: Option<Decimal>,
+    pub transaction_count: i64,
+    pub breakdown: Vec<SummaryBreakdownOutDTO>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SummaryBreakdownOutDTO {
+    pub id: Uuid,
+    pub name: String,
+    pub income: Money,
+    pub expenses: Money,
+    pub net: Money,
+    pub savings_rate: Option<Decimal>,
+    pub transaction_count: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ComparisonPeriodOutDTO {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub total: Money,
+}
+
+// Expenses per category, largest first. Changes are in percent of the
+// earlier amount and missing when nothing was spent back then.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CategoryReportOutDTO {
+    pub currency: String,
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub total: Money,
+    pub previous_period: ComparisonPeriodOutDTO,
+    pub last_year: ComparisonPeriodOutDTO,
+    pub categories: Vec<CategorySpendingOutDTO>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CategorySpendingOutDTO {
+    pub category_id: Uuid,
+    pub name: String,
+    pub amount: Money,
+    pub share: Option<Decimal>,
+    pub previous_amount: Money,
+    pub change_from_previous: Option<Decimal>,
+    pub last_year_amount: Money,
+    pub change_from_last_year: Option<Decimal>,
+}
+
+// Net worth at the end of each period. A point is `reconstructed` when some
+// balance had no snapshot that day and was worked out from transactions.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct NetWorthHistoryOutDTO {
+    pub currency: String,
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub granularity: ReportGranularity,
+    pub points: Vec<NetWorthPointOutDTO>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct NetWorthPointOutDTO {
+    pub date: NaiveDate,
+    pub net_worth: Money,
+    pub reconstructed: bool,
+    pub account_types: Vec<AccountTypeBalanceOutDTO>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AccountTypeBalanceOutDTO {
+    pub account_type: AccountType,
+    pub balance: Money,
+}
+
+// Projected balances for the days after today, from recurring transactions
+// plus the average daily spending in categories they don't cover and the
+// average daily allocation transfers.
+// `negative_dates` are the days on which any account ends below zero.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ForecastOutDTO {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub negative_dates: Vec<NaiveDate>,
+    pub accounts: Vec<AccountForecastOutDTO>,
+    pub upcoming: Vec<ScheduledOccurrenceOutDTO>,
+}
+
+// Amounts are in the account's currency.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AccountForecastOutDTO {
+    pub account_id: Uuid,
+    pub name: String,
+    pub currency: String,
+    pub starting_balance: Money,
+    pub ending_balance: Money,
+    pub lowest_balance: Money,
+    pub lowest_balance_date: NaiveDate,
+    pub first_negative_date: Option<NaiveDate>,
+    pub daily_discretionary: Money,
+    pub daily_transfers: Money,
+    pub days: Vec<ForecastDayOutDTO>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ForecastDayOutDTO {
+    pub date: NaiveDate,
+    pub scheduled: Money,
+    pub discretionary: Money,
+    pub transfers: Money,
+    pub balance: Money,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ScheduledOccurrenceOutDTO {
+    pub date: NaiveDate,
+    pub recurring_transaction_id: Uuid,
+    pub account_id: Uuid,
+    pub title: String,
+    pub amount: Money,
+    pub transaction_type: TransactionType,
+}
+
+// A year of activity in `currency`, from January 1st to `to`, which is
+// today while the year is still running.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct YearInReviewOutDTO {
+    pub year: i32,
+    pub currency: String,
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub income: Money,
+    pub expenses: Money,
+    pub net: Money,
+    pub savings_rate: Option<Decimal>,
+    pub transaction_count: i64,
+    pub months: Vec<MonthInReviewOutDTO>,
+    pub biggest_spending_month: Option<NaiveDate>,
+    pub biggest_income_month: Option<NaiveDate>,
+    pub top_categories: Vec<TopCategoryOutDTO>,
+    pub top_payees: Vec<TopPayeeOutDTO>,
+    pub longest_no_spend_streak: NoSpendStreakOutDTO,
+    pub goals_completed: Vec<CompletedGoalOutDTO>,
+    pub budgets: BudgetsKeptOutDTO,
+    pub year_over_year: YearOverYearOutDTO,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MonthInReviewOutDTO {
+    pub month: NaiveDate,
+    pub income: Money,
+    pub expenses: Money,
+    pub net: Money,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TopCategoryOutDTO {
+    pub category_id: Uuid,
+    pub name: String,
+    pub amount: Money,
+    pub share: Option<Decimal>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TopPayeeOutDTO {
+    pub payee: String,
+    pub name: String,
+    pub amount: Money,
+    pub transaction_count: usize,
+    pub share: Option<Decimal>,
+}
+
+// The most days in a row without an expense; `from` and `to` are unset when
+// every day had one.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct NoSpendStreakOutDTO {
+    pub days: i64,
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CompletedGoalOutDTO {
+    pub goal_id: Uuid,
+    pub title: String,
+    pub target_amount: Money,
+    pub completed_on: NaiveDate,
+}
+
+// Budgets that ended during the year. They are measured in the user's base
+// currency, not the report's.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BudgetsKeptOutDTO {
+    pub currency: String,
+    pub kept: usize,
+    pub total: usize,
+    pub budgets: Vec<BudgetResultOutDTO>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BudgetResultOutDTO {
+    pub budget_id: Uuid,
+    pub name: String,
+    pub amount: Money,
+    pub spent: Money,
+    pub kept: bool,
+}
+
+// The same days of the year before, and how this year moved from them in
+// percent.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct YearOverYearOutDTO {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub income: Money,
+    pub expenses: Money,
+    pub net: Money,
+    pub income_change: Option<Decimal>,
+    pub expenses_change: Option<Decimal>,
+    pub net_change: Option<Decimal>,
+}
@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::money::Money;
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct RoundUpRuleInDTO {
+    pub account_id: Uuid,
+    pub goal_id: Uuid,
+    pub round_to: i32,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RoundUpRuleOutDTO {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub account_id: Uuid,
+    pub goal_id: Uuid,
+    pub round_to: i32,
+    pub created_at: chrono::NaiveDateTime,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RoundUpSummaryOutDTO {
+    pub goal_id: Uuid,
+    pub goal_title: String,
+    pub month: chrono::NaiveDate,
+    pub total: Money,
+}
@@ -1,23 +1,39 @@
 use rocket::serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::money::Money;
+
 #[derive(Debug, Deserialize)]
 pub struct SavingGoalInDTO {
     pub title: String,
-    pub target_amount: f64,
-    pub current_amount: f64,
+    pub target_amount: Money,
+    pub current_amount: Money,
     pub deadline: chrono::NaiveDate,
-    pub user_id: Uuid,
+}
+
+impl SavingGoalInDTO {
+    // Goals are kept in their owner's base currency.
+    pub fn validate(&self, currency: &str) -> Result<(), &'static str> {
+        if self.target_amount.round_to(currency) != self.target_amount {
+            return Err("Target amount has more decimals than its currency allows.");
+        }
+        if self.current_amount.round_to(currency) != self.current_amount {
+            return Err("Current amount has more decimals than its currency allows.");
+        }
+
+        Ok(())
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct SavingGoalOutDTO {
     pub id: Uuid,
     pub title: String,
-    pub target_amount: f64,
-    pub current_amount: f64,
+    pub target_amount: Money,
+    pub current_amount: Money,
     pub deadline: chrono::NaiveDate,
     pub user_id: Uuid,
     pub created_at: chrono::NaiveDateTime,
     pub updated_at: chrono::NaiveDateTime,
+    pub household_id: Option<Uuid>,
 }
@@ -0,0 +1,33 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::enums::custom_enums::RecurrenceFrequency;
+use crate::money::Money;
+
+// A charge that keeps coming back at a regular interval for about the same
+// amount. `payee` is the normalised name that, with the account, identifies
+// the candidate; `name` is how the latest charge was titled. `amount` is the
+// latest charge, and `annualized_cost` what a year of it comes to.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SubscriptionCandidateOutDTO {
+    pub account_id: Uuid,
+    pub payee: String,
+    pub name: String,
+    pub category_id: Uuid,
+    pub frequency: RecurrenceFrequency,
+    pub every: i32,
+    pub amount: Money,
+    pub currency: String,
+    pub charge_count: usize,
+    pub first_charge: NaiveDate,
+    pub last_charge: NaiveDate,
+    pub next_expected_charge: NaiveDate,
+    pub annualized_cost: Money,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct SubscriptionCandidateInDTO {
+    pub account_id: Uuid,
+    pub payee: String,
+}
@@ -1,27 +1,93 @@
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::enums::custom_enums::TransactionType;
+use crate::models::transactions::AccountAmount;
+use crate::money::{is_currency_code, Money};
 
 #[derive(Debug, Deserialize, Serialize)]
 pub struct TransactionInDTO {
     pub title: String,
-    pub amount: f64,
-    pub date: chrono::NaiveDateTime,
+    pub amount: Money,
+    pub date: chrono::DateTime<chrono::Utc>,
     pub category_id: Uuid,
     pub transaction_type: TransactionType,
-    pub user_id: Uuid,
     pub account_id: Uuid,
+    // The currency `amount` is in, the account's unless given.
+    pub currency: Option<String>,
+    // Units of the account's currency per unit of `currency`. Required when the
+    // two differ.
+    pub exchange_rate: Option<Decimal>,
+}
+
+impl TransactionInDTO {
+    pub fn validate(&self) -> Result<(), &'static str> {
+        if self
+            .currency
+            .as_deref()
+            .is_some_and(|currency| !is_currency_code(currency))
+        {
+            return Err("Currency must be a three letter ISO 4217 code.");
+        }
+        if self.exchange_rate.is_some_and(|rate| rate <= Decimal::ZERO) {
+            return Err("Exchange rate must be positive.");
+        }
+
+        Ok(())
+    }
+
+    pub fn in_account_currency(
+        &self,
+        account_currency: &str,
+    ) -> Result<AccountAmount, &'static str> {
+        let currency = self.currency.as_deref().unwrap_or(account_currency);
+        if self.amount.round_to(currency) != self.amount {
+            return Err("Amount has more decimals than its currency allows.");
+        }
+
+        match self.exchange_rate {
+            None if currency == account_currency => Ok(AccountAmount {
+                amount: self.amount,
+                currency: account_currency.to_string(),
+                original_amount: None,
+                original_currency: None,
+            }),
+            None => Err("Currency differs from the account's; an exchange_rate is required."),
+            Some(_) if currency == account_currency => {
+                Err("An exchange_rate is only accepted for a currency other than the account's.")
+            }
+            Some(rate) => Ok(AccountAmount {
+                amount: self.amount.convert(rate, account_currency),
+                currency: account_currency.to_string(),
+                original_amount: Some(self.amount),
+                original_currency: Some(currency.to_string()),
+            }),
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct TransactionOutDTO {
     pub id: Uuid,
     pub title: String,
-    pub amount: f64,
-    pub date: chrono::NaiveDateTime,
+    pub amount: Money,
+    pub currency: String,
+    pub original_amount: Option<Money>,
+    pub original_currency: Option<String>,
+    pub date: chrono::DateTime<chrono::Utc>,
     pub category_id: Uuid,
     pub transaction_type: TransactionType,
     pub user_id: Uuid,
     pub account_id: Uuid,
 }
+
+// A transaction in an account's listing. `running_balance` is what the account
+// held right after it, and is only included when asked for.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AccountTransactionOutDTO {
+    #[serde(flatten)]
+    pub transaction: TransactionOutDTO,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub running_balance: Option<Money>,
+}
@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TotpEnrollmentOutDTO {
+    pub secret: String,
+    pub provisioning_uri: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct TotpCodeInDTO {
+    pub code: String,
+}
+
+// Either a current authenticator code or one of the recovery codes.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct SecondFactorInDTO {
+    pub totp_code: Option<String>,
+    pub recovery_code: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RecoveryCodesOutDTO {
+    pub recovery_codes: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TwoFactorStatusOutDTO {
+    pub enabled: bool,
+    pub recovery_codes_remaining: i64,
+}
@@ -0,0 +1,86 @@
+use chrono::format::{Item, StrftimeItems};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+
+use crate::enums::custom_enums::WeekDay;
+use crate::money::is_currency_code;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UserPreferencesOutDTO {
+    pub base_currency: String,
+    pub locale: String,
+    pub timezone: String,
+    pub week_start: WeekDay,
+    pub fiscal_year_start_month: i32,
+    pub date_format: String,
+    pub updated_at: Option<chrono::NaiveDateTime>,
+}
+
+// Fields left out keep their current value.
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct UserPreferencesInDTO {
+    pub base_currency: Option<String>,
+    pub locale: Option<String>,
+    pub timezone: Option<String>,
+    pub week_start: Option<WeekDay>,
+    pub fiscal_year_start_month: Option<i32>,
+    pub date_format: Option<String>,
+}
+
+// A BCP 47 tag such as "en", "en-US" or "zh-Hant-TW".
+fn is_locale(locale: &str) -> bool {
+    let mut subtags = locale.split('-');
+    let language = subtags.next().unwrap_or_default();
+    (2..=3).contains(&language.len())
+        && language.chars().all(|c| c.is_ascii_alphabetic())
+        && subtags.all(|subtag| {
+            (2..=8).contains(&subtag.len()) && subtag.chars().all(|c| c.is_ascii_alphanumeric())
+        })
+}
+
+fn is_date_format(format: &str) -> bool {
+    !format.trim().is_empty()
+        && format.len() <= 64
+        && StrftimeItems::new(format).all(|item| item != Item::Error)
+}
+
+impl UserPreferencesInDTO {
+    pub fn validate(&self) -> Result<(), &'static str> {
+        if self
+            .base_currency
+            .as_deref()
+            .is_some_and(|currency| !is_currency_code(currency))
+        {
+            return Err("Base currency must be a three letter ISO 4217 code.");
+        }
+        if self
+            .locale
+            .as_deref()
+            .is_some_and(|locale| !is_locale(locale))
+        {
+            return Err("Locale must be a language tag such as en-US.");
+        }
+        if self
+            .timezone
+            .as_deref()
+            .is_some_and(|timezone| timezone.parse::<Tz>().is_err())
+        {
+            return Err("Timezone must be an IANA timezone such as Europe/Berlin.");
+        }
+        if self
+            .fiscal_year_start_month
+            .is_some_and(|month| !(1..=12).contains(&month))
+        {
+            return Err("Fiscal year start month must be between 1 and 12.");
+        }
+        if self
+            .date_format
+            .as_deref()
+            .is_some_and(|format| !is_date_format(format))
+        {
+            return Err("Date format is not a valid strftime format.");
+        }
+
+        Ok(())
+    }
+}
@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use std::str::FromStr;
 
 #[derive(sqlx::Type, Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
 #[sqlx(type_name = "transaction_type", rename_all = "PascalCase")]
@@ -7,8 +8,10 @@ pub enum TransactionType {
     Expense,
 }
 
-impl TransactionType {
-    pub fn from_str(s: &str) -> Result<TransactionType, &'static str> {
+impl FromStr for TransactionType {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<TransactionType, Self::Err> {
         match s {
             "Income" => Ok(TransactionType::Income),
             "Expense" => Ok(TransactionType::Expense),
@@ -25,8 +28,10 @@ pub enum AccountType {
     Card,
 }
 
-impl AccountType {
-    pub fn from_str(s: &str) -> Result<AccountType, &'static str> {
+impl FromStr for AccountType {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<AccountType, Self::Err> {
         match s {
             "bank" => Ok(AccountType::Bank),
             "cash" => Ok(AccountType::Cash),
@@ -36,6 +41,186 @@ impl AccountType {
     }
 }
 
+#[derive(sqlx::Type, Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[sqlx(type_name = "achievement_kind", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum AchievementKind {
+    Manual,
+    Milestone,
+    EarlyCompletion,
+}
+
+#[derive(sqlx::Type, Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[sqlx(type_name = "challenge_type", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum ChallengeType {
+    FiftyTwoWeek,
+    NoSpendMonth,
+    IncomePercentage,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ChallengeStatus {
+    InProgress,
+    Completed,
+    Failed,
+}
+
+#[derive(sqlx::Type, Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[sqlx(type_name = "contribution_source", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum ContributionSource {
+    Manual,
+    RoundUp,
+    Allocation,
+}
+
+#[derive(sqlx::Type, Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[sqlx(type_name = "allocation_target_type", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum AllocationTargetType {
+    SavingGoal,
+    Budget,
+    Account,
+}
+
+#[derive(sqlx::Type, Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[sqlx(type_name = "allocation_amount_type", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum AllocationAmountType {
+    Fixed,
+    Percentage,
+}
+
+#[derive(sqlx::Type, Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[sqlx(type_name = "household_role", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum HouseholdRole {
+    Owner,
+    Editor,
+    Viewer,
+}
+
+#[derive(sqlx::Type, Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[sqlx(type_name = "user_role", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum UserRole {
+    User,
+    Admin,
+}
+
+#[derive(sqlx::Type, Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[sqlx(type_name = "week_day", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum WeekDay {
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+    Sunday,
+}
+
+impl From<WeekDay> for chrono::Weekday {
+    fn from(day: WeekDay) -> chrono::Weekday {
+        match day {
+            WeekDay::Monday => chrono::Weekday::Mon,
+            WeekDay::Tuesday => chrono::Weekday::Tue,
+            WeekDay::Wednesday => chrono::Weekday::Wed,
+            WeekDay::Thursday => chrono::Weekday::Thu,
+            WeekDay::Friday => chrono::Weekday::Fri,
+            WeekDay::Saturday => chrono::Weekday::Sat,
+            WeekDay::Sunday => chrono::Weekday::Sun,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SharedRecordType {
+    Account,
+    Budget,
+    Category,
+    SavingGoal,
+}
+
+impl FromStr for SharedRecordType {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<SharedRecordType, Self::Err> {
+        match s {
+            "account" => Ok(SharedRecordType::Account),
+            "budget" => Ok(SharedRecordType::Budget),
+            "category" => Ok(SharedRecordType::Category),
+            "saving_goal" => Ok(SharedRecordType::SavingGoal),
+            _ => Err("Unknown record type"),
+        }
+    }
+}
+
+#[derive(sqlx::Type, Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[sqlx(type_name = "recurrence_frequency", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum RecurrenceFrequency {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportGranularity {
+    Day,
+    Week,
+    Month,
+    Year,
+}
+
+impl FromStr for ReportGranularity {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<ReportGranularity, Self::Err> {
+        match s {
+            "day" => Ok(ReportGranularity::Day),
+            "week" => Ok(ReportGranularity::Week),
+            "month" => Ok(ReportGranularity::Month),
+            "year" => Ok(ReportGranularity::Year),
+            _ => Err("Granularity must be one of day, week, month or year."),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportBreakdown {
+    Account,
+    Category,
+}
+
+impl FromStr for ReportBreakdown {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<ReportBreakdown, Self::Err> {
+        match s {
+            "account" => Ok(ReportBreakdown::Account),
+            "category" => Ok(ReportBreakdown::Category),
+            _ => Err("Breakdown must be either account or category."),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum AnomalyKind {
+    CategoryOutlier,
+    PayeeOutlier,
+    NewPayee,
+    CategorySpike,
+}
+
 #[derive(sqlx::Type, Debug, Clone, PartialEq)]
 #[sqlx(type_name = "INT4", rename_all = "snake_case")]
 pub enum CategoryEnum {
@@ -59,8 +244,8 @@ impl From<i32> for CategoryEnum {
     }
 }
 
-impl Into<i32> for CategoryEnum {
-    fn into(self) -> i32 {
-        self as i32
+impl From<CategoryEnum> for i32 {
+    fn from(val: CategoryEnum) -> Self {
+        val as i32
     }
 }
@@ -0,0 +1,131 @@
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use std::str::FromStr;
+
+use crate::money::is_currency_code;
+
+// One rate from an imported file, quoted against the file's base currency.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportedRate {
+    pub date: NaiveDate,
+    pub currency: String,
+    pub rate: Decimal,
+}
+
+// Reads either of the formats the ECB publishes its reference rates in,
+// telling them apart by the first character.
+pub fn parse_rates(body: &str) -> Result<Vec<ImportedRate>, &'static str> {
+    if body.trim_start().starts_with('<') {
+        parse_ecb_xml(body)
+    } else {
+        parse_ecb_csv(body)
+    }
+}
+
+fn parse_rate(currency: &str, rate: &str) -> Result<Decimal, &'static str> {
+    if !is_currency_code(currency) {
+        return Err("Currencies must be three letter ISO 4217 codes.");
+    }
+    match Decimal::from_str(rate.trim()) {
+        Ok(rate) if rate > Decimal::ZERO => Ok(rate),
+        _ => Err("Rates must be positive numbers."),
+    }
+}
+
+fn parse_date(date: &str) -> Result<NaiveDate, &'static str> {
+    NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d")
+        .map_err(|_| "Dates must look like 2024-01-31.")
+}
+
+// The value of `name="..."` or `name='...'` inside a tag.
+fn attribute<'a>(tag: &'a str, name: &str) -> Option<&'a str> {
+    let mut rest = tag;
+    while let Some(index) = rest.find(name) {
+        let before = rest[..index].chars().next_back();
+        let after = rest[index + name.len()..].trim_start();
+        rest = &rest[index + name.len()..];
+        if !before.is_some_and(char::is_whitespace) {
+            continue;
+        }
+        let Some(value) = after.strip_prefix('=') else {
+            continue;
+        };
+        let value = value.trim_start();
+        let quote = value.chars().next()?;
+        if quote != '"' && quote != '\'' {
+            return None;
+        }
+        return value[1..].split(quote).next();
+    }
+    None
+}
+
+// The eurofxref daily and historical XML files: `Cube` elements carrying a
+// `time` wrap `Cube` elements carrying a `currency` and a `rate`.
+pub fn parse_ecb_xml(xml: &str) -> Result<Vec<ImportedRate>, &'static str> {
+    let mut rates = Vec::new();
+    let mut date = None;
+
+    for element in xml.split("<Cube").skip(1) {
+        let Some(end) = element.find('>') else {
+            return Err("The XML file is cut off.");
+        };
+        let tag = &element[..end];
+        if let Some(time) = attribute(tag, "time") {
+            date = Some(parse_date(time)?);
+        }
+        if let (Some(currency), Some(rate)) = (attribute(tag, "currency"), attribute(tag, "rate")) {
+            let Some(date) = date else {
+                return Err("A rate appears before any date.");
+            };
+            rates.push(ImportedRate {
+                date,
+                currency: currency.to_string(),
+                rate: parse_rate(currency, rate)?,
+            });
+        }
+    }
+
+    if rates.is_empty() {
+        return Err("The file contains no rates.");
+    }
+    Ok(rates)
+}
+
+// The eurofxref CSV files: a `Date` column followed by one column per
+// currency. Currencies that weren't quoted on a day are marked N/A.
+pub fn parse_ecb_csv(csv: &str) -> Result<Vec<ImportedRate>, &'static str> {
+    let mut lines = csv.lines().filter(|line| !line.trim().is_empty());
+    let Some(header) = lines.next() else {
+        return Err("The file contains no rates.");
+    };
+    let columns: Vec<&str> = header.split(',').map(str::trim).collect();
+    if !columns
+        .first()
+        .is_some_and(|column| column.eq_ignore_ascii_case("date"))
+    {
+        return Err("The first CSV column must be Date.");
+    }
+
+    let mut rates = Vec::new();
+    for line in lines {
+        let mut values = line.split(',');
+        let date = parse_date(values.next().unwrap_or_default())?;
+        for (currency, value) in columns[1..].iter().zip(values) {
+            let value = value.trim();
+            if currency.is_empty() || value.is_empty() || value == "N/A" {
+                continue;
+            }
+            rates.push(ImportedRate {
+                date,
+                currency: currency.to_string(),
+                rate: parse_rate(currency, value)?,
+            });
+        }
+    }
+
+    if rates.is_empty() {
+        return Err("The file contains no rates.");
+    }
+    Ok(rates)
+}
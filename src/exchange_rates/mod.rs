@@ -0,0 +1,101 @@
+pub mod import;
+
+use chrono::{Duration, NaiveDate};
+use rust_decimal::Decimal;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+use crate::{models::exchange_rate::ExchangeRate, money::Money};
+
+// Rates aren't published on weekends and holidays, so a day without one falls
+// back to the latest earlier rate, as long as it is at most this old.
+pub const MAX_RATE_AGE_DAYS: i64 = 7;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MissingRate {
+    pub from: String,
+    pub to: String,
+    pub date: NaiveDate,
+}
+
+impl fmt::Display for MissingRate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "No exchange rate from {} to {} within {} days before {}.",
+            self.from, self.to, MAX_RATE_AGE_DAYS, self.date
+        )
+    }
+}
+
+// Published rates indexed by currency pair and day.
+#[derive(Debug, Default)]
+pub struct RateTable {
+    rates: HashMap<(String, String), BTreeMap<NaiveDate, Decimal>>,
+}
+
+impl RateTable {
+    pub fn new(rates: &[ExchangeRate]) -> RateTable {
+        let mut table = RateTable::default();
+        for rate in rates {
+            table
+                .rates
+                .entry((rate.base_currency.clone(), rate.quote_currency.clone()))
+                .or_default()
+                .insert(rate.rate_date, rate.rate);
+        }
+        table
+    }
+
+    fn published(&self, base: &str, quote: &str, date: NaiveDate) -> Option<Decimal> {
+        let oldest = date - Duration::days(MAX_RATE_AGE_DAYS);
+        self.rates
+            .get(&(base.to_string(), quote.to_string()))?
+            .range(oldest..=date)
+            .next_back()
+            .map(|(_, rate)| *rate)
+    }
+
+    // Units of `to` per unit of `from` on the given day. Pairs that weren't
+    // published directly are inverted or crossed through a shared base, the
+    // way ECB rates all go through the euro.
+    pub fn rate(&self, from: &str, to: &str, date: NaiveDate) -> Option<Decimal> {
+        if from == to {
+            return Some(Decimal::ONE);
+        }
+        if let Some(rate) = self.published(from, to, date) {
+            return Some(rate);
+        }
+        if let Some(rate) = self.published(to, from, date) {
+            return Some(Decimal::ONE / rate);
+        }
+
+        self.rates
+            .keys()
+            .filter(|(_, quote)| quote == from)
+            .find_map(|(base, _)| {
+                let base_to_from = self.published(base, from, date)?;
+                let base_to_to = self.published(base, to, date)?;
+                Some(base_to_to / base_to_from)
+            })
+    }
+
+    pub fn convert(
+        &self,
+        amount: Money,
+        from: &str,
+        to: &str,
+        date: NaiveDate,
+    ) -> Result<Money, MissingRate> {
+        if from == to {
+            return Ok(amount);
+        }
+        self.rate(from, to, date)
+            .map(|rate| amount.convert(rate, to))
+            .ok_or_else(|| MissingRate {
+                from: from.to_string(),
+                to: to.to_string(),
+                date,
+            })
+    }
+}
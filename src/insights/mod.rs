@@ -0,0 +1,286 @@
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use uuid::Uuid;
+
+use crate::{
+    dtos::insight_dtos::{AnomalyOutDTO, AnomalyStatisticsOutDTO},
+    enums::custom_enums::AnomalyKind,
+    models::{categories::Category, subscription::Charge},
+    money::{minor_units, Money},
+    subscriptions::payee_key,
+};
+
+// How much earlier spending expenses are compared with.
+pub const BASELINE_DAYS: i64 = 180;
+// An expense is an outlier once it is this many times the median and above
+// anything seen before.
+const OUTLIER_MULTIPLE: Decimal = Decimal::from_parts(3, 0, 0, false, 0);
+const MIN_CATEGORY_SAMPLES: usize = 5;
+const MIN_PAYEE_SAMPLES: usize = 3;
+// Earlier expenses needed before a first payment can count as large.
+const MIN_NEW_PAYEE_SAMPLES: usize = 10;
+// A category spikes when it is this many times its usual pace.
+const SPIKE_MULTIPLE: Decimal = Decimal::from_parts(15, 0, 0, false, 1);
+const MIN_SPIKE_SAMPLES: usize = 3;
+
+fn shown(amount: Money, currency: &str) -> String {
+    format!(
+        "{:.*} {}",
+        minor_units(currency) as usize,
+        amount.amount(),
+        currency
+    )
+}
+
+fn ratio(amount: Money, whole: Money) -> Option<Decimal> {
+    if whole.is_zero() {
+        return None;
+    }
+    Some((amount.amount() / whole.amount()).round_dp(2))
+}
+
+// Earlier amounts, sorted.
+struct Baseline(Vec<Money>);
+
+impl Baseline {
+    fn median(&self) -> Money {
+        self.0[(self.0.len() - 1) / 2]
+    }
+
+    fn maximum(&self) -> Money {
+        self.0[self.0.len() - 1]
+    }
+
+    fn percentile_90(&self) -> Money {
+        self.0[(self.0.len() * 9).div_ceil(10) - 1]
+    }
+
+    // The statistics behind the flag when `amount` stands out.
+    fn outlier(&self, amount: Money, min_samples: usize) -> Option<AnomalyStatisticsOutDTO> {
+        if self.0.len() < min_samples || !self.median().is_positive() {
+            return None;
+        }
+        let threshold = (self.median() * OUTLIER_MULTIPLE).max(self.maximum());
+        (amount > threshold).then(|| AnomalyStatisticsOutDTO {
+            sample_size: self.0.len(),
+            median: Some(self.median()),
+            maximum: Some(self.maximum()),
+            percentile_90: None,
+            expected: None,
+            threshold,
+            ratio: ratio(amount, self.median()),
+        })
+    }
+}
+
+fn baselines<K: std::hash::Hash + Eq>(
+    amounts: impl Iterator<Item = (K, Money)>,
+) -> HashMap<K, Baseline> {
+    let mut baselines: HashMap<K, Vec<Money>> = HashMap::new();
+    for (key, amount) in amounts {
+        baselines.entry(key).or_default().push(amount);
+    }
+    baselines
+        .into_iter()
+        .map(|(key, mut amounts)| {
+            amounts.sort();
+            (key, Baseline(amounts))
+        })
+        .collect()
+}
+
+// Expenses between `from` and `to` that are out of line with the ones before
+// `from`: far above the usual for their category or payee, large first
+// payments to a payee, and categories spent on much faster than usual.
+// `charges` must be in date order; amounts are only compared within a
+// currency.
+pub fn detect_anomalies(
+    charges: &[Charge],
+    categories: &[Category],
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Vec<AnomalyOutDTO> {
+    let category_names: HashMap<Uuid, &str> = categories
+        .iter()
+        .map(|category| (category.id, category.name.as_str()))
+        .collect();
+    let category_name = |id: &Uuid| category_names.get(id).copied().unwrap_or_default();
+
+    let (earlier, current): (Vec<&Charge>, Vec<&Charge>) = charges
+        .iter()
+        .filter(|charge| charge.day <= to)
+        .partition(|charge| charge.day < from);
+
+    let by_category = baselines(
+        earlier
+            .iter()
+            .map(|c| ((c.category_id, c.currency.as_str()), c.amount)),
+    );
+    let by_payee = baselines(
+        earlier
+            .iter()
+            .map(|c| ((payee_key(&c.title), c.currency.as_str()), c.amount)),
+    );
+    let overall = baselines(earlier.iter().map(|c| (c.currency.as_str(), c.amount)));
+    let mut seen_payees: HashSet<String> = earlier.iter().map(|c| payee_key(&c.title)).collect();
+
+    let mut anomalies = Vec::new();
+    for charge in &current {
+        let flag = |kind, payee: Option<&str>, explanation, statistics| AnomalyOutDTO {
+            kind,
+            transaction_id: Some(charge.transaction_id),
+            date: Some(charge.day),
+            category_id: charge.category_id,
+            category_name: category_name(&charge.category_id).to_string(),
+            payee: payee.map(str::to_string),
+            amount: charge.amount,
+            currency: charge.currency.clone(),
+            explanation,
+            statistics,
+        };
+        let amount = shown(charge.amount, &charge.currency);
+        let payee = payee_key(&charge.title);
+
+        if let Some(statistics) = by_category
+            .get(&(charge.category_id, charge.currency.as_str()))
+            .and_then(|baseline| baseline.outlier(charge.amount, MIN_CATEGORY_SAMPLES))
+        {
+            let explanation = format!(
+                "{} is {} times the usual {} spent on {}, the median of {} earlier expenses.",
+                amount,
+                statistics.ratio.unwrap_or_default(),
+                shown(statistics.median.unwrap_or_default(), &charge.currency),
+                category_name(&charge.category_id),
+                statistics.sample_size
+            );
+            anomalies.push(flag(
+                AnomalyKind::CategoryOutlier,
+                None,
+                explanation,
+                statistics,
+            ));
+        }
+
+        if payee.is_empty() {
+            continue;
+        }
+        if let Some(statistics) = by_payee
+            .get(&(payee.clone(), charge.currency.as_str()))
+            .and_then(|baseline| baseline.outlier(charge.amount, MIN_PAYEE_SAMPLES))
+        {
+            let explanation = format!(
+                "{} to {} is {} times the usual {}, the median of {} earlier payments.",
+                amount,
+                charge.title.trim(),
+                statistics.ratio.unwrap_or_default(),
+                shown(statistics.median.unwrap_or_default(), &charge.currency),
+                statistics.sample_size
+            );
+            anomalies.push(flag(
+                AnomalyKind::PayeeOutlier,
+                Some(&payee),
+                explanation,
+                statistics,
+            ));
+        }
+
+        if seen_payees.insert(payee.clone()) {
+            let Some(baseline) = overall.get(charge.currency.as_str()) else {
+                continue;
+            };
+            if baseline.0.len() < MIN_NEW_PAYEE_SAMPLES || charge.amount <= baseline.percentile_90()
+            {
+                continue;
+            }
+            let explanation = format!(
+                "First payment to {}, and at {} it is larger than 90% of the {} earlier expenses.",
+                charge.title.trim(),
+                amount,
+                baseline.0.len()
+            );
+            let statistics = AnomalyStatisticsOutDTO {
+                sample_size: baseline.0.len(),
+                median: Some(baseline.median()),
+                maximum: None,
+                percentile_90: Some(baseline.percentile_90()),
+                expected: None,
+                threshold: baseline.percentile_90(),
+                ratio: ratio(charge.amount, baseline.median()),
+            };
+            anomalies.push(flag(
+                AnomalyKind::NewPayee,
+                Some(&payee),
+                explanation,
+                statistics,
+            ));
+        }
+    }
+
+    // Spikes compare the period's total with the pace of the baseline, so
+    // they need at least as much history as the period is long.
+    let Some(first_day) = earlier.first().map(|charge| charge.day) else {
+        return anomalies;
+    };
+    let baseline_days = (from - first_day).num_days();
+    let period_days = (to - from).num_days() + 1;
+    if baseline_days < period_days {
+        return anomalies;
+    }
+
+    let mut totals: BTreeMap<(&str, Uuid, &str), Money> = BTreeMap::new();
+    for charge in &current {
+        *totals
+            .entry((
+                category_name(&charge.category_id),
+                charge.category_id,
+                charge.currency.as_str(),
+            ))
+            .or_default() += charge.amount;
+    }
+    for ((name, category_id, currency), total) in totals {
+        let Some(baseline) = by_category.get(&(category_id, currency)) else {
+            continue;
+        };
+        if baseline.0.len() < MIN_SPIKE_SAMPLES {
+            continue;
+        }
+        let spent = baseline.0.iter().copied().sum::<Money>().amount();
+        let expected =
+            Money::new(spent * Decimal::from(period_days) / Decimal::from(baseline_days))
+                .round_to(currency);
+        let threshold = (expected * SPIKE_MULTIPLE).round_to(currency);
+        if !expected.is_positive() || total <= threshold {
+            continue;
+        }
+        let statistics = AnomalyStatisticsOutDTO {
+            sample_size: baseline.0.len(),
+            median: None,
+            maximum: None,
+            percentile_90: None,
+            expected: Some(expected),
+            threshold,
+            ratio: ratio(total, expected),
+        };
+        anomalies.push(AnomalyOutDTO {
+            kind: AnomalyKind::CategorySpike,
+            transaction_id: None,
+            date: None,
+            category_id,
+            category_name: name.to_string(),
+            payee: None,
+            amount: total,
+            currency: currency.to_string(),
+            explanation: format!(
+                "{} spent on {} is {} times the {} the previous {} days would lead to expect.",
+                shown(total, currency),
+                name,
+                statistics.ratio.unwrap_or_default(),
+                shown(expected, currency),
+                baseline_days
+            ),
+            statistics,
+        });
+    }
+    anomalies
+}
@@ -0,0 +1,98 @@
+use rocket::{error, fairing::AdHoc};
+use sqlx::PgPool;
+use std::{env, future::Future, time::Duration};
+
+use crate::operations::{
+    badge_ops::evaluate_all_badges, balance_snapshot_ops::take_balance_snapshots,
+    session_ops::delete_expired_sessions,
+};
+
+const DEFAULT_BADGE_INTERVAL_SECS: u64 = 24 * 60 * 60;
+const DEFAULT_SESSION_SWEEP_INTERVAL_SECS: u64 = 60 * 60;
+// Snapshots are kept per account and day, so running more often than daily
+// only refreshes today's and makes sure a restart doesn't skip a day.
+const DEFAULT_BALANCE_SNAPSHOT_INTERVAL_SECS: u64 = 60 * 60;
+
+fn interval_from_env(key: &str, default_secs: u64) -> Duration {
+    let secs = env::var(key)
+        .ok()
+        .and_then(|value| value.parse::<u64>().ok())
+        .filter(|secs| *secs > 0)
+        .unwrap_or(default_secs);
+    Duration::from_secs(secs)
+}
+
+// Runs `job` every `period` for as long as the server is up. The first run
+// happens one period after launch so startup isn't slowed down.
+fn spawn_periodic<F, Fut>(name: &'static str, pool: PgPool, period: Duration, job: F)
+where
+    F: Fn(PgPool) -> Fut + Send + 'static,
+    Fut: Future<Output = Result<(), sqlx::Error>> + Send,
+{
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval_at(tokio::time::Instant::now() + period, period);
+        loop {
+            interval.tick().await;
+            if let Err(e) = job(pool.clone()).await {
+                error!("{} failed: {}", name, e);
+            }
+        }
+    });
+}
+
+pub fn badge_evaluator() -> AdHoc {
+    AdHoc::on_liftoff("Badge evaluator", |rocket| {
+        Box::pin(async move {
+            dotenv::dotenv().ok();
+            if let Some(pool) = rocket.state::<PgPool>() {
+                let period = interval_from_env(
+                    "BADGE_EVALUATION_INTERVAL_SECS",
+                    DEFAULT_BADGE_INTERVAL_SECS,
+                );
+                spawn_periodic(
+                    "Badge evaluation",
+                    pool.clone(),
+                    period,
+                    |pool| async move { evaluate_all_badges(&pool).await },
+                );
+            }
+        })
+    })
+}
+
+pub fn session_sweeper() -> AdHoc {
+    AdHoc::on_liftoff("Session sweeper", |rocket| {
+        Box::pin(async move {
+            dotenv::dotenv().ok();
+            if let Some(pool) = rocket.state::<PgPool>() {
+                let period = interval_from_env(
+                    "SESSION_SWEEP_INTERVAL_SECS",
+                    DEFAULT_SESSION_SWEEP_INTERVAL_SECS,
+                );
+                spawn_periodic("Session sweep", pool.clone(), period, |pool| async move {
+                    delete_expired_sessions(&pool).await.map(|_| ())
+                });
+            }
+        })
+    })
+}
+
+pub fn balance_snapshotter() -> AdHoc {
+    AdHoc::on_liftoff("Balance snapshotter", |rocket| {
+        Box::pin(async move {
+            dotenv::dotenv().ok();
+            if let Some(pool) = rocket.state::<PgPool>() {
+                let period = interval_from_env(
+                    "BALANCE_SNAPSHOT_INTERVAL_SECS",
+                    DEFAULT_BALANCE_SNAPSHOT_INTERVAL_SECS,
+                );
+                spawn_periodic(
+                    "Balance snapshot",
+                    pool.clone(),
+                    period,
+                    |pool| async move { take_balance_snapshots(&pool).await.map(|_| ()) },
+                );
+            }
+        })
+    })
+}
@@ -1,9 +1,21 @@
+pub mod auth;
+pub mod badges;
+pub mod calendar;
+pub mod challenges;
+pub mod date_param;
 pub mod db;
 pub mod dtos;
 pub mod enums;
+pub mod exchange_rates;
+pub mod insights;
+pub mod jobs;
 pub mod models;
+pub mod money;
 pub mod operations;
+pub mod reports;
 pub mod routes;
+pub mod rules;
+pub mod subscriptions;
 pub mod uuid_param;
 // // ... other modules ...
 // #[macro_use]
@@ -12,8 +24,16 @@ pub mod uuid_param;
 use rocket::Build;
 use routes::{
     account_routes::account_routes, achievement_routes::achievement_routes,
+    admin_routes::admin_routes, allocation_routes::allocation_routes,
+    api_token_routes::api_token_routes, auth_routes::auth_routes, badge_routes::badge_routes,
     budget_routes::budget_routes, category_routes::category_routes,
-    saving_goal_routes::saving_goal_routes, transaction_routes::transaction_routes, user_routes::*,
+    challenge_routes::challenge_routes, exchange_rate_routes::exchange_rate_routes,
+    household_routes::household_routes, insight_routes::insight_routes, oidc_routes::oidc_routes,
+    recurring_transaction_routes::recurring_transaction_routes, report_routes::report_routes,
+    round_up_routes::round_up_routes, saving_goal_routes::saving_goal_routes,
+    subscription_routes::subscription_routes, transaction_routes::transaction_routes,
+    two_factor_routes::two_factor_routes, user_preferences_routes::user_preferences_routes,
+    user_routes::*,
 };
 use sqlx::PgPool;
 
@@ -21,10 +41,30 @@ pub fn create_rocket(pool: PgPool) -> rocket::Rocket<Build> {
     rocket::build()
         .manage(pool)
         .mount("/", user_routes())
+        .mount("/", user_preferences_routes())
+        .mount("/", auth_routes())
+        .mount("/", oidc_routes())
+        .mount("/", two_factor_routes())
+        .mount("/", api_token_routes())
+        .mount("/", household_routes())
+        .mount("/", admin_routes())
         .mount("/accounts", account_routes())
         .mount("/", transaction_routes())
         .mount("/", category_routes())
         .mount("/", budget_routes())
         .mount("/", saving_goal_routes())
         .mount("/", achievement_routes())
+        .mount("/", badge_routes())
+        .mount("/", challenge_routes())
+        .mount("/", round_up_routes())
+        .mount("/", allocation_routes())
+        .mount("/", recurring_transaction_routes())
+        .mount("/", subscription_routes())
+        .mount("/", insight_routes())
+        .mount("/", exchange_rate_routes())
+        .mount("/", report_routes())
+        .attach(auth::impersonation::impersonation_auditor())
+        .attach(jobs::badge_evaluator())
+        .attach(jobs::session_sweeper())
+        .attach(jobs::balance_snapshotter())
 }
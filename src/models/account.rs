@@ -1,15 +1,21 @@
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::{dtos::account_dtos::AccountOutDTO, enums::custom_enums::AccountType};
+use crate::{
+    dtos::account_dtos::{AccountOutDTO, ConvertedBalanceOutDTO, CurrencyBalanceOutDTO},
+    enums::custom_enums::AccountType,
+    money::Money,
+};
 
 #[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
 pub struct Account {
     pub id: Uuid,
     pub name: String,
     pub account_type: AccountType,
-    pub balance: f64,
+    pub balance: Money,
+    pub currency: String,
     pub user_id: Uuid,
+    pub household_id: Option<Uuid>,
 }
 
 impl Account {
@@ -17,9 +23,36 @@ impl Account {
         AccountOutDTO {
             id: self.id,
             name: self.name.clone(),
-            account_type: self.account_type.clone(),
+            account_type: self.account_type,
             balance: self.balance,
+            currency: self.currency.clone(),
             user_id: self.user_id,
+            household_id: self.household_id,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
+pub struct CurrencyBalance {
+    pub currency: String,
+    pub balance: Money,
+    pub accounts: i64,
+}
+
+impl CurrencyBalance {
+    pub fn to_currency_balance_out_dto(&self) -> CurrencyBalanceOutDTO {
+        CurrencyBalanceOutDTO {
+            currency: self.currency.clone(),
+            balance: self.balance,
+            accounts: self.accounts,
+        }
+    }
+    pub fn to_converted_balance_out_dto(&self, converted_balance: Money) -> ConvertedBalanceOutDTO {
+        ConvertedBalanceOutDTO {
+            currency: self.currency.clone(),
+            balance: self.balance,
+            accounts: self.accounts,
+            converted_balance,
         }
     }
 }
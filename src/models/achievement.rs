@@ -1,14 +1,19 @@
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::dtos::achievement_dtos::AchievementOutDTO;
+use crate::{
+    dtos::achievement_dtos::AchievementOutDTO, enums::custom_enums::AchievementKind, money::Money,
+};
 
 #[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
 pub struct Achievement {
     pub id: Uuid,
     pub goal_id: Uuid,
-    pub date_achieved: chrono::NaiveDateTime,
-    pub amount_saved: f64,
+    pub date_achieved: chrono::DateTime<chrono::Utc>,
+    pub amount_saved: Money,
+    pub kind: AchievementKind,
+    pub milestone: Option<i32>,
+    pub seen_at: Option<chrono::NaiveDateTime>,
 }
 
 impl Achievement {
@@ -18,6 +23,8 @@ impl Achievement {
             goal_id: self.goal_id,
             date_achieved: self.date_achieved,
             amount_saved: self.amount_saved,
+            kind: self.kind,
+            milestone: self.milestone,
         }
     }
 }
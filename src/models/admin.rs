@@ -0,0 +1,66 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::dtos::admin_dtos::{ImpersonationLogEntryOutDTO, UserOverviewOutDTO};
+use crate::enums::custom_enums::UserRole;
+
+// A user as admins see them, with a summary of how much they use the app.
+#[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
+pub struct UserOverview {
+    pub id: Uuid,
+    pub username: String,
+    pub email: String,
+    pub role: UserRole,
+    pub created_at: chrono::NaiveDateTime,
+    pub suspended_at: Option<chrono::NaiveDateTime>,
+    pub last_seen_at: Option<chrono::NaiveDateTime>,
+    pub active_sessions: i64,
+    pub account_count: i64,
+    pub transaction_count: i64,
+    pub saving_goal_count: i64,
+}
+
+impl UserOverview {
+    pub fn to_user_overview_out_dto(&self) -> UserOverviewOutDTO {
+        UserOverviewOutDTO {
+            id: self.id,
+            username: self.username.clone(),
+            email: self.email.clone(),
+            role: self.role,
+            created_at: self.created_at,
+            suspended_at: self.suspended_at,
+            last_seen_at: self.last_seen_at,
+            active_sessions: self.active_sessions,
+            account_count: self.account_count,
+            transaction_count: self.transaction_count,
+            saving_goal_count: self.saving_goal_count,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
+pub struct ImpersonationLogEntry {
+    pub id: Uuid,
+    pub impersonator_id: Uuid,
+    pub user_id: Uuid,
+    pub session_id: Uuid,
+    pub method: String,
+    pub path: String,
+    pub status: i32,
+    pub created_at: chrono::NaiveDateTime,
+}
+
+impl ImpersonationLogEntry {
+    pub fn to_impersonation_log_entry_out_dto(&self) -> ImpersonationLogEntryOutDTO {
+        ImpersonationLogEntryOutDTO {
+            id: self.id,
+            impersonator_id: self.impersonator_id,
+            user_id: self.user_id,
+            session_id: self.session_id,
+            method: self.method.clone(),
+            path: self.path.clone(),
+            status: self.status,
+            created_at: self.created_at,
+        }
+    }
+}
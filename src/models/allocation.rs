@@ -0,0 +1,90 @@
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    dtos::allocation_dtos::{AllocationOutDTO, AllocationRuleOutDTO, AllocationTargetOutDTO},
+    enums::custom_enums::{AllocationAmountType, AllocationTargetType},
+    money::Money,
+};
+
+#[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
+pub struct AllocationRule {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub name: String,
+    pub match_title: Option<String>,
+    pub match_account_id: Option<Uuid>,
+    pub priority: i32,
+    pub auto_apply: bool,
+    pub created_at: chrono::NaiveDateTime,
+}
+
+impl AllocationRule {
+    pub fn to_allocation_rule_out_dto(
+        &self,
+        targets: &[AllocationRuleTarget],
+    ) -> AllocationRuleOutDTO {
+        AllocationRuleOutDTO {
+            id: self.id,
+            user_id: self.user_id,
+            name: self.name.clone(),
+            match_title: self.match_title.clone(),
+            match_account_id: self.match_account_id,
+            priority: self.priority,
+            auto_apply: self.auto_apply,
+            targets: targets
+                .iter()
+                .map(|target| target.to_allocation_target_out_dto())
+                .collect(),
+            created_at: self.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
+pub struct AllocationRuleTarget {
+    pub id: Uuid,
+    pub rule_id: Uuid,
+    pub position: i32,
+    pub target_type: AllocationTargetType,
+    pub target_id: Uuid,
+    pub amount_type: AllocationAmountType,
+    pub value: Decimal,
+}
+
+impl AllocationRuleTarget {
+    pub fn to_allocation_target_out_dto(&self) -> AllocationTargetOutDTO {
+        AllocationTargetOutDTO {
+            target_type: self.target_type,
+            target_id: self.target_id,
+            amount_type: self.amount_type,
+            value: self.value,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
+pub struct Allocation {
+    pub id: Uuid,
+    pub source_transaction_id: Uuid,
+    pub rule_id: Option<Uuid>,
+    pub target_type: AllocationTargetType,
+    pub target_id: Uuid,
+    pub amount: Money,
+    pub created_at: chrono::NaiveDateTime,
+}
+
+impl Allocation {
+    pub fn to_allocation_out_dto(&self) -> AllocationOutDTO {
+        AllocationOutDTO {
+            id: self.id,
+            source_transaction_id: self.source_transaction_id,
+            rule_id: self.rule_id,
+            target_type: self.target_type,
+            target_id: self.target_id,
+            amount: self.amount,
+            created_at: self.created_at,
+        }
+    }
+}
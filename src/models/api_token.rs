@@ -0,0 +1,31 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::dtos::api_token_dtos::ApiTokenOutDTO;
+
+#[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
+pub struct ApiToken {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub name: String,
+    pub token_prefix: String,
+    pub token_hash: String,
+    pub scopes: Vec<String>,
+    pub expires_at: chrono::NaiveDateTime,
+    pub last_used_at: Option<chrono::NaiveDateTime>,
+    pub created_at: chrono::NaiveDateTime,
+}
+
+impl ApiToken {
+    pub fn to_api_token_out_dto(&self) -> ApiTokenOutDTO {
+        ApiTokenOutDTO {
+            id: self.id,
+            name: self.name.clone(),
+            token_prefix: self.token_prefix.clone(),
+            scopes: self.scopes.clone(),
+            expires_at: self.expires_at,
+            last_used_at: self.last_used_at,
+            created_at: self.created_at,
+        }
+    }
+}
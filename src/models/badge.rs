@@ -0,0 +1,53 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    badges::{find_badge, progress_percentage},
+    dtos::badge_dtos::{BadgeOutDTO, StreakOutDTO},
+};
+
+#[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
+pub struct UserBadge {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub badge_code: String,
+    pub progress: i32,
+    pub earned_at: Option<chrono::NaiveDateTime>,
+    pub updated_at: chrono::NaiveDateTime,
+}
+
+impl UserBadge {
+    pub fn to_badge_out_dto(&self) -> Option<BadgeOutDTO> {
+        let badge = find_badge(&self.badge_code)?;
+        Some(BadgeOutDTO {
+            code: self.badge_code.clone(),
+            name: badge.name.to_string(),
+            description: badge.description.to_string(),
+            progress: self.progress,
+            target: badge.target,
+            progress_percentage: progress_percentage(self.progress, badge.target),
+            earned: self.earned_at.is_some(),
+            earned_at: self.earned_at,
+        })
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
+pub struct UserStreak {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub streak_code: String,
+    pub current_length: i32,
+    pub longest_length: i32,
+    pub updated_at: chrono::NaiveDateTime,
+}
+
+impl UserStreak {
+    pub fn to_streak_out_dto(&self) -> StreakOutDTO {
+        StreakOutDTO {
+            code: self.streak_code.clone(),
+            current: self.current_length,
+            longest: self.longest_length,
+        }
+    }
+}
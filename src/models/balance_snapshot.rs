@@ -0,0 +1,15 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{enums::custom_enums::AccountType, money::Money};
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct BalanceSnapshot {
+    pub account_id: Uuid,
+    pub snapshot_date: NaiveDate,
+    pub account_type: AccountType,
+    pub balance: Money,
+    pub currency: String,
+    pub taken_at: NaiveDateTime,
+}
@@ -2,16 +2,18 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::dtos::budget_dtos::BudgetOutDTO;
+use crate::money::Money;
 
 #[derive(Debug, Deserialize, Serialize, sqlx::FromRow)]
 pub struct Budget {
     pub id: Uuid,
     pub name: String,
-    pub amount: f64,
-    pub start_date: chrono::NaiveDateTime,
-    pub end_date: chrono::NaiveDateTime,
+    pub amount: Money,
+    pub start_date: chrono::DateTime<chrono::Utc>,
+    pub end_date: chrono::DateTime<chrono::Utc>,
     pub user_id: Uuid,
     pub category_id: Option<Uuid>,
+    pub household_id: Option<Uuid>,
 }
 
 impl Budget {
@@ -24,6 +26,7 @@ impl Budget {
             end_date: self.end_date,
             user_id: self.user_id,
             category_id: self.category_id,
+            household_id: self.household_id,
         }
     }
 }
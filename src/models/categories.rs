@@ -7,6 +7,7 @@ pub struct Category {
     pub id: uuid::Uuid,
     pub name: String,
     pub user_id: uuid::Uuid,
+    pub household_id: Option<uuid::Uuid>,
 }
 
 impl Category {
@@ -15,6 +16,7 @@ impl Category {
             id: self.id,
             name: self.name.clone(),
             user_id: self.user_id,
+            household_id: self.household_id,
         }
     }
 }
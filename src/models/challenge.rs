@@ -0,0 +1,38 @@
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    dtos::challenge_dtos::ChallengeOutDTO, enums::custom_enums::ChallengeType, money::Money,
+};
+
+#[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
+pub struct SavingChallenge {
+    pub id: Uuid,
+    pub challenge_type: ChallengeType,
+    pub goal_id: Uuid,
+    pub user_id: Uuid,
+    pub start_date: chrono::NaiveDate,
+    pub end_date: chrono::NaiveDate,
+    pub unit_amount: Option<Money>,
+    pub percentage: Option<Decimal>,
+    pub category_ids: Vec<Uuid>,
+    pub created_at: chrono::NaiveDateTime,
+}
+
+impl SavingChallenge {
+    pub fn to_challenge_out_dto(&self) -> ChallengeOutDTO {
+        ChallengeOutDTO {
+            id: self.id,
+            challenge_type: self.challenge_type,
+            goal_id: self.goal_id,
+            user_id: self.user_id,
+            start_date: self.start_date,
+            end_date: self.end_date,
+            unit_amount: self.unit_amount,
+            percentage: self.percentage,
+            category_ids: self.category_ids.clone(),
+            created_at: self.created_at,
+        }
+    }
+}
@@ -0,0 +1,32 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    dtos::contribution_dtos::ContributionOutDTO, enums::custom_enums::ContributionSource,
+    money::Money,
+};
+
+#[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
+pub struct GoalContribution {
+    pub id: Uuid,
+    pub goal_id: Uuid,
+    pub amount: Money,
+    pub contributed_at: chrono::NaiveDateTime,
+    pub note: Option<String>,
+    pub source: ContributionSource,
+    pub transaction_id: Option<Uuid>,
+}
+
+impl GoalContribution {
+    pub fn to_contribution_out_dto(&self) -> ContributionOutDTO {
+        ContributionOutDTO {
+            id: self.id,
+            goal_id: self.goal_id,
+            amount: self.amount,
+            contributed_at: self.contributed_at,
+            note: self.note.clone(),
+            source: self.source,
+            transaction_id: self.transaction_id,
+        }
+    }
+}
@@ -0,0 +1,25 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::dtos::exchange_rate_dtos::ExchangeRateOutDTO;
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct ExchangeRate {
+    pub base_currency: String,
+    pub quote_currency: String,
+    pub rate_date: NaiveDate,
+    pub rate: Decimal,
+    pub imported_at: NaiveDateTime,
+}
+
+impl ExchangeRate {
+    pub fn to_exchange_rate_out_dto(&self) -> ExchangeRateOutDTO {
+        ExchangeRateOutDTO {
+            base_currency: self.base_currency.clone(),
+            quote_currency: self.quote_currency.clone(),
+            rate_date: self.rate_date,
+            rate: self.rate,
+        }
+    }
+}
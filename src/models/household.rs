@@ -0,0 +1,44 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::dtos::household_dtos::{HouseholdMemberOutDTO, HouseholdOutDTO};
+use crate::enums::custom_enums::HouseholdRole;
+
+#[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
+pub struct Household {
+    pub id: Uuid,
+    pub name: String,
+    pub created_at: chrono::NaiveDateTime,
+}
+
+impl Household {
+    pub fn to_household_out_dto(&self) -> HouseholdOutDTO {
+        HouseholdOutDTO {
+            id: self.id,
+            name: self.name.clone(),
+            created_at: self.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
+pub struct HouseholdMember {
+    pub household_id: Uuid,
+    pub user_id: Uuid,
+    pub username: String,
+    pub email: String,
+    pub role: HouseholdRole,
+    pub joined_at: chrono::NaiveDateTime,
+}
+
+impl HouseholdMember {
+    pub fn to_household_member_out_dto(&self) -> HouseholdMemberOutDTO {
+        HouseholdMemberOutDTO {
+            user_id: self.user_id,
+            username: self.username.clone(),
+            email: self.email.clone(),
+            role: self.role,
+            joined_at: self.joined_at,
+        }
+    }
+}
@@ -1,7 +1,23 @@
 pub mod account;
 pub mod achievement;
+pub mod admin;
+pub mod allocation;
+pub mod api_token;
+pub mod badge;
+pub mod balance_snapshot;
 pub mod budget;
 pub mod categories;
+pub mod challenge;
+pub mod contribution;
+pub mod exchange_rate;
+pub mod household;
+pub mod recurring_transaction;
+pub mod report;
+pub mod round_up_rule;
 pub mod saving_goals;
+pub mod session;
+pub mod subscription;
 pub mod transactions;
+pub mod two_factor;
 pub mod user;
+pub mod user_preferences;
@@ -0,0 +1,71 @@
+use chrono::{Duration, Months, NaiveDate};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::dtos::recurring_transaction_dtos::RecurringTransactionOutDTO;
+use crate::enums::custom_enums::{RecurrenceFrequency, TransactionType};
+use crate::money::Money;
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct RecurringTransaction {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub account_id: Uuid,
+    pub category_id: Uuid,
+    pub title: String,
+    pub amount: Money,
+    pub transaction_type: TransactionType,
+    pub frequency: RecurrenceFrequency,
+    pub every: i32,
+    pub start_date: NaiveDate,
+    pub end_date: Option<NaiveDate>,
+    pub created_at: chrono::NaiveDateTime,
+}
+
+impl RecurringTransaction {
+    pub fn to_recurring_transaction_out_dto(&self) -> RecurringTransactionOutDTO {
+        RecurringTransactionOutDTO {
+            id: self.id,
+            user_id: self.user_id,
+            account_id: self.account_id,
+            category_id: self.category_id,
+            title: self.title.clone(),
+            amount: self.amount,
+            transaction_type: self.transaction_type,
+            frequency: self.frequency,
+            every: self.every,
+            start_date: self.start_date,
+            end_date: self.end_date,
+            created_at: self.created_at,
+        }
+    }
+
+    // The n-th occurrence, counted from the start date. Monthly and yearly
+    // ones are always counted from the start, so one on the 31st falls on the
+    // last day of shorter months and is back on the 31st after them.
+    fn occurrence(&self, n: u32) -> Option<NaiveDate> {
+        let steps = n.checked_mul(self.every as u32)?;
+        match self.frequency {
+            RecurrenceFrequency::Daily => self
+                .start_date
+                .checked_add_signed(Duration::days(i64::from(steps))),
+            RecurrenceFrequency::Weekly => self
+                .start_date
+                .checked_add_signed(Duration::weeks(i64::from(steps))),
+            RecurrenceFrequency::Monthly => self.start_date.checked_add_months(Months::new(steps)),
+            RecurrenceFrequency::Yearly => self
+                .start_date
+                .checked_add_months(Months::new(steps.checked_mul(12)?)),
+        }
+    }
+
+    // Every day it comes back on between `from` and `to`, both included.
+    pub fn occurrences_between(&self, from: NaiveDate, to: NaiveDate) -> Vec<NaiveDate> {
+        let last = self.end_date.map_or(to, |end| end.min(to));
+        (0..)
+            .map_while(|n| self.occurrence(n))
+            .take_while(|date| *date <= last)
+            .filter(|date| *date >= from)
+            .collect()
+    }
+}
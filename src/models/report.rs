@@ -0,0 +1,85 @@
+use chrono::NaiveDate;
+use uuid::Uuid;
+
+use crate::{
+    dtos::report_dtos::{BudgetResultOutDTO, CompletedGoalOutDTO},
+    enums::custom_enums::TransactionType,
+    money::Money,
+};
+
+// One day's transactions of a single type and currency, optionally narrowed
+// down to one account or category.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct DailyTotal {
+    pub day: NaiveDate,
+    pub transaction_type: TransactionType,
+    pub currency: String,
+    pub group_id: Option<Uuid>,
+    pub group_name: Option<String>,
+    pub amount: Money,
+    pub transaction_count: i64,
+}
+
+// Income minus expenses booked on an account on one local day.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct DailyAccountFlow {
+    pub account_id: Uuid,
+    pub day: NaiveDate,
+    pub net: Money,
+}
+
+// Everything spent from an account in one category over some stretch of days.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct CategorySpending {
+    pub account_id: Uuid,
+    pub category_id: Uuid,
+    pub amount: Money,
+}
+
+// What transfers added to or took from an account over some stretch of days.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct AccountTransferTotal {
+    pub account_id: Uuid,
+    pub net: Money,
+}
+
+// A saving goal that reached its target, with the local day it got there.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct CompletedGoal {
+    pub goal_id: Uuid,
+    pub title: String,
+    pub target_amount: Money,
+    pub completed_on: NaiveDate,
+}
+
+impl CompletedGoal {
+    pub fn to_completed_goal_out_dto(&self) -> CompletedGoalOutDTO {
+        CompletedGoalOutDTO {
+            goal_id: self.goal_id,
+            title: self.title.clone(),
+            target_amount: self.target_amount,
+            completed_on: self.completed_on,
+        }
+    }
+}
+
+// A finished budget and what was spent against it.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct BudgetResult {
+    pub budget_id: Uuid,
+    pub name: String,
+    pub amount: Money,
+    pub spent: Money,
+}
+
+impl BudgetResult {
+    pub fn to_budget_result_out_dto(&self) -> BudgetResultOutDTO {
+        BudgetResultOutDTO {
+            budget_id: self.budget_id,
+            name: self.name.clone(),
+            amount: self.amount,
+            spent: self.spent,
+            kept: self.spent <= self.amount,
+        }
+    }
+}
@@ -0,0 +1,47 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::dtos::round_up_dtos::{RoundUpRuleOutDTO, RoundUpSummaryOutDTO};
+use crate::money::Money;
+
+#[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
+pub struct RoundUpRule {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub account_id: Uuid,
+    pub goal_id: Uuid,
+    pub round_to: i32,
+    pub created_at: chrono::NaiveDateTime,
+}
+
+impl RoundUpRule {
+    pub fn to_round_up_rule_out_dto(&self) -> RoundUpRuleOutDTO {
+        RoundUpRuleOutDTO {
+            id: self.id,
+            user_id: self.user_id,
+            account_id: self.account_id,
+            goal_id: self.goal_id,
+            round_to: self.round_to,
+            created_at: self.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
+pub struct RoundUpMonthlyTotal {
+    pub goal_id: Uuid,
+    pub goal_title: String,
+    pub month: chrono::NaiveDate,
+    pub total: Money,
+}
+
+impl RoundUpMonthlyTotal {
+    pub fn to_round_up_summary_out_dto(&self) -> RoundUpSummaryOutDTO {
+        RoundUpSummaryOutDTO {
+            goal_id: self.goal_id,
+            goal_title: self.goal_title.clone(),
+            month: self.month,
+            total: self.total,
+        }
+    }
+}
@@ -2,17 +2,19 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::dtos::saving_goal_dtos::SavingGoalOutDTO;
+use crate::money::Money;
 
 #[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
 pub struct SavingGoal {
     pub id: Uuid,
     pub title: String,
-    pub target_amount: f64,
-    pub current_amount: f64,
+    pub target_amount: Money,
+    pub current_amount: Money,
     pub deadline: chrono::NaiveDate,
     pub user_id: Uuid,
     pub created_at: chrono::NaiveDateTime,
     pub updated_at: chrono::NaiveDateTime,
+    pub household_id: Option<Uuid>,
 }
 
 impl SavingGoal {
@@ -26,6 +28,7 @@ impl SavingGoal {
             user_id: self.user_id,
             created_at: self.created_at,
             updated_at: self.updated_at,
+            household_id: self.household_id,
         }
     }
 }
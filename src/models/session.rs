@@ -0,0 +1,12 @@
+use uuid::Uuid;
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct Session {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub created_at: chrono::NaiveDateTime,
+    pub last_seen_at: chrono::NaiveDateTime,
+    pub expires_at: chrono::NaiveDateTime,
+    pub two_factor_verified: bool,
+    pub impersonator_id: Option<Uuid>,
+}
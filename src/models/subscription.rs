@@ -0,0 +1,26 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::money::Money;
+
+// An expense as the subscription and anomaly detectors see it, dated by the payer's
+// local day.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct Charge {
+    pub transaction_id: Uuid,
+    pub account_id: Uuid,
+    pub category_id: Uuid,
+    pub title: String,
+    pub day: NaiveDate,
+    pub amount: Money,
+    pub currency: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
+pub struct SubscriptionDismissal {
+    pub user_id: Uuid,
+    pub account_id: Uuid,
+    pub payee: String,
+    pub dismissed_at: chrono::NaiveDateTime,
+}
@@ -1,14 +1,21 @@
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::{dtos::transaction_dtos::TransactionOutDTO, enums::custom_enums::TransactionType};
+use crate::{
+    dtos::transaction_dtos::{AccountTransactionOutDTO, TransactionOutDTO},
+    enums::custom_enums::TransactionType,
+    money::Money,
+};
 
 #[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
 pub struct Transaction {
     pub id: Uuid,
     pub title: String,
-    pub amount: f64,
-    pub date: chrono::NaiveDateTime,
+    pub amount: Money,
+    pub currency: String,
+    pub original_amount: Option<Money>,
+    pub original_currency: Option<String>,
+    pub date: chrono::DateTime<chrono::Utc>,
     pub category_id: Uuid,
     pub transaction_type: TransactionType,
     pub user_id: Uuid,
@@ -21,6 +28,9 @@ impl Transaction {
             id: self.id,
             title: self.title.clone(),
             amount: self.amount,
+            currency: self.currency.clone(),
+            original_amount: self.original_amount,
+            original_currency: self.original_currency.clone(),
             date: self.date,
             category_id: self.category_id,
             transaction_type: self.transaction_type,
@@ -29,3 +39,30 @@ impl Transaction {
         }
     }
 }
+
+// A transaction amount as it is stored, in its account's currency.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AccountAmount {
+    pub amount: Money,
+    pub currency: String,
+    pub original_amount: Option<Money>,
+    pub original_currency: Option<String>,
+}
+
+#[derive(Debug)]
+pub struct AccountTransaction {
+    pub transaction: Transaction,
+    pub running_balance: Money,
+}
+
+impl AccountTransaction {
+    pub fn to_account_transaction_out_dto(
+        &self,
+        with_running_balance: bool,
+    ) -> AccountTransactionOutDTO {
+        AccountTransactionOutDTO {
+            transaction: self.transaction.to_transaction_out_dto(),
+            running_balance: with_running_balance.then_some(self.running_balance),
+        }
+    }
+}
@@ -0,0 +1,70 @@
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc, Weekday};
+use chrono_tz::Tz;
+
+use crate::{
+    calendar, dtos::user_preferences_dtos::UserPreferencesOutDTO, enums::custom_enums::WeekDay,
+};
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct UserPreferences {
+    pub user_id: uuid::Uuid,
+    pub base_currency: String,
+    pub locale: String,
+    pub timezone: String,
+    pub week_start: WeekDay,
+    pub fiscal_year_start_month: i32,
+    pub date_format: String,
+    pub updated_at: Option<NaiveDateTime>,
+}
+
+impl UserPreferences {
+    pub fn to_user_preferences_out_dto(&self) -> UserPreferencesOutDTO {
+        UserPreferencesOutDTO {
+            base_currency: self.base_currency.clone(),
+            locale: self.locale.clone(),
+            timezone: self.timezone.clone(),
+            week_start: self.week_start,
+            fiscal_year_start_month: self.fiscal_year_start_month,
+            date_format: self.date_format.clone(),
+            updated_at: self.updated_at,
+        }
+    }
+
+    // Timezones are validated on the way in; UTC only covers rows written
+    // before a zone was renamed out of the database.
+    pub fn tz(&self) -> Tz {
+        self.timezone.parse().unwrap_or(Tz::UTC)
+    }
+
+    pub fn now(&self) -> NaiveDateTime {
+        calendar::now_in(self.tz())
+    }
+
+    pub fn today(&self) -> NaiveDate {
+        self.now().date()
+    }
+
+    pub fn local_date(&self, instant: DateTime<Utc>) -> NaiveDate {
+        calendar::local_date(instant, self.tz())
+    }
+
+    pub fn first_day_of_week(&self) -> Weekday {
+        Weekday::from(self.week_start)
+    }
+
+    pub fn week_start_of(&self, date: NaiveDate) -> NaiveDate {
+        calendar::week_start(date, self.first_day_of_week())
+    }
+
+    pub fn fiscal_year_start_of(&self, date: NaiveDate) -> NaiveDate {
+        calendar::fiscal_year_start(date, self.fiscal_year_start_month as u32)
+    }
+
+    pub fn format_date(&self, date: NaiveDate) -> String {
+        date.format(&self.date_format).to_string()
+    }
+
+    pub fn month_name(&self, date: NaiveDate) -> &'static str {
+        calendar::month_name(date, &self.locale)
+    }
+}
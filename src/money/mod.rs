@@ -0,0 +1,212 @@
+use rust_decimal::{Decimal, RoundingStrategy};
+use serde::{de, Deserialize, Deserializer, Serialize};
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+// Money columns are NUMERIC(19, 4); four decimals cover the minor unit of
+// every ISO 4217 currency.
+pub const MAX_MINOR_UNITS: u32 = 4;
+pub const DEFAULT_MINOR_UNITS: u32 = 2;
+// Fifteen integer digits are left once the decimals are taken.
+const MAX_STORABLE: Decimal = Decimal::from_parts(0xA4C6_8000, 0x38D7E, 0, false, 0);
+
+// Currencies whose minor unit isn't a hundredth.
+const MINOR_UNIT_EXCEPTIONS: &[(&str, u32)] = &[
+    ("BHD", 3),
+    ("BIF", 0),
+    ("CLF", 4),
+    ("CLP", 0),
+    ("DJF", 0),
+    ("GNF", 0),
+    ("IQD", 3),
+    ("ISK", 0),
+    ("JOD", 3),
+    ("JPY", 0),
+    ("KMF", 0),
+    ("KRW", 0),
+    ("KWD", 3),
+    ("LYD", 3),
+    ("OMR", 3),
+    ("PYG", 0),
+    ("RWF", 0),
+    ("TND", 3),
+    ("UGX", 0),
+    ("UYI", 0),
+    ("UYW", 4),
+    ("VND", 0),
+    ("VUV", 0),
+    ("XAF", 0),
+    ("XOF", 0),
+    ("XPF", 0),
+];
+
+// An ISO 4217 code such as "EUR".
+pub fn is_currency_code(code: &str) -> bool {
+    code.len() == 3 && code.chars().all(|c| c.is_ascii_uppercase())
+}
+
+// The number of decimals the currency's smallest unit needs.
+pub fn minor_units(currency: &str) -> u32 {
+    MINOR_UNIT_EXCEPTIONS
+        .iter()
+        .find(|(code, _)| *code == currency)
+        .map_or(DEFAULT_MINOR_UNITS, |(_, units)| *units)
+}
+
+// An exact amount of money. It goes over JSON as a string so clients don't
+// lose precision parsing it as a float; plain JSON numbers are accepted too.
+// Amounts that wouldn't fit the money columns are rejected when parsed.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, sqlx::Type,
+)]
+#[serde(transparent)]
+#[sqlx(transparent)]
+pub struct Money(Decimal);
+
+impl Money {
+    pub const ZERO: Money = Money(Decimal::ZERO);
+
+    pub const fn new(amount: Decimal) -> Money {
+        Money(amount)
+    }
+
+    pub fn amount(self) -> Decimal {
+        self.0
+    }
+
+    pub fn is_zero(self) -> bool {
+        self.0.is_zero()
+    }
+
+    pub fn is_positive(self) -> bool {
+        self.0 > Decimal::ZERO
+    }
+
+    pub fn is_negative(self) -> bool {
+        self.0 < Decimal::ZERO
+    }
+
+    pub fn abs(self) -> Money {
+        Money(self.0.abs())
+    }
+
+    // Rounds half away from zero to the currency's minor unit.
+    pub fn round_to(self, currency: &str) -> Money {
+        Money(self.0.round_dp_with_strategy(
+            minor_units(currency),
+            RoundingStrategy::MidpointAwayFromZero,
+        ))
+    }
+
+    // Whether the amount can be stored without rounding or overflowing.
+    pub fn fits_storage(self) -> bool {
+        self.0.normalize().scale() <= MAX_MINOR_UNITS && self.0.abs() < MAX_STORABLE
+    }
+
+    // Converts at `rate` units of `currency` per unit of this amount.
+    pub fn convert(self, rate: Decimal, currency: &str) -> Money {
+        Money(self.0 * rate).round_to(currency)
+    }
+
+    pub fn percent(self, percentage: Decimal) -> Money {
+        Money(self.0 * percentage / Decimal::ONE_HUNDRED)
+    }
+
+    // The share of `whole` this amount makes up, in percent.
+    pub fn percentage_of(self, whole: Money) -> Option<Decimal> {
+        if whole.is_zero() {
+            return None;
+        }
+        Some(self.0 / whole.0 * Decimal::ONE_HUNDRED)
+    }
+}
+
+impl<'de> Deserialize<'de> for Money {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Money, D::Error> {
+        let money = Money(<Decimal as Deserialize>::deserialize(deserializer)?);
+        if !money.fits_storage() {
+            return Err(de::Error::custom(
+                "amounts can have at most 4 decimals and 15 integer digits",
+            ));
+        }
+        Ok(money)
+    }
+}
+
+impl From<Decimal> for Money {
+    fn from(amount: Decimal) -> Money {
+        Money(amount)
+    }
+}
+
+impl From<i64> for Money {
+    fn from(amount: i64) -> Money {
+        Money(Decimal::from(amount))
+    }
+}
+
+impl FromStr for Money {
+    type Err = rust_decimal::Error;
+
+    fn from_str(s: &str) -> Result<Money, Self::Err> {
+        Decimal::from_str(s).map(Money)
+    }
+}
+
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl Add for Money {
+    type Output = Money;
+
+    fn add(self, other: Money) -> Money {
+        Money(self.0 + other.0)
+    }
+}
+
+impl Sub for Money {
+    type Output = Money;
+
+    fn sub(self, other: Money) -> Money {
+        Money(self.0 - other.0)
+    }
+}
+
+impl AddAssign for Money {
+    fn add_assign(&mut self, other: Money) {
+        self.0 += other.0;
+    }
+}
+
+impl SubAssign for Money {
+    fn sub_assign(&mut self, other: Money) {
+        self.0 -= other.0;
+    }
+}
+
+impl Mul<Decimal> for Money {
+    type Output = Money;
+
+    fn mul(self, factor: Decimal) -> Money {
+        Money(self.0 * factor)
+    }
+}
+
+impl Neg for Money {
+    type Output = Money;
+
+    fn neg(self) -> Money {
+        Money(-self.0)
+    }
+}
+
+impl Sum for Money {
+    fn sum<I: Iterator<Item = Money>>(iter: I) -> Money {
+        iter.fold(Money::ZERO, Add::add)
+    }
+}
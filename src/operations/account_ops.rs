@@ -1,25 +1,55 @@
-use crate::models::account::Account;
-use crate::{dtos::account_dtos::AccountInDTO, enums::custom_enums::AccountType};
+use crate::models::account::{Account, CurrencyBalance};
+use crate::{dtos::account_dtos::AccountInDTO, enums::custom_enums::AccountType, money::Money};
+use chrono::NaiveDate;
 use sqlx::{Error, PgPool, Row};
+use std::str::FromStr;
 use uuid::Uuid;
 
-pub async fn get_all_accounts(pool: &PgPool) -> Result<Vec<Account>, Error> {
+pub async fn get_all_accounts(pool: &PgPool, user_id: Uuid) -> Result<Vec<Account>, Error> {
     let transactions = sqlx::query_as::<_, Account>(
         r#"
-        SELECT * FROM accounts
+        SELECT * FROM accounts WHERE can_view_record(user_id, household_id, $1)
     "#,
     )
+    .bind(user_id)
     .fetch_all(pool)
     .await?;
 
     Ok(transactions)
 }
 
-pub async fn find_account_by_id(pool: &PgPool, account_id: Uuid) -> Result<Option<Account>, Error> {
-    let row = sqlx::query("SELECT *, account_type::TEXT FROM accounts WHERE id = $1")
-        .bind(account_id)
-        .fetch_optional(pool)
-        .await?;
+// Balances of every visible account, added up per currency.
+pub async fn get_balances_by_currency(
+    pool: &PgPool,
+    user_id: Uuid,
+) -> Result<Vec<CurrencyBalance>, Error> {
+    let balances = sqlx::query_as::<_, CurrencyBalance>(
+        r#"
+        SELECT currency, SUM(balance) AS balance, COUNT(*) AS accounts
+        FROM accounts
+        WHERE can_view_record(user_id, household_id, $1)
+        GROUP BY currency
+        ORDER BY currency
+    "#,
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(balances)
+}
+
+pub async fn find_account_by_id(
+    pool: &PgPool,
+    user_id: Uuid,
+    account_id: Uuid,
+) -> Result<Option<Account>, Error> {
+    let row =
+        sqlx::query("SELECT *, account_type::TEXT FROM accounts WHERE id = $1 AND can_view_record(user_id, household_id, $2)")
+            .bind(account_id)
+            .bind(user_id)
+            .fetch_optional(pool)
+            .await?;
 
     if let Some(row) = row {
         let account = Account {
@@ -28,7 +58,9 @@ pub async fn find_account_by_id(pool: &PgPool, account_id: Uuid) -> Result<Optio
             account_type: AccountType::from_str(row.get::<&str, _>("account_type"))
                 .unwrap_or(AccountType::Bank),
             balance: row.get("balance"),
+            currency: row.get("currency"),
             user_id: row.get("user_id"),
+            household_id: row.get("household_id"),
         };
         Ok(Some(account))
     } else {
@@ -36,18 +68,23 @@ pub async fn find_account_by_id(pool: &PgPool, account_id: Uuid) -> Result<Optio
     }
 }
 
-pub async fn create_account(pool: &PgPool, account_dto: &AccountInDTO) -> Result<Account, Error> {
+pub async fn create_account(
+    pool: &PgPool,
+    user_id: Uuid,
+    account_dto: &AccountInDTO,
+) -> Result<Account, Error> {
     let account = sqlx::query_as::<_, Account>(
         r#"
-        INSERT INTO accounts (name, account_type, balance, user_id)
-        VALUES ($1, $2, $3, $4)
+        INSERT INTO accounts (name, account_type, balance, user_id, currency)
+        VALUES ($1, $2, $3, $4, COALESCE($5, user_base_currency($4)))
         RETURNING *
     "#,
     )
     .bind(&account_dto.name)
-    .bind(&account_dto.account_type)
+    .bind(account_dto.account_type)
     .bind(account_dto.balance)
-    .bind(&account_dto.user_id)
+    .bind(user_id)
+    .bind(&account_dto.currency)
     .fetch_one(pool)
     .await?;
 
@@ -56,35 +93,85 @@ pub async fn create_account(pool: &PgPool, account_dto: &AccountInDTO) -> Result
 
 pub async fn update_account(
     pool: &PgPool,
+    user_id: Uuid,
     account_id: Uuid,
     account_dto: &AccountInDTO,
-) -> Result<Account, Error> {
+) -> Result<Option<Account>, Error> {
     let account = sqlx::query_as::<_, Account>(
         r#"
         UPDATE accounts
-        SET name = $1, account_type = $2, balance = $3
-        WHERE id = $4
-        RETURNING id, name, account_type, balance, user_id
+        SET name = $1, account_type = $2, balance = $3, currency = COALESCE($4, currency)
+        WHERE id = $5 AND can_edit_record(user_id, household_id, $6)
+        RETURNING *
         "#,
     )
     .bind(&account_dto.name)
-    .bind(&account_dto.account_type)
-    .bind(&account_dto.balance)
+    .bind(account_dto.account_type)
+    .bind(account_dto.balance)
+    .bind(&account_dto.currency)
     .bind(account_id)
-    .fetch_one(pool)
+    .bind(user_id)
+    .fetch_optional(pool)
     .await?;
 
     Ok(account)
 }
 
-pub async fn delete_account(pool: &PgPool, account_id: Uuid) -> Result<(), Error> {
-    sqlx::query(
+pub async fn delete_account(pool: &PgPool, user_id: Uuid, account_id: Uuid) -> Result<u64, Error> {
+    let deleted = sqlx::query(
         r#"
-        DELETE FROM accounts WHERE id = $1"#,
+        DELETE FROM accounts WHERE id = $1 AND user_id = $2"#,
     )
     .bind(account_id)
+    .bind(user_id)
     .execute(pool)
     .await?;
 
-    Ok(())
+    Ok(deleted.rows_affected())
+}
+
+// Amounts already recorded against an account are in its currency, so the
+// currency is fixed once it has transactions.
+pub async fn account_has_transactions(pool: &PgPool, account_id: Uuid) -> Result<bool, Error> {
+    let (exists,): (bool,) =
+        sqlx::query_as("SELECT EXISTS (SELECT 1 FROM transactions WHERE account_id = $1)")
+            .bind(account_id)
+            .fetch_one(pool)
+            .await?;
+
+    Ok(exists)
+}
+
+// The balance at the end of `date` in the user's timezone: the current
+// balance with every transaction booked after that day taken back out.
+pub async fn account_balance_on(
+    pool: &PgPool,
+    user_id: Uuid,
+    account_id: Uuid,
+    date: NaiveDate,
+) -> Result<Option<Money>, Error> {
+    let balance: Option<(Money,)> = sqlx::query_as(
+        r#"
+        SELECT a.balance - COALESCE(SUM(
+            CASE WHEN t.transaction_type = 'Income' THEN t.amount ELSE -t.amount END
+        ), 0) - COALESCE((
+            SELECT SUM(CASE WHEN x.to_account_id = a.id THEN x.amount ELSE -x.amount END)
+            FROM account_transfers x
+            WHERE (x.from_account_id = a.id OR x.to_account_id = a.id)
+                AND (x.transferred_at AT TIME ZONE user_timezone($2))::DATE > $3
+        ), 0)
+        FROM accounts a
+        LEFT JOIN transactions t ON t.account_id = a.id
+            AND (t.date AT TIME ZONE user_timezone($2))::DATE > $3
+        WHERE a.id = $1 AND can_view_record(a.user_id, a.household_id, $2)
+        GROUP BY a.id
+    "#,
+    )
+    .bind(account_id)
+    .bind(user_id)
+    .bind(date)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(balance.map(|(balance,)| balance))
 }
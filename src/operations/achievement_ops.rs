@@ -1,25 +1,98 @@
-use crate::{dtos::achievement_dtos::AchievementInDTO, models::achievement::Achievement};
+use crate::{
+    dtos::achievement_dtos::AchievementInDTO,
+    enums::custom_enums::AchievementKind,
+    models::{achievement::Achievement, saving_goals::SavingGoal},
+    money::Money,
+    operations::user_preferences_ops::find_user_preferences,
+};
+use chrono::{Local, Utc};
+use rust_decimal::Decimal;
 use sqlx::{postgres::PgPool, Error};
+use std::env;
 use uuid::Uuid;
 
+const DEFAULT_MILESTONES: [i32; 4] = [25, 50, 75, 100];
+
+// Milestones are percentages of a goal's target_amount. They can be overridden
+// with a comma separated ACHIEVEMENT_MILESTONES variable, e.g. "10,50,100".
+pub fn milestone_percentages() -> Vec<i32> {
+    dotenv::dotenv().ok();
+
+    let mut milestones: Vec<i32> = env::var("ACHIEVEMENT_MILESTONES")
+        .ok()
+        .map(|value| {
+            value
+                .split(',')
+                .filter_map(|part| part.trim().parse::<i32>().ok())
+                .filter(|percentage| (1..=100).contains(percentage))
+                .collect()
+        })
+        .filter(|parsed: &Vec<i32>| !parsed.is_empty())
+        .unwrap_or_else(|| DEFAULT_MILESTONES.to_vec());
+
+    milestones.sort_unstable();
+    milestones.dedup();
+    milestones
+}
+
+pub fn reached_milestones(goal: &SavingGoal, milestones: &[i32]) -> Vec<i32> {
+    if !goal.target_amount.is_positive() {
+        return Vec::new();
+    }
+
+    let progress = goal
+        .current_amount
+        .percentage_of(goal.target_amount)
+        .unwrap_or_default();
+    milestones
+        .iter()
+        .copied()
+        .filter(|milestone| progress >= Decimal::from(*milestone))
+        .collect()
+}
+
+pub fn is_completed_early(goal: &SavingGoal, today: chrono::NaiveDate) -> bool {
+    goal.target_amount.is_positive()
+        && goal.current_amount >= goal.target_amount
+        && today <= goal.deadline
+}
+
 pub async fn find_achievement_by_id(
     pool: &PgPool,
+    user_id: Uuid,
     achievement_id: Uuid,
 ) -> Result<Option<Achievement>, Error> {
     let achievement = sqlx::query_as!(
         Achievement,
-        "SELECT * FROM achievements WHERE id = $1",
-        achievement_id
+        r#"
+        SELECT a.id, a.goal_id, a.date_achieved, a.amount_saved AS "amount_saved: Money", a.kind AS "kind: AchievementKind", a.milestone, a.seen_at
+        FROM achievements a
+        JOIN saving_goals g ON g.id = a.goal_id
+        WHERE a.id = $1 AND can_view_record(g.user_id, g.household_id, $2)
+        "#,
+        achievement_id,
+        user_id
     )
     .fetch_optional(pool)
     .await?;
     Ok(achievement)
 }
 
-pub async fn fetch_all_achievements(pool: &PgPool) -> Result<Vec<Achievement>, sqlx::Error> {
-    let achievements = sqlx::query_as::<_, Achievement>(r#"SELECT * FROM achievements"#)
-        .fetch_all(pool)
-        .await?;
+pub async fn fetch_all_achievements(
+    pool: &PgPool,
+    user_id: Uuid,
+) -> Result<Vec<Achievement>, sqlx::Error> {
+    let achievements = sqlx::query_as::<_, Achievement>(
+        r#"
+        SELECT a.*
+        FROM achievements a
+        JOIN saving_goals g ON g.id = a.goal_id
+        WHERE can_view_record(g.user_id, g.household_id, $1)
+        "#,
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await?;
 
     Ok(achievements)
 }
@@ -35,9 +108,9 @@ pub async fn create_achievement(
         RETURNING *
         "#,
     )
-    .bind(&achievement_dto.goal_id)
-    .bind(&achievement_dto.date_achieved)
-    .bind(&achievement_dto.amount_saved)
+    .bind(achievement_dto.goal_id)
+    .bind(achievement_dto.date_achieved)
+    .bind(achievement_dto.amount_saved)
     .fetch_one(pool)
     .await?;
 
@@ -46,37 +119,141 @@ pub async fn create_achievement(
 
 pub async fn update_achievement_in_db(
     pool: &PgPool,
+    user_id: Uuid,
     achievement_id: Uuid,
     achievement_dto: &AchievementInDTO,
-) -> Result<Achievement, sqlx::Error> {
+) -> Result<Option<Achievement>, sqlx::Error> {
     let row = sqlx::query_as::<_, Achievement>(
         r#"
-        UPDATE achievements
+        UPDATE achievements a
         SET goal_id = $1, date_achieved = $2, amount_saved = $3
-        WHERE id = $4
-        RETURNING *
+        FROM saving_goals g
+        WHERE a.id = $4 AND g.id = a.goal_id AND can_edit_record(g.user_id, g.household_id, $5)
+        RETURNING a.*
         "#,
     )
-    .bind(&achievement_dto.goal_id)
-    .bind(&achievement_dto.date_achieved)
-    .bind(&achievement_dto.amount_saved)
+    .bind(achievement_dto.goal_id)
+    .bind(achievement_dto.date_achieved)
+    .bind(achievement_dto.amount_saved)
     .bind(achievement_id)
-    .fetch_one(pool)
+    .bind(user_id)
+    .fetch_optional(pool)
     .await?;
 
     Ok(row)
 }
 
-pub async fn delete_achievement(pool: &PgPool, achievement_id: Uuid) -> Result<(), sqlx::Error> {
-    sqlx::query(
+pub async fn delete_achievement(
+    pool: &PgPool,
+    user_id: Uuid,
+    achievement_id: Uuid,
+) -> Result<u64, sqlx::Error> {
+    let deleted = sqlx::query(
         r#"
-        DELETE FROM achievements
-        WHERE id = $1
+        DELETE FROM achievements a
+        USING saving_goals g
+        WHERE a.id = $1 AND g.id = a.goal_id AND can_edit_record(g.user_id, g.household_id, $2)
     "#,
     )
     .bind(achievement_id)
+    .bind(user_id)
+    .execute(pool)
+    .await?;
+
+    Ok(deleted.rows_affected())
+}
+
+// Records every milestone the goal has crossed, plus the early completion award
+// when the target is met on or before the deadline. Already earned achievements
+// are skipped by the unique indexes, so only the new ones are returned.
+pub async fn award_goal_achievements(
+    pool: &PgPool,
+    goal: &SavingGoal,
+) -> Result<Vec<Achievement>, Error> {
+    let now = Utc::now();
+    let mut awarded = Vec::new();
+
+    for milestone in reached_milestones(goal, &milestone_percentages()) {
+        let achievement = sqlx::query_as::<_, Achievement>(
+            r#"
+            INSERT INTO achievements (goal_id, date_achieved, amount_saved, kind, milestone)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT DO NOTHING
+            RETURNING *
+            "#,
+        )
+        .bind(goal.id)
+        .bind(now)
+        .bind(goal.current_amount)
+        .bind(AchievementKind::Milestone)
+        .bind(milestone)
+        .fetch_optional(pool)
+        .await?;
+
+        awarded.extend(achievement);
+    }
+
+    let today = find_user_preferences(pool, goal.user_id).await?.today();
+    if is_completed_early(goal, today) {
+        let achievement = sqlx::query_as::<_, Achievement>(
+            r#"
+            INSERT INTO achievements (goal_id, date_achieved, amount_saved, kind)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT DO NOTHING
+            RETURNING *
+            "#,
+        )
+        .bind(goal.id)
+        .bind(now)
+        .bind(goal.current_amount)
+        .bind(AchievementKind::EarlyCompletion)
+        .fetch_optional(pool)
+        .await?;
+
+        awarded.extend(achievement);
+    }
+
+    Ok(awarded)
+}
+
+// The achievements the user has not marked as seen yet, oldest first.
+pub async fn fetch_new_achievements_for_user(
+    pool: &PgPool,
+    user_id: Uuid,
+) -> Result<Vec<Achievement>, Error> {
+    sqlx::query_as::<_, Achievement>(
+        r#"
+        SELECT a.* FROM achievements a
+        JOIN saving_goals g ON g.id = a.goal_id
+        WHERE g.user_id = $1 AND a.seen_at IS NULL
+        ORDER BY a.date_achieved, a.milestone NULLS FIRST
+        "#,
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await
+}
+
+// Marks the given achievements as seen. Ids of other users' achievements are
+// ignored.
+pub async fn mark_achievements_seen(
+    pool: &PgPool,
+    user_id: Uuid,
+    achievement_ids: &[Uuid],
+) -> Result<u64, Error> {
+    let updated = sqlx::query(
+        r#"
+        UPDATE achievements AS a
+        SET seen_at = $3
+        FROM saving_goals AS g
+        WHERE a.goal_id = g.id AND g.user_id = $1 AND a.id = ANY($2) AND a.seen_at IS NULL
+        "#,
+    )
+    .bind(user_id)
+    .bind(achievement_ids)
+    .bind(Local::now().naive_local())
     .execute(pool)
     .await?;
 
-    Ok(())
+    Ok(updated.rows_affected())
 }
@@ -0,0 +1,137 @@
+use chrono::Local;
+use sqlx::{postgres::PgPool, Error};
+use uuid::Uuid;
+
+use crate::auth::impersonation::Impersonation;
+use crate::enums::custom_enums::UserRole;
+use crate::models::admin::{ImpersonationLogEntry, UserOverview};
+
+pub async fn find_user_role(pool: &PgPool, user_id: Uuid) -> Result<Option<UserRole>, Error> {
+    let role: Option<(UserRole,)> = sqlx::query_as("SELECT role FROM users WHERE id = $1")
+        .bind(user_id)
+        .fetch_optional(pool)
+        .await?;
+
+    Ok(role.map(|(role,)| role))
+}
+
+pub async fn is_admin(pool: &PgPool, user_id: Uuid) -> Result<bool, Error> {
+    Ok(find_user_role(pool, user_id).await? == Some(UserRole::Admin))
+}
+
+pub async fn is_suspended(pool: &PgPool, user_id: Uuid) -> Result<bool, Error> {
+    let (suspended,): (bool,) = sqlx::query_as(
+        "SELECT EXISTS (SELECT 1 FROM users WHERE id = $1 AND suspended_at IS NOT NULL)",
+    )
+    .bind(user_id)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(suspended)
+}
+
+pub async fn fetch_user_overviews(pool: &PgPool) -> Result<Vec<UserOverview>, Error> {
+    let overviews = sqlx::query_as::<_, UserOverview>(
+        r#"
+        SELECT u.id, u.username, u.email, u.role, u.created_at, u.suspended_at,
+               (SELECT MAX(s.last_seen_at) FROM sessions s WHERE s.user_id = u.id) AS last_seen_at,
+               (SELECT COUNT(*) FROM sessions s
+                WHERE s.user_id = u.id AND s.expires_at > $1) AS active_sessions,
+               (SELECT COUNT(*) FROM accounts a WHERE a.user_id = u.id) AS account_count,
+               (SELECT COUNT(*) FROM transactions t WHERE t.user_id = u.id) AS transaction_count,
+               (SELECT COUNT(*) FROM saving_goals g WHERE g.user_id = u.id) AS saving_goal_count
+        FROM users u
+        ORDER BY u.created_at
+        "#,
+    )
+    .bind(Local::now().naive_local())
+    .fetch_all(pool)
+    .await?;
+
+    Ok(overviews)
+}
+
+pub async fn set_user_role(pool: &PgPool, user_id: Uuid, role: UserRole) -> Result<u64, Error> {
+    let updated = sqlx::query("UPDATE users SET role = $1 WHERE id = $2")
+        .bind(role)
+        .bind(user_id)
+        .execute(pool)
+        .await?;
+
+    Ok(updated.rows_affected())
+}
+
+// Suspending also ends every session of the user; API tokens stop working
+// while the suspension lasts.
+pub async fn suspend_user(pool: &PgPool, user_id: Uuid) -> Result<u64, Error> {
+    let mut tx = pool.begin().await?;
+
+    let updated =
+        sqlx::query("UPDATE users SET suspended_at = COALESCE(suspended_at, $1) WHERE id = $2")
+            .bind(Local::now().naive_local())
+            .bind(user_id)
+            .execute(&mut tx)
+            .await?;
+
+    sqlx::query("DELETE FROM sessions WHERE user_id = $1")
+        .bind(user_id)
+        .execute(&mut tx)
+        .await?;
+
+    tx.commit().await?;
+
+    Ok(updated.rows_affected())
+}
+
+pub async fn reactivate_user(pool: &PgPool, user_id: Uuid) -> Result<u64, Error> {
+    let updated = sqlx::query("UPDATE users SET suspended_at = NULL WHERE id = $1")
+        .bind(user_id)
+        .execute(pool)
+        .await?;
+
+    Ok(updated.rows_affected())
+}
+
+pub async fn record_impersonated_request(
+    pool: &PgPool,
+    impersonation: &Impersonation,
+    method: &str,
+    path: &str,
+    status: u16,
+) -> Result<(), Error> {
+    sqlx::query(
+        r#"
+        INSERT INTO impersonation_log (impersonator_id, user_id, session_id, method, path, status)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        "#,
+    )
+    .bind(impersonation.impersonator_id)
+    .bind(impersonation.user_id)
+    .bind(impersonation.session_id)
+    .bind(method)
+    .bind(path)
+    .bind(i32::from(status))
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+// Newest first; `user_id` narrows the log to one impersonated user.
+pub async fn fetch_impersonation_log(
+    pool: &PgPool,
+    user_id: Option<Uuid>,
+) -> Result<Vec<ImpersonationLogEntry>, Error> {
+    let entries = sqlx::query_as::<_, ImpersonationLogEntry>(
+        r#"
+        SELECT * FROM impersonation_log
+        WHERE $1::UUID IS NULL OR user_id = $1
+        ORDER BY created_at DESC
+        "#,
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(entries)
+}
@@ -0,0 +1,483 @@
+use crate::{
+    dtos::allocation_dtos::AllocationRuleInDTO,
+    enums::custom_enums::{AllocationTargetType, ContributionSource, TransactionType},
+    models::{
+        allocation::{Allocation, AllocationRule, AllocationRuleTarget},
+        saving_goals::SavingGoal,
+        transactions::Transaction,
+    },
+    money::Money,
+    rules::allocation::{plan_allocations, AllocationPlan},
+};
+use chrono::Local;
+use sqlx::{postgres::PgPool, Error, PgConnection, Postgres};
+use uuid::Uuid;
+
+pub async fn create_allocation_rule(
+    pool: &PgPool,
+    user_id: Uuid,
+    rule_dto: &AllocationRuleInDTO,
+) -> Result<(AllocationRule, Vec<AllocationRuleTarget>), Error> {
+    let mut tx = pool.begin().await?;
+
+    let rule = sqlx::query_as::<_, AllocationRule>(
+        r#"
+        INSERT INTO allocation_rules (user_id, name, match_title, match_account_id, priority, auto_apply, created_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        RETURNING *
+        "#,
+    )
+    .bind(user_id)
+    .bind(&rule_dto.name)
+    .bind(&rule_dto.match_title)
+    .bind(rule_dto.match_account_id)
+    .bind(rule_dto.priority)
+    .bind(rule_dto.auto_apply)
+    .bind(Local::now().naive_local())
+    .fetch_one(&mut tx)
+    .await?;
+
+    let mut targets = Vec::new();
+    for (position, target_dto) in rule_dto.targets.iter().enumerate() {
+        let target = sqlx::query_as::<_, AllocationRuleTarget>(
+            r#"
+            INSERT INTO allocation_rule_targets (rule_id, position, target_type, target_id, amount_type, value)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING *
+            "#,
+        )
+        .bind(rule.id)
+        .bind(position as i32)
+        .bind(target_dto.target_type)
+        .bind(target_dto.target_id)
+        .bind(target_dto.amount_type)
+        .bind(target_dto.value)
+        .fetch_one(&mut tx)
+        .await?;
+        targets.push(target);
+    }
+
+    tx.commit().await?;
+
+    Ok((rule, targets))
+}
+
+async fn fetch_rule_targets(
+    conn: &mut PgConnection,
+    rule_id: Uuid,
+) -> Result<Vec<AllocationRuleTarget>, Error> {
+    sqlx::query_as::<_, AllocationRuleTarget>(
+        r#"SELECT * FROM allocation_rule_targets WHERE rule_id = $1 ORDER BY position"#,
+    )
+    .bind(rule_id)
+    .fetch_all(conn)
+    .await
+}
+
+// The rule's targets that can still receive money from the transaction. A
+// target that was deleted or that the user may no longer edit is left out,
+// as is one held in another currency than the transaction, and its share
+// stays unallocated. Goals and budgets are held in their owner's base
+// currency.
+async fn fetch_usable_targets(
+    conn: &mut PgConnection,
+    rule_id: Uuid,
+    transaction: &Transaction,
+) -> Result<Vec<AllocationRuleTarget>, Error> {
+    sqlx::query_as::<_, AllocationRuleTarget>(
+        r#"
+        SELECT t.* FROM allocation_rule_targets t
+        WHERE t.rule_id = $1 AND CASE t.target_type
+            WHEN 'saving_goal' THEN EXISTS (
+                SELECT 1 FROM saving_goals g
+                WHERE g.id = t.target_id AND user_base_currency(g.user_id) = $3
+                    AND can_edit_record(g.user_id, g.household_id, $2)
+            )
+            WHEN 'budget' THEN EXISTS (
+                SELECT 1 FROM budgets b
+                WHERE b.id = t.target_id AND user_base_currency(b.user_id) = $3
+                    AND can_edit_record(b.user_id, b.household_id, $2)
+            )
+            WHEN 'account' THEN EXISTS (
+                SELECT 1 FROM accounts a
+                WHERE a.id = t.target_id AND a.currency = $3
+                    AND can_edit_record(a.user_id, a.household_id, $2)
+            )
+        END
+        ORDER BY t.position
+        "#,
+    )
+    .bind(rule_id)
+    .bind(transaction.user_id)
+    .bind(&transaction.currency)
+    .fetch_all(conn)
+    .await
+}
+
+pub async fn fetch_allocation_rules_for_user(
+    pool: &PgPool,
+    user_id: Uuid,
+) -> Result<Vec<(AllocationRule, Vec<AllocationRuleTarget>)>, Error> {
+    let mut conn = pool.acquire().await?;
+    let rules = sqlx::query_as::<_, AllocationRule>(
+        r#"SELECT * FROM allocation_rules WHERE user_id = $1 ORDER BY priority DESC, created_at"#,
+    )
+    .bind(user_id)
+    .fetch_all(&mut conn)
+    .await?;
+
+    let mut rules_with_targets = Vec::new();
+    for rule in rules {
+        let targets = fetch_rule_targets(&mut conn, rule.id).await?;
+        rules_with_targets.push((rule, targets));
+    }
+
+    Ok(rules_with_targets)
+}
+
+pub async fn delete_allocation_rule(
+    pool: &PgPool,
+    user_id: Uuid,
+    rule_id: Uuid,
+) -> Result<u64, Error> {
+    let deleted = sqlx::query(
+        r#"
+        DELETE FROM allocation_rules
+        WHERE id = $1 AND user_id = $2
+    "#,
+    )
+    .bind(rule_id)
+    .bind(user_id)
+    .execute(pool)
+    .await?;
+
+    Ok(deleted.rows_affected())
+}
+
+// The highest priority rule whose title and account filters both accept the
+// income transaction. `only_auto` skips rules that must be applied by hand.
+async fn find_matching_rule(
+    conn: &mut PgConnection,
+    transaction: &Transaction,
+    only_auto: bool,
+) -> Result<Option<(AllocationRule, Vec<AllocationRuleTarget>)>, Error> {
+    if transaction.transaction_type != TransactionType::Income {
+        return Ok(None);
+    }
+
+    let rule = sqlx::query_as::<_, AllocationRule>(
+        r#"
+        SELECT * FROM allocation_rules
+        WHERE user_id = $1
+          AND (match_title IS NULL OR $2 ILIKE '%' || match_title || '%')
+          AND (match_account_id IS NULL OR match_account_id = $3)
+          AND (auto_apply OR NOT $4)
+        ORDER BY priority DESC, created_at
+        LIMIT 1
+        "#,
+    )
+    .bind(transaction.user_id)
+    .bind(&transaction.title)
+    .bind(transaction.account_id)
+    .bind(only_auto)
+    .fetch_optional(&mut *conn)
+    .await?;
+
+    match rule {
+        Some(rule) => {
+            let targets = fetch_usable_targets(conn, rule.id, transaction).await?;
+            Ok(Some((rule, targets)))
+        }
+        None => Ok(None),
+    }
+}
+
+pub async fn preview_allocation_plan(
+    pool: &PgPool,
+    transaction: &Transaction,
+) -> Result<Option<(AllocationRule, AllocationPlan)>, Error> {
+    let mut conn = pool.acquire().await?;
+    let matched = find_matching_rule(&mut conn, transaction, false).await?;
+
+    let Some((rule, targets)) = matched else {
+        return Ok(None);
+    };
+    let plan = plan_allocations(transaction.amount, &targets, &transaction.currency);
+
+    Ok(Some((rule, plan)))
+}
+
+async fn apply_rule(
+    tx: &mut sqlx::Transaction<'_, Postgres>,
+    transaction: &Transaction,
+    rule: &AllocationRule,
+    targets: &[AllocationRuleTarget],
+) -> Result<Vec<SavingGoal>, Error> {
+    let now = Local::now().naive_local();
+    let plan = plan_allocations(transaction.amount, targets, &transaction.currency);
+    let mut goals = Vec::new();
+
+    for planned in &plan.allocations {
+        // A target removed since the plan was made is skipped rather than
+        // failing the transaction that triggered the rule.
+        let applied = match planned.target_type {
+            AllocationTargetType::SavingGoal => {
+                let goal = sqlx::query_as::<_, SavingGoal>(
+                    r#"
+                    UPDATE saving_goals
+                    SET current_amount = current_amount + $1, updated_at = $2
+                    WHERE id = $3 AND can_edit_record(user_id, household_id, $4)
+                    RETURNING *
+                    "#,
+                )
+                .bind(planned.amount)
+                .bind(now)
+                .bind(planned.target_id)
+                .bind(transaction.user_id)
+                .fetch_optional(&mut *tx)
+                .await?;
+
+                if let Some(goal) = goal {
+                    sqlx::query(
+                        r#"
+                        INSERT INTO goal_contributions (goal_id, amount, contributed_at, note, source, transaction_id)
+                        VALUES ($1, $2, $3, $4, $5, $6)
+                        "#,
+                    )
+                    .bind(goal.id)
+                    .bind(planned.amount)
+                    .bind(transaction.date.with_timezone(&Local).naive_local())
+                    .bind(format!("Allocated by {}", rule.name))
+                    .bind(ContributionSource::Allocation)
+                    .bind(transaction.id)
+                    .execute(&mut *tx)
+                    .await?;
+
+                    goals.push(goal);
+                    true
+                } else {
+                    false
+                }
+            }
+            AllocationTargetType::Budget => sqlx::query(
+                r#"
+                UPDATE budgets SET amount = amount + $1
+                WHERE id = $2 AND can_edit_record(user_id, household_id, $3)
+                RETURNING id
+                "#,
+            )
+            .bind(planned.amount)
+            .bind(planned.target_id)
+            .bind(transaction.user_id)
+            .fetch_optional(&mut *tx)
+            .await?
+            .is_some(),
+            AllocationTargetType::Account => {
+                move_to_account(tx, transaction, planned.target_id, planned.amount).await?
+            }
+        };
+        if !applied {
+            continue;
+        }
+
+        let (allocation_id,): (Uuid,) = sqlx::query_as(
+            r#"
+            INSERT INTO allocations (source_transaction_id, rule_id, target_type, target_id, amount, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING id
+            "#,
+        )
+        .bind(transaction.id)
+        .bind(rule.id)
+        .bind(planned.target_type)
+        .bind(planned.target_id)
+        .bind(planned.amount)
+        .bind(now)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        if planned.target_type == AllocationTargetType::Account {
+            sqlx::query(
+                r#"
+                INSERT INTO account_transfers (allocation_id, from_account_id, to_account_id, amount, transferred_at)
+                VALUES ($1, $2, $3, $4, $5)
+                "#,
+            )
+            .bind(allocation_id)
+            .bind(transaction.account_id)
+            .bind(planned.target_id)
+            .bind(planned.amount)
+            .bind(transaction.date)
+            .execute(&mut *tx)
+            .await?;
+        }
+    }
+
+    Ok(goals)
+}
+
+// Moves money out of the account that received the income into another
+// account. Returns false, moving nothing, unless the user may edit both.
+async fn move_to_account(
+    tx: &mut sqlx::Transaction<'_, Postgres>,
+    transaction: &Transaction,
+    target_account_id: Uuid,
+    amount: Money,
+) -> Result<bool, Error> {
+    let editable: Vec<(Uuid,)> = sqlx::query_as(
+        r#"
+        SELECT id FROM accounts
+        WHERE id IN ($1, $2) AND can_edit_record(user_id, household_id, $3)
+        FOR UPDATE
+        "#,
+    )
+    .bind(transaction.account_id)
+    .bind(target_account_id)
+    .bind(transaction.user_id)
+    .fetch_all(&mut *tx)
+    .await?;
+    let is_editable = |account_id| editable.iter().any(|(id,)| *id == account_id);
+    if !is_editable(transaction.account_id) || !is_editable(target_account_id) {
+        return Ok(false);
+    }
+
+    for (account_id, change) in [
+        (transaction.account_id, -amount),
+        (target_account_id, amount),
+    ] {
+        sqlx::query(r#"UPDATE accounts SET balance = balance + $1 WHERE id = $2"#)
+            .bind(change)
+            .bind(account_id)
+            .execute(&mut *tx)
+            .await?;
+    }
+
+    Ok(true)
+}
+
+// Applies the matching rule inside the caller's database transaction. When
+// `rule_id` is given that rule is used as long as it still matches, otherwise
+// the best automatic rule is picked. Returns the saving goals that received
+// money so milestones can be awarded after commit.
+pub async fn apply_allocation_rules(
+    tx: &mut sqlx::Transaction<'_, Postgres>,
+    transaction: &Transaction,
+    rule_id: Option<Uuid>,
+) -> Result<Vec<SavingGoal>, Error> {
+    let matched = match rule_id {
+        Some(rule_id) => find_matching_rule(tx, transaction, false)
+            .await?
+            .filter(|(rule, _)| rule.id == rule_id),
+        None => find_matching_rule(tx, transaction, true).await?,
+    };
+
+    match matched {
+        Some((rule, targets)) => apply_rule(tx, transaction, &rule, &targets).await,
+        None => Ok(Vec::new()),
+    }
+}
+
+// Applies the best matching rule, automatic or not, to a transaction that
+// hasn't been allocated yet. Returns `None` when no rule matches, otherwise
+// the saving goals that received money.
+pub async fn apply_best_allocation_rule(
+    tx: &mut sqlx::Transaction<'_, Postgres>,
+    transaction: &Transaction,
+) -> Result<Option<Vec<SavingGoal>>, Error> {
+    match find_matching_rule(tx, transaction, false).await? {
+        Some((rule, targets)) => Ok(Some(apply_rule(tx, transaction, &rule, &targets).await?)),
+        None => Ok(None),
+    }
+}
+
+pub async fn is_allocated(
+    tx: &mut sqlx::Transaction<'_, Postgres>,
+    transaction_id: Uuid,
+) -> Result<bool, Error> {
+    let (allocated,): (bool,) = sqlx::query_as(
+        r#"SELECT EXISTS (SELECT 1 FROM allocations WHERE source_transaction_id = $1)"#,
+    )
+    .bind(transaction_id)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    Ok(allocated)
+}
+
+// Undoes every allocation made from the transaction and returns the rule that
+// produced them, if it still exists.
+pub async fn reverse_allocations(
+    tx: &mut sqlx::Transaction<'_, Postgres>,
+    transaction: &Transaction,
+) -> Result<Option<Uuid>, Error> {
+    let allocations = sqlx::query_as::<_, Allocation>(
+        r#"DELETE FROM allocations WHERE source_transaction_id = $1 RETURNING *"#,
+    )
+    .bind(transaction.id)
+    .fetch_all(&mut *tx)
+    .await?;
+
+    for allocation in &allocations {
+        match allocation.target_type {
+            AllocationTargetType::SavingGoal => {
+                sqlx::query(
+                    r#"
+                    UPDATE saving_goals
+                    SET current_amount = current_amount - $1, updated_at = $2
+                    WHERE id = $3
+                    "#,
+                )
+                .bind(allocation.amount)
+                .bind(Local::now().naive_local())
+                .bind(allocation.target_id)
+                .execute(&mut *tx)
+                .await?;
+            }
+            AllocationTargetType::Budget => {
+                sqlx::query(r#"UPDATE budgets SET amount = amount - $1 WHERE id = $2"#)
+                    .bind(allocation.amount)
+                    .bind(allocation.target_id)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+            AllocationTargetType::Account => {
+                sqlx::query(r#"UPDATE accounts SET balance = balance + $1 WHERE id = $2"#)
+                    .bind(allocation.amount)
+                    .bind(transaction.account_id)
+                    .execute(&mut *tx)
+                    .await?;
+                sqlx::query(r#"UPDATE accounts SET balance = balance - $1 WHERE id = $2"#)
+                    .bind(allocation.amount)
+                    .bind(allocation.target_id)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+        }
+    }
+
+    sqlx::query(r#"DELETE FROM goal_contributions WHERE transaction_id = $1 AND source = $2"#)
+        .bind(transaction.id)
+        .bind(ContributionSource::Allocation)
+        .execute(&mut *tx)
+        .await?;
+
+    Ok(allocations.iter().find_map(|allocation| allocation.rule_id))
+}
+
+pub async fn fetch_allocations_for_transaction(
+    pool: &PgPool,
+    transaction_id: Uuid,
+) -> Result<Vec<Allocation>, Error> {
+    let allocations = sqlx::query_as::<_, Allocation>(
+        r#"
+        SELECT * FROM allocations
+        WHERE source_transaction_id = $1
+        ORDER BY created_at
+        "#,
+    )
+    .bind(transaction_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(allocations)
+}
@@ -0,0 +1,93 @@
+use chrono::Local;
+use sqlx::postgres::PgPool;
+use uuid::Uuid;
+
+use crate::dtos::api_token_dtos::ApiTokenInDTO;
+use crate::models::{api_token::ApiToken, user::User};
+
+pub async fn create_api_token(
+    pool: &PgPool,
+    user_id: Uuid,
+    api_token_in: &ApiTokenInDTO,
+    token_prefix: &str,
+    token_hash: &str,
+) -> Result<ApiToken, sqlx::Error> {
+    let api_token = sqlx::query_as::<_, ApiToken>(
+        r#"
+        INSERT INTO api_tokens (user_id, name, token_prefix, token_hash, scopes, expires_at)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        RETURNING *
+        "#,
+    )
+    .bind(user_id)
+    .bind(&api_token_in.name)
+    .bind(token_prefix)
+    .bind(token_hash)
+    .bind(&api_token_in.scopes)
+    .bind(api_token_in.expires_at)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(api_token)
+}
+
+pub async fn fetch_api_tokens_for_user(
+    pool: &PgPool,
+    user_id: Uuid,
+) -> Result<Vec<ApiToken>, sqlx::Error> {
+    let api_tokens = sqlx::query_as::<_, ApiToken>(
+        "SELECT * FROM api_tokens WHERE user_id = $1 ORDER BY created_at DESC",
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(api_tokens)
+}
+
+pub async fn delete_api_token(
+    pool: &PgPool,
+    user_id: Uuid,
+    api_token_id: Uuid,
+) -> Result<u64, sqlx::Error> {
+    let deleted = sqlx::query("DELETE FROM api_tokens WHERE id = $1 AND user_id = $2")
+        .bind(api_token_id)
+        .bind(user_id)
+        .execute(pool)
+        .await?
+        .rows_affected();
+
+    Ok(deleted)
+}
+
+// Resolves an unexpired token to its user and scopes and records the use.
+pub async fn find_api_token_user(
+    pool: &PgPool,
+    token_hash: &str,
+) -> Result<Option<(User, Vec<String>)>, sqlx::Error> {
+    let now = Local::now().naive_local();
+    let token = sqlx::query_as::<_, (Uuid, Vec<String>)>(
+        r#"
+        UPDATE api_tokens SET last_used_at = $2
+        WHERE token_hash = $1 AND expires_at > $2
+        RETURNING user_id, scopes
+        "#,
+    )
+    .bind(token_hash)
+    .bind(now)
+    .fetch_optional(pool)
+    .await?;
+
+    let (user_id, scopes) = match token {
+        Some(token) => token,
+        None => return Ok(None),
+    };
+    let user = sqlx::query_as::<_, User>(
+        "SELECT id, username, email, created_at, updated_at FROM users WHERE id = $1 AND suspended_at IS NULL",
+    )
+    .bind(user_id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(user.map(|user| (user, scopes)))
+}
@@ -0,0 +1,134 @@
+use crate::{
+    badges::{BadgeContext, BADGES},
+    models::{
+        badge::{UserBadge, UserStreak},
+        budget::Budget,
+        saving_goals::SavingGoal,
+        transactions::Transaction,
+    },
+    operations::user_preferences_ops::find_user_preferences,
+};
+use chrono::Local;
+use rocket::error;
+use sqlx::{postgres::PgPool, Error};
+use uuid::Uuid;
+
+// Recomputes every badge and streak for the user from their transactions,
+// budgets and saving goals. Earned badges keep their original earned_at and
+// the longest streak never shrinks, even if old records are deleted later.
+pub async fn evaluate_user_badges(pool: &PgPool, user_id: Uuid) -> Result<(), Error> {
+    let transactions = sqlx::query_as::<_, Transaction>(
+        r#"
+        SELECT * FROM transactions WHERE user_id = $1
+    "#,
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await?;
+    let budgets = sqlx::query_as::<_, Budget>(r#"SELECT * FROM budgets WHERE user_id = $1"#)
+        .bind(user_id)
+        .fetch_all(pool)
+        .await?;
+    let saving_goals =
+        sqlx::query_as::<_, SavingGoal>(r#"SELECT * FROM saving_goals WHERE user_id = $1"#)
+            .bind(user_id)
+            .fetch_all(pool)
+            .await?;
+
+    let preferences = find_user_preferences(pool, user_id).await?;
+    let now = Local::now().naive_local();
+    let context = BadgeContext {
+        transactions: &transactions,
+        budgets: &budgets,
+        saving_goals: &saving_goals,
+        today: preferences.today(),
+        week_start: preferences.first_day_of_week(),
+        timezone: preferences.tz(),
+        currency: &preferences.base_currency,
+    };
+
+    for badge in BADGES {
+        let progress = context.progress(badge);
+        sqlx::query(
+            r#"
+            INSERT INTO user_badges (user_id, badge_code, progress, earned_at, updated_at)
+            VALUES ($1, $2, $3, CASE WHEN $3 >= $4 THEN $5 END, $5)
+            ON CONFLICT (user_id, badge_code) DO UPDATE
+            SET progress = EXCLUDED.progress,
+                earned_at = COALESCE(user_badges.earned_at, EXCLUDED.earned_at),
+                updated_at = EXCLUDED.updated_at
+        "#,
+        )
+        .bind(user_id)
+        .bind(badge.code)
+        .bind(progress)
+        .bind(badge.target)
+        .bind(now)
+        .execute(pool)
+        .await?;
+    }
+
+    for (code, streak) in context.streaks() {
+        sqlx::query(
+            r#"
+            INSERT INTO user_streaks (user_id, streak_code, current_length, longest_length, updated_at)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (user_id, streak_code) DO UPDATE
+            SET current_length = EXCLUDED.current_length,
+                longest_length = GREATEST(user_streaks.longest_length, EXCLUDED.longest_length),
+                updated_at = EXCLUDED.updated_at
+        "#,
+        )
+        .bind(user_id)
+        .bind(code)
+        .bind(streak.current)
+        .bind(streak.longest)
+        .bind(now)
+        .execute(pool)
+        .await?;
+    }
+
+    Ok(())
+}
+
+// One user's failure is logged and doesn't keep the others from being
+// evaluated.
+pub async fn evaluate_all_badges(pool: &PgPool) -> Result<(), Error> {
+    let user_ids: Vec<Uuid> = sqlx::query_scalar(r#"SELECT id FROM users"#)
+        .fetch_all(pool)
+        .await?;
+
+    for user_id in user_ids {
+        if let Err(e) = evaluate_user_badges(pool, user_id).await {
+            error!("Badge evaluation failed for user {}: {}", user_id, e);
+        }
+    }
+
+    Ok(())
+}
+
+pub async fn fetch_user_badges(pool: &PgPool, user_id: Uuid) -> Result<Vec<UserBadge>, Error> {
+    let badges = sqlx::query_as::<_, UserBadge>(
+        r#"
+        SELECT * FROM user_badges WHERE user_id = $1 ORDER BY badge_code
+    "#,
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(badges)
+}
+
+pub async fn fetch_user_streaks(pool: &PgPool, user_id: Uuid) -> Result<Vec<UserStreak>, Error> {
+    let streaks = sqlx::query_as::<_, UserStreak>(
+        r#"
+        SELECT * FROM user_streaks WHERE user_id = $1 ORDER BY streak_code
+    "#,
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(streaks)
+}
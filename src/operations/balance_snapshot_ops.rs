@@ -0,0 +1,45 @@
+use crate::models::balance_snapshot::BalanceSnapshot;
+use chrono::{Local, NaiveDate};
+use sqlx::{postgres::PgPool, Error};
+use uuid::Uuid;
+
+// Records every account's current balance under today's date in its owner's
+// timezone, replacing a snapshot already taken today.
+pub async fn take_balance_snapshots(pool: &PgPool) -> Result<u64, Error> {
+    let taken = sqlx::query(
+        r#"
+        INSERT INTO account_balance_snapshots
+            (account_id, snapshot_date, account_type, balance, currency, taken_at)
+        SELECT id, (NOW() AT TIME ZONE user_timezone(user_id))::DATE, account_type, balance, currency, $1
+        FROM accounts
+        ON CONFLICT (account_id, snapshot_date)
+        DO UPDATE SET account_type = EXCLUDED.account_type, balance = EXCLUDED.balance,
+            currency = EXCLUDED.currency, taken_at = EXCLUDED.taken_at
+    "#,
+    )
+    .bind(Local::now().naive_local())
+    .execute(pool)
+    .await?;
+
+    Ok(taken.rows_affected())
+}
+
+// Snapshots of the accounts the user can see, from `from` on.
+pub async fn fetch_balance_snapshots(
+    pool: &PgPool,
+    user_id: Uuid,
+    from: NaiveDate,
+) -> Result<Vec<BalanceSnapshot>, Error> {
+    sqlx::query_as::<_, BalanceSnapshot>(
+        r#"
+        SELECT s.* FROM account_balance_snapshots s
+        JOIN accounts a ON a.id = s.account_id
+        WHERE can_view_record(a.user_id, a.household_id, $1) AND s.snapshot_date >= $2
+        ORDER BY s.snapshot_date
+    "#,
+    )
+    .bind(user_id)
+    .bind(from)
+    .fetch_all(pool)
+    .await
+}
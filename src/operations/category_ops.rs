@@ -4,28 +4,37 @@ use uuid::Uuid;
 
 pub async fn find_category_by_id(
     pool: &PgPool,
+    user_id: Uuid,
     category_id: Uuid,
 ) -> Result<Option<Category>, Error> {
     let category = sqlx::query_as!(
         Category,
-        "SELECT * FROM categories WHERE id = $1",
-        category_id
+        "SELECT * FROM categories WHERE id = $1 AND can_view_record(user_id, household_id, $2)",
+        category_id,
+        user_id
     )
     .fetch_optional(pool)
     .await?;
     Ok(category)
 }
 
-pub async fn fetch_all_categories(pool: &PgPool) -> Result<Vec<Category>, sqlx::Error> {
-    let categories = sqlx::query_as::<_, Category>(r#"SELECT * FROM categories"#)
-        .fetch_all(pool)
-        .await?;
+pub async fn fetch_all_categories(
+    pool: &PgPool,
+    user_id: Uuid,
+) -> Result<Vec<Category>, sqlx::Error> {
+    let categories = sqlx::query_as::<_, Category>(
+        r#"SELECT * FROM categories WHERE can_view_record(user_id, household_id, $1)"#,
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await?;
 
     Ok(categories)
 }
 
 pub async fn create_category(
     pool: &PgPool,
+    user_id: Uuid,
     category_dto: &CategoryInDTO,
 ) -> Result<Category, sqlx::Error> {
     let category = sqlx::query_as::<_, Category>(
@@ -36,7 +45,7 @@ pub async fn create_category(
     "#,
     )
     .bind(&category_dto.name)
-    .bind(&category_dto.user_id)
+    .bind(user_id)
     .fetch_one(pool)
     .await?;
 
@@ -45,32 +54,41 @@ pub async fn create_category(
 
 pub async fn update_category_in_db(
     pool: &PgPool,
+    user_id: Uuid,
     category_id: Uuid,
     category_dto: &CategoryInDTO,
-) -> Result<Category, sqlx::Error> {
+) -> Result<Option<Category>, sqlx::Error> {
     let category = sqlx::query_as::<_, Category>(
         r#"
-        UPDATE categories SET name = $1 WHERE id = $2 RETURNING *
+        UPDATE categories SET name = $1
+        WHERE id = $2 AND can_edit_record(user_id, household_id, $3)
+        RETURNING *
     "#,
     )
     .bind(&category_dto.name)
     .bind(category_id)
-    .fetch_one(pool)
+    .bind(user_id)
+    .fetch_optional(pool)
     .await?;
 
     Ok(category)
 }
 
-pub async fn delete_category(pool: &PgPool, category_id: Uuid) -> Result<(), sqlx::Error> {
-    sqlx::query(
+pub async fn delete_category(
+    pool: &PgPool,
+    user_id: Uuid,
+    category_id: Uuid,
+) -> Result<u64, sqlx::Error> {
+    let deleted = sqlx::query(
         r#"
         DELETE FROM categories
-        WHERE id = $1
+        WHERE id = $1 AND user_id = $2
     "#,
     )
     .bind(category_id)
+    .bind(user_id)
     .execute(pool)
     .await?;
 
-    Ok(())
+    Ok(deleted.rows_affected())
 }
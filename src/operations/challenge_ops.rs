@@ -0,0 +1,142 @@
+use crate::{
+    challenges::{challenge_end_date, DEFAULT_UNIT_AMOUNT},
+    dtos::challenge_dtos::ChallengeInDTO,
+    enums::custom_enums::ChallengeType,
+    models::{
+        challenge::SavingChallenge, contribution::GoalContribution, transactions::Transaction,
+    },
+};
+use chrono::Local;
+use sqlx::{postgres::PgPool, Error};
+use uuid::Uuid;
+
+pub async fn create_challenge(
+    pool: &PgPool,
+    user_id: Uuid,
+    challenge_dto: &ChallengeInDTO,
+) -> Result<SavingChallenge, Error> {
+    let unit_amount = match challenge_dto.challenge_type {
+        ChallengeType::FiftyTwoWeek => {
+            Some(challenge_dto.unit_amount.unwrap_or(DEFAULT_UNIT_AMOUNT))
+        }
+        _ => None,
+    };
+    // The route validates the end date up front, so this only guards direct
+    // callers.
+    let end_date = challenge_end_date(
+        challenge_dto.challenge_type,
+        challenge_dto.start_date,
+        challenge_dto.duration_weeks,
+    )
+    .ok_or_else(|| Error::Protocol("Challenge end date is out of range.".to_string()))?;
+
+    let challenge = sqlx::query_as::<_, SavingChallenge>(
+        r#"
+        INSERT INTO saving_challenges
+            (challenge_type, goal_id, user_id, start_date, end_date, unit_amount, percentage, category_ids, created_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+        RETURNING *
+        "#,
+    )
+    .bind(challenge_dto.challenge_type)
+    .bind(challenge_dto.goal_id)
+    .bind(user_id)
+    .bind(challenge_dto.start_date)
+    .bind(end_date)
+    .bind(unit_amount)
+    .bind(challenge_dto.percentage)
+    .bind(&challenge_dto.category_ids)
+    .bind(Local::now().naive_local())
+    .fetch_one(pool)
+    .await?;
+
+    Ok(challenge)
+}
+
+pub async fn find_challenge_by_id(
+    pool: &PgPool,
+    user_id: Uuid,
+    challenge_id: Uuid,
+) -> Result<Option<SavingChallenge>, Error> {
+    let challenge = sqlx::query_as::<_, SavingChallenge>(
+        r#"SELECT * FROM saving_challenges WHERE id = $1 AND user_id = $2"#,
+    )
+    .bind(challenge_id)
+    .bind(user_id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(challenge)
+}
+
+pub async fn fetch_challenges_for_user(
+    pool: &PgPool,
+    user_id: Uuid,
+) -> Result<Vec<SavingChallenge>, Error> {
+    let challenges = sqlx::query_as::<_, SavingChallenge>(
+        r#"
+        SELECT * FROM saving_challenges
+        WHERE user_id = $1
+        ORDER BY start_date
+        "#,
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(challenges)
+}
+
+// Loads the goal contributions and the user's transactions that fall inside
+// the challenge window, which is everything needed to build its schedule.
+// Contributions are recorded in server time, so a day either side is loaded
+// and the schedule places them in the user's timezone.
+pub async fn fetch_challenge_activity(
+    pool: &PgPool,
+    challenge: &SavingChallenge,
+) -> Result<(Vec<GoalContribution>, Vec<Transaction>), Error> {
+    let contributions = sqlx::query_as::<_, GoalContribution>(
+        r#"
+        SELECT * FROM goal_contributions
+        WHERE goal_id = $1 AND contributed_at::DATE BETWEEN $2::DATE - 1 AND $3::DATE + 1
+        "#,
+    )
+    .bind(challenge.goal_id)
+    .bind(challenge.start_date)
+    .bind(challenge.end_date)
+    .fetch_all(pool)
+    .await?;
+
+    let transactions = sqlx::query_as::<_, Transaction>(
+        r#"
+        SELECT * FROM transactions
+        WHERE user_id = $1 AND (date AT TIME ZONE user_timezone(user_id))::DATE BETWEEN $2 AND $3
+        "#,
+    )
+    .bind(challenge.user_id)
+    .bind(challenge.start_date)
+    .bind(challenge.end_date)
+    .fetch_all(pool)
+    .await?;
+
+    Ok((contributions, transactions))
+}
+
+pub async fn delete_challenge(
+    pool: &PgPool,
+    user_id: Uuid,
+    challenge_id: Uuid,
+) -> Result<u64, Error> {
+    let deleted = sqlx::query(
+        r#"
+        DELETE FROM saving_challenges
+        WHERE id = $1 AND user_id = $2
+    "#,
+    )
+    .bind(challenge_id)
+    .bind(user_id)
+    .execute(pool)
+    .await?;
+
+    Ok(deleted.rows_affected())
+}
@@ -0,0 +1,74 @@
+use crate::{
+    dtos::contribution_dtos::ContributionInDTO,
+    models::{contribution::GoalContribution, saving_goals::SavingGoal},
+    operations::achievement_ops::award_goal_achievements,
+};
+use chrono::Local;
+use sqlx::{postgres::PgPool, Error};
+use uuid::Uuid;
+
+// Stores the contribution and adds it to the goal's current_amount in one
+// database transaction, then awards any milestone the goal has now reached.
+pub async fn add_goal_contribution(
+    pool: &PgPool,
+    goal_id: Uuid,
+    contribution_dto: &ContributionInDTO,
+) -> Result<GoalContribution, Error> {
+    let mut tx = pool.begin().await?;
+
+    let contribution = sqlx::query_as::<_, GoalContribution>(
+        r#"
+        INSERT INTO goal_contributions (goal_id, amount, contributed_at, note)
+        VALUES ($1, $2, $3, $4)
+        RETURNING *
+        "#,
+    )
+    .bind(goal_id)
+    .bind(contribution_dto.amount)
+    .bind(
+        contribution_dto
+            .contributed_at
+            .unwrap_or_else(|| Local::now().naive_local()),
+    )
+    .bind(&contribution_dto.note)
+    .fetch_one(&mut tx)
+    .await?;
+
+    let goal = sqlx::query_as::<_, SavingGoal>(
+        r#"
+        UPDATE saving_goals
+        SET current_amount = current_amount + $1, updated_at = $2
+        WHERE id = $3
+        RETURNING *
+        "#,
+    )
+    .bind(contribution.amount)
+    .bind(Local::now().naive_local())
+    .bind(goal_id)
+    .fetch_one(&mut tx)
+    .await?;
+
+    tx.commit().await?;
+
+    award_goal_achievements(pool, &goal).await?;
+
+    Ok(contribution)
+}
+
+pub async fn fetch_goal_contributions(
+    pool: &PgPool,
+    goal_id: Uuid,
+) -> Result<Vec<GoalContribution>, Error> {
+    let contributions = sqlx::query_as::<_, GoalContribution>(
+        r#"
+        SELECT * FROM goal_contributions
+        WHERE goal_id = $1
+        ORDER BY contributed_at
+        "#,
+    )
+    .bind(goal_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(contributions)
+}
@@ -0,0 +1,75 @@
+use crate::{
+    exchange_rates::{import::ImportedRate, RateTable, MAX_RATE_AGE_DAYS},
+    models::exchange_rate::ExchangeRate,
+};
+use chrono::{Duration, Local, NaiveDate};
+use rust_decimal::Decimal;
+use sqlx::{postgres::PgPool, Error};
+
+// Stores rates quoted against `base`, replacing any already stored for the
+// same day so a corrected file can simply be imported again.
+pub async fn import_exchange_rates(
+    pool: &PgPool,
+    base: &str,
+    rates: &[ImportedRate],
+) -> Result<u64, Error> {
+    let rates: Vec<&ImportedRate> = rates.iter().filter(|rate| rate.currency != base).collect();
+    let currencies: Vec<String> = rates.iter().map(|rate| rate.currency.clone()).collect();
+    let dates: Vec<NaiveDate> = rates.iter().map(|rate| rate.date).collect();
+    let values: Vec<Decimal> = rates.iter().map(|rate| rate.rate).collect();
+
+    let imported = sqlx::query(
+        r#"
+        INSERT INTO exchange_rates (base_currency, quote_currency, rate_date, rate, imported_at)
+        SELECT $1, quote_currency, rate_date, rate, $5
+        FROM UNNEST($2::VARCHAR[], $3::DATE[], $4::NUMERIC[]) AS t(quote_currency, rate_date, rate)
+        ON CONFLICT (base_currency, quote_currency, rate_date)
+        DO UPDATE SET rate = EXCLUDED.rate, imported_at = EXCLUDED.imported_at
+    "#,
+    )
+    .bind(base)
+    .bind(&currencies)
+    .bind(&dates)
+    .bind(&values)
+    .bind(Local::now().naive_local())
+    .execute(pool)
+    .await?;
+
+    Ok(imported.rows_affected())
+}
+
+// Every rate a conversion between the two days could fall back on.
+pub async fn fetch_rate_table(
+    pool: &PgPool,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Result<RateTable, Error> {
+    let rates = sqlx::query_as::<_, ExchangeRate>(
+        r#"
+        SELECT * FROM exchange_rates
+        WHERE rate_date BETWEEN $1 AND $2
+    "#,
+    )
+    .bind(from - Duration::days(MAX_RATE_AGE_DAYS))
+    .bind(to)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(RateTable::new(&rates))
+}
+
+pub async fn fetch_exchange_rates_on(
+    pool: &PgPool,
+    date: NaiveDate,
+) -> Result<Vec<ExchangeRate>, Error> {
+    sqlx::query_as::<_, ExchangeRate>(
+        r#"
+        SELECT * FROM exchange_rates
+        WHERE rate_date = $1
+        ORDER BY base_currency, quote_currency
+    "#,
+    )
+    .bind(date)
+    .fetch_all(pool)
+    .await
+}
@@ -0,0 +1,277 @@
+use sqlx::{postgres::PgPool, Error, Postgres};
+use uuid::Uuid;
+
+use crate::dtos::household_dtos::HouseholdInDTO;
+use crate::enums::custom_enums::HouseholdRole;
+use crate::models::household::{Household, HouseholdMember};
+use crate::operations::ownership_ops::Owned;
+
+const SHAREABLE: [Owned; 4] = [
+    Owned::Account,
+    Owned::Budget,
+    Owned::Category,
+    Owned::SavingGoal,
+];
+
+#[derive(Debug, PartialEq)]
+pub enum MembershipChange {
+    Done,
+    NotMember,
+    // The change would leave the household without an owner.
+    LastOwner,
+}
+
+// Creates the household with its creator as the first owner.
+pub async fn create_household(
+    pool: &PgPool,
+    user_id: Uuid,
+    household_dto: &HouseholdInDTO,
+) -> Result<Household, Error> {
+    let mut tx = pool.begin().await?;
+
+    let household =
+        sqlx::query_as::<_, Household>("INSERT INTO households (name) VALUES ($1) RETURNING *")
+            .bind(&household_dto.name)
+            .fetch_one(&mut tx)
+            .await?;
+
+    sqlx::query("INSERT INTO household_members (household_id, user_id, role) VALUES ($1, $2, $3)")
+        .bind(household.id)
+        .bind(user_id)
+        .bind(HouseholdRole::Owner)
+        .execute(&mut tx)
+        .await?;
+
+    tx.commit().await?;
+
+    Ok(household)
+}
+
+pub async fn fetch_households_for_user(
+    pool: &PgPool,
+    user_id: Uuid,
+) -> Result<Vec<Household>, Error> {
+    let households = sqlx::query_as::<_, Household>(
+        r#"
+        SELECT h.* FROM households h
+        JOIN household_members m ON m.household_id = h.id
+        WHERE m.user_id = $1
+        ORDER BY h.created_at
+        "#,
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(households)
+}
+
+pub async fn find_household_by_id(
+    pool: &PgPool,
+    household_id: Uuid,
+) -> Result<Option<Household>, Error> {
+    sqlx::query_as::<_, Household>("SELECT * FROM households WHERE id = $1")
+        .bind(household_id)
+        .fetch_optional(pool)
+        .await
+}
+
+pub async fn find_member_role(
+    pool: &PgPool,
+    household_id: Uuid,
+    user_id: Uuid,
+) -> Result<Option<HouseholdRole>, Error> {
+    let role: Option<(HouseholdRole,)> = sqlx::query_as(
+        "SELECT role FROM household_members WHERE household_id = $1 AND user_id = $2",
+    )
+    .bind(household_id)
+    .bind(user_id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(role.map(|(role,)| role))
+}
+
+pub async fn fetch_household_members(
+    pool: &PgPool,
+    household_id: Uuid,
+) -> Result<Vec<HouseholdMember>, Error> {
+    let members = sqlx::query_as::<_, HouseholdMember>(
+        r#"
+        SELECT m.household_id, m.user_id, u.username, u.email, m.role, m.joined_at
+        FROM household_members m
+        JOIN users u ON u.id = m.user_id
+        WHERE m.household_id = $1
+        ORDER BY m.joined_at
+        "#,
+    )
+    .bind(household_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(members)
+}
+
+// Returns false when the user already belongs to the household.
+pub async fn add_household_member(
+    pool: &PgPool,
+    household_id: Uuid,
+    user_id: Uuid,
+    role: HouseholdRole,
+) -> Result<bool, Error> {
+    let inserted = sqlx::query(
+        r#"
+        INSERT INTO household_members (household_id, user_id, role)
+        VALUES ($1, $2, $3)
+        ON CONFLICT DO NOTHING
+        "#,
+    )
+    .bind(household_id)
+    .bind(user_id)
+    .bind(role)
+    .execute(pool)
+    .await?;
+
+    Ok(inserted.rows_affected() == 1)
+}
+
+async fn has_owner(
+    tx: &mut sqlx::Transaction<'_, Postgres>,
+    household_id: Uuid,
+) -> Result<bool, Error> {
+    let (exists,): (bool,) = sqlx::query_as(
+        "SELECT EXISTS (SELECT 1 FROM household_members WHERE household_id = $1 AND role = $2)",
+    )
+    .bind(household_id)
+    .bind(HouseholdRole::Owner)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    Ok(exists)
+}
+
+pub async fn update_member_role(
+    pool: &PgPool,
+    household_id: Uuid,
+    user_id: Uuid,
+    role: HouseholdRole,
+) -> Result<MembershipChange, Error> {
+    let mut tx = pool.begin().await?;
+
+    let updated = sqlx::query(
+        "UPDATE household_members SET role = $1 WHERE household_id = $2 AND user_id = $3",
+    )
+    .bind(role)
+    .bind(household_id)
+    .bind(user_id)
+    .execute(&mut tx)
+    .await?;
+    if updated.rows_affected() == 0 {
+        return Ok(MembershipChange::NotMember);
+    }
+    if !has_owner(&mut tx, household_id).await? {
+        return Ok(MembershipChange::LastOwner);
+    }
+
+    tx.commit().await?;
+
+    Ok(MembershipChange::Done)
+}
+
+// Removes the member and takes back everything they had shared with the
+// household, since the remaining members lose their claim to it.
+pub async fn remove_household_member(
+    pool: &PgPool,
+    household_id: Uuid,
+    user_id: Uuid,
+) -> Result<MembershipChange, Error> {
+    let mut tx = pool.begin().await?;
+
+    let deleted =
+        sqlx::query("DELETE FROM household_members WHERE household_id = $1 AND user_id = $2")
+            .bind(household_id)
+            .bind(user_id)
+            .execute(&mut tx)
+            .await?;
+    if deleted.rows_affected() == 0 {
+        return Ok(MembershipChange::NotMember);
+    }
+    if !has_owner(&mut tx, household_id).await? {
+        return Ok(MembershipChange::LastOwner);
+    }
+
+    for owned in SHAREABLE {
+        let query = format!(
+            "UPDATE {} SET household_id = NULL WHERE household_id = $1 AND user_id = $2",
+            owned.table()
+        );
+        sqlx::query(&query)
+            .bind(household_id)
+            .bind(user_id)
+            .execute(&mut tx)
+            .await?;
+    }
+
+    tx.commit().await?;
+
+    Ok(MembershipChange::Done)
+}
+
+// Shared records fall back to their owners through ON DELETE SET NULL.
+pub async fn delete_household(pool: &PgPool, household_id: Uuid) -> Result<u64, Error> {
+    let deleted = sqlx::query("DELETE FROM households WHERE id = $1")
+        .bind(household_id)
+        .execute(pool)
+        .await?;
+
+    Ok(deleted.rows_affected())
+}
+
+// Only the record's owner can share it; a record belongs to at most one
+// household, so sharing it again moves it.
+pub async fn share_record(
+    pool: &PgPool,
+    user_id: Uuid,
+    household_id: Uuid,
+    owned: Owned,
+    record_id: Uuid,
+) -> Result<u64, Error> {
+    let query = format!(
+        "UPDATE {} SET household_id = $1 WHERE id = $2 AND user_id = $3",
+        owned.table()
+    );
+    let updated = sqlx::query(&query)
+        .bind(household_id)
+        .bind(record_id)
+        .bind(user_id)
+        .execute(pool)
+        .await?;
+
+    Ok(updated.rows_affected())
+}
+
+// `owner_id` limits the change to records of that user; household owners
+// pass `None` to unshare anything shared with their household.
+pub async fn unshare_record(
+    pool: &PgPool,
+    owner_id: Option<Uuid>,
+    household_id: Uuid,
+    owned: Owned,
+    record_id: Uuid,
+) -> Result<u64, Error> {
+    let query = format!(
+        r#"
+        UPDATE {} SET household_id = NULL
+        WHERE id = $1 AND household_id = $2 AND ($3::UUID IS NULL OR user_id = $3)
+        "#,
+        owned.table()
+    );
+    let updated = sqlx::query(&query)
+        .bind(record_id)
+        .bind(household_id)
+        .bind(owner_id)
+        .execute(pool)
+        .await?;
+
+    Ok(updated.rows_affected())
+}
@@ -0,0 +1,76 @@
+use sqlx::postgres::PgPool;
+use uuid::Uuid;
+
+use crate::models::user::User;
+
+pub async fn find_identity_user(
+    pool: &PgPool,
+    issuer: &str,
+    subject: &str,
+) -> Result<Option<User>, sqlx::Error> {
+    let user = sqlx::query_as::<_, User>(
+        r#"
+        SELECT u.id, u.username, u.email, u.created_at, u.updated_at
+        FROM users u
+        JOIN user_identities i ON i.user_id = u.id
+        WHERE i.issuer = $1 AND i.subject = $2
+        "#,
+    )
+    .bind(issuer)
+    .bind(subject)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(user)
+}
+
+pub async fn link_identity(
+    pool: &PgPool,
+    user_id: Uuid,
+    issuer: &str,
+    subject: &str,
+) -> Result<(), sqlx::Error> {
+    sqlx::query("INSERT INTO user_identities (user_id, issuer, subject) VALUES ($1, $2, $3)")
+        .bind(user_id)
+        .bind(issuer)
+        .bind(subject)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
+
+// First login through a provider: the user row and its identity link are
+// created together so a failure can't leave an unreachable account behind.
+pub async fn create_user_with_identity(
+    pool: &PgPool,
+    username: &str,
+    email: &str,
+    issuer: &str,
+    subject: &str,
+) -> Result<User, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    let user = sqlx::query_as::<_, User>(
+        r#"
+        INSERT INTO users (username, email)
+        VALUES ($1, $2)
+        RETURNING id, username, email, created_at, updated_at
+        "#,
+    )
+    .bind(username)
+    .bind(email)
+    .fetch_one(&mut tx)
+    .await?;
+
+    sqlx::query("INSERT INTO user_identities (user_id, issuer, subject) VALUES ($1, $2, $3)")
+        .bind(user.id)
+        .bind(issuer)
+        .bind(subject)
+        .execute(&mut tx)
+        .await?;
+
+    tx.commit().await?;
+
+    Ok(user)
+}
@@ -1,7 +1,25 @@
 pub mod account_ops;
 pub mod achievement_ops;
+pub mod admin_ops;
+pub mod allocation_ops;
+pub mod api_token_ops;
+pub mod badge_ops;
+pub mod balance_snapshot_ops;
 pub mod budget_ops;
 pub mod category_ops;
+pub mod challenge_ops;
+pub mod contribution_ops;
+pub mod exchange_rate_ops;
+pub mod household_ops;
+pub mod identity_ops;
+pub mod ownership_ops;
+pub mod recurring_transaction_ops;
+pub mod report_ops;
+pub mod round_up_ops;
 pub mod saving_goal_ops;
+pub mod session_ops;
+pub mod subscription_ops;
 pub mod transaction_ops;
+pub mod two_factor_ops;
 pub mod user_ops;
+pub mod user_preferences_ops;
@@ -0,0 +1,93 @@
+use sqlx::{postgres::PgPool, Error};
+use uuid::Uuid;
+
+use crate::enums::custom_enums::{AllocationTargetType, SharedRecordType};
+
+// Records that other records can point at. Every one of these tables carries
+// the owning `user_id` and the `household_id` it is shared with, if any.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Owned {
+    Account,
+    Budget,
+    Category,
+    SavingGoal,
+}
+
+impl Owned {
+    pub fn table(self) -> &'static str {
+        match self {
+            Owned::Account => "accounts",
+            Owned::Budget => "budgets",
+            Owned::Category => "categories",
+            Owned::SavingGoal => "saving_goals",
+        }
+    }
+}
+
+impl From<AllocationTargetType> for Owned {
+    fn from(target_type: AllocationTargetType) -> Self {
+        match target_type {
+            AllocationTargetType::SavingGoal => Owned::SavingGoal,
+            AllocationTargetType::Budget => Owned::Budget,
+            AllocationTargetType::Account => Owned::Account,
+        }
+    }
+}
+
+impl From<SharedRecordType> for Owned {
+    fn from(record_type: SharedRecordType) -> Self {
+        match record_type {
+            SharedRecordType::Account => Owned::Account,
+            SharedRecordType::Budget => Owned::Budget,
+            SharedRecordType::Category => Owned::Category,
+            SharedRecordType::SavingGoal => Owned::SavingGoal,
+        }
+    }
+}
+
+async fn check_access(
+    pool: &PgPool,
+    check: &str,
+    user_id: Uuid,
+    owned: Owned,
+    id: Uuid,
+) -> Result<bool, Error> {
+    let query = format!(
+        "SELECT EXISTS (SELECT 1 FROM {} WHERE id = $1 AND {}(user_id, household_id, $2))",
+        owned.table(),
+        check
+    );
+    let (exists,): (bool,) = sqlx::query_as(&query)
+        .bind(id)
+        .bind(user_id)
+        .fetch_one(pool)
+        .await?;
+
+    Ok(exists)
+}
+
+// True when the user owns the record or it is shared with one of their
+// households.
+pub async fn can_view(pool: &PgPool, user_id: Uuid, owned: Owned, id: Uuid) -> Result<bool, Error> {
+    check_access(pool, "can_view_record", user_id, owned, id).await
+}
+
+// Like `can_view`, but household viewers are left out.
+pub async fn can_edit(pool: &PgPool, user_id: Uuid, owned: Owned, id: Uuid) -> Result<bool, Error> {
+    check_access(pool, "can_edit_record", user_id, owned, id).await
+}
+
+// True when every referenced record exists and the user may edit it.
+pub async fn can_edit_references(
+    pool: &PgPool,
+    user_id: Uuid,
+    references: &[(Owned, Uuid)],
+) -> Result<bool, Error> {
+    for (owned, id) in references {
+        if !can_edit(pool, user_id, *owned, *id).await? {
+            return Ok(false);
+        }
+    }
+
+    Ok(true)
+}
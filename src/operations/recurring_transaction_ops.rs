@@ -0,0 +1,68 @@
+use crate::{
+    dtos::recurring_transaction_dtos::RecurringTransactionInDTO,
+    models::recurring_transaction::RecurringTransaction,
+};
+use chrono::Local;
+use sqlx::{postgres::PgPool, Error};
+use uuid::Uuid;
+
+pub async fn create_recurring_transaction(
+    pool: &PgPool,
+    user_id: Uuid,
+    recurring_dto: &RecurringTransactionInDTO,
+) -> Result<RecurringTransaction, Error> {
+    sqlx::query_as::<_, RecurringTransaction>(
+        r#"
+        INSERT INTO recurring_transactions (
+            user_id, account_id, category_id, title, amount, transaction_type,
+            frequency, every, start_date, end_date, created_at
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+        RETURNING *
+        "#,
+    )
+    .bind(user_id)
+    .bind(recurring_dto.account_id)
+    .bind(recurring_dto.category_id)
+    .bind(&recurring_dto.title)
+    .bind(recurring_dto.amount)
+    .bind(recurring_dto.transaction_type)
+    .bind(recurring_dto.frequency)
+    .bind(recurring_dto.every.unwrap_or(1))
+    .bind(recurring_dto.start_date)
+    .bind(recurring_dto.end_date)
+    .bind(Local::now().naive_local())
+    .fetch_one(pool)
+    .await
+}
+
+pub async fn fetch_recurring_transactions_for_user(
+    pool: &PgPool,
+    user_id: Uuid,
+) -> Result<Vec<RecurringTransaction>, Error> {
+    sqlx::query_as::<_, RecurringTransaction>(
+        r#"SELECT * FROM recurring_transactions WHERE user_id = $1 ORDER BY start_date, title"#,
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await
+}
+
+pub async fn delete_recurring_transaction(
+    pool: &PgPool,
+    user_id: Uuid,
+    recurring_id: Uuid,
+) -> Result<u64, Error> {
+    let deleted = sqlx::query(
+        r#"
+        DELETE FROM recurring_transactions
+        WHERE id = $1 AND user_id = $2
+    "#,
+    )
+    .bind(recurring_id)
+    .bind(user_id)
+    .execute(pool)
+    .await?;
+
+    Ok(deleted.rows_affected())
+}
@@ -0,0 +1,242 @@
+use crate::{
+    enums::custom_enums::ReportBreakdown,
+    models::{
+        report::{
+            AccountTransferTotal, BudgetResult, CategorySpending, CompletedGoal, DailyAccountFlow,
+            DailyTotal,
+        },
+        subscription::Charge,
+    },
+};
+use chrono::NaiveDate;
+use sqlx::{postgres::PgPool, Error};
+use uuid::Uuid;
+
+// Totals per local day, transaction type and currency, over every account
+// the user can see. Days are kept apart so every total can still be converted
+// at the rate of the day it was spent on.
+pub async fn fetch_daily_totals(
+    pool: &PgPool,
+    user_id: Uuid,
+    from: NaiveDate,
+    to: NaiveDate,
+    breakdown: Option<ReportBreakdown>,
+) -> Result<Vec<DailyTotal>, Error> {
+    let breakdown = breakdown.map(|breakdown| match breakdown {
+        ReportBreakdown::Account => "account",
+        ReportBreakdown::Category => "category",
+    });
+
+    sqlx::query_as::<_, DailyTotal>(
+        r#"
+        SELECT
+            (t.date AT TIME ZONE user_timezone($1))::DATE AS day,
+            t.transaction_type,
+            t.currency,
+            CASE $4::TEXT WHEN 'account' THEN a.id WHEN 'category' THEN c.id END AS group_id,
+            CASE $4::TEXT WHEN 'account' THEN a.name WHEN 'category' THEN c.name END AS group_name,
+            SUM(t.amount) AS amount,
+            COUNT(*) AS transaction_count
+        FROM transactions t
+        JOIN accounts a ON a.id = t.account_id
+        LEFT JOIN categories c ON c.id = t.category_id
+        WHERE can_view_record(a.user_id, a.household_id, $1)
+            AND (t.date AT TIME ZONE user_timezone($1))::DATE BETWEEN $2 AND $3
+        GROUP BY 1, 2, 3, 4, 5
+        ORDER BY 1
+    "#,
+    )
+    .bind(user_id)
+    .bind(from)
+    .bind(to)
+    .bind(breakdown)
+    .fetch_all(pool)
+    .await
+}
+
+// Expenses on every account the user can see between two local days, both
+// included, as charges for payee lists.
+pub async fn fetch_visible_charges(
+    pool: &PgPool,
+    user_id: Uuid,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Result<Vec<Charge>, Error> {
+    sqlx::query_as::<_, Charge>(
+        r#"
+        SELECT
+            t.id AS transaction_id,
+            t.account_id,
+            t.category_id,
+            t.title,
+            (t.date AT TIME ZONE user_timezone($1))::DATE AS day,
+            t.amount,
+            t.currency
+        FROM transactions t
+        JOIN accounts a ON a.id = t.account_id
+        WHERE can_view_record(a.user_id, a.household_id, $1)
+            AND t.transaction_type = 'Expense'
+            AND (t.date AT TIME ZONE user_timezone($1))::DATE BETWEEN $2 AND $3
+        ORDER BY t.date
+    "#,
+    )
+    .bind(user_id)
+    .bind(from)
+    .bind(to)
+    .fetch_all(pool)
+    .await
+}
+
+// What each visible account gained or lost per local day after `after`, from
+// transactions and transfers.
+pub async fn fetch_daily_account_flows(
+    pool: &PgPool,
+    user_id: Uuid,
+    after: NaiveDate,
+) -> Result<Vec<DailyAccountFlow>, Error> {
+    sqlx::query_as::<_, DailyAccountFlow>(
+        r#"
+        SELECT
+            m.account_id,
+            (m.moved_at AT TIME ZONE user_timezone($1))::DATE AS day,
+            SUM(m.amount) AS net
+        FROM (
+            SELECT
+                account_id,
+                date AS moved_at,
+                CASE WHEN transaction_type = 'Income' THEN amount ELSE -amount END AS amount
+            FROM transactions
+            UNION ALL
+            SELECT from_account_id, transferred_at, -amount FROM account_transfers
+            UNION ALL
+            SELECT to_account_id, transferred_at, amount FROM account_transfers
+        ) m
+        JOIN accounts a ON a.id = m.account_id
+        WHERE can_view_record(a.user_id, a.household_id, $1)
+            AND (m.moved_at AT TIME ZONE user_timezone($1))::DATE > $2
+        GROUP BY 1, 2
+        ORDER BY 2
+    "#,
+    )
+    .bind(user_id)
+    .bind(after)
+    .fetch_all(pool)
+    .await
+}
+
+// Expenses per visible account and category between two local days, both
+// included.
+pub async fn fetch_category_spending(
+    pool: &PgPool,
+    user_id: Uuid,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Result<Vec<CategorySpending>, Error> {
+    sqlx::query_as::<_, CategorySpending>(
+        r#"
+        SELECT t.account_id, t.category_id, SUM(t.amount) AS amount
+        FROM transactions t
+        JOIN accounts a ON a.id = t.account_id
+        WHERE can_view_record(a.user_id, a.household_id, $1)
+            AND t.transaction_type = 'Expense'
+            AND (t.date AT TIME ZONE user_timezone($1))::DATE BETWEEN $2 AND $3
+        GROUP BY 1, 2
+    "#,
+    )
+    .bind(user_id)
+    .bind(from)
+    .bind(to)
+    .fetch_all(pool)
+    .await
+}
+
+// Net transfers into each visible account between two local days, both
+// included.
+pub async fn fetch_net_transfers(
+    pool: &PgPool,
+    user_id: Uuid,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Result<Vec<AccountTransferTotal>, Error> {
+    sqlx::query_as::<_, AccountTransferTotal>(
+        r#"
+        SELECT m.account_id, SUM(m.amount) AS net
+        FROM (
+            SELECT from_account_id AS account_id, transferred_at, -amount AS amount
+            FROM account_transfers
+            UNION ALL
+            SELECT to_account_id, transferred_at, amount FROM account_transfers
+        ) m
+        JOIN accounts a ON a.id = m.account_id
+        WHERE can_view_record(a.user_id, a.household_id, $1)
+            AND (m.transferred_at AT TIME ZONE user_timezone($1))::DATE BETWEEN $2 AND $3
+        GROUP BY 1
+    "#,
+    )
+    .bind(user_id)
+    .bind(from)
+    .bind(to)
+    .fetch_all(pool)
+    .await
+}
+
+// Visible goals at or above their target that reached it between two local
+// days.
+pub async fn fetch_completed_goals(
+    pool: &PgPool,
+    user_id: Uuid,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Result<Vec<CompletedGoal>, Error> {
+    sqlx::query_as::<_, CompletedGoal>(
+        r#"
+        SELECT g.id AS goal_id, g.title, g.target_amount,
+            (g.completed_at AT TIME ZONE user_timezone($1))::DATE AS completed_on
+        FROM saving_goals g
+        WHERE can_view_record(g.user_id, g.household_id, $1)
+            AND (g.completed_at AT TIME ZONE user_timezone($1))::DATE BETWEEN $2 AND $3
+        ORDER BY g.completed_at, g.title
+    "#,
+    )
+    .bind(user_id)
+    .bind(from)
+    .bind(to)
+    .fetch_all(pool)
+    .await
+}
+
+// Visible budgets that ended between two local days, with the visible
+// expenses in `currency` that count against them. Like budget badges, only
+// one currency is measured.
+pub async fn fetch_budget_results(
+    pool: &PgPool,
+    user_id: Uuid,
+    from: NaiveDate,
+    to: NaiveDate,
+    currency: &str,
+) -> Result<Vec<BudgetResult>, Error> {
+    sqlx::query_as::<_, BudgetResult>(
+        r#"
+        SELECT b.id AS budget_id, b.name, b.amount, COALESCE(SUM(t.amount), 0) AS spent
+        FROM budgets b
+        LEFT JOIN (transactions t JOIN accounts a ON a.id = t.account_id)
+            ON can_view_record(a.user_id, a.household_id, $1)
+            AND t.transaction_type = 'Expense'
+            AND t.currency = $4
+            AND (b.category_id IS NULL OR t.category_id = b.category_id)
+            AND (t.date AT TIME ZONE user_timezone($1))::DATE
+                BETWEEN (b.start_date AT TIME ZONE user_timezone($1))::DATE
+                AND (b.end_date AT TIME ZONE user_timezone($1))::DATE
+        WHERE can_view_record(b.user_id, b.household_id, $1)
+            AND (b.end_date AT TIME ZONE user_timezone($1))::DATE BETWEEN $2 AND $3
+        GROUP BY b.id, b.name, b.amount, b.end_date
+        ORDER BY b.end_date, b.name
+    "#,
+    )
+    .bind(user_id)
+    .bind(from)
+    .bind(to)
+    .bind(currency)
+    .fetch_all(pool)
+    .await
+}
@@ -0,0 +1,196 @@
+use crate::{
+    dtos::round_up_dtos::RoundUpRuleInDTO,
+    enums::custom_enums::{ContributionSource, TransactionType},
+    models::{
+        round_up_rule::{RoundUpMonthlyTotal, RoundUpRule},
+        saving_goals::SavingGoal,
+        transactions::Transaction,
+    },
+    money::Money,
+    rules::round_up::round_up_difference,
+};
+use chrono::Local;
+use sqlx::{postgres::PgPool, Error, Postgres};
+use uuid::Uuid;
+
+pub async fn create_round_up_rule(
+    pool: &PgPool,
+    user_id: Uuid,
+    rule_dto: &RoundUpRuleInDTO,
+) -> Result<RoundUpRule, Error> {
+    let rule = sqlx::query_as::<_, RoundUpRule>(
+        r#"
+        INSERT INTO round_up_rules (user_id, account_id, goal_id, round_to, created_at)
+        VALUES ($1, $2, $3, $4, $5)
+        RETURNING *
+        "#,
+    )
+    .bind(user_id)
+    .bind(rule_dto.account_id)
+    .bind(rule_dto.goal_id)
+    .bind(rule_dto.round_to)
+    .bind(Local::now().naive_local())
+    .fetch_one(pool)
+    .await?;
+
+    Ok(rule)
+}
+
+pub async fn fetch_round_up_rules_for_user(
+    pool: &PgPool,
+    user_id: Uuid,
+) -> Result<Vec<RoundUpRule>, Error> {
+    let rules = sqlx::query_as::<_, RoundUpRule>(
+        r#"SELECT * FROM round_up_rules WHERE user_id = $1 ORDER BY created_at"#,
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rules)
+}
+
+pub async fn delete_round_up_rule(
+    pool: &PgPool,
+    user_id: Uuid,
+    rule_id: Uuid,
+) -> Result<u64, Error> {
+    let deleted = sqlx::query(
+        r#"
+        DELETE FROM round_up_rules
+        WHERE id = $1 AND user_id = $2
+    "#,
+    )
+    .bind(rule_id)
+    .bind(user_id)
+    .execute(pool)
+    .await?;
+
+    Ok(deleted.rows_affected())
+}
+
+// Adds the round-up of an Expense transaction to the goal configured for its
+// account. The goal is held in its owner's base currency, so nothing is added
+// while that differs from the account's. Returns the updated goal so the
+// caller can award milestones once the surrounding database transaction has
+// been committed.
+pub async fn apply_round_up(
+    tx: &mut sqlx::Transaction<'_, Postgres>,
+    transaction: &Transaction,
+) -> Result<Option<SavingGoal>, Error> {
+    if transaction.transaction_type != TransactionType::Expense {
+        return Ok(None);
+    }
+
+    let rule = sqlx::query_as::<_, RoundUpRule>(
+        r#"SELECT * FROM round_up_rules WHERE account_id = $1 AND user_id = $2"#,
+    )
+    .bind(transaction.account_id)
+    .bind(transaction.user_id)
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    let Some(rule) = rule else {
+        return Ok(None);
+    };
+    let difference = round_up_difference(transaction.amount, rule.round_to);
+    if !difference.is_positive() {
+        return Ok(None);
+    }
+
+    let goal = sqlx::query_as::<_, SavingGoal>(
+        r#"
+        UPDATE saving_goals
+        SET current_amount = current_amount + $1, updated_at = $2
+        WHERE id = $3 AND user_base_currency(user_id) = $4
+        RETURNING *
+        "#,
+    )
+    .bind(difference)
+    .bind(Local::now().naive_local())
+    .bind(rule.goal_id)
+    .bind(&transaction.currency)
+    .fetch_optional(&mut *tx)
+    .await?;
+    let Some(goal) = goal else {
+        return Ok(None);
+    };
+
+    sqlx::query(
+        r#"
+        INSERT INTO goal_contributions (goal_id, amount, contributed_at, note, source, transaction_id)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        "#,
+    )
+    .bind(goal.id)
+    .bind(difference)
+    .bind(transaction.date.with_timezone(&Local).naive_local())
+    .bind(format!("Round-up of {}", transaction.title))
+    .bind(ContributionSource::RoundUp)
+    .bind(transaction.id)
+    .execute(&mut *tx)
+    .await?;
+
+    Ok(Some(goal))
+}
+
+// Takes back every round-up that was made for the transaction, so that it can
+// be recalculated after an edit or dropped when the transaction is deleted.
+pub async fn reverse_round_up(
+    tx: &mut sqlx::Transaction<'_, Postgres>,
+    transaction_id: Uuid,
+) -> Result<(), Error> {
+    let reversed: Vec<(Uuid, Money)> = sqlx::query_as(
+        r#"
+        DELETE FROM goal_contributions
+        WHERE transaction_id = $1 AND source = $2
+        RETURNING goal_id, amount
+        "#,
+    )
+    .bind(transaction_id)
+    .bind(ContributionSource::RoundUp)
+    .fetch_all(&mut *tx)
+    .await?;
+
+    for (goal_id, amount) in reversed {
+        sqlx::query(
+            r#"
+            UPDATE saving_goals
+            SET current_amount = current_amount - $1, updated_at = $2
+            WHERE id = $3
+            "#,
+        )
+        .bind(amount)
+        .bind(Local::now().naive_local())
+        .bind(goal_id)
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    Ok(())
+}
+
+pub async fn fetch_round_up_summary(
+    pool: &PgPool,
+    user_id: Uuid,
+) -> Result<Vec<RoundUpMonthlyTotal>, Error> {
+    let totals = sqlx::query_as::<_, RoundUpMonthlyTotal>(
+        r#"
+        SELECT g.id AS goal_id,
+               g.title AS goal_title,
+               DATE_TRUNC('month', c.contributed_at AT TIME ZONE user_timezone(g.user_id))::DATE AS month,
+               SUM(c.amount) AS total
+        FROM goal_contributions c
+        JOIN saving_goals g ON g.id = c.goal_id
+        WHERE can_view_record(g.user_id, g.household_id, $1) AND c.source = $2
+        GROUP BY g.id, g.title, DATE_TRUNC('month', c.contributed_at AT TIME ZONE user_timezone(g.user_id))
+        ORDER BY month, g.title
+        "#,
+    )
+    .bind(user_id)
+    .bind(ContributionSource::RoundUp)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(totals)
+}
@@ -1,32 +1,49 @@
-use crate::{dtos::saving_goal_dtos::SavingGoalInDTO, models::saving_goals::SavingGoal};
+use crate::{
+    dtos::saving_goal_dtos::SavingGoalInDTO, models::saving_goals::SavingGoal, money::Money,
+    operations::achievement_ops::award_goal_achievements,
+};
 use chrono::Local;
 use sqlx::{postgres::PgPool, Error};
 use uuid::Uuid;
 
 pub async fn find_saving_goal_by_id(
     pool: &PgPool,
+    user_id: Uuid,
     saving_goal_id: Uuid,
 ) -> Result<Option<SavingGoal>, Error> {
     let saving_goal = sqlx::query_as!(
         SavingGoal,
-        "SELECT * FROM saving_goals WHERE id = $1",
-        saving_goal_id
+        r#"
+        SELECT id, title, target_amount AS "target_amount: Money", current_amount AS "current_amount: Money",
+               deadline, user_id, created_at, updated_at, household_id
+        FROM saving_goals
+        WHERE id = $1 AND can_view_record(user_id, household_id, $2)
+        "#,
+        saving_goal_id,
+        user_id
     )
     .fetch_optional(pool)
     .await?;
     Ok(saving_goal)
 }
 
-pub async fn fetch_all_saving_goals(pool: &PgPool) -> Result<Vec<SavingGoal>, sqlx::Error> {
-    let saving_goals = sqlx::query_as::<_, SavingGoal>(r#"SELECT * FROM saving_goals"#)
-        .fetch_all(pool)
-        .await?;
+pub async fn fetch_all_saving_goals(
+    pool: &PgPool,
+    user_id: Uuid,
+) -> Result<Vec<SavingGoal>, sqlx::Error> {
+    let saving_goals = sqlx::query_as::<_, SavingGoal>(
+        r#"SELECT * FROM saving_goals WHERE can_view_record(user_id, household_id, $1)"#,
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await?;
 
     Ok(saving_goals)
 }
 
 pub async fn create_saving_goal(
     pool: &PgPool,
+    user_id: Uuid,
     saving_goal_dto: &SavingGoalInDTO,
 ) -> Result<SavingGoal, sqlx::Error> {
     let row = sqlx::query_as::<_, SavingGoal>(
@@ -37,61 +54,66 @@ pub async fn create_saving_goal(
         "#,
     )
     .bind(&saving_goal_dto.title)
-    .bind(&saving_goal_dto.target_amount)
-    .bind(&saving_goal_dto.current_amount)
-    .bind(&saving_goal_dto.deadline)
-    .bind(&saving_goal_dto.user_id)
+    .bind(saving_goal_dto.target_amount)
+    .bind(saving_goal_dto.current_amount)
+    .bind(saving_goal_dto.deadline)
+    .bind(user_id)
     .bind(Local::now().naive_local())
     .bind(Local::now().naive_local())
     .fetch_one(pool)
     .await?;
 
+    award_goal_achievements(pool, &row).await?;
+
     Ok(row)
 }
 
 pub async fn update_saving_goal_in_db(
     pool: &PgPool,
+    user_id: Uuid,
     saving_goal_id: Uuid,
     saving_goal_dto: &SavingGoalInDTO,
-) -> Result<SavingGoal, sqlx::Error> {
+) -> Result<Option<SavingGoal>, sqlx::Error> {
     let row = sqlx::query_as::<_, SavingGoal>(
         r#"
         UPDATE saving_goals
-        SET title = $1, target_amount = $2, current_amount = $3, deadline = $4, user_id = $5, updated_at = $6
-        WHERE id = $7
+        SET title = $1, target_amount = $2, current_amount = $3, deadline = $4, updated_at = $5
+        WHERE id = $6 AND can_edit_record(user_id, household_id, $7)
         RETURNING *
         "#,
     )
     .bind(&saving_goal_dto.title)
-    .bind(&saving_goal_dto.target_amount)
-    .bind(&saving_goal_dto.current_amount)
-    .bind(&saving_goal_dto.deadline)
-    .bind(&saving_goal_dto.user_id)
+    .bind(saving_goal_dto.target_amount)
+    .bind(saving_goal_dto.current_amount)
+    .bind(saving_goal_dto.deadline)
     .bind(Local::now().naive_local())
     .bind(saving_goal_id)
-    .fetch_one(pool)
+    .bind(user_id)
+    .fetch_optional(pool)
     .await?;
 
+    if let Some(goal) = &row {
+        award_goal_achievements(pool, goal).await?;
+    }
+
     Ok(row)
 }
 
-pub async fn delete_saving_goal(pool: &PgPool, saving_goal_id: Uuid) -> Result<(), sqlx::Error> {
-    // let rows_deleted = sqlx::query("DELETE FROM saving_goals WHERE id = $1")
-    //     .bind(saving_goal_id)
-    //     .execute(pool)
-    //     .await?
-    //     .rows_affected();
-
-    // Ok(rows_deleted)
-    sqlx::query(
+pub async fn delete_saving_goal(
+    pool: &PgPool,
+    user_id: Uuid,
+    saving_goal_id: Uuid,
+) -> Result<u64, sqlx::Error> {
+    let deleted = sqlx::query(
         r#"
         DELETE FROM saving_goals
-        WHERE id = $1
+        WHERE id = $1 AND user_id = $2
     "#,
     )
     .bind(saving_goal_id)
+    .bind(user_id)
     .execute(pool)
     .await?;
 
-    Ok(())
+    Ok(deleted.rows_affected())
 }
@@ -0,0 +1,133 @@
+use chrono::{Duration, Local};
+use sqlx::postgres::PgPool;
+use uuid::Uuid;
+
+use crate::models::{session::Session, user::User};
+
+pub async fn create_session(
+    pool: &PgPool,
+    user_id: Uuid,
+    ttl: Duration,
+    two_factor_verified: bool,
+) -> Result<Session, sqlx::Error> {
+    let now = Local::now().naive_local();
+    let session = sqlx::query_as::<_, Session>(
+        r#"
+        INSERT INTO sessions (user_id, created_at, last_seen_at, expires_at, two_factor_verified)
+        VALUES ($1, $2, $2, $3, $4)
+        RETURNING *
+        "#,
+    )
+    .bind(user_id)
+    .bind(now)
+    .bind(now + ttl)
+    .bind(two_factor_verified)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(session)
+}
+
+// Opens a session as `user_id` on behalf of an admin. The admin already
+// proved who they are, so no second factor is asked of the user.
+pub async fn create_impersonation_session(
+    pool: &PgPool,
+    user_id: Uuid,
+    impersonator_id: Uuid,
+    ttl: Duration,
+) -> Result<Session, sqlx::Error> {
+    let now = Local::now().naive_local();
+    let session = sqlx::query_as::<_, Session>(
+        r#"
+        INSERT INTO sessions (user_id, created_at, last_seen_at, expires_at, two_factor_verified, impersonator_id)
+        VALUES ($1, $2, $2, $3, TRUE, $4)
+        RETURNING *
+        "#,
+    )
+    .bind(user_id)
+    .bind(now)
+    .bind(now + ttl)
+    .bind(impersonator_id)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(session)
+}
+
+#[derive(sqlx::FromRow)]
+struct SessionUserRow {
+    id: Uuid,
+    username: String,
+    email: String,
+    created_at: chrono::NaiveDateTime,
+    updated_at: Option<chrono::NaiveDateTime>,
+    impersonator_id: Option<Uuid>,
+}
+
+// Resolves a live session to its user, and the admin impersonating them if
+// any, and records the activity. Sessions of users with two-factor enabled
+// only count once the second factor was given; suspended users have none.
+pub async fn find_session_user(
+    pool: &PgPool,
+    session_id: Uuid,
+) -> Result<Option<(User, Option<Uuid>)>, sqlx::Error> {
+    let now = Local::now().naive_local();
+    let row = sqlx::query_as::<_, SessionUserRow>(
+        r#"
+        WITH session AS (
+            UPDATE sessions SET last_seen_at = $2
+            WHERE id = $1 AND expires_at > $2
+            RETURNING user_id, two_factor_verified, impersonator_id
+        )
+        SELECT u.id, u.username, u.email, u.created_at, u.updated_at, s.impersonator_id
+        FROM users u
+        JOIN session s ON s.user_id = u.id
+        WHERE (NOT u.totp_enabled OR s.two_factor_verified) AND u.suspended_at IS NULL
+        "#,
+    )
+    .bind(session_id)
+    .bind(now)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(row.map(|row| {
+        let user = User {
+            id: row.id,
+            username: row.username,
+            email: row.email,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        };
+        (user, row.impersonator_id)
+    }))
+}
+
+pub async fn delete_session(pool: &PgPool, session_id: Uuid) -> Result<(), sqlx::Error> {
+    sqlx::query("DELETE FROM sessions WHERE id = $1")
+        .bind(session_id)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
+
+// Signs the user out everywhere, including impersonation sessions.
+pub async fn delete_user_sessions(pool: &PgPool, user_id: Uuid) -> Result<u64, sqlx::Error> {
+    let deleted = sqlx::query("DELETE FROM sessions WHERE user_id = $1")
+        .bind(user_id)
+        .execute(pool)
+        .await?
+        .rows_affected();
+
+    Ok(deleted)
+}
+
+pub async fn delete_expired_sessions(pool: &PgPool) -> Result<u64, sqlx::Error> {
+    let deleted = sqlx::query("DELETE FROM sessions WHERE expires_at <= $1")
+        .bind(Local::now().naive_local())
+        .execute(pool)
+        .await?
+        .rows_affected();
+
+    Ok(deleted)
+}
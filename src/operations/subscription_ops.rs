@@ -0,0 +1,68 @@
+use crate::models::subscription::{Charge, SubscriptionDismissal};
+use chrono::{Local, NaiveDate};
+use sqlx::{postgres::PgPool, Error};
+use uuid::Uuid;
+
+// The user's expenses from `from` on, oldest first.
+pub async fn fetch_charges_since(
+    pool: &PgPool,
+    user_id: Uuid,
+    from: NaiveDate,
+) -> Result<Vec<Charge>, Error> {
+    sqlx::query_as::<_, Charge>(
+        r#"
+        SELECT
+            t.id AS transaction_id,
+            t.account_id,
+            t.category_id,
+            t.title,
+            (t.date AT TIME ZONE user_timezone(t.user_id))::DATE AS day,
+            t.amount,
+            t.currency
+        FROM transactions t
+        WHERE t.user_id = $1
+            AND t.transaction_type = 'Expense'
+            AND (t.date AT TIME ZONE user_timezone(t.user_id))::DATE >= $2
+        ORDER BY t.date
+    "#,
+    )
+    .bind(user_id)
+    .bind(from)
+    .fetch_all(pool)
+    .await
+}
+
+pub async fn fetch_subscription_dismissals(
+    pool: &PgPool,
+    user_id: Uuid,
+) -> Result<Vec<SubscriptionDismissal>, Error> {
+    sqlx::query_as::<_, SubscriptionDismissal>(
+        r#"SELECT * FROM subscription_dismissals WHERE user_id = $1"#,
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await
+}
+
+pub async fn dismiss_subscription(
+    pool: &PgPool,
+    user_id: Uuid,
+    account_id: Uuid,
+    payee: &str,
+) -> Result<(), Error> {
+    sqlx::query(
+        r#"
+        INSERT INTO subscription_dismissals (user_id, account_id, payee, dismissed_at)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT (user_id, account_id, payee) DO NOTHING
+    "#,
+    )
+    .bind(user_id)
+    .bind(account_id)
+    .bind(payee)
+    .bind(Local::now().naive_local())
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
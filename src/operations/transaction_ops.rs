@@ -1,24 +1,102 @@
-use crate::{dtos::transaction_dtos::TransactionInDTO, models::transactions::Transaction, enums::custom_enums::TransactionType};
-use sqlx::{postgres::PgPool, Row};
+use crate::{
+    dtos::transaction_dtos::TransactionInDTO,
+    enums::custom_enums::TransactionType,
+    models::{
+        allocation::Allocation,
+        saving_goals::SavingGoal,
+        transactions::{AccountAmount, AccountTransaction, Transaction},
+    },
+    operations::{
+        achievement_ops::award_goal_achievements,
+        allocation_ops::{
+            apply_allocation_rules, apply_best_allocation_rule, fetch_allocations_for_transaction,
+            is_allocated, reverse_allocations,
+        },
+        round_up_ops::{apply_round_up, reverse_round_up},
+    },
+};
+use sqlx::{postgres::PgPool, FromRow, Postgres, Row};
+use std::str::FromStr;
 use uuid::Uuid;
 
-pub async fn fetch_all_transactions(pool: &PgPool) -> Result<Vec<Transaction>, sqlx::Error> {
+pub async fn fetch_all_transactions(
+    pool: &PgPool,
+    user_id: Uuid,
+) -> Result<Vec<Transaction>, sqlx::Error> {
     let transactions = sqlx::query_as::<_, Transaction>(
         r#"
-        SELECT * FROM transactions
+        SELECT * FROM transactions t
+        WHERE t.user_id = $1 OR EXISTS (
+            SELECT 1 FROM accounts a
+            WHERE a.id = t.account_id AND can_view_record(a.user_id, a.household_id, $1)
+        )
     "#,
     )
+    .bind(user_id)
     .fetch_all(pool)
     .await?;
 
     Ok(transactions)
 }
 
-pub async fn find_transaction_by_id(pool: &PgPool, transaction_id: Uuid) -> Result<Option<Transaction>, sqlx::Error> {
-    let row = sqlx::query("SELECT *, transaction_type::TEXT FROM transactions WHERE id = $1")
-        .bind(transaction_id)
-        .fetch_optional(pool)
-        .await?;
+// An account's transactions, newest first, each with the balance the account
+// had right after it: the current balance less everything booked later.
+// Transfers made at the same instant, like an allocation out of the income
+// that triggered it, count as later.
+pub async fn fetch_account_transactions(
+    pool: &PgPool,
+    account_id: Uuid,
+) -> Result<Vec<AccountTransaction>, sqlx::Error> {
+    let rows = sqlx::query(
+        r#"
+        SELECT t.*, a.balance - COALESCE(SUM(
+            CASE WHEN t.transaction_type = 'Income' THEN t.amount ELSE -t.amount END
+        ) OVER (
+            ORDER BY t.date DESC, t.id DESC ROWS BETWEEN UNBOUNDED PRECEDING AND 1 PRECEDING
+        ), 0) - COALESCE((
+            SELECT SUM(CASE WHEN x.to_account_id = a.id THEN x.amount ELSE -x.amount END)
+            FROM account_transfers x
+            WHERE (x.from_account_id = a.id OR x.to_account_id = a.id)
+                AND x.transferred_at >= t.date
+        ), 0) AS running_balance
+        FROM transactions t
+        JOIN accounts a ON a.id = t.account_id
+        WHERE t.account_id = $1
+        ORDER BY t.date DESC, t.id DESC
+    "#,
+    )
+    .bind(account_id)
+    .fetch_all(pool)
+    .await?;
+
+    rows.iter()
+        .map(|row| {
+            Ok(AccountTransaction {
+                transaction: Transaction::from_row(row)?,
+                running_balance: row.try_get("running_balance")?,
+            })
+        })
+        .collect()
+}
+
+pub async fn find_transaction_by_id(
+    pool: &PgPool,
+    user_id: Uuid,
+    transaction_id: Uuid,
+) -> Result<Option<Transaction>, sqlx::Error> {
+    let row = sqlx::query(
+        r#"
+        SELECT *, transaction_type::TEXT FROM transactions t
+        WHERE t.id = $1 AND (t.user_id = $2 OR EXISTS (
+            SELECT 1 FROM accounts a
+            WHERE a.id = t.account_id AND can_view_record(a.user_id, a.household_id, $2)
+        ))
+        "#,
+    )
+    .bind(transaction_id)
+    .bind(user_id)
+    .fetch_optional(pool)
+    .await?;
 
     if let Some(row) = row {
         let transaction = Transaction {
@@ -28,6 +106,9 @@ pub async fn find_transaction_by_id(pool: &PgPool, transaction_id: Uuid) -> Resu
             user_id: row.get("user_id"),
             title: row.get("title"),
             amount: row.get("amount"),
+            currency: row.get("currency"),
+            original_amount: row.get("original_amount"),
+            original_currency: row.get("original_currency"),
             date: row.get("date"),
             category_id: row.get("category_id"),
             account_id: row.get("account_id"),
@@ -38,59 +119,99 @@ pub async fn find_transaction_by_id(pool: &PgPool, transaction_id: Uuid) -> Resu
     }
 }
 
-
 pub async fn create_transaction(
     pool: &PgPool,
+    user_id: Uuid,
     transaction_dto: &TransactionInDTO,
 ) -> Result<Transaction, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    let amount = account_amount(&mut tx, transaction_dto).await?;
+
     let transaction = sqlx::query_as::<_, Transaction>(
         r#"
-        INSERT INTO transactions (title, amount, date, category_id, transaction_type, user_id, account_id)
-        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        INSERT INTO transactions (
+            title, amount, date, category_id, transaction_type, user_id, account_id,
+            currency, original_amount, original_currency
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
         RETURNING *
     "#,
     )
     .bind(&transaction_dto.title)
-    .bind(transaction_dto.amount)
-    .bind(transaction_dto.date) 
-    .bind(&transaction_dto.category_id)
-    .bind(&transaction_dto.transaction_type)
-    .bind(&transaction_dto.user_id)
-    .bind(&transaction_dto.account_id)
-    .fetch_one(pool)
+    .bind(amount.amount)
+    .bind(transaction_dto.date)
+    .bind(transaction_dto.category_id)
+    .bind(transaction_dto.transaction_type)
+    .bind(user_id)
+    .bind(transaction_dto.account_id)
+    .bind(&amount.currency)
+    .bind(amount.original_amount)
+    .bind(&amount.original_currency)
+    .fetch_one(&mut tx)
     .await?;
 
+    let goals = apply_savings_rules(&mut tx, &transaction, None).await?;
+    tx.commit().await?;
+    award_achievements(pool, &goals).await?;
+
     Ok(transaction)
 }
 
 pub async fn update_transaction(
     pool: &PgPool,
+    user_id: Uuid,
     transaction_id: Uuid,
     transaction_dto: &TransactionInDTO,
-) -> Result<Transaction, sqlx::Error> {
+) -> Result<Option<Transaction>, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    let previous = match find_editable_transaction(&mut tx, user_id, transaction_id).await? {
+        Some(previous) => previous,
+        None => return Ok(None),
+    };
+    let previous_rule_id = reverse_savings_rules(&mut tx, &previous).await?;
+    let amount = account_amount(&mut tx, transaction_dto).await?;
+
     let transaction = sqlx::query_as::<_, Transaction>(
         r#"
         UPDATE transactions
-        SET title = $1, amount = $2, date = $3, category_id = $4, transaction_type = $5, user_id = $6, account_id = $7
-        WHERE id = $8
+        SET title = $1, amount = $2, date = $3, category_id = $4, transaction_type = $5,
+            account_id = $6, currency = $7, original_amount = $8, original_currency = $9
+        WHERE id = $10
         RETURNING *
     "#,
     )
     .bind(&transaction_dto.title)
-    .bind(transaction_dto.amount)
-    .bind(transaction_dto.date) 
-    .bind(&transaction_dto.category_id)
-    .bind(&transaction_dto.transaction_type)
-    .bind(&transaction_dto.user_id)
-    .bind(&transaction_dto.account_id)
+    .bind(amount.amount)
+    .bind(transaction_dto.date)
+    .bind(transaction_dto.category_id)
+    .bind(transaction_dto.transaction_type)
+    .bind(transaction_dto.account_id)
+    .bind(&amount.currency)
+    .bind(amount.original_amount)
+    .bind(&amount.original_currency)
     .bind(transaction_id)
-    .fetch_one(pool)
+    .fetch_one(&mut tx)
     .await?;
 
-    Ok(transaction)
+    let goals = apply_savings_rules(&mut tx, &transaction, previous_rule_id).await?;
+    tx.commit().await?;
+    award_achievements(pool, &goals).await?;
+
+    Ok(Some(transaction))
 }
 
-pub async fn delete_transaction(pool: &PgPool, transaction_id: Uuid) -> Result<u64, sqlx::Error> {
+pub async fn delete_transaction(
+    pool: &PgPool,
+    user_id: Uuid,
+    transaction_id: Uuid,
+) -> Result<u64, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    let previous = match find_editable_transaction(&mut tx, user_id, transaction_id).await? {
+        Some(previous) => previous,
+        None => return Ok(0),
+    };
+    reverse_savings_rules(&mut tx, &previous).await?;
+
     let deleted = sqlx::query(
         r#"
         DELETE FROM transactions
@@ -98,9 +219,113 @@ pub async fn delete_transaction(pool: &PgPool, transaction_id: Uuid) -> Result<u
     "#,
     )
     .bind(transaction_id)
-    .execute(pool)
+    .execute(&mut tx)
     .await?;
 
+    tx.commit().await?;
+
     Ok(deleted.rows_affected())
 }
 
+// The routes reject amounts that don't fit the account's currency up front,
+// so this only fails if the account changed currency in the meantime.
+async fn account_amount(
+    tx: &mut sqlx::Transaction<'_, Postgres>,
+    transaction_dto: &TransactionInDTO,
+) -> Result<AccountAmount, sqlx::Error> {
+    let (currency,): (String,) = sqlx::query_as("SELECT currency FROM accounts WHERE id = $1")
+        .bind(transaction_dto.account_id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+    transaction_dto
+        .in_account_currency(&currency)
+        .map_err(|message| sqlx::Error::Protocol(message.to_string()))
+}
+
+// Round-ups and paycheck allocations both move money into goals, budgets and
+// accounts whenever a transaction is written. They run inside the same
+// database transaction as the write so either everything lands or nothing.
+async fn apply_savings_rules(
+    tx: &mut sqlx::Transaction<'_, Postgres>,
+    transaction: &Transaction,
+    allocation_rule_id: Option<Uuid>,
+) -> Result<Vec<SavingGoal>, sqlx::Error> {
+    let mut goals: Vec<SavingGoal> = apply_round_up(tx, transaction).await?.into_iter().collect();
+    goals.extend(apply_allocation_rules(tx, transaction, allocation_rule_id).await?);
+    Ok(goals)
+}
+
+// Household members may change transactions on accounts they can edit, not
+// only the ones they entered themselves.
+async fn find_editable_transaction(
+    tx: &mut sqlx::Transaction<'_, Postgres>,
+    user_id: Uuid,
+    transaction_id: Uuid,
+) -> Result<Option<Transaction>, sqlx::Error> {
+    sqlx::query_as::<_, Transaction>(
+        r#"
+        SELECT t.* FROM transactions t
+        WHERE t.id = $1 AND (t.user_id = $2 OR EXISTS (
+            SELECT 1 FROM accounts a
+            WHERE a.id = t.account_id AND can_edit_record(a.user_id, a.household_id, $2)
+        ))
+        FOR UPDATE OF t
+        "#,
+    )
+    .bind(transaction_id)
+    .bind(user_id)
+    .fetch_optional(&mut *tx)
+    .await
+}
+
+async fn reverse_savings_rules(
+    tx: &mut sqlx::Transaction<'_, Postgres>,
+    previous: &Transaction,
+) -> Result<Option<Uuid>, sqlx::Error> {
+    reverse_round_up(tx, previous.id).await?;
+    reverse_allocations(tx, previous).await
+}
+
+async fn award_achievements(pool: &PgPool, goals: &[SavingGoal]) -> Result<(), sqlx::Error> {
+    for goal in goals {
+        award_goal_achievements(pool, goal).await?;
+    }
+    Ok(())
+}
+
+#[derive(Debug)]
+pub enum ManualAllocation {
+    Applied(Vec<Allocation>),
+    // Missing, or on an account the user can't edit.
+    NotFound,
+    AlreadyAllocated,
+    NoMatchingRule,
+}
+
+// Applies the best matching allocation rule by hand. The transaction row stays
+// locked until commit so two requests can't both allocate it.
+pub async fn apply_allocations_to_transaction(
+    pool: &PgPool,
+    user_id: Uuid,
+    transaction_id: Uuid,
+) -> Result<ManualAllocation, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    let transaction = match find_editable_transaction(&mut tx, user_id, transaction_id).await? {
+        Some(transaction) => transaction,
+        None => return Ok(ManualAllocation::NotFound),
+    };
+    if is_allocated(&mut tx, transaction.id).await? {
+        return Ok(ManualAllocation::AlreadyAllocated);
+    }
+    let goals = match apply_best_allocation_rule(&mut tx, &transaction).await? {
+        Some(goals) => goals,
+        None => return Ok(ManualAllocation::NoMatchingRule),
+    };
+    tx.commit().await?;
+    award_achievements(pool, &goals).await?;
+
+    Ok(ManualAllocation::Applied(
+        fetch_allocations_for_transaction(pool, transaction.id).await?,
+    ))
+}
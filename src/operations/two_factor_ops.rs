@@ -0,0 +1,189 @@
+use chrono::Local;
+use sqlx::postgres::PgPool;
+use uuid::Uuid;
+
+use crate::models::{two_factor::TotpState, user::User};
+
+pub async fn find_totp_state(
+    pool: &PgPool,
+    user_id: Uuid,
+) -> Result<Option<TotpState>, sqlx::Error> {
+    let state = sqlx::query_as::<_, TotpState>(
+        "SELECT totp_secret, totp_enabled, totp_last_step FROM users WHERE id = $1",
+    )
+    .bind(user_id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(state)
+}
+
+// Stores a secret that is not enforced until confirmed. Re-enrolling before
+// confirming replaces it; an enabled secret is left alone.
+pub async fn set_pending_totp_secret(
+    pool: &PgPool,
+    user_id: Uuid,
+    secret: &str,
+) -> Result<u64, sqlx::Error> {
+    let updated = sqlx::query(
+        "UPDATE users SET totp_secret = $1, totp_last_step = NULL WHERE id = $2 AND NOT totp_enabled",
+    )
+    .bind(secret)
+    .bind(user_id)
+    .execute(pool)
+    .await?
+    .rows_affected();
+
+    Ok(updated)
+}
+
+// Records the step a code was accepted for. Fails when that step (or a later
+// one) was already used, which is what stops a code from being replayed.
+pub async fn record_totp_step(
+    pool: &PgPool,
+    user_id: Uuid,
+    step: i64,
+) -> Result<bool, sqlx::Error> {
+    let updated = sqlx::query(
+        r#"
+        UPDATE users SET totp_last_step = $2
+        WHERE id = $1 AND (totp_last_step IS NULL OR totp_last_step < $2)
+        "#,
+    )
+    .bind(user_id)
+    .bind(step)
+    .execute(pool)
+    .await?
+    .rows_affected();
+
+    Ok(updated == 1)
+}
+
+pub async fn enable_totp(
+    pool: &PgPool,
+    user_id: Uuid,
+    session_id: Uuid,
+    recovery_code_hashes: &[String],
+) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    sqlx::query("UPDATE users SET totp_enabled = TRUE WHERE id = $1")
+        .bind(user_id)
+        .execute(&mut tx)
+        .await?;
+    sqlx::query("DELETE FROM recovery_codes WHERE user_id = $1")
+        .bind(user_id)
+        .execute(&mut tx)
+        .await?;
+    for code_hash in recovery_code_hashes {
+        sqlx::query("INSERT INTO recovery_codes (user_id, code_hash) VALUES ($1, $2)")
+            .bind(user_id)
+            .bind(code_hash)
+            .execute(&mut tx)
+            .await?;
+    }
+    // Every other session has to supply the second factor before it is let
+    // back in.
+    sqlx::query("UPDATE sessions SET two_factor_verified = (id = $1) WHERE user_id = $2")
+        .bind(session_id)
+        .bind(user_id)
+        .execute(&mut tx)
+        .await?;
+
+    tx.commit().await?;
+
+    Ok(())
+}
+
+pub async fn disable_totp(pool: &PgPool, user_id: Uuid) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    sqlx::query(
+        r#"
+        UPDATE users SET totp_secret = NULL, totp_enabled = FALSE, totp_last_step = NULL
+        WHERE id = $1
+        "#,
+    )
+    .bind(user_id)
+    .execute(&mut tx)
+    .await?;
+    sqlx::query("DELETE FROM recovery_codes WHERE user_id = $1")
+        .bind(user_id)
+        .execute(&mut tx)
+        .await?;
+
+    tx.commit().await?;
+
+    Ok(())
+}
+
+// Spends a recovery code; each one works exactly once.
+pub async fn use_recovery_code(
+    pool: &PgPool,
+    user_id: Uuid,
+    code_hash: &str,
+) -> Result<bool, sqlx::Error> {
+    let updated = sqlx::query(
+        r#"
+        UPDATE recovery_codes SET used_at = $3
+        WHERE id = (
+            SELECT id FROM recovery_codes
+            WHERE user_id = $1 AND code_hash = $2 AND used_at IS NULL
+            LIMIT 1
+        )
+        "#,
+    )
+    .bind(user_id)
+    .bind(code_hash)
+    .bind(Local::now().naive_local())
+    .execute(pool)
+    .await?
+    .rows_affected();
+
+    Ok(updated == 1)
+}
+
+pub async fn count_unused_recovery_codes(pool: &PgPool, user_id: Uuid) -> Result<i64, sqlx::Error> {
+    let (count,): (i64,) = sqlx::query_as(
+        "SELECT COUNT(*) FROM recovery_codes WHERE user_id = $1 AND used_at IS NULL",
+    )
+    .bind(user_id)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(count)
+}
+
+// The user behind a live session that still owes its second factor.
+pub async fn find_pending_two_factor_user(
+    pool: &PgPool,
+    session_id: Uuid,
+) -> Result<Option<User>, sqlx::Error> {
+    let user = sqlx::query_as::<_, User>(
+        r#"
+        SELECT u.id, u.username, u.email, u.created_at, u.updated_at
+        FROM users u
+        JOIN sessions s ON s.user_id = u.id
+        WHERE s.id = $1 AND s.expires_at > $2
+          AND u.totp_enabled AND NOT s.two_factor_verified
+        "#,
+    )
+    .bind(session_id)
+    .bind(Local::now().naive_local())
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(user)
+}
+
+pub async fn mark_session_two_factor_verified(
+    pool: &PgPool,
+    session_id: Uuid,
+) -> Result<(), sqlx::Error> {
+    sqlx::query("UPDATE sessions SET two_factor_verified = TRUE WHERE id = $1")
+        .bind(session_id)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
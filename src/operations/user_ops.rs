@@ -3,12 +3,19 @@
 use sqlx::{postgres::PgPool, Error};
 use uuid::Uuid;
 
-use crate::{dtos::user_dtos::UserInDTO, models::user::User};
+use crate::{
+    dtos::{auth_dtos::RegisterInDTO, user_dtos::UserInDTO},
+    models::user::User,
+};
 
 pub async fn find_user_by_id(pool: &PgPool, user_id: Uuid) -> Result<Option<User>, Error> {
-    let user = sqlx::query_as!(User, "SELECT * FROM users WHERE id = $1", user_id)
-        .fetch_optional(pool)
-        .await?;
+    let user = sqlx::query_as!(
+        User,
+        "SELECT id, username, email, created_at, updated_at FROM users WHERE id = $1",
+        user_id
+    )
+    .fetch_optional(pool)
+    .await?;
     Ok(user)
 }
 
@@ -41,6 +48,66 @@ pub async fn create_user(pool: &PgPool, user_dto: &UserInDTO) -> Result<User, sq
     Ok(user)
 }
 
+pub async fn register_user(
+    pool: &PgPool,
+    register_dto: &RegisterInDTO,
+    password_hash: &str,
+) -> Result<User, sqlx::Error> {
+    let user = sqlx::query_as::<_, User>(
+        r#"
+        INSERT INTO users (username, email, password_hash)
+        VALUES ($1, $2, $3)
+        RETURNING id, username, email, created_at, updated_at
+    "#,
+    )
+    .bind(&register_dto.username)
+    .bind(&register_dto.email)
+    .bind(password_hash)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(user)
+}
+
+pub async fn find_user_by_email(pool: &PgPool, email: &str) -> Result<Option<User>, Error> {
+    let user = sqlx::query_as::<_, User>(
+        r#"
+        SELECT id, username, email, created_at, updated_at
+        FROM users
+        WHERE email = $1
+    "#,
+    )
+    .bind(email)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(user)
+}
+
+pub async fn find_password_hash(pool: &PgPool, user_id: Uuid) -> Result<Option<String>, Error> {
+    let password_hash: Option<(Option<String>,)> =
+        sqlx::query_as("SELECT password_hash FROM users WHERE id = $1")
+            .bind(user_id)
+            .fetch_optional(pool)
+            .await?;
+
+    Ok(password_hash.and_then(|(hash,)| hash))
+}
+
+pub async fn set_user_password(
+    pool: &PgPool,
+    user_id: Uuid,
+    password_hash: &str,
+) -> Result<(), Error> {
+    sqlx::query("UPDATE users SET password_hash = $1 WHERE id = $2")
+        .bind(password_hash)
+        .bind(user_id)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
+
 pub async fn update_user_in_db(
     pool: &PgPool,
     user_id: Uuid,
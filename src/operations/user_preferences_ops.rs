@@ -0,0 +1,59 @@
+use chrono::Local;
+use sqlx::{postgres::PgPool, Error};
+use uuid::Uuid;
+
+use crate::dtos::user_preferences_dtos::UserPreferencesInDTO;
+use crate::models::user_preferences::UserPreferences;
+
+async fn ensure_user_preferences(pool: &PgPool, user_id: Uuid) -> Result<(), Error> {
+    sqlx::query("INSERT INTO user_preferences (user_id) VALUES ($1) ON CONFLICT DO NOTHING")
+        .bind(user_id)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
+
+// Every user has preferences; the row is created with the defaults the first
+// time it's needed.
+pub async fn find_user_preferences(pool: &PgPool, user_id: Uuid) -> Result<UserPreferences, Error> {
+    ensure_user_preferences(pool, user_id).await?;
+
+    sqlx::query_as::<_, UserPreferences>("SELECT * FROM user_preferences WHERE user_id = $1")
+        .bind(user_id)
+        .fetch_one(pool)
+        .await
+}
+
+pub async fn update_user_preferences(
+    pool: &PgPool,
+    user_id: Uuid,
+    preferences_dto: &UserPreferencesInDTO,
+) -> Result<UserPreferences, Error> {
+    ensure_user_preferences(pool, user_id).await?;
+
+    sqlx::query_as::<_, UserPreferences>(
+        r#"
+        UPDATE user_preferences
+        SET base_currency = COALESCE($1, base_currency),
+            locale = COALESCE($2, locale),
+            timezone = COALESCE($3, timezone),
+            week_start = COALESCE($4, week_start),
+            fiscal_year_start_month = COALESCE($5, fiscal_year_start_month),
+            date_format = COALESCE($6, date_format),
+            updated_at = $7
+        WHERE user_id = $8
+        RETURNING *
+        "#,
+    )
+    .bind(&preferences_dto.base_currency)
+    .bind(&preferences_dto.locale)
+    .bind(&preferences_dto.timezone)
+    .bind(preferences_dto.week_start)
+    .bind(preferences_dto.fiscal_year_start_month)
+    .bind(&preferences_dto.date_format)
+    .bind(Local::now().naive_local())
+    .bind(user_id)
+    .fetch_one(pool)
+    .await
+}
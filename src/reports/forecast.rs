@@ -0,0 +1,144 @@
+use chrono::{Duration, NaiveDate};
+use rust_decimal::Decimal;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+
+use crate::{
+    dtos::report_dtos::{
+        AccountForecastOutDTO, ForecastDayOutDTO, ForecastOutDTO, ScheduledOccurrenceOutDTO,
+    },
+    enums::custom_enums::TransactionType,
+    models::{
+        account::Account,
+        recurring_transaction::RecurringTransaction,
+        report::{AccountTransferTotal, CategorySpending},
+    },
+    money::Money,
+};
+
+// How many days of past spending and transfers the daily averages are taken
+// over.
+pub const HISTORY_DAYS: i64 = 90;
+
+// Projects every account's balance for the `days` days after `today`.
+// Spending in a category that a recurring expense on the same account already
+// covers isn't counted again as discretionary spending. Allocation transfers
+// in and out of an account are carried forward at their daily average.
+pub fn forecast(
+    accounts: &[Account],
+    recurring: &[RecurringTransaction],
+    spending: &[CategorySpending],
+    transfers: &[AccountTransferTotal],
+    today: NaiveDate,
+    days: i64,
+) -> ForecastOutDTO {
+    let from = today + Duration::days(1);
+    let to = today + Duration::days(days);
+
+    let mut upcoming: Vec<ScheduledOccurrenceOutDTO> = recurring
+        .iter()
+        .filter(|item| accounts.iter().any(|account| account.id == item.account_id))
+        .flat_map(|item| {
+            item.occurrences_between(from, to)
+                .into_iter()
+                .map(|date| ScheduledOccurrenceOutDTO {
+                    date,
+                    recurring_transaction_id: item.id,
+                    account_id: item.account_id,
+                    title: item.title.clone(),
+                    amount: item.amount,
+                    transaction_type: item.transaction_type,
+                })
+        })
+        .collect();
+    upcoming.sort_by(|a, b| a.date.cmp(&b.date).then_with(|| a.title.cmp(&b.title)));
+
+    let mut negative_dates = BTreeSet::new();
+    let mut account_forecasts = Vec::new();
+    for account in accounts {
+        let covered: HashSet<_> = recurring
+            .iter()
+            .filter(|item| {
+                item.account_id == account.id && item.transaction_type == TransactionType::Expense
+            })
+            .map(|item| item.category_id)
+            .collect();
+        let discretionary = Money::new(
+            spending
+                .iter()
+                .filter(|spent| {
+                    spent.account_id == account.id && !covered.contains(&spent.category_id)
+                })
+                .map(|spent| spent.amount)
+                .sum::<Money>()
+                .amount()
+                / Decimal::from(HISTORY_DAYS),
+        );
+        let transferred = Money::new(
+            transfers
+                .iter()
+                .filter(|transfer| transfer.account_id == account.id)
+                .map(|transfer| transfer.net)
+                .sum::<Money>()
+                .amount()
+                / Decimal::from(HISTORY_DAYS),
+        );
+
+        let mut scheduled: BTreeMap<NaiveDate, Money> = BTreeMap::new();
+        for occurrence in upcoming.iter().filter(|o| o.account_id == account.id) {
+            let signed = match occurrence.transaction_type {
+                TransactionType::Income => occurrence.amount,
+                TransactionType::Expense => -occurrence.amount,
+            };
+            *scheduled.entry(occurrence.date).or_default() += signed;
+        }
+
+        // The running balance keeps its fractions of a cent; only what's
+        // shown is rounded.
+        let mut balance = account.balance;
+        let mut forecast_days = Vec::new();
+        for date in from.iter_days().take_while(|date| *date <= to) {
+            let scheduled = scheduled.get(&date).copied().unwrap_or_default();
+            balance += scheduled + transferred - discretionary;
+            let shown = balance.round_to(&account.currency);
+            if shown.is_negative() {
+                negative_dates.insert(date);
+            }
+            forecast_days.push(ForecastDayOutDTO {
+                date,
+                scheduled,
+                discretionary: discretionary.round_to(&account.currency),
+                transfers: transferred.round_to(&account.currency),
+                balance: shown,
+            });
+        }
+
+        let lowest = forecast_days
+            .iter()
+            .min_by_key(|day| day.balance)
+            .expect("a forecast covers at least one day");
+        account_forecasts.push(AccountForecastOutDTO {
+            account_id: account.id,
+            name: account.name.clone(),
+            currency: account.currency.clone(),
+            starting_balance: account.balance,
+            ending_balance: balance.round_to(&account.currency),
+            lowest_balance: lowest.balance,
+            lowest_balance_date: lowest.date,
+            first_negative_date: forecast_days
+                .iter()
+                .find(|day| day.balance.is_negative())
+                .map(|day| day.date),
+            daily_discretionary: discretionary.round_to(&account.currency),
+            daily_transfers: transferred.round_to(&account.currency),
+            days: forecast_days,
+        });
+    }
+
+    ForecastOutDTO {
+        from,
+        to,
+        negative_dates: negative_dates.into_iter().collect(),
+        accounts: account_forecasts,
+        upcoming,
+    }
+}
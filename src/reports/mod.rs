@@ -0,0 +1,310 @@
+pub mod forecast;
+pub mod net_worth;
+pub mod year_in_review;
+
+use chrono::{Datelike, Duration, Months, NaiveDate};
+use rust_decimal::Decimal;
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+use crate::{
+    dtos::report_dtos::{
+        CategoryReportOutDTO, CategorySpendingOutDTO, ComparisonPeriodOutDTO,
+        SummaryBreakdownOutDTO, SummaryPeriodOutDTO, SummaryReportOutDTO,
+    },
+    enums::custom_enums::{ReportGranularity, TransactionType},
+    exchange_rates::{MissingRate, RateTable},
+    models::{report::DailyTotal, user_preferences::UserPreferences},
+    money::Money,
+};
+
+#[derive(Debug, Default, Clone, Copy)]
+struct Totals {
+    income: Money,
+    expenses: Money,
+    transaction_count: i64,
+}
+
+impl Totals {
+    fn add(&mut self, transaction_type: TransactionType, amount: Money, transaction_count: i64) {
+        match transaction_type {
+            TransactionType::Income => self.income += amount,
+            TransactionType::Expense => self.expenses += amount,
+        }
+        self.transaction_count += transaction_count;
+    }
+
+    fn net(&self) -> Money {
+        self.income - self.expenses
+    }
+
+    // The share of income that wasn't spent, in percent. Undefined without
+    // any income.
+    fn savings_rate(&self) -> Option<Decimal> {
+        if !self.income.is_positive() {
+            return None;
+        }
+        self.net()
+            .percentage_of(self.income)
+            .map(|rate| rate.round_dp(2))
+    }
+}
+
+// The first day of the period that contains `date`. Weeks start on the
+// user's first day of the week and years on their fiscal year start.
+pub fn period_start(
+    date: NaiveDate,
+    granularity: ReportGranularity,
+    preferences: &UserPreferences,
+) -> NaiveDate {
+    match granularity {
+        ReportGranularity::Day => date,
+        ReportGranularity::Week => preferences.week_start_of(date),
+        ReportGranularity::Month => date.with_day(1).expect("every month has a first day"),
+        ReportGranularity::Year => preferences.fiscal_year_start_of(date),
+    }
+}
+
+fn next_period_start(start: NaiveDate, granularity: ReportGranularity) -> NaiveDate {
+    match granularity {
+        ReportGranularity::Day => start + Duration::days(1),
+        ReportGranularity::Week => start + Duration::days(7),
+        ReportGranularity::Month => start + Months::new(1),
+        ReportGranularity::Year => start + Months::new(12),
+    }
+}
+
+// The last day of the period starting on `start`.
+pub fn period_end(start: NaiveDate, granularity: ReportGranularity) -> NaiveDate {
+    next_period_start(start, granularity) - Duration::days(1)
+}
+
+// The longest report that will be built, to keep daily reports over decades
+// from being requested by accident.
+pub const MAX_PERIODS: usize = 1000;
+
+// The first day of every period that overlaps the days between `from` and
+// `to`.
+pub fn period_starts(
+    from: NaiveDate,
+    to: NaiveDate,
+    granularity: ReportGranularity,
+    preferences: &UserPreferences,
+) -> impl Iterator<Item = NaiveDate> {
+    std::iter::successors(
+        Some(period_start(from, granularity, preferences)),
+        move |start| Some(next_period_start(*start, granularity)),
+    )
+    .take_while(move |start| *start <= to)
+}
+
+#[derive(Default)]
+struct Period {
+    totals: Totals,
+    breakdown: BTreeMap<(String, Uuid), Totals>,
+}
+
+// Buckets daily totals into periods between `from` and `to`, converting each
+// day into `currency` first. Every period in the range is listed, including
+// the ones without transactions.
+pub fn summarize(
+    daily_totals: &[DailyTotal],
+    rates: &RateTable,
+    currency: &str,
+    granularity: ReportGranularity,
+    preferences: &UserPreferences,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Result<SummaryReportOutDTO, MissingRate> {
+    let mut periods: BTreeMap<NaiveDate, Period> =
+        period_starts(from, to, granularity, preferences)
+            .map(|start| (start, Period::default()))
+            .collect();
+
+    let mut totals = Totals::default();
+    for daily in daily_totals {
+        let amount = rates.convert(daily.amount, &daily.currency, currency, daily.day)?;
+        let period = periods
+            .entry(period_start(daily.day, granularity, preferences))
+            .or_default();
+        period
+            .totals
+            .add(daily.transaction_type, amount, daily.transaction_count);
+        if let (Some(id), Some(name)) = (daily.group_id, &daily.group_name) {
+            period.breakdown.entry((name.clone(), id)).or_default().add(
+                daily.transaction_type,
+                amount,
+                daily.transaction_count,
+            );
+        }
+        totals.add(daily.transaction_type, amount, daily.transaction_count);
+    }
+
+    let periods = periods
+        .into_iter()
+        .map(|(start, period)| SummaryPeriodOutDTO {
+            start: start.max(from),
+            end: period_end(start, granularity).min(to),
+            income: period.totals.income,
+            expenses: period.totals.expenses,
+            net: period.totals.net(),
+            savings_rate: period.totals.savings_rate(),
+            transaction_count: period.totals.transaction_count,
+            breakdown: period
+                .breakdown
+                .into_iter()
+                .map(|((name, id), totals)| SummaryBreakdownOutDTO {
+                    id,
+                    name,
+                    income: totals.income,
+                    expenses: totals.expenses,
+                    net: totals.net(),
+                    savings_rate: totals.savings_rate(),
+                    transaction_count: totals.transaction_count,
+                })
+                .collect(),
+        })
+        .collect();
+
+    Ok(SummaryReportOutDTO {
+        currency: currency.to_string(),
+        from,
+        to,
+        granularity,
+        income: totals.income,
+        expenses: totals.expenses,
+        net: totals.net(),
+        savings_rate: totals.savings_rate(),
+        transaction_count: totals.transaction_count,
+        periods,
+    })
+}
+
+// A range of days, both included, with the totals fetched for it.
+pub struct PeriodTotals<'a> {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub daily_totals: &'a [DailyTotal],
+}
+
+fn is_month_end(date: NaiveDate) -> bool {
+    (date + Duration::days(1)).day() == 1
+}
+
+// Moves a day back by whole months. A month's last day stays the last day,
+// so February 28th a year after a leap year goes back to the 29th.
+fn months_before(date: NaiveDate, months: u32) -> NaiveDate {
+    let shifted = date - Months::new(months);
+    if is_month_end(date) {
+        shifted.with_day(1).expect("every month has a first day") + Months::new(1)
+            - Duration::days(1)
+    } else {
+        shifted
+    }
+}
+
+// The periods a report is compared with: the one right before it and the
+// same days a year earlier. Reports starting on the first of a month step
+// back by whole months, so this month so far compares with the same days
+// of last month.
+pub fn comparison_periods(
+    from: NaiveDate,
+    to: NaiveDate,
+) -> ((NaiveDate, NaiveDate), (NaiveDate, NaiveDate)) {
+    let previous = if from.day() == 1 {
+        let months = (to.year() - from.year()) * 12 + to.month() as i32 - from.month() as i32 + 1;
+        let months = months as u32;
+        (months_before(from, months), months_before(to, months))
+    } else {
+        let days = (to - from).num_days() + 1;
+        (from - Duration::days(days), from - Duration::days(1))
+    };
+    let last_year = (months_before(from, 12), months_before(to, 12));
+    (previous, last_year)
+}
+
+fn expenses_by_category(
+    period: &PeriodTotals,
+    rates: &RateTable,
+    currency: &str,
+) -> Result<BTreeMap<Uuid, (String, Money)>, MissingRate> {
+    let mut categories: BTreeMap<Uuid, (String, Money)> = BTreeMap::new();
+    for daily in period.daily_totals {
+        if daily.transaction_type != TransactionType::Expense {
+            continue;
+        }
+        if let (Some(id), Some(name)) = (daily.group_id, &daily.group_name) {
+            let amount = rates.convert(daily.amount, &daily.currency, currency, daily.day)?;
+            categories
+                .entry(id)
+                .or_insert_with(|| (name.clone(), Money::ZERO))
+                .1 += amount;
+        }
+    }
+    Ok(categories)
+}
+
+fn change(amount: Money, before: Money) -> Option<Decimal> {
+    (amount - before)
+        .percentage_of(before)
+        .map(|change| change.round_dp(2))
+}
+
+// Expenses per category in `currency`, with each category's share of the
+// total and how it moved since the previous period and last year. Categories
+// only spent on in the earlier periods are listed with a zero amount.
+pub fn spending_by_category(
+    current: PeriodTotals,
+    previous: PeriodTotals,
+    last_year: PeriodTotals,
+    rates: &RateTable,
+    currency: &str,
+) -> Result<CategoryReportOutDTO, MissingRate> {
+    let spent = expenses_by_category(&current, rates, currency)?;
+    let spent_before = expenses_by_category(&previous, rates, currency)?;
+    let spent_last_year = expenses_by_category(&last_year, rates, currency)?;
+    let total: Money = spent.values().map(|(_, amount)| *amount).sum();
+    let comparison =
+        |period: &PeriodTotals, spent: &BTreeMap<Uuid, (String, Money)>| ComparisonPeriodOutDTO {
+            from: period.from,
+            to: period.to,
+            total: spent.values().map(|(_, amount)| *amount).sum(),
+        };
+
+    let mut names: BTreeMap<Uuid, String> = BTreeMap::new();
+    for (id, (name, _)) in spent_last_year.iter().chain(&spent_before).chain(&spent) {
+        names.insert(*id, name.clone());
+    }
+    let amount_in = |spent: &BTreeMap<Uuid, (String, Money)>, id: &Uuid| {
+        spent.get(id).map_or(Money::ZERO, |(_, amount)| *amount)
+    };
+    let mut categories: Vec<CategorySpendingOutDTO> = names
+        .into_iter()
+        .map(|(category_id, name)| {
+            let amount = amount_in(&spent, &category_id);
+            let previous_amount = amount_in(&spent_before, &category_id);
+            let last_year_amount = amount_in(&spent_last_year, &category_id);
+            CategorySpendingOutDTO {
+                category_id,
+                name,
+                amount,
+                share: amount.percentage_of(total).map(|share| share.round_dp(2)),
+                previous_amount,
+                change_from_previous: change(amount, previous_amount),
+                last_year_amount,
+                change_from_last_year: change(amount, last_year_amount),
+            }
+        })
+        .collect();
+    categories.sort_by(|a, b| b.amount.cmp(&a.amount).then_with(|| a.name.cmp(&b.name)));
+
+    Ok(CategoryReportOutDTO {
+        currency: currency.to_string(),
+        from: current.from,
+        to: current.to,
+        total,
+        previous_period: comparison(&previous, &spent_before),
+        last_year: comparison(&last_year, &spent_last_year),
+        categories,
+    })
+}
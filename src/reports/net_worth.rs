@@ -0,0 +1,136 @@
+use chrono::NaiveDate;
+use std::collections::{BTreeMap, HashMap};
+use uuid::Uuid;
+
+use crate::{
+    dtos::report_dtos::{AccountTypeBalanceOutDTO, NetWorthPointOutDTO},
+    enums::custom_enums::AccountType,
+    exchange_rates::{MissingRate, RateTable},
+    models::{account::Account, balance_snapshot::BalanceSnapshot, report::DailyAccountFlow},
+    money::Money,
+};
+
+// What an account held at the end of a day.
+struct HistoricalBalance {
+    account_type: AccountType,
+    balance: Money,
+    currency: String,
+    reconstructed: bool,
+}
+
+// Everything known about one account's past balances.
+struct AccountHistory<'a> {
+    account: &'a Account,
+    snapshots: BTreeMap<NaiveDate, &'a BalanceSnapshot>,
+    flows: BTreeMap<NaiveDate, Money>,
+}
+
+impl AccountHistory<'_> {
+    // The snapshot taken that day if there is one. Otherwise the next later
+    // snapshot, or the current balance after the last one, with the
+    // transactions booked since `date` taken back out.
+    fn balance_on(&self, date: NaiveDate) -> HistoricalBalance {
+        if let Some(snapshot) = self.snapshots.get(&date) {
+            return HistoricalBalance {
+                account_type: snapshot.account_type,
+                balance: snapshot.balance,
+                currency: snapshot.currency.clone(),
+                reconstructed: false,
+            };
+        }
+
+        let later = date.succ_opt().expect("dates are far from the end of time");
+        let (account_type, balance, currency, undone) = match self.snapshots.range(later..).next() {
+            Some((anchor_date, snapshot)) => (
+                snapshot.account_type,
+                snapshot.balance,
+                &snapshot.currency,
+                self.flows
+                    .range(later..=*anchor_date)
+                    .map(|(_, net)| *net)
+                    .sum(),
+            ),
+            None => (
+                self.account.account_type,
+                self.account.balance,
+                &self.account.currency,
+                self.flows
+                    .range(later..)
+                    .map(|(_, net)| *net)
+                    .sum::<Money>(),
+            ),
+        };
+        HistoricalBalance {
+            account_type,
+            balance: balance - undone,
+            currency: currency.clone(),
+            reconstructed: true,
+        }
+    }
+}
+
+// Net worth in `currency` at the end of each of `dates`, split by account
+// type. Snapshots and flows are expected to cover every day after the first
+// date.
+pub fn net_worth_history(
+    accounts: &[Account],
+    snapshots: &[BalanceSnapshot],
+    flows: &[DailyAccountFlow],
+    rates: &RateTable,
+    currency: &str,
+    dates: &[NaiveDate],
+) -> Result<Vec<NetWorthPointOutDTO>, MissingRate> {
+    let mut histories: HashMap<Uuid, AccountHistory> = accounts
+        .iter()
+        .map(|account| {
+            (
+                account.id,
+                AccountHistory {
+                    account,
+                    snapshots: BTreeMap::new(),
+                    flows: BTreeMap::new(),
+                },
+            )
+        })
+        .collect();
+    for snapshot in snapshots {
+        if let Some(history) = histories.get_mut(&snapshot.account_id) {
+            history.snapshots.insert(snapshot.snapshot_date, snapshot);
+        }
+    }
+    for flow in flows {
+        if let Some(history) = histories.get_mut(&flow.account_id) {
+            *history.flows.entry(flow.day).or_default() += flow.net;
+        }
+    }
+
+    let mut points = Vec::new();
+    for date in dates {
+        let mut account_types: Vec<AccountTypeBalanceOutDTO> = Vec::new();
+        let mut reconstructed = false;
+        for account in accounts {
+            let historical = histories[&account.id].balance_on(*date);
+            let balance =
+                rates.convert(historical.balance, &historical.currency, currency, *date)?;
+            reconstructed |= historical.reconstructed;
+            match account_types
+                .iter_mut()
+                .find(|total| total.account_type == historical.account_type)
+            {
+                Some(total) => total.balance += balance,
+                None => account_types.push(AccountTypeBalanceOutDTO {
+                    account_type: historical.account_type,
+                    balance,
+                }),
+            }
+        }
+
+        points.push(NetWorthPointOutDTO {
+            date: *date,
+            net_worth: account_types.iter().map(|total| total.balance).sum(),
+            reconstructed,
+            account_types,
+        });
+    }
+    Ok(points)
+}
@@ -0,0 +1,375 @@
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use std::collections::{BTreeMap, HashSet};
+use std::fmt::Write;
+
+use super::{change, expenses_by_category, summarize, PeriodTotals};
+use crate::{
+    dtos::report_dtos::{
+        BudgetsKeptOutDTO, MonthInReviewOutDTO, NoSpendStreakOutDTO, TopCategoryOutDTO,
+        TopPayeeOutDTO, YearInReviewOutDTO, YearOverYearOutDTO,
+    },
+    enums::custom_enums::{ReportGranularity, TransactionType},
+    exchange_rates::{MissingRate, RateTable},
+    models::{
+        report::{BudgetResult, CompletedGoal},
+        subscription::Charge,
+        user_preferences::UserPreferences,
+    },
+    money::Money,
+    subscriptions::payee_key,
+};
+
+// How many categories and payees make the top lists.
+pub const TOP_COUNT: usize = 5;
+
+fn share(amount: Money, total: Money) -> Option<Decimal> {
+    amount.percentage_of(total).map(|share| share.round_dp(2))
+}
+
+fn top_payees(
+    charges: &[Charge],
+    from: NaiveDate,
+    to: NaiveDate,
+    expenses: Money,
+    rates: &RateTable,
+    currency: &str,
+) -> Result<Vec<TopPayeeOutDTO>, MissingRate> {
+    let mut payees: BTreeMap<String, TopPayeeOutDTO> = BTreeMap::new();
+    for charge in charges
+        .iter()
+        .filter(|charge| charge.day >= from && charge.day <= to)
+    {
+        let payee = payee_key(&charge.title);
+        if payee.is_empty() {
+            continue;
+        }
+        let amount = rates.convert(charge.amount, &charge.currency, currency, charge.day)?;
+        let total = payees
+            .entry(payee.clone())
+            .or_insert_with(|| TopPayeeOutDTO {
+                payee,
+                name: String::new(),
+                amount: Money::ZERO,
+                transaction_count: 0,
+                share: None,
+            });
+        total.name = charge.title.trim().to_string();
+        total.amount += amount;
+        total.transaction_count += 1;
+    }
+
+    let mut payees: Vec<TopPayeeOutDTO> = payees.into_values().collect();
+    payees.sort_by(|a, b| b.amount.cmp(&a.amount).then_with(|| a.payee.cmp(&b.payee)));
+    payees.truncate(TOP_COUNT);
+    for payee in &mut payees {
+        payee.share = share(payee.amount, expenses);
+    }
+    Ok(payees)
+}
+
+fn longest_no_spend_streak(current: &PeriodTotals) -> NoSpendStreakOutDTO {
+    let spending_days: HashSet<NaiveDate> = current
+        .daily_totals
+        .iter()
+        .filter(|daily| daily.transaction_type == TransactionType::Expense)
+        .map(|daily| daily.day)
+        .collect();
+
+    let mut longest = NoSpendStreakOutDTO {
+        days: 0,
+        from: None,
+        to: None,
+    };
+    let mut run: Option<(NaiveDate, i64)> = None;
+    for day in current
+        .from
+        .iter_days()
+        .take_while(|day| *day <= current.to)
+    {
+        if spending_days.contains(&day) {
+            run = None;
+            continue;
+        }
+        let (start, days) = run.map_or((day, 1), |(start, days)| (start, days + 1));
+        run = Some((start, days));
+        if days > longest.days {
+            longest = NoSpendStreakOutDTO {
+                days,
+                from: Some(start),
+                to: Some(day),
+            };
+        }
+    }
+    longest
+}
+
+// Everything a year-end review shows, in `currency`. Budgets are passed in
+// already measured in `budget_currency`.
+#[allow(clippy::too_many_arguments)]
+pub fn year_in_review(
+    year: i32,
+    preferences: &UserPreferences,
+    current: PeriodTotals,
+    last_year: PeriodTotals,
+    charges: &[Charge],
+    goals: &[CompletedGoal],
+    budgets: &[BudgetResult],
+    budget_currency: &str,
+    rates: &RateTable,
+    currency: &str,
+) -> Result<YearInReviewOutDTO, MissingRate> {
+    let summary = summarize(
+        current.daily_totals,
+        rates,
+        currency,
+        ReportGranularity::Month,
+        preferences,
+        current.from,
+        current.to,
+    )?;
+    let previous = summarize(
+        last_year.daily_totals,
+        rates,
+        currency,
+        ReportGranularity::Month,
+        preferences,
+        last_year.from,
+        last_year.to,
+    )?;
+
+    let months: Vec<MonthInReviewOutDTO> = summary
+        .periods
+        .iter()
+        .map(|period| MonthInReviewOutDTO {
+            month: period.start,
+            income: period.income,
+            expenses: period.expenses,
+            net: period.net,
+        })
+        .collect();
+    // Ties go to the earlier month.
+    let biggest = |amount: fn(&MonthInReviewOutDTO) -> Money| {
+        months
+            .iter()
+            .rev()
+            .filter(|month| amount(month).is_positive())
+            .max_by_key(|month| amount(month))
+            .map(|month| month.month)
+    };
+    let biggest_spending_month = biggest(|month| month.expenses);
+    let biggest_income_month = biggest(|month| month.income);
+
+    let mut top_categories: Vec<TopCategoryOutDTO> =
+        expenses_by_category(&current, rates, currency)?
+            .into_iter()
+            .map(|(category_id, (name, amount))| TopCategoryOutDTO {
+                category_id,
+                name,
+                amount,
+                share: share(amount, summary.expenses),
+            })
+            .collect();
+    top_categories.sort_by(|a, b| b.amount.cmp(&a.amount).then_with(|| a.name.cmp(&b.name)));
+    top_categories.truncate(TOP_COUNT);
+
+    let budgets: Vec<_> = budgets
+        .iter()
+        .map(|budget| budget.to_budget_result_out_dto())
+        .collect();
+
+    Ok(YearInReviewOutDTO {
+        year,
+        currency: currency.to_string(),
+        from: current.from,
+        to: current.to,
+        income: summary.income,
+        expenses: summary.expenses,
+        net: summary.net,
+        savings_rate: summary.savings_rate,
+        transaction_count: summary.transaction_count,
+        biggest_spending_month,
+        biggest_income_month,
+        months,
+        top_categories,
+        top_payees: top_payees(
+            charges,
+            current.from,
+            current.to,
+            summary.expenses,
+            rates,
+            currency,
+        )?,
+        longest_no_spend_streak: longest_no_spend_streak(&current),
+        goals_completed: goals
+            .iter()
+            .map(|goal| goal.to_completed_goal_out_dto())
+            .collect(),
+        budgets: BudgetsKeptOutDTO {
+            currency: budget_currency.to_string(),
+            kept: budgets.iter().filter(|budget| budget.kept).count(),
+            total: budgets.len(),
+            budgets,
+        },
+        year_over_year: YearOverYearOutDTO {
+            from: last_year.from,
+            to: last_year.to,
+            income: previous.income,
+            expenses: previous.expenses,
+            net: previous.net,
+            income_change: change(summary.income, previous.income),
+            expenses_change: change(summary.expenses, previous.expenses),
+            // Against the size of last year's net, so going from a loss to a
+            // smaller loss is still an increase.
+            net_change: (summary.net - previous.net)
+                .percentage_of(previous.net.abs())
+                .map(|change| change.round_dp(2)),
+        },
+    })
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+fn percent(value: Option<Decimal>) -> String {
+    value.map_or("–".to_string(), |value| format!("{}%", value))
+}
+
+// The review as a standalone HTML page, with dates in the user's format and
+// month names in their locale.
+pub fn render_html(review: &YearInReviewOutDTO, preferences: &UserPreferences) -> String {
+    let money = |amount: Money| {
+        format!(
+            "{} {}",
+            amount.round_to(&review.currency),
+            escape(&review.currency)
+        )
+    };
+    let day = |date: NaiveDate| escape(&preferences.format_date(date));
+    let month_name =
+        |month: Option<NaiveDate>| month.map_or("–", |month| preferences.month_name(month));
+
+    let mut html = String::new();
+    let _ = write!(
+        html,
+        "<!DOCTYPE html>\n<html lang=\"{lang}\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>{year} in review</title>\n<style>\n\
+         body {{ font-family: sans-serif; max-width: 48rem; margin: 2rem auto; color: #222; }}\n\
+         table {{ border-collapse: collapse; width: 100%; }}\n\
+         th, td {{ padding: 0.25rem 0.5rem; border-bottom: 1px solid #ddd; text-align: left; }}\n\
+         td.amount {{ text-align: right; }}\n\
+         </style>\n</head>\n<body>\n<h1>{year} in review</h1>\n\
+         <p>{from} to {to}</p>\n",
+        lang = escape(&preferences.locale),
+        year = review.year,
+        from = day(review.from),
+        to = day(review.to),
+    );
+
+    let _ = write!(
+        html,
+        "<h2>Totals</h2>\n<table>\n\
+         <tr><th>Income</th><td class=\"amount\">{}</td><td>{} on last year</td></tr>\n\
+         <tr><th>Expenses</th><td class=\"amount\">{}</td><td>{} on last year</td></tr>\n\
+         <tr><th>Net</th><td class=\"amount\">{}</td><td>{} on last year</td></tr>\n\
+         <tr><th>Savings rate</th><td class=\"amount\">{}</td><td></td></tr>\n\
+         </table>\n\
+         <p>Biggest spending month: {}. Biggest income month: {}.</p>\n",
+        money(review.income),
+        percent(review.year_over_year.income_change),
+        money(review.expenses),
+        percent(review.year_over_year.expenses_change),
+        money(review.net),
+        percent(review.year_over_year.net_change),
+        percent(review.savings_rate),
+        month_name(review.biggest_spending_month),
+        month_name(review.biggest_income_month),
+    );
+
+    html.push_str("<h2>Months</h2>\n<table>\n<tr><th>Month</th><th>Income</th><th>Expenses</th><th>Net</th></tr>\n");
+    for month in &review.months {
+        let _ = writeln!(
+            html,
+            "<tr><td>{}</td><td class=\"amount\">{}</td><td class=\"amount\">{}</td><td class=\"amount\">{}</td></tr>",
+            preferences.month_name(month.month),
+            money(month.income),
+            money(month.expenses),
+            money(month.net),
+        );
+    }
+    html.push_str("</table>\n");
+
+    html.push_str("<h2>Top categories</h2>\n<table>\n");
+    for category in &review.top_categories {
+        let _ = writeln!(
+            html,
+            "<tr><td>{}</td><td class=\"amount\">{}</td><td class=\"amount\">{}</td></tr>",
+            escape(&category.name),
+            money(category.amount),
+            percent(category.share),
+        );
+    }
+    html.push_str("</table>\n<h2>Top payees</h2>\n<table>\n");
+    for payee in &review.top_payees {
+        let _ = writeln!(
+            html,
+            "<tr><td>{}</td><td class=\"amount\">{}</td><td class=\"amount\">{} payments</td></tr>",
+            escape(&payee.name),
+            money(payee.amount),
+            payee.transaction_count,
+        );
+    }
+    html.push_str("</table>\n");
+
+    let streak = &review.longest_no_spend_streak;
+    let _ = match (streak.from, streak.to) {
+        (Some(from), Some(to)) => writeln!(
+            html,
+            "<h2>Longest no-spend streak</h2>\n<p>{} days, from {} to {}.</p>",
+            streak.days,
+            day(from),
+            day(to)
+        ),
+        _ => writeln!(
+            html,
+            "<h2>Longest no-spend streak</h2>\n<p>No day went without spending.</p>"
+        ),
+    };
+
+    let _ = writeln!(
+        html,
+        "<h2>Goals completed</h2>\n<p>{} saving goals reached their target.</p>\n<ul>",
+        review.goals_completed.len()
+    );
+    for goal in &review.goals_completed {
+        let _ = writeln!(
+            html,
+            "<li>{} ({}) on {}</li>",
+            escape(&goal.title),
+            goal.target_amount.round_to(&review.currency),
+            day(goal.completed_on)
+        );
+    }
+    let _ = writeln!(
+        html,
+        "</ul>\n<h2>Budgets</h2>\n<p>{} of {} budgets kept.</p>\n<ul>",
+        review.budgets.kept, review.budgets.total
+    );
+    for budget in &review.budgets.budgets {
+        let _ = writeln!(
+            html,
+            "<li>{}: {} spent of {} {}</li>",
+            escape(&budget.name),
+            budget.spent.round_to(&review.budgets.currency),
+            budget.amount.round_to(&review.budgets.currency),
+            escape(&review.budgets.currency)
+        );
+    }
+    html.push_str("</ul>\n</body>\n</html>\n");
+    html
+}
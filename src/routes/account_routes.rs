@@ -3,14 +3,29 @@ use rocket::response::status;
 use rocket::serde::json::Json;
 use rocket::{delete, get, patch, post, routes, Route, State};
 use sqlx::PgPool;
+use uuid::Uuid;
 
-use crate::dtos::account_dtos::{AccountInDTO, AccountOutDTO};
+use crate::auth::AuthUser;
+use crate::date_param::DateParam;
+use crate::dtos::account_dtos::{
+    AccountBalanceOutDTO, AccountInDTO, AccountOutDTO, CurrencyBalanceOutDTO, NetWorthOutDTO,
+};
+use crate::dtos::transaction_dtos::AccountTransactionOutDTO;
+use crate::money::is_currency_code;
 use crate::operations::account_ops::*;
+use crate::operations::exchange_rate_ops::fetch_rate_table;
+use crate::operations::ownership_ops::Owned;
+use crate::operations::transaction_ops::fetch_account_transactions;
+use crate::operations::user_preferences_ops::find_user_preferences;
+use crate::routes::household_routes::record_access_error;
 use crate::uuid_param::UuidParam;
 
 #[get("/")]
-async fn read_all(db: &State<PgPool>) -> Result<Json<Vec<AccountOutDTO>>, status::Custom<String>> {
-    match get_all_accounts(db).await {
+async fn read_all(
+    db: &State<PgPool>,
+    auth: AuthUser,
+) -> Result<Json<Vec<AccountOutDTO>>, status::Custom<String>> {
+    match get_all_accounts(db, auth.0.id).await {
         Ok(accounts) => {
             let accounts_dto: Vec<AccountOutDTO> = accounts
                 .into_iter()
@@ -25,13 +40,90 @@ async fn read_all(db: &State<PgPool>) -> Result<Json<Vec<AccountOutDTO>>, status
     }
 }
 
+#[get("/balances")]
+pub async fn get_balances(
+    db: &State<PgPool>,
+    auth: AuthUser,
+) -> Result<Json<Vec<CurrencyBalanceOutDTO>>, status::Custom<String>> {
+    match get_balances_by_currency(db, auth.0.id).await {
+        Ok(balances) => Ok(Json(
+            balances
+                .iter()
+                .map(|balance| balance.to_currency_balance_out_dto())
+                .collect(),
+        )),
+        Err(_) => Err(status::Custom(
+            Status::InternalServerError,
+            "Failed to fetch balances.".to_string(),
+        )),
+    }
+}
+
+// Balances in every currency added up in one, the user's base currency unless
+// another is asked for, at today's rates.
+#[get("/net_worth?<currency>")]
+pub async fn get_net_worth(
+    db: &State<PgPool>,
+    auth: AuthUser,
+    currency: Option<String>,
+) -> Result<Json<NetWorthOutDTO>, status::Custom<String>> {
+    if currency
+        .as_deref()
+        .is_some_and(|currency| !is_currency_code(currency))
+    {
+        return Err(status::Custom(
+            Status::BadRequest,
+            "Currency must be a three letter ISO 4217 code.".to_string(),
+        ));
+    }
+
+    let data = async {
+        let preferences = find_user_preferences(db, auth.0.id).await?;
+        let balances = get_balances_by_currency(db, auth.0.id).await?;
+        let today = preferences.today();
+        let rates = fetch_rate_table(db, today, today).await?;
+        Ok::<_, sqlx::Error>((preferences, balances, today, rates))
+    };
+    let (preferences, balances, today, rates) = match data.await {
+        Ok(data) => data,
+        Err(_) => {
+            return Err(status::Custom(
+                Status::InternalServerError,
+                "Failed to calculate net worth.".to_string(),
+            ))
+        }
+    };
+
+    let currency = currency.unwrap_or(preferences.base_currency);
+    let mut converted = Vec::new();
+    for balance in &balances {
+        match rates.convert(balance.balance, &balance.currency, &currency, today) {
+            Ok(amount) => converted.push(balance.to_converted_balance_out_dto(amount)),
+            Err(missing) => {
+                return Err(status::Custom(
+                    Status::UnprocessableEntity,
+                    missing.to_string(),
+                ))
+            }
+        }
+    }
+
+    Ok(Json(NetWorthOutDTO {
+        net_worth: converted.iter().map(|b| b.converted_balance).sum(),
+        currency,
+        date: today,
+        balances: converted,
+    }))
+}
+
 #[get("/<account_id_param>")]
 pub async fn get_account_by_id(
     db: &rocket::State<PgPool>,
+    auth: AuthUser,
     account_id_param: UuidParam,
 ) -> Result<Json<AccountOutDTO>, status::Custom<String>> {
     let account_id = account_id_param.0;
-    match find_account_by_id(db, account_id).await {
+    match find_account_by_id(db, auth.0.id, account_id).await {
         Ok(Some(account)) => Ok(Json(account.to_account_out_dto())),
         Ok(None) => Err(status::Custom(
             Status::NotFound,
@@ -44,12 +136,106 @@ pub async fn get_account_by_id(
     }
 }
 
+// Defaults to the end of today.
+#[get("/<account_id_param>/balance?<at>")]
+pub async fn get_account_balance(
+    db: &rocket::State<PgPool>,
+    auth: AuthUser,
+    account_id_param: UuidParam,
+    at: Option<DateParam>,
+) -> Result<Json<AccountBalanceOutDTO>, status::Custom<String>> {
+    let account_id = account_id_param.0;
+    let data = async {
+        let account = find_account_by_id(db, auth.0.id, account_id).await?;
+        let date = match at {
+            Some(at) => at.0,
+            None => find_user_preferences(db, auth.0.id).await?.today(),
+        };
+        let balance = account_balance_on(db, auth.0.id, account_id, date).await?;
+        Ok::<_, sqlx::Error>((account, date, balance))
+    };
+
+    match data.await {
+        Ok((Some(account), date, Some(balance))) => Ok(Json(AccountBalanceOutDTO {
+            account_id,
+            date,
+            balance,
+            currency: account.currency,
+        })),
+        Ok(_) => Err(status::Custom(
+            Status::NotFound,
+            "Account not found.".to_string(),
+        )),
+        Err(_) => Err(status::Custom(
+            Status::InternalServerError,
+            "Failed to calculate balance.".to_string(),
+        )),
+    }
+}
+
+#[get("/<account_id_param>/transactions?<running_balance>")]
+pub async fn get_account_transactions(
+    db: &rocket::State<PgPool>,
+    auth: AuthUser,
+    account_id_param: UuidParam,
+    running_balance: Option<bool>,
+) -> Result<Json<Vec<AccountTransactionOutDTO>>, status::Custom<String>> {
+    let account_id = account_id_param.0;
+    let internal_error = |_| {
+        status::Custom(
+            Status::InternalServerError,
+            "Failed to fetch transactions.".to_string(),
+        )
+    };
+    if find_account_by_id(db, auth.0.id, account_id)
+        .await
+        .map_err(internal_error)?
+        .is_none()
+    {
+        return Err(status::Custom(
+            Status::NotFound,
+            "Account not found.".to_string(),
+        ));
+    }
+
+    let transactions = fetch_account_transactions(db, account_id)
+        .await
+        .map_err(internal_error)?;
+    let with_running_balance = running_balance.unwrap_or(false);
+    Ok(Json(
+        transactions
+            .iter()
+            .map(|transaction| transaction.to_account_transaction_out_dto(with_running_balance))
+            .collect(),
+    ))
+}
+
 #[post("/", data = "<account_in>")]
 pub async fn post_account(
     db: &rocket::State<PgPool>,
+    auth: AuthUser,
     account_in: Json<AccountInDTO>,
 ) -> Result<Json<AccountOutDTO>, status::Custom<String>> {
-    match create_account(db.inner(), &account_in.0).await {
+    if let Err(message) = account_in.validate() {
+        return Err(status::Custom(Status::BadRequest, message.to_string()));
+    }
+    let currency = match &account_in.currency {
+        Some(currency) => currency.clone(),
+        None => match find_user_preferences(db, auth.0.id).await {
+            Ok(preferences) => preferences.base_currency,
+            Err(_) => {
+                return Err(status::Custom(
+                    Status::InternalServerError,
+                    "Failed to create account.".to_string(),
+                ))
+            }
+        },
+    };
+    if let Err(message) = account_in.validate_balance(&currency) {
+        return Err(status::Custom(Status::BadRequest, message.to_string()));
+    }
+
+    match create_account(db.inner(), auth.0.id, &account_in.0).await {
         Ok(account) => Ok(Json(account.to_account_out_dto())),
         Err(_) => Err(status::Custom(
             Status::InternalServerError,
@@ -61,12 +247,21 @@ pub async fn post_account(
 #[patch("/<account_id_param>", data = "<account_in>")]
 pub async fn patch_account(
     db: &rocket::State<PgPool>,
+    auth: AuthUser,
     account_id_param: UuidParam,
     account_in: Json<AccountInDTO>,
 ) -> Result<Json<AccountOutDTO>, status::Custom<String>> {
     let account_id = account_id_param.0;
-    match update_account(db, account_id, &account_in.0).await {
-        Ok(account) => Ok(Json(account.to_account_out_dto())),
+    if let Err(message) = account_in.validate() {
+        return Err(status::Custom(Status::BadRequest, message.to_string()));
+    }
+    check_account_update(db, auth.0.id, account_id, &account_in).await?;
+
+    match update_account(db, auth.0.id, account_id, &account_in.0).await {
+        Ok(Some(account)) => Ok(Json(account.to_account_out_dto())),
+        Ok(None) => {
+            Err(record_access_error(db, auth.0.id, Owned::Account, account_id, "Account").await)
+        }
         Err(_) => Err(status::Custom(
             Status::InternalServerError,
             "Failed to update account.".to_string(),
@@ -74,13 +269,53 @@ pub async fn patch_account(
     }
 }
 
+async fn check_account_update(
+    db: &PgPool,
+    user_id: Uuid,
+    account_id: Uuid,
+    account_in: &AccountInDTO,
+) -> Result<(), status::Custom<String>> {
+    let internal_error = |_| {
+        status::Custom(
+            Status::InternalServerError,
+            "Failed to update account.".to_string(),
+        )
+    };
+    let Some(account) = find_account_by_id(db, user_id, account_id)
+        .await
+        .map_err(internal_error)?
+    else {
+        return Ok(());
+    };
+    let currency = account_in.currency.as_deref().unwrap_or(&account.currency);
+    if let Err(message) = account_in.validate_balance(currency) {
+        return Err(status::Custom(Status::BadRequest, message.to_string()));
+    }
+    if account.currency != currency
+        && account_has_transactions(db, account_id)
+            .await
+            .map_err(internal_error)?
+    {
+        return Err(status::Custom(
+            Status::Conflict,
+            "The currency of an account with transactions can't be changed.".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
 #[delete("/<account_id_param>")]
 pub async fn delete_account_route(
     db: &rocket::State<PgPool>,
+    auth: AuthUser,
     account_id_param: UuidParam,
 ) -> Result<status::NoContent, status::Custom<String>> {
     let account_id = account_id_param.0;
-    match delete_account(db, account_id).await {
+    match delete_account(db, auth.0.id, account_id).await {
+        Ok(0) => {
+            Err(record_access_error(db, auth.0.id, Owned::Account, account_id, "Account").await)
+        }
         Ok(_) => Ok(status::NoContent),
         Err(_) => Err(status::Custom(
             Status::InternalServerError,
@@ -92,7 +327,11 @@ pub async fn delete_account_route(
 pub fn account_routes() -> Vec<Route> {
     routes![
         read_all,
+        get_balances,
+        get_net_worth,
         get_account_by_id,
+        get_account_balance,
+        get_account_transactions,
         post_account,
         patch_account,
         delete_account_route
@@ -1,5 +1,7 @@
-use crate::dtos::achievement_dtos::{AchievementInDTO, AchievementOutDTO};
+use crate::auth::AuthUser;
+use crate::dtos::achievement_dtos::{AchievementInDTO, AchievementOutDTO, AchievementsSeenInDTO};
 use crate::operations::achievement_ops::*;
+use crate::operations::ownership_ops::{can_edit_references, Owned};
 use crate::uuid_param::UuidParam;
 use rocket::http::Status;
 use rocket::response::status;
@@ -10,8 +12,9 @@ use sqlx::PgPool;
 #[get("/achievements")]
 pub async fn get_all_achievements(
     db: &rocket::State<PgPool>,
+    auth: AuthUser,
 ) -> Result<Json<Vec<AchievementOutDTO>>, status::Custom<String>> {
-    match fetch_all_achievements(db).await {
+    match fetch_all_achievements(db, auth.0.id).await {
         Ok(achievements) => {
             let achievements_dto: Vec<AchievementOutDTO> = achievements
                 .into_iter()
@@ -26,13 +29,51 @@ pub async fn get_all_achievements(
     }
 }
 
+// Achievements the user hasn't acknowledged yet. Reading the feed doesn't
+// change it; clients post the ids they showed to /achievements/feed/seen.
+#[get("/achievements/feed")]
+pub async fn get_achievement_feed(
+    db: &rocket::State<PgPool>,
+    auth: AuthUser,
+) -> Result<Json<Vec<AchievementOutDTO>>, status::Custom<String>> {
+    match fetch_new_achievements_for_user(db, auth.0.id).await {
+        Ok(achievements) => {
+            let achievements_dto: Vec<AchievementOutDTO> = achievements
+                .into_iter()
+                .map(|achievement| achievement.to_achievement_out_dto())
+                .collect();
+            Ok(Json(achievements_dto))
+        }
+        Err(_) => Err(status::Custom(
+            Status::InternalServerError,
+            "Failed to fetch achievement feed.".to_string(),
+        )),
+    }
+}
+
+#[post("/achievements/feed/seen", data = "<seen_in>")]
+pub async fn post_achievements_seen(
+    db: &rocket::State<PgPool>,
+    auth: AuthUser,
+    seen_in: Json<AchievementsSeenInDTO>,
+) -> Result<status::NoContent, status::Custom<String>> {
+    match mark_achievements_seen(db, auth.0.id, &seen_in.achievement_ids).await {
+        Ok(_) => Ok(status::NoContent),
+        Err(_) => Err(status::Custom(
+            Status::InternalServerError,
+            "Failed to update achievement feed.".to_string(),
+        )),
+    }
+}
+
 #[get("/achievements/<achievement_id_param>")]
 pub async fn get_achievement_by_id(
     db: &rocket::State<PgPool>,
+    auth: AuthUser,
     achievement_id_param: UuidParam,
 ) -> Result<Json<AchievementOutDTO>, status::Custom<String>> {
     let achievement_id = achievement_id_param.0;
-    match find_achievement_by_id(db, achievement_id).await {
+    match find_achievement_by_id(db, auth.0.id, achievement_id).await {
         Ok(Some(achievement)) => Ok(Json(achievement.to_achievement_out_dto())),
         Ok(None) => Err(status::Custom(
             Status::NotFound,
@@ -48,8 +89,31 @@ pub async fn get_achievement_by_id(
 #[post("/achievements", data = "<achievement_in>")]
 pub async fn post_achievement(
     db: &rocket::State<PgPool>,
+    auth: AuthUser,
     achievement_in: Json<AchievementInDTO>,
 ) -> Result<Json<AchievementOutDTO>, status::Custom<String>> {
+    match can_edit_references(
+        db,
+        auth.0.id,
+        &[(Owned::SavingGoal, achievement_in.goal_id)],
+    )
+    .await
+    {
+        Ok(true) => {}
+        Ok(false) => {
+            return Err(status::Custom(
+                Status::BadRequest,
+                "Saving goal not found for this user.".to_string(),
+            ))
+        }
+        Err(_) => {
+            return Err(status::Custom(
+                Status::InternalServerError,
+                "Failed to create achievement.".to_string(),
+            ))
+        }
+    }
+
     match create_achievement(db.inner(), &achievement_in.0).await {
         Ok(achievement) => Ok(Json(achievement.to_achievement_out_dto())),
         Err(_) => Err(status::Custom(
@@ -62,12 +126,39 @@ pub async fn post_achievement(
 #[patch("/achievements/<achievement_id_param>", data = "<achievement_update>")]
 pub async fn update_achievement(
     db: &rocket::State<PgPool>,
+    auth: AuthUser,
     achievement_id_param: UuidParam,
     achievement_update: Json<AchievementInDTO>,
 ) -> Result<Json<AchievementOutDTO>, status::Custom<String>> {
     let achievement_id = achievement_id_param.0;
-    match update_achievement_in_db(db, achievement_id, &achievement_update.0).await {
-        Ok(achievement) => Ok(Json(achievement.to_achievement_out_dto())),
+    match can_edit_references(
+        db,
+        auth.0.id,
+        &[(Owned::SavingGoal, achievement_update.goal_id)],
+    )
+    .await
+    {
+        Ok(true) => {}
+        Ok(false) => {
+            return Err(status::Custom(
+                Status::BadRequest,
+                "Saving goal not found for this user.".to_string(),
+            ))
+        }
+        Err(_) => {
+            return Err(status::Custom(
+                Status::InternalServerError,
+                "Failed to update achievement.".to_string(),
+            ))
+        }
+    }
+
+    match update_achievement_in_db(db, auth.0.id, achievement_id, &achievement_update.0).await {
+        Ok(Some(achievement)) => Ok(Json(achievement.to_achievement_out_dto())),
+        Ok(None) => Err(status::Custom(
+            Status::NotFound,
+            "Achievement not found.".to_string(),
+        )),
         Err(_) => Err(status::Custom(
             Status::InternalServerError,
             "Failed to update achievement.".to_string(),
@@ -78,10 +169,15 @@ pub async fn update_achievement(
 #[delete("/achievements/<achievement_id_param>")]
 pub async fn delete_achievement_by_id(
     db: &rocket::State<PgPool>,
+    auth: AuthUser,
     achievement_id_param: UuidParam,
 ) -> Result<status::NoContent, status::Custom<String>> {
     let achievement_id = achievement_id_param.0;
-    match delete_achievement(db, achievement_id).await {
+    match delete_achievement(db, auth.0.id, achievement_id).await {
+        Ok(0) => Err(status::Custom(
+            Status::NotFound,
+            "Achievement not found.".to_string(),
+        )),
         Ok(_) => Ok(status::NoContent),
         Err(_) => Err(status::Custom(
             Status::InternalServerError,
@@ -94,6 +190,8 @@ pub fn achievement_routes() -> Vec<Route> {
     routes![
         post_achievement,
         get_all_achievements,
+        get_achievement_feed,
+        post_achievements_seen,
         get_achievement_by_id,
         update_achievement,
         delete_achievement_by_id
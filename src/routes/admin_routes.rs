@@ -0,0 +1,274 @@
+use crate::auth::impersonation::{Impersonation, IMPERSONATOR_SESSION_COOKIE};
+use crate::auth::{session_id_from_cookies, set_session_cookie, AdminUser, AuthUser};
+use crate::dtos::admin_dtos::{ImpersonationLogEntryOutDTO, UserOverviewOutDTO, UserRoleInDTO};
+use crate::dtos::user_dtos::UserOutDTO;
+use crate::operations::admin_ops::*;
+use crate::operations::session_ops::{
+    create_impersonation_session, delete_session, delete_user_sessions,
+};
+use crate::operations::user_ops::find_user_by_id;
+use crate::uuid_param::UuidParam;
+use chrono::Duration;
+use rocket::http::{Cookie, CookieJar, SameSite, Status};
+use rocket::response::status;
+use rocket::serde::json::Json;
+use rocket::{get, patch, post, routes, Route};
+use sqlx::PgPool;
+use std::str::FromStr;
+use uuid::Uuid;
+
+const IMPERSONATION_TTL_MINUTES: i64 = 60;
+
+fn not_found() -> status::Custom<String> {
+    status::Custom(Status::NotFound, "User not found.".to_string())
+}
+
+// Admins manage other users; acting on their own account this way would let
+// them lock themselves out.
+fn reject_self(admin: &AdminUser, user_id: Uuid) -> Result<(), status::Custom<String>> {
+    if admin.0.id == user_id {
+        return Err(status::Custom(
+            Status::BadRequest,
+            "Admins cannot do this to their own account.".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+#[get("/admin/users")]
+pub async fn get_user_overviews(
+    db: &rocket::State<PgPool>,
+    _admin: AdminUser,
+) -> Result<Json<Vec<UserOverviewOutDTO>>, status::Custom<String>> {
+    match fetch_user_overviews(db).await {
+        Ok(overviews) => Ok(Json(
+            overviews
+                .iter()
+                .map(|overview| overview.to_user_overview_out_dto())
+                .collect(),
+        )),
+        Err(_) => Err(status::Custom(
+            Status::InternalServerError,
+            "Failed to fetch users.".to_string(),
+        )),
+    }
+}
+
+#[patch("/admin/users/<user_id_param>/role", data = "<role_in>")]
+pub async fn patch_user_role(
+    db: &rocket::State<PgPool>,
+    admin: AdminUser,
+    user_id_param: UuidParam,
+    role_in: Json<UserRoleInDTO>,
+) -> Result<status::NoContent, status::Custom<String>> {
+    let user_id = user_id_param.0;
+    reject_self(&admin, user_id)?;
+
+    match set_user_role(db, user_id, role_in.role).await {
+        Ok(0) => Err(not_found()),
+        Ok(_) => Ok(status::NoContent),
+        Err(_) => Err(status::Custom(
+            Status::InternalServerError,
+            "Failed to update role.".to_string(),
+        )),
+    }
+}
+
+#[post("/admin/users/<user_id_param>/suspend")]
+pub async fn post_suspend_user(
+    db: &rocket::State<PgPool>,
+    admin: AdminUser,
+    user_id_param: UuidParam,
+) -> Result<status::NoContent, status::Custom<String>> {
+    let user_id = user_id_param.0;
+    reject_self(&admin, user_id)?;
+
+    match suspend_user(db, user_id).await {
+        Ok(0) => Err(not_found()),
+        Ok(_) => Ok(status::NoContent),
+        Err(_) => Err(status::Custom(
+            Status::InternalServerError,
+            "Failed to suspend user.".to_string(),
+        )),
+    }
+}
+
+#[post("/admin/users/<user_id_param>/reactivate")]
+pub async fn post_reactivate_user(
+    db: &rocket::State<PgPool>,
+    _admin: AdminUser,
+    user_id_param: UuidParam,
+) -> Result<status::NoContent, status::Custom<String>> {
+    match reactivate_user(db, user_id_param.0).await {
+        Ok(0) => Err(not_found()),
+        Ok(_) => Ok(status::NoContent),
+        Err(_) => Err(status::Custom(
+            Status::InternalServerError,
+            "Failed to reactivate user.".to_string(),
+        )),
+    }
+}
+
+#[post("/admin/users/<user_id_param>/logout")]
+pub async fn post_force_logout(
+    db: &rocket::State<PgPool>,
+    _admin: AdminUser,
+    user_id_param: UuidParam,
+) -> Result<status::NoContent, status::Custom<String>> {
+    let user_id = user_id_param.0;
+    match find_user_by_id(db, user_id).await {
+        Ok(Some(_)) => {}
+        Ok(None) => return Err(not_found()),
+        Err(_) => {
+            return Err(status::Custom(
+                Status::InternalServerError,
+                "Failed to log out user.".to_string(),
+            ))
+        }
+    }
+
+    match delete_user_sessions(db, user_id).await {
+        Ok(_) => Ok(status::NoContent),
+        Err(_) => Err(status::Custom(
+            Status::InternalServerError,
+            "Failed to log out user.".to_string(),
+        )),
+    }
+}
+
+// Switches the admin's browser to a short-lived session as the user. The
+// admin's own session is parked in a separate cookie until they stop.
+#[post("/admin/users/<user_id_param>/impersonate")]
+pub async fn post_impersonate_user(
+    db: &rocket::State<PgPool>,
+    cookies: &CookieJar<'_>,
+    admin: AdminUser,
+    user_id_param: UuidParam,
+) -> Result<Json<UserOutDTO>, status::Custom<String>> {
+    let user_id = user_id_param.0;
+    reject_self(&admin, user_id)?;
+    let internal_error = || {
+        status::Custom(
+            Status::InternalServerError,
+            "Failed to start impersonation.".to_string(),
+        )
+    };
+
+    let user = find_user_by_id(db, user_id)
+        .await
+        .map_err(|_| internal_error())?
+        .ok_or_else(not_found)?;
+    if is_admin(db, user_id).await.map_err(|_| internal_error())? {
+        return Err(status::Custom(
+            Status::Forbidden,
+            "Admins cannot be impersonated.".to_string(),
+        ));
+    }
+    if is_suspended(db, user_id)
+        .await
+        .map_err(|_| internal_error())?
+    {
+        return Err(status::Custom(
+            Status::Conflict,
+            "Suspended users cannot be impersonated.".to_string(),
+        ));
+    }
+
+    let admin_session_id = session_id_from_cookies(cookies).ok_or_else(|| {
+        status::Custom(
+            Status::BadRequest,
+            "Impersonation needs a browser session.".to_string(),
+        )
+    })?;
+    let session = create_impersonation_session(
+        db,
+        user_id,
+        admin.0.id,
+        Duration::minutes(IMPERSONATION_TTL_MINUTES),
+    )
+    .await
+    .map_err(|_| internal_error())?;
+    let impersonation = Impersonation {
+        impersonator_id: admin.0.id,
+        user_id,
+        session_id: session.id,
+    };
+    record_impersonated_request(
+        db,
+        &impersonation,
+        "POST",
+        &format!("/admin/users/{}/impersonate", user_id),
+        Status::Ok.code,
+    )
+    .await
+    .map_err(|_| internal_error())?;
+
+    let parked = Cookie::build(IMPERSONATOR_SESSION_COOKIE, admin_session_id.to_string())
+        .http_only(true)
+        .same_site(SameSite::Lax)
+        .finish();
+    cookies.add_private(parked);
+    set_session_cookie(cookies, session.id);
+
+    Ok(Json(user.to_user_out_dto()))
+}
+
+// Ends the impersonation session and puts the admin's own session back.
+#[post("/admin/impersonation/stop")]
+pub async fn post_stop_impersonation(
+    db: &rocket::State<PgPool>,
+    cookies: &CookieJar<'_>,
+    _auth: AuthUser,
+    impersonation: Impersonation,
+) -> Result<status::NoContent, status::Custom<String>> {
+    if delete_session(db, impersonation.session_id).await.is_err() {
+        return Err(status::Custom(
+            Status::InternalServerError,
+            "Failed to stop impersonation.".to_string(),
+        ));
+    }
+
+    let admin_session_id = cookies
+        .get_private(IMPERSONATOR_SESSION_COOKIE)
+        .and_then(|cookie| Uuid::from_str(cookie.value()).ok());
+    cookies.remove_private(Cookie::named(IMPERSONATOR_SESSION_COOKIE));
+    match admin_session_id {
+        Some(session_id) => set_session_cookie(cookies, session_id),
+        None => cookies.remove_private(Cookie::named(crate::auth::SESSION_COOKIE)),
+    }
+
+    Ok(status::NoContent)
+}
+
+#[get("/admin/impersonation_log?<user_id>")]
+pub async fn get_impersonation_log(
+    db: &rocket::State<PgPool>,
+    _admin: AdminUser,
+    user_id: Option<UuidParam>,
+) -> Result<Json<Vec<ImpersonationLogEntryOutDTO>>, status::Custom<String>> {
+    match fetch_impersonation_log(db, user_id.map(|user_id| user_id.0)).await {
+        Ok(entries) => Ok(Json(
+            entries
+                .iter()
+                .map(|entry| entry.to_impersonation_log_entry_out_dto())
+                .collect(),
+        )),
+        Err(_) => Err(status::Custom(
+            Status::InternalServerError,
+            "Failed to fetch impersonation log.".to_string(),
+        )),
+    }
+}
+
+pub fn admin_routes() -> Vec<Route> {
+    routes![
+        get_user_overviews,
+        patch_user_role,
+        post_suspend_user,
+        post_reactivate_user,
+        post_force_logout,
+        post_impersonate_user,
+        post_stop_impersonation,
+        get_impersonation_log
+    ]
+}
@@ -0,0 +1,213 @@
+use crate::auth::AuthUser;
+use crate::dtos::allocation_dtos::{
+    AllocationOutDTO, AllocationPlanOutDTO, AllocationRuleInDTO, AllocationRuleOutDTO,
+};
+use crate::operations::allocation_ops::*;
+use crate::operations::ownership_ops::{can_edit_references, Owned};
+use crate::operations::transaction_ops::{
+    apply_allocations_to_transaction, find_transaction_by_id, ManualAllocation,
+};
+use crate::uuid_param::UuidParam;
+use rocket::http::Status;
+use rocket::response::status;
+use rocket::serde::json::Json;
+use rocket::{delete, get, post, routes, Route};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+#[post("/allocation_rules", data = "<rule_in>")]
+pub async fn post_allocation_rule(
+    db: &rocket::State<PgPool>,
+    auth: AuthUser,
+    rule_in: Json<AllocationRuleInDTO>,
+) -> Result<Json<AllocationRuleOutDTO>, status::Custom<String>> {
+    if let Err(message) = rule_in.validate() {
+        return Err(status::Custom(Status::BadRequest, message.to_string()));
+    }
+
+    let mut references: Vec<(Owned, Uuid)> = rule_in
+        .targets
+        .iter()
+        .map(|target| (Owned::from(target.target_type), target.target_id))
+        .collect();
+    if let Some(account_id) = rule_in.match_account_id {
+        references.push((Owned::Account, account_id));
+    }
+    match can_edit_references(db, auth.0.id, &references).await {
+        Ok(true) => {}
+        Ok(false) => {
+            return Err(status::Custom(
+                Status::BadRequest,
+                "Allocation target or account not found for this user.".to_string(),
+            ))
+        }
+        Err(_) => {
+            return Err(status::Custom(
+                Status::InternalServerError,
+                "Failed to create allocation rule.".to_string(),
+            ))
+        }
+    }
+
+    match create_allocation_rule(db, auth.0.id, &rule_in.0).await {
+        Ok((rule, targets)) => Ok(Json(rule.to_allocation_rule_out_dto(&targets))),
+        Err(_) => Err(status::Custom(
+            Status::InternalServerError,
+            "Failed to create allocation rule.".to_string(),
+        )),
+    }
+}
+
+#[get("/allocation_rules")]
+pub async fn get_allocation_rules(
+    db: &rocket::State<PgPool>,
+    auth: AuthUser,
+) -> Result<Json<Vec<AllocationRuleOutDTO>>, status::Custom<String>> {
+    match fetch_allocation_rules_for_user(db, auth.0.id).await {
+        Ok(rules) => Ok(Json(
+            rules
+                .iter()
+                .map(|(rule, targets)| rule.to_allocation_rule_out_dto(targets))
+                .collect(),
+        )),
+        Err(_) => Err(status::Custom(
+            Status::InternalServerError,
+            "Failed to fetch allocation rules.".to_string(),
+        )),
+    }
+}
+
+#[delete("/allocation_rules/<rule_id_param>")]
+pub async fn delete_allocation_rule_by_id(
+    db: &rocket::State<PgPool>,
+    auth: AuthUser,
+    rule_id_param: UuidParam,
+) -> Result<status::NoContent, status::Custom<String>> {
+    match delete_allocation_rule(db, auth.0.id, rule_id_param.0).await {
+        Ok(0) => Err(status::Custom(
+            Status::NotFound,
+            "Allocation rule not found.".to_string(),
+        )),
+        Ok(_) => Ok(status::NoContent),
+        Err(_) => Err(status::Custom(
+            Status::InternalServerError,
+            "Failed to delete allocation rule.".to_string(),
+        )),
+    }
+}
+
+#[get("/transactions/<transaction_id_param>/allocation_plan")]
+pub async fn get_allocation_plan(
+    db: &rocket::State<PgPool>,
+    auth: AuthUser,
+    transaction_id_param: UuidParam,
+) -> Result<Json<AllocationPlanOutDTO>, status::Custom<String>> {
+    let transaction_id = transaction_id_param.0;
+    let transaction = match find_transaction_by_id(db, auth.0.id, transaction_id).await {
+        Ok(Some(transaction)) => transaction,
+        Ok(None) => {
+            return Err(status::Custom(
+                Status::NotFound,
+                "Transaction not found.".to_string(),
+            ))
+        }
+        Err(_) => {
+            return Err(status::Custom(
+                Status::InternalServerError,
+                "Failed to fetch transaction.".to_string(),
+            ))
+        }
+    };
+
+    match preview_allocation_plan(db, &transaction).await {
+        Ok(Some((rule, plan))) => Ok(Json(plan.to_allocation_plan_out_dto(&transaction, &rule))),
+        Ok(None) => Err(status::Custom(
+            Status::NotFound,
+            "No allocation rule matches this transaction.".to_string(),
+        )),
+        Err(_) => Err(status::Custom(
+            Status::InternalServerError,
+            "Failed to build allocation plan.".to_string(),
+        )),
+    }
+}
+
+#[post("/transactions/<transaction_id_param>/allocations")]
+pub async fn post_allocations(
+    db: &rocket::State<PgPool>,
+    auth: AuthUser,
+    transaction_id_param: UuidParam,
+) -> Result<Json<Vec<AllocationOutDTO>>, status::Custom<String>> {
+    match apply_allocations_to_transaction(db, auth.0.id, transaction_id_param.0).await {
+        Ok(ManualAllocation::Applied(allocations)) => Ok(Json(
+            allocations
+                .iter()
+                .map(|allocation| allocation.to_allocation_out_dto())
+                .collect(),
+        )),
+        Ok(ManualAllocation::NotFound) => Err(status::Custom(
+            Status::NotFound,
+            "Transaction not found.".to_string(),
+        )),
+        Ok(ManualAllocation::AlreadyAllocated) => Err(status::Custom(
+            Status::Conflict,
+            "Transaction has already been allocated.".to_string(),
+        )),
+        Ok(ManualAllocation::NoMatchingRule) => Err(status::Custom(
+            Status::NotFound,
+            "No allocation rule matches this transaction.".to_string(),
+        )),
+        Err(_) => Err(status::Custom(
+            Status::InternalServerError,
+            "Failed to apply allocation rule.".to_string(),
+        )),
+    }
+}
+
+#[get("/transactions/<transaction_id_param>/allocations")]
+pub async fn get_allocations(
+    db: &rocket::State<PgPool>,
+    auth: AuthUser,
+    transaction_id_param: UuidParam,
+) -> Result<Json<Vec<AllocationOutDTO>>, status::Custom<String>> {
+    let transaction_id = transaction_id_param.0;
+    match find_transaction_by_id(db, auth.0.id, transaction_id).await {
+        Ok(Some(_)) => {}
+        Ok(None) => {
+            return Err(status::Custom(
+                Status::NotFound,
+                "Transaction not found.".to_string(),
+            ))
+        }
+        Err(_) => {
+            return Err(status::Custom(
+                Status::InternalServerError,
+                "Failed to fetch transaction.".to_string(),
+            ))
+        }
+    }
+
+    match fetch_allocations_for_transaction(db, transaction_id).await {
+        Ok(allocations) => Ok(Json(
+            allocations
+                .iter()
+                .map(|allocation| allocation.to_allocation_out_dto())
+                .collect(),
+        )),
+        Err(_) => Err(status::Custom(
+            Status::InternalServerError,
+            "Failed to fetch allocations.".to_string(),
+        )),
+    }
+}
+
+pub fn allocation_routes() -> Vec<Route> {
+    routes![
+        post_allocation_rule,
+        get_allocation_rules,
+        delete_allocation_rule_by_id,
+        get_allocation_plan,
+        post_allocations,
+        get_allocations
+    ]
+}
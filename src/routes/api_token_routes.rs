@@ -0,0 +1,109 @@
+use crate::auth::tokens::{displayed_prefix, generate_token, hash_token, is_known_scope};
+use crate::auth::AuthUser;
+use crate::dtos::api_token_dtos::{ApiTokenCreatedOutDTO, ApiTokenInDTO, ApiTokenOutDTO};
+use crate::operations::api_token_ops::*;
+use crate::uuid_param::UuidParam;
+use chrono::Local;
+use rocket::http::Status;
+use rocket::response::status;
+use rocket::serde::json::Json;
+use rocket::{delete, get, post, routes, Route};
+use sqlx::PgPool;
+
+#[post("/api_tokens", data = "<api_token_in>")]
+pub async fn post_api_token(
+    db: &rocket::State<PgPool>,
+    auth: AuthUser,
+    api_token_in: Json<ApiTokenInDTO>,
+) -> Result<Json<ApiTokenCreatedOutDTO>, status::Custom<String>> {
+    if api_token_in.name.trim().is_empty() {
+        return Err(status::Custom(
+            Status::BadRequest,
+            "Token name must not be empty.".to_string(),
+        ));
+    }
+    if api_token_in.scopes.is_empty() {
+        return Err(status::Custom(
+            Status::BadRequest,
+            "At least one scope is required.".to_string(),
+        ));
+    }
+    if let Some(scope) = api_token_in
+        .scopes
+        .iter()
+        .find(|scope| !is_known_scope(scope))
+    {
+        return Err(status::Custom(
+            Status::BadRequest,
+            format!("Unknown scope: {}.", scope),
+        ));
+    }
+    if api_token_in.expires_at <= Local::now().naive_local() {
+        return Err(status::Custom(
+            Status::BadRequest,
+            "expires_at must be in the future.".to_string(),
+        ));
+    }
+
+    let token = generate_token();
+    match create_api_token(
+        db,
+        auth.0.id,
+        &api_token_in.0,
+        &displayed_prefix(&token),
+        &hash_token(&token),
+    )
+    .await
+    {
+        Ok(api_token) => Ok(Json(ApiTokenCreatedOutDTO {
+            token,
+            api_token: api_token.to_api_token_out_dto(),
+        })),
+        Err(_) => Err(status::Custom(
+            Status::InternalServerError,
+            "Failed to create API token.".to_string(),
+        )),
+    }
+}
+
+#[get("/api_tokens")]
+pub async fn get_api_tokens(
+    db: &rocket::State<PgPool>,
+    auth: AuthUser,
+) -> Result<Json<Vec<ApiTokenOutDTO>>, status::Custom<String>> {
+    match fetch_api_tokens_for_user(db, auth.0.id).await {
+        Ok(api_tokens) => Ok(Json(
+            api_tokens
+                .iter()
+                .map(|api_token| api_token.to_api_token_out_dto())
+                .collect(),
+        )),
+        Err(_) => Err(status::Custom(
+            Status::InternalServerError,
+            "Failed to fetch API tokens.".to_string(),
+        )),
+    }
+}
+
+#[delete("/api_tokens/<api_token_id_param>")]
+pub async fn delete_api_token_by_id(
+    db: &rocket::State<PgPool>,
+    auth: AuthUser,
+    api_token_id_param: UuidParam,
+) -> Result<status::NoContent, status::Custom<String>> {
+    match delete_api_token(db, auth.0.id, api_token_id_param.0).await {
+        Ok(0) => Err(status::Custom(
+            Status::NotFound,
+            "API token not found.".to_string(),
+        )),
+        Ok(_) => Ok(status::NoContent),
+        Err(_) => Err(status::Custom(
+            Status::InternalServerError,
+            "Failed to revoke API token.".to_string(),
+        )),
+    }
+}
+
+pub fn api_token_routes() -> Vec<Route> {
+    routes![post_api_token, get_api_tokens, delete_api_token_by_id]
+}
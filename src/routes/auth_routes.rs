@@ -0,0 +1,194 @@
+use crate::auth::{
+    hash_password, session_id_from_cookies, session_ttl, set_session_cookie, verify_password,
+    AuthUser, DUMMY_PASSWORD_HASH, MIN_PASSWORD_LENGTH, SESSION_COOKIE,
+};
+use crate::dtos::auth_dtos::{LoginInDTO, RegisterInDTO};
+use crate::dtos::user_dtos::UserOutDTO;
+use crate::operations::admin_ops::is_suspended;
+use crate::operations::session_ops::{create_session, delete_session};
+use crate::operations::two_factor_ops::find_totp_state;
+use crate::operations::user_ops::{find_password_hash, find_user_by_email, register_user};
+use crate::routes::two_factor_routes::second_factor_accepted;
+use rocket::http::{Cookie, CookieJar, Status};
+use rocket::response::status;
+use rocket::serde::json::Json;
+use rocket::{get, post, routes, Route};
+use sqlx::PgPool;
+
+#[post("/auth/register", data = "<register_in>")]
+pub async fn register(
+    db: &rocket::State<PgPool>,
+    cookies: &CookieJar<'_>,
+    register_in: Json<RegisterInDTO>,
+) -> Result<Json<UserOutDTO>, status::Custom<String>> {
+    if register_in.password.chars().count() < MIN_PASSWORD_LENGTH {
+        return Err(status::Custom(
+            Status::BadRequest,
+            format!(
+                "Password must be at least {} characters long.",
+                MIN_PASSWORD_LENGTH
+            ),
+        ));
+    }
+
+    match find_user_by_email(db, &register_in.email).await {
+        Ok(None) => {}
+        Ok(Some(_)) => {
+            return Err(status::Custom(
+                Status::Conflict,
+                "Email is already registered.".to_string(),
+            ))
+        }
+        Err(_) => {
+            return Err(status::Custom(
+                Status::InternalServerError,
+                "Failed to register user.".to_string(),
+            ))
+        }
+    }
+
+    let registered = async {
+        let password_hash = hash_password(&register_in.password).ok()?;
+        let user = register_user(db, &register_in.0, &password_hash)
+            .await
+            .ok()?;
+        let session = create_session(db, user.id, session_ttl(), true)
+            .await
+            .ok()?;
+        Some((user, session))
+    };
+    match registered.await {
+        Some((user, session)) => {
+            set_session_cookie(cookies, session.id);
+            Ok(Json(user.to_user_out_dto()))
+        }
+        None => Err(status::Custom(
+            Status::InternalServerError,
+            "Failed to register user.".to_string(),
+        )),
+    }
+}
+
+#[post("/auth/login", data = "<login_in>")]
+pub async fn login(
+    db: &rocket::State<PgPool>,
+    cookies: &CookieJar<'_>,
+    login_in: Json<LoginInDTO>,
+) -> Result<Json<UserOutDTO>, status::Custom<String>> {
+    let credentials = async {
+        let user = match find_user_by_email(db, &login_in.email).await? {
+            Some(user) => user,
+            None => return Ok(None),
+        };
+        let password_hash = find_password_hash(db, user.id).await?;
+        Ok::<_, sqlx::Error>(password_hash.map(|hash| (user, hash)))
+    };
+
+    let user = match credentials.await {
+        Ok(Some((user, hash))) if verify_password(&login_in.password, &hash) => user,
+        Ok(found) => {
+            if found.is_none() {
+                verify_password(&login_in.password, DUMMY_PASSWORD_HASH);
+            }
+            return Err(status::Custom(
+                Status::Unauthorized,
+                "Invalid email or password.".to_string(),
+            ));
+        }
+        Err(_) => {
+            return Err(status::Custom(
+                Status::InternalServerError,
+                "Failed to log in.".to_string(),
+            ))
+        }
+    };
+
+    match is_suspended(db, user.id).await {
+        Ok(false) => {}
+        Ok(true) => {
+            return Err(status::Custom(
+                Status::Forbidden,
+                "Account is suspended.".to_string(),
+            ))
+        }
+        Err(_) => {
+            return Err(status::Custom(
+                Status::InternalServerError,
+                "Failed to log in.".to_string(),
+            ))
+        }
+    }
+
+    let two_factor_enabled = match find_totp_state(db, user.id).await {
+        Ok(state) => state.is_some_and(|state| state.totp_enabled),
+        Err(_) => {
+            return Err(status::Custom(
+                Status::InternalServerError,
+                "Failed to log in.".to_string(),
+            ))
+        }
+    };
+    if two_factor_enabled {
+        let totp_code = login_in.totp_code.as_deref();
+        let recovery_code = login_in.recovery_code.as_deref();
+        if totp_code.is_none() && recovery_code.is_none() {
+            return Err(status::Custom(
+                Status::Unauthorized,
+                "Two-factor code required.".to_string(),
+            ));
+        }
+        match second_factor_accepted(db, user.id, totp_code, recovery_code).await {
+            Ok(true) => {}
+            Ok(false) => {
+                return Err(status::Custom(
+                    Status::Unauthorized,
+                    "Invalid two-factor code.".to_string(),
+                ))
+            }
+            Err(_) => {
+                return Err(status::Custom(
+                    Status::InternalServerError,
+                    "Failed to log in.".to_string(),
+                ))
+            }
+        }
+    }
+
+    match create_session(db, user.id, session_ttl(), true).await {
+        Ok(session) => {
+            set_session_cookie(cookies, session.id);
+            Ok(Json(user.to_user_out_dto()))
+        }
+        Err(_) => Err(status::Custom(
+            Status::InternalServerError,
+            "Failed to log in.".to_string(),
+        )),
+    }
+}
+
+#[post("/auth/logout")]
+pub async fn logout(
+    db: &rocket::State<PgPool>,
+    cookies: &CookieJar<'_>,
+) -> Result<status::NoContent, status::Custom<String>> {
+    if let Some(session_id) = session_id_from_cookies(cookies) {
+        if delete_session(db, session_id).await.is_err() {
+            return Err(status::Custom(
+                Status::InternalServerError,
+                "Failed to log out.".to_string(),
+            ));
+        }
+    }
+    cookies.remove_private(Cookie::named(SESSION_COOKIE));
+
+    Ok(status::NoContent)
+}
+
+#[get("/auth/me")]
+pub async fn me(auth: AuthUser) -> Json<UserOutDTO> {
+    Json(auth.0.to_user_out_dto())
+}
+
+pub fn auth_routes() -> Vec<Route> {
+    routes![register, login, logout, me]
+}
@@ -0,0 +1,63 @@
+use crate::auth::AuthUser;
+use crate::dtos::badge_dtos::{BadgeOutDTO, UserBadgesOutDTO};
+use crate::operations::badge_ops::*;
+use crate::uuid_param::UuidParam;
+use rocket::http::Status;
+use rocket::response::status;
+use rocket::serde::json::Json;
+use rocket::{get, routes, Route};
+use sqlx::PgPool;
+
+// Badges are private to their owner; anyone else gets the same 404 as for an
+// unknown user. They are served as the badge evaluator job last stored them;
+// a user it hasn't reached yet, such as everyone right after launch, is
+// evaluated on the spot.
+#[get("/users/<user_id_param>/badges")]
+pub async fn get_user_badges(
+    db: &rocket::State<PgPool>,
+    auth: AuthUser,
+    user_id_param: UuidParam,
+) -> Result<Json<UserBadgesOutDTO>, status::Custom<String>> {
+    let user_id = user_id_param.0;
+    if user_id != auth.0.id {
+        return Err(status::Custom(
+            Status::NotFound,
+            "User not found.".to_string(),
+        ));
+    }
+
+    let fetched = async {
+        let mut badges = fetch_user_badges(db, user_id).await?;
+        if badges.is_empty() {
+            evaluate_user_badges(db, user_id).await?;
+            badges = fetch_user_badges(db, user_id).await?;
+        }
+        let streaks = fetch_user_streaks(db, user_id).await?;
+        Ok::<_, sqlx::Error>((badges, streaks))
+    };
+
+    match fetched.await {
+        Ok((badges, streaks)) => {
+            let (earned, in_progress): (Vec<BadgeOutDTO>, Vec<BadgeOutDTO>) = badges
+                .iter()
+                .filter_map(|badge| badge.to_badge_out_dto())
+                .partition(|badge| badge.earned);
+            Ok(Json(UserBadgesOutDTO {
+                earned,
+                in_progress,
+                streaks: streaks
+                    .iter()
+                    .map(|streak| streak.to_streak_out_dto())
+                    .collect(),
+            }))
+        }
+        Err(_) => Err(status::Custom(
+            Status::InternalServerError,
+            "Failed to fetch badges.".to_string(),
+        )),
+    }
+}
+
+pub fn badge_routes() -> Vec<Route> {
+    routes![get_user_badges]
+}
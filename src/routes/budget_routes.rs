@@ -1,17 +1,23 @@
+use crate::auth::AuthUser;
 use crate::dtos::budget_dtos::{BudgetInDTO, BudgetOutDTO};
 use crate::operations::budget_ops::*;
+use crate::operations::ownership_ops::{can_edit_references, Owned};
+use crate::operations::user_preferences_ops::find_user_preferences;
+use crate::routes::household_routes::record_access_error;
 use crate::uuid_param::UuidParam;
 use rocket::http::Status;
 use rocket::response::status;
 use rocket::serde::json::Json;
 use rocket::{delete, get, patch, post, routes, Route};
 use sqlx::PgPool;
+use uuid::Uuid;
 
 #[get("/budgets")]
 pub async fn get_all_budgets(
     db: &rocket::State<PgPool>,
+    auth: AuthUser,
 ) -> Result<Json<Vec<BudgetOutDTO>>, status::Custom<String>> {
-    match fetch_all_budgets(db).await {
+    match fetch_all_budgets(db, auth.0.id).await {
         Ok(budgets) => {
             let budgets_dto: Vec<BudgetOutDTO> = budgets
                 .into_iter()
@@ -29,10 +35,11 @@ pub async fn get_all_budgets(
 #[get("/budgets/<budget_id_param>")]
 pub async fn get_budget_by_id(
     db: &rocket::State<PgPool>,
+    auth: AuthUser,
     budget_id_param: UuidParam,
 ) -> Result<Json<BudgetOutDTO>, status::Custom<String>> {
     let budget_id = budget_id_param.0;
-    match find_budget_by_id(db, budget_id).await {
+    match find_budget_by_id(db, auth.0.id, budget_id).await {
         Ok(Some(budget)) => Ok(Json(budget.to_budget_out_dto())),
         Ok(None) => Err(status::Custom(
             Status::NotFound,
@@ -48,9 +55,40 @@ pub async fn get_budget_by_id(
 #[post("/budgets", data = "<budget_in>")]
 pub async fn post_budget(
     db: &rocket::State<PgPool>,
+    auth: AuthUser,
     budget_in: Json<BudgetInDTO>,
 ) -> Result<Json<BudgetOutDTO>, status::Custom<String>> {
-    match create_budget(db.inner(), &budget_in.0).await {
+    let currency = budget_currency(db, auth.0.id, None).await.map_err(|_| {
+        status::Custom(
+            Status::InternalServerError,
+            "Failed to create budget.".to_string(),
+        )
+    })?;
+    if let Err(message) = budget_in.validate(&currency) {
+        return Err(status::Custom(Status::BadRequest, message.to_string()));
+    }
+    let references: Vec<(Owned, Uuid)> = budget_in
+        .category_id
+        .map(|category_id| (Owned::Category, category_id))
+        .into_iter()
+        .collect();
+    match can_edit_references(db, auth.0.id, &references).await {
+        Ok(true) => {}
+        Ok(false) => {
+            return Err(status::Custom(
+                Status::BadRequest,
+                "Category not found for this user.".to_string(),
+            ))
+        }
+        Err(_) => {
+            return Err(status::Custom(
+                Status::InternalServerError,
+                "Failed to create budget.".to_string(),
+            ))
+        }
+    }
+
+    match create_budget(db.inner(), auth.0.id, &budget_in.0).await {
         Ok(budget) => Ok(Json(budget.to_budget_out_dto())),
         Err(_) => Err(status::Custom(
             Status::InternalServerError,
@@ -62,12 +100,48 @@ pub async fn post_budget(
 #[patch("/budgets/<budget_id_param>", data = "<budget_update>")]
 pub async fn update_budget(
     db: &rocket::State<PgPool>,
+    auth: AuthUser,
     budget_id_param: UuidParam,
     budget_update: Json<BudgetInDTO>,
 ) -> Result<Json<BudgetOutDTO>, status::Custom<String>> {
     let budget_id = budget_id_param.0;
-    match update_budget_in_db(db, budget_id, &budget_update.0).await {
-        Ok(budget) => Ok(Json(budget.to_budget_out_dto())),
+    let currency = budget_currency(db, auth.0.id, Some(budget_id))
+        .await
+        .map_err(|_| {
+            status::Custom(
+                Status::InternalServerError,
+                "Failed to update budget.".to_string(),
+            )
+        })?;
+    if let Err(message) = budget_update.validate(&currency) {
+        return Err(status::Custom(Status::BadRequest, message.to_string()));
+    }
+    let references: Vec<(Owned, Uuid)> = budget_update
+        .category_id
+        .map(|category_id| (Owned::Category, category_id))
+        .into_iter()
+        .collect();
+    match can_edit_references(db, auth.0.id, &references).await {
+        Ok(true) => {}
+        Ok(false) => {
+            return Err(status::Custom(
+                Status::BadRequest,
+                "Category not found for this user.".to_string(),
+            ))
+        }
+        Err(_) => {
+            return Err(status::Custom(
+                Status::InternalServerError,
+                "Failed to update budget.".to_string(),
+            ))
+        }
+    }
+
+    match update_budget_in_db(db, auth.0.id, budget_id, &budget_update.0).await {
+        Ok(Some(budget)) => Ok(Json(budget.to_budget_out_dto())),
+        Ok(None) => {
+            Err(record_access_error(db, auth.0.id, Owned::Budget, budget_id, "Budget").await)
+        }
         Err(_) => Err(status::Custom(
             Status::InternalServerError,
             "Failed to update budget.".to_string(),
@@ -75,13 +149,30 @@ pub async fn update_budget(
     }
 }
 
+// The base currency of the budget's owner, or of the user for a new budget.
+async fn budget_currency(
+    db: &PgPool,
+    user_id: Uuid,
+    budget_id: Option<Uuid>,
+) -> Result<String, sqlx::Error> {
+    let owner = match budget_id {
+        Some(budget_id) => find_budget_by_id(db, user_id, budget_id)
+            .await?
+            .map_or(user_id, |budget| budget.user_id),
+        None => user_id,
+    };
+    Ok(find_user_preferences(db, owner).await?.base_currency)
+}
+
 #[delete("/budgets/<budget_id_param>")]
 pub async fn delete_budget_by_id(
     db: &rocket::State<PgPool>,
+    auth: AuthUser,
     budget_id_param: UuidParam,
 ) -> Result<status::NoContent, status::Custom<String>> {
     let budget_id = budget_id_param.0;
-    match delete_budget(db, budget_id).await {
+    match delete_budget(db, auth.0.id, budget_id).await {
+        Ok(0) => Err(record_access_error(db, auth.0.id, Owned::Budget, budget_id, "Budget").await),
         Ok(_) => Ok(status::NoContent),
         Err(_) => Err(status::Custom(
             Status::InternalServerError,
@@ -1,6 +1,9 @@
+use crate::auth::AuthUser;
 use crate::dtos::category_dtos::{CategoryInDTO, CategoryOutDTO};
 use crate::operations::category_ops::*;
 // use personal_finance_tracker::dtos::category_dtos::{CategoryInDTO, CategoryOutDTO};
+use crate::operations::ownership_ops::Owned;
+use crate::routes::household_routes::record_access_error;
 use crate::uuid_param::UuidParam;
 use rocket::http::Status;
 use rocket::response::status;
@@ -11,8 +14,9 @@ use sqlx::PgPool;
 #[get("/categories")]
 pub async fn get_all_categories(
     db: &rocket::State<PgPool>,
+    auth: AuthUser,
 ) -> Result<Json<Vec<CategoryOutDTO>>, status::Custom<String>> {
-    match fetch_all_categories(db).await {
+    match fetch_all_categories(db, auth.0.id).await {
         Ok(categories) => {
             let categories_dto: Vec<CategoryOutDTO> = categories
                 .into_iter()
@@ -30,10 +34,11 @@ pub async fn get_all_categories(
 #[get("/categories/<category_id_param>")]
 pub async fn get_category_by_id(
     db: &rocket::State<PgPool>,
+    auth: AuthUser,
     category_id_param: UuidParam,
 ) -> Result<Json<CategoryOutDTO>, status::Custom<String>> {
     let category_id = category_id_param.0;
-    match find_category_by_id(db, category_id).await {
+    match find_category_by_id(db, auth.0.id, category_id).await {
         Ok(Some(category)) => Ok(Json(category.to_category_out_dto())),
         Ok(None) => Err(status::Custom(
             Status::NotFound,
@@ -49,9 +54,10 @@ pub async fn get_category_by_id(
 #[post("/categories", data = "<category_in>")]
 pub async fn post_category(
     db: &rocket::State<PgPool>,
+    auth: AuthUser,
     category_in: Json<CategoryInDTO>,
 ) -> Result<Json<CategoryOutDTO>, status::Custom<String>> {
-    match create_category(db.inner(), &category_in.0).await {
+    match create_category(db.inner(), auth.0.id, &category_in.0).await {
         Ok(category) => Ok(Json(category.to_category_out_dto())),
         Err(_) => Err(status::Custom(
             Status::InternalServerError,
@@ -63,12 +69,16 @@ pub async fn post_category(
 #[patch("/categories/<category_id_param>", data = "<category_update>")]
 pub async fn update_category(
     db: &rocket::State<PgPool>,
+    auth: AuthUser,
     category_id_param: UuidParam,
     category_update: Json<CategoryInDTO>,
 ) -> Result<Json<CategoryOutDTO>, status::Custom<String>> {
     let category_id = category_id_param.0;
-    match update_category_in_db(db, category_id, &category_update.0).await {
-        Ok(category) => Ok(Json(category.to_category_out_dto())),
+    match update_category_in_db(db, auth.0.id, category_id, &category_update.0).await {
+        Ok(Some(category)) => Ok(Json(category.to_category_out_dto())),
+        Ok(None) => {
+            Err(record_access_error(db, auth.0.id, Owned::Category, category_id, "Category").await)
+        }
         Err(_) => Err(status::Custom(
             Status::InternalServerError,
             "Failed to update category.".to_string(),
@@ -79,10 +89,14 @@ pub async fn update_category(
 #[delete("/categories/<category_id_param>")]
 pub async fn delete_category_by_id(
     db: &rocket::State<PgPool>,
+    auth: AuthUser,
     category_id_param: UuidParam,
 ) -> Result<status::NoContent, status::Custom<String>> {
     let category_id = category_id_param.0;
-    match delete_category(db, category_id).await {
+    match delete_category(db, auth.0.id, category_id).await {
+        Ok(0) => {
+            Err(record_access_error(db, auth.0.id, Owned::Category, category_id, "Category").await)
+        }
         Ok(_) => Ok(status::NoContent),
         Err(_) => Err(status::Custom(
             Status::InternalServerError,
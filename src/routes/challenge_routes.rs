@@ -0,0 +1,162 @@
+use crate::auth::AuthUser;
+use crate::challenges::build_report;
+use crate::dtos::challenge_dtos::{ChallengeInDTO, ChallengeOutDTO, ChallengeReportOutDTO};
+use crate::operations::challenge_ops::*;
+use crate::operations::exchange_rate_ops::fetch_rate_table;
+use crate::operations::ownership_ops::{can_edit_references, Owned};
+use crate::operations::user_preferences_ops::find_user_preferences;
+use crate::uuid_param::UuidParam;
+use rocket::http::Status;
+use rocket::response::status;
+use rocket::serde::json::Json;
+use rocket::{delete, get, post, routes, Route};
+use sqlx::PgPool;
+
+#[post("/challenges", data = "<challenge_in>")]
+pub async fn post_challenge(
+    db: &rocket::State<PgPool>,
+    auth: AuthUser,
+    challenge_in: Json<ChallengeInDTO>,
+) -> Result<Json<ChallengeOutDTO>, status::Custom<String>> {
+    if let Err(message) = challenge_in.validate() {
+        return Err(status::Custom(Status::BadRequest, message.to_string()));
+    }
+
+    let mut references = vec![(Owned::SavingGoal, challenge_in.goal_id)];
+    references.extend(
+        challenge_in
+            .category_ids
+            .iter()
+            .map(|category_id| (Owned::Category, *category_id)),
+    );
+    match can_edit_references(db, auth.0.id, &references).await {
+        Ok(true) => {}
+        Ok(false) => {
+            return Err(status::Custom(
+                Status::BadRequest,
+                "Saving goal or category not found for this user.".to_string(),
+            ))
+        }
+        Err(_) => {
+            return Err(status::Custom(
+                Status::InternalServerError,
+                "Failed to fetch saving_goal.".to_string(),
+            ))
+        }
+    }
+
+    match create_challenge(db.inner(), auth.0.id, &challenge_in.0).await {
+        Ok(challenge) => Ok(Json(challenge.to_challenge_out_dto())),
+        Err(_) => Err(status::Custom(
+            Status::InternalServerError,
+            "Failed to create challenge.".to_string(),
+        )),
+    }
+}
+
+#[get("/challenges")]
+pub async fn get_user_challenges(
+    db: &rocket::State<PgPool>,
+    auth: AuthUser,
+) -> Result<Json<Vec<ChallengeOutDTO>>, status::Custom<String>> {
+    match fetch_challenges_for_user(db, auth.0.id).await {
+        Ok(challenges) => {
+            let challenges_dto: Vec<ChallengeOutDTO> = challenges
+                .into_iter()
+                .map(|challenge| challenge.to_challenge_out_dto())
+                .collect();
+            Ok(Json(challenges_dto))
+        }
+        Err(_) => Err(status::Custom(
+            Status::InternalServerError,
+            "Failed to fetch challenges.".to_string(),
+        )),
+    }
+}
+
+#[get("/challenges/<challenge_id_param>")]
+pub async fn get_challenge_report(
+    db: &rocket::State<PgPool>,
+    auth: AuthUser,
+    challenge_id_param: UuidParam,
+) -> Result<Json<ChallengeReportOutDTO>, status::Custom<String>> {
+    let challenge_id = challenge_id_param.0;
+    let challenge = match find_challenge_by_id(db, auth.0.id, challenge_id).await {
+        Ok(Some(challenge)) => challenge,
+        Ok(None) => {
+            return Err(status::Custom(
+                Status::NotFound,
+                "Challenge not found.".to_string(),
+            ))
+        }
+        Err(_) => {
+            return Err(status::Custom(
+                Status::InternalServerError,
+                "Failed to fetch challenge.".to_string(),
+            ))
+        }
+    };
+
+    let activity = async {
+        let preferences = find_user_preferences(db, auth.0.id).await?;
+        let (contributions, transactions) = fetch_challenge_activity(db, &challenge).await?;
+        let rates = fetch_rate_table(db, challenge.start_date, challenge.end_date).await?;
+        Ok::<_, sqlx::Error>((contributions, transactions, preferences, rates))
+    };
+    let (contributions, transactions, preferences, rates) = match activity.await {
+        Ok(activity) => activity,
+        Err(_) => {
+            return Err(status::Custom(
+                Status::InternalServerError,
+                "Failed to fetch challenge progress.".to_string(),
+            ))
+        }
+    };
+
+    // Targets are kept in the base currency, so spending and income in other
+    // currencies is converted into it.
+    match build_report(
+        &challenge,
+        &contributions,
+        &transactions,
+        &rates,
+        preferences.today(),
+        preferences.tz(),
+        &preferences.base_currency,
+    ) {
+        Ok(report) => Ok(Json(report)),
+        Err(missing) => Err(status::Custom(
+            Status::UnprocessableEntity,
+            missing.to_string(),
+        )),
+    }
+}
+
+#[delete("/challenges/<challenge_id_param>")]
+pub async fn delete_challenge_by_id(
+    db: &rocket::State<PgPool>,
+    auth: AuthUser,
+    challenge_id_param: UuidParam,
+) -> Result<status::NoContent, status::Custom<String>> {
+    let challenge_id = challenge_id_param.0;
+    match delete_challenge(db, auth.0.id, challenge_id).await {
+        Ok(0) => Err(status::Custom(
+            Status::NotFound,
+            "Challenge not found.".to_string(),
+        )),
+        Ok(_) => Ok(status::NoContent),
+        Err(_) => Err(status::Custom(
+            Status::InternalServerError,
+            "Failed to delete challenge.".to_string(),
+        )),
+    }
+}
+
+pub fn challenge_routes() -> Vec<Route> {
+    routes![
+        post_challenge,
+        get_user_challenges,
+        get_challenge_report,
+        delete_challenge_by_id
+    ]
+}
@@ -0,0 +1,159 @@
+use crate::auth::{AdminUser, AuthUser};
+use crate::date_param::DateParam;
+use crate::dtos::exchange_rate_dtos::{
+    ExchangeRateImportOutDTO, ExchangeRateOutDTO, ExchangeRateQuoteOutDTO,
+};
+use crate::exchange_rates::{import::parse_rates, MissingRate};
+use crate::money::is_currency_code;
+use crate::operations::exchange_rate_ops::*;
+use crate::operations::user_preferences_ops::find_user_preferences;
+use rocket::data::{Data, ToByteUnit};
+use rocket::http::Status;
+use rocket::response::status;
+use rocket::serde::json::Json;
+use rocket::{get, post, routes, Route};
+use sqlx::PgPool;
+
+// The full ECB history since 1999 is a little over 1.5 MiB as CSV.
+const MAX_IMPORT_MEBIBYTES: u8 = 8;
+
+// Imports an ECB XML or CSV file. ECB rates are quoted against the euro;
+// files from other sources can name their base currency.
+#[post("/admin/exchange_rates?<base>", data = "<file>")]
+pub async fn post_exchange_rates(
+    db: &rocket::State<PgPool>,
+    _admin: AdminUser,
+    base: Option<String>,
+    file: Data<'_>,
+) -> Result<Json<ExchangeRateImportOutDTO>, status::Custom<String>> {
+    let base = base.unwrap_or_else(|| "EUR".to_string());
+    if !is_currency_code(&base) {
+        return Err(status::Custom(
+            Status::BadRequest,
+            "Base currency must be a three letter ISO 4217 code.".to_string(),
+        ));
+    }
+
+    let body = match file
+        .open(MAX_IMPORT_MEBIBYTES.mebibytes())
+        .into_string()
+        .await
+    {
+        Ok(body) if body.is_complete() => body.into_inner(),
+        Ok(_) => {
+            return Err(status::Custom(
+                Status::PayloadTooLarge,
+                "Exchange rate file is too large.".to_string(),
+            ))
+        }
+        Err(_) => {
+            return Err(status::Custom(
+                Status::BadRequest,
+                "Exchange rate file must be UTF-8 text.".to_string(),
+            ))
+        }
+    };
+    let rates = match parse_rates(&body) {
+        Ok(rates) => rates,
+        Err(message) => return Err(status::Custom(Status::BadRequest, message.to_string())),
+    };
+
+    match import_exchange_rates(db, &base, &rates).await {
+        Ok(imported) => Ok(Json(ExchangeRateImportOutDTO {
+            imported,
+            first_date: rates.iter().map(|rate| rate.date).min(),
+            last_date: rates.iter().map(|rate| rate.date).max(),
+        })),
+        Err(_) => Err(status::Custom(
+            Status::InternalServerError,
+            "Failed to import exchange rates.".to_string(),
+        )),
+    }
+}
+
+// The rates published on a day, today in the user's timezone by default.
+#[get("/exchange_rates?<date>")]
+pub async fn get_exchange_rates(
+    db: &rocket::State<PgPool>,
+    auth: AuthUser,
+    date: Option<DateParam>,
+) -> Result<Json<Vec<ExchangeRateOutDTO>>, status::Custom<String>> {
+    let rates = async {
+        let date = match date {
+            Some(date) => date.0,
+            None => find_user_preferences(db, auth.0.id).await?.today(),
+        };
+        fetch_exchange_rates_on(db, date).await
+    };
+
+    match rates.await {
+        Ok(rates) => Ok(Json(
+            rates
+                .iter()
+                .map(|rate| rate.to_exchange_rate_out_dto())
+                .collect(),
+        )),
+        Err(_) => Err(status::Custom(
+            Status::InternalServerError,
+            "Failed to fetch exchange rates.".to_string(),
+        )),
+    }
+}
+
+#[get("/exchange_rates/<from>/<to>?<date>")]
+pub async fn get_exchange_rate_quote(
+    db: &rocket::State<PgPool>,
+    auth: AuthUser,
+    from: &str,
+    to: &str,
+    date: Option<DateParam>,
+) -> Result<Json<ExchangeRateQuoteOutDTO>, status::Custom<String>> {
+    if !is_currency_code(from) || !is_currency_code(to) {
+        return Err(status::Custom(
+            Status::BadRequest,
+            "Currencies must be three letter ISO 4217 codes.".to_string(),
+        ));
+    }
+
+    let table = async {
+        let date = match date {
+            Some(date) => date.0,
+            None => find_user_preferences(db, auth.0.id).await?.today(),
+        };
+        Ok::<_, sqlx::Error>((date, fetch_rate_table(db, date, date).await?))
+    };
+    let (date, table) = match table.await {
+        Ok(table) => table,
+        Err(_) => {
+            return Err(status::Custom(
+                Status::InternalServerError,
+                "Failed to fetch exchange rates.".to_string(),
+            ))
+        }
+    };
+
+    match table.rate(from, to, date) {
+        Some(rate) => Ok(Json(ExchangeRateQuoteOutDTO {
+            from: from.to_string(),
+            to: to.to_string(),
+            date,
+            rate,
+        })),
+        None => {
+            let missing = MissingRate {
+                from: from.to_string(),
+                to: to.to_string(),
+                date,
+            };
+            Err(status::Custom(Status::NotFound, missing.to_string()))
+        }
+    }
+}
+
+pub fn exchange_rate_routes() -> Vec<Route> {
+    routes![
+        post_exchange_rates,
+        get_exchange_rates,
+        get_exchange_rate_quote
+    ]
+}
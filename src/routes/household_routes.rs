@@ -0,0 +1,361 @@
+use crate::auth::AuthUser;
+use crate::dtos::household_dtos::{
+    HouseholdDetailOutDTO, HouseholdInDTO, HouseholdMemberInDTO, HouseholdMemberOutDTO,
+    HouseholdOutDTO, HouseholdRoleInDTO, HouseholdShareInDTO,
+};
+use crate::enums::custom_enums::{HouseholdRole, SharedRecordType};
+use crate::operations::household_ops::*;
+use crate::operations::ownership_ops::{can_view, Owned};
+use crate::operations::user_ops::find_user_by_email;
+use crate::uuid_param::UuidParam;
+use rocket::http::Status;
+use rocket::response::status;
+use rocket::serde::json::Json;
+use rocket::{delete, get, patch, post, routes, Route};
+use sqlx::PgPool;
+use std::str::FromStr;
+use uuid::Uuid;
+
+// The error for an update or delete of a shareable record that matched
+// nothing: household members who can only see the record get 403, everyone
+// else 404.
+pub async fn record_access_error(
+    db: &PgPool,
+    user_id: Uuid,
+    owned: Owned,
+    id: Uuid,
+    name: &str,
+) -> status::Custom<String> {
+    match can_view(db, user_id, owned, id).await {
+        Ok(true) => status::Custom(
+            Status::Forbidden,
+            format!("Not allowed to change this {}.", name.to_lowercase()),
+        ),
+        Ok(false) => status::Custom(Status::NotFound, format!("{} not found.", name)),
+        Err(_) => status::Custom(
+            Status::InternalServerError,
+            format!("Failed to check {} access.", name.to_lowercase()),
+        ),
+    }
+}
+
+// The caller's role in the household; non-members are told it doesn't exist.
+async fn member_role(
+    db: &PgPool,
+    household_id: Uuid,
+    user_id: Uuid,
+) -> Result<HouseholdRole, status::Custom<String>> {
+    match find_member_role(db, household_id, user_id).await {
+        Ok(Some(role)) => Ok(role),
+        Ok(None) => Err(status::Custom(
+            Status::NotFound,
+            "Household not found.".to_string(),
+        )),
+        Err(_) => Err(status::Custom(
+            Status::InternalServerError,
+            "Failed to fetch household.".to_string(),
+        )),
+    }
+}
+
+async fn require_owner(
+    db: &PgPool,
+    household_id: Uuid,
+    user_id: Uuid,
+) -> Result<(), status::Custom<String>> {
+    match member_role(db, household_id, user_id).await? {
+        HouseholdRole::Owner => Ok(()),
+        _ => Err(status::Custom(
+            Status::Forbidden,
+            "Only household owners can do this.".to_string(),
+        )),
+    }
+}
+
+fn membership_result(
+    change: Result<MembershipChange, sqlx::Error>,
+    action: &str,
+) -> Result<(), status::Custom<String>> {
+    match change {
+        Ok(MembershipChange::Done) => Ok(()),
+        Ok(MembershipChange::NotMember) => Err(status::Custom(
+            Status::NotFound,
+            "Member not found.".to_string(),
+        )),
+        Ok(MembershipChange::LastOwner) => Err(status::Custom(
+            Status::Conflict,
+            "A household needs at least one owner.".to_string(),
+        )),
+        Err(_) => Err(status::Custom(
+            Status::InternalServerError,
+            format!("Failed to {} member.", action),
+        )),
+    }
+}
+
+#[post("/households", data = "<household_in>")]
+pub async fn post_household(
+    db: &rocket::State<PgPool>,
+    auth: AuthUser,
+    household_in: Json<HouseholdInDTO>,
+) -> Result<Json<HouseholdOutDTO>, status::Custom<String>> {
+    if household_in.name.trim().is_empty() {
+        return Err(status::Custom(
+            Status::BadRequest,
+            "Household name must not be empty.".to_string(),
+        ));
+    }
+
+    match create_household(db, auth.0.id, &household_in.0).await {
+        Ok(household) => Ok(Json(household.to_household_out_dto())),
+        Err(_) => Err(status::Custom(
+            Status::InternalServerError,
+            "Failed to create household.".to_string(),
+        )),
+    }
+}
+
+#[get("/households")]
+pub async fn get_households(
+    db: &rocket::State<PgPool>,
+    auth: AuthUser,
+) -> Result<Json<Vec<HouseholdOutDTO>>, status::Custom<String>> {
+    match fetch_households_for_user(db, auth.0.id).await {
+        Ok(households) => Ok(Json(
+            households
+                .iter()
+                .map(|household| household.to_household_out_dto())
+                .collect(),
+        )),
+        Err(_) => Err(status::Custom(
+            Status::InternalServerError,
+            "Failed to fetch households.".to_string(),
+        )),
+    }
+}
+
+#[get("/households/<household_id_param>")]
+pub async fn get_household_by_id(
+    db: &rocket::State<PgPool>,
+    auth: AuthUser,
+    household_id_param: UuidParam,
+) -> Result<Json<HouseholdDetailOutDTO>, status::Custom<String>> {
+    let household_id = household_id_param.0;
+    member_role(db, household_id, auth.0.id).await?;
+
+    let details = async {
+        let household = find_household_by_id(db, household_id).await?;
+        let members = fetch_household_members(db, household_id).await?;
+        Ok::<_, sqlx::Error>(household.map(|household| (household, members)))
+    };
+
+    match details.await {
+        Ok(Some((household, members))) => Ok(Json(HouseholdDetailOutDTO {
+            household: household.to_household_out_dto(),
+            members: members
+                .iter()
+                .map(|member| member.to_household_member_out_dto())
+                .collect(),
+        })),
+        Ok(None) => Err(status::Custom(
+            Status::NotFound,
+            "Household not found.".to_string(),
+        )),
+        Err(_) => Err(status::Custom(
+            Status::InternalServerError,
+            "Failed to fetch household.".to_string(),
+        )),
+    }
+}
+
+#[delete("/households/<household_id_param>")]
+pub async fn delete_household_by_id(
+    db: &rocket::State<PgPool>,
+    auth: AuthUser,
+    household_id_param: UuidParam,
+) -> Result<status::NoContent, status::Custom<String>> {
+    let household_id = household_id_param.0;
+    require_owner(db, household_id, auth.0.id).await?;
+
+    match delete_household(db, household_id).await {
+        Ok(0) => Err(status::Custom(
+            Status::NotFound,
+            "Household not found.".to_string(),
+        )),
+        Ok(_) => Ok(status::NoContent),
+        Err(_) => Err(status::Custom(
+            Status::InternalServerError,
+            "Failed to delete household.".to_string(),
+        )),
+    }
+}
+
+#[post("/households/<household_id_param>/members", data = "<member_in>")]
+pub async fn post_household_member(
+    db: &rocket::State<PgPool>,
+    auth: AuthUser,
+    household_id_param: UuidParam,
+    member_in: Json<HouseholdMemberInDTO>,
+) -> Result<Json<Vec<HouseholdMemberOutDTO>>, status::Custom<String>> {
+    let household_id = household_id_param.0;
+    require_owner(db, household_id, auth.0.id).await?;
+
+    let internal_error = || {
+        status::Custom(
+            Status::InternalServerError,
+            "Failed to add member.".to_string(),
+        )
+    };
+
+    let user = find_user_by_email(db, member_in.email.trim())
+        .await
+        .map_err(|_| internal_error())?
+        .ok_or_else(|| status::Custom(Status::NotFound, "User not found.".to_string()))?;
+    let added = add_household_member(db, household_id, user.id, member_in.role)
+        .await
+        .map_err(|_| internal_error())?;
+    if !added {
+        return Err(status::Custom(
+            Status::Conflict,
+            "User is already a member of this household.".to_string(),
+        ));
+    }
+
+    match fetch_household_members(db, household_id).await {
+        Ok(members) => Ok(Json(
+            members
+                .iter()
+                .map(|member| member.to_household_member_out_dto())
+                .collect(),
+        )),
+        Err(_) => Err(internal_error()),
+    }
+}
+
+#[patch(
+    "/households/<household_id_param>/members/<user_id_param>",
+    data = "<role_in>"
+)]
+pub async fn patch_household_member(
+    db: &rocket::State<PgPool>,
+    auth: AuthUser,
+    household_id_param: UuidParam,
+    user_id_param: UuidParam,
+    role_in: Json<HouseholdRoleInDTO>,
+) -> Result<status::NoContent, status::Custom<String>> {
+    let household_id = household_id_param.0;
+    require_owner(db, household_id, auth.0.id).await?;
+
+    membership_result(
+        update_member_role(db, household_id, user_id_param.0, role_in.role).await,
+        "update",
+    )?;
+
+    Ok(status::NoContent)
+}
+
+// Owners can remove anyone; every member can leave on their own.
+#[delete("/households/<household_id_param>/members/<user_id_param>")]
+pub async fn delete_household_member(
+    db: &rocket::State<PgPool>,
+    auth: AuthUser,
+    household_id_param: UuidParam,
+    user_id_param: UuidParam,
+) -> Result<status::NoContent, status::Custom<String>> {
+    let household_id = household_id_param.0;
+    let user_id = user_id_param.0;
+    if user_id == auth.0.id {
+        member_role(db, household_id, auth.0.id).await?;
+    } else {
+        require_owner(db, household_id, auth.0.id).await?;
+    }
+
+    membership_result(
+        remove_household_member(db, household_id, user_id).await,
+        "remove",
+    )?;
+
+    Ok(status::NoContent)
+}
+
+#[post("/households/<household_id_param>/shares", data = "<share_in>")]
+pub async fn post_household_share(
+    db: &rocket::State<PgPool>,
+    auth: AuthUser,
+    household_id_param: UuidParam,
+    share_in: Json<HouseholdShareInDTO>,
+) -> Result<status::NoContent, status::Custom<String>> {
+    let household_id = household_id_param.0;
+    if member_role(db, household_id, auth.0.id).await? == HouseholdRole::Viewer {
+        return Err(status::Custom(
+            Status::Forbidden,
+            "Viewers cannot share records.".to_string(),
+        ));
+    }
+
+    let owned = Owned::from(share_in.record_type);
+    match share_record(db, auth.0.id, household_id, owned, share_in.record_id).await {
+        Ok(0) => Err(status::Custom(
+            Status::NotFound,
+            "Record not found.".to_string(),
+        )),
+        Ok(_) => Ok(status::NoContent),
+        Err(_) => Err(status::Custom(
+            Status::InternalServerError,
+            "Failed to share record.".to_string(),
+        )),
+    }
+}
+
+// The record's owner or a household owner can take a record out of the
+// household.
+#[delete("/households/<household_id_param>/shares/<record_type>/<record_id_param>")]
+pub async fn delete_household_share(
+    db: &rocket::State<PgPool>,
+    auth: AuthUser,
+    household_id_param: UuidParam,
+    record_type: &str,
+    record_id_param: UuidParam,
+) -> Result<status::NoContent, status::Custom<String>> {
+    let household_id = household_id_param.0;
+    let record_type = SharedRecordType::from_str(record_type)
+        .map_err(|e| status::Custom(Status::BadRequest, format!("{}.", e)))?;
+    let owner_id = match member_role(db, household_id, auth.0.id).await? {
+        HouseholdRole::Owner => None,
+        _ => Some(auth.0.id),
+    };
+
+    match unshare_record(
+        db,
+        owner_id,
+        household_id,
+        Owned::from(record_type),
+        record_id_param.0,
+    )
+    .await
+    {
+        Ok(0) => Err(status::Custom(
+            Status::NotFound,
+            "Shared record not found.".to_string(),
+        )),
+        Ok(_) => Ok(status::NoContent),
+        Err(_) => Err(status::Custom(
+            Status::InternalServerError,
+            "Failed to unshare record.".to_string(),
+        )),
+    }
+}
+
+pub fn household_routes() -> Vec<Route> {
+    routes![
+        post_household,
+        get_households,
+        get_household_by_id,
+        delete_household_by_id,
+        post_household_member,
+        patch_household_member,
+        delete_household_member,
+        post_household_share,
+        delete_household_share
+    ]
+}
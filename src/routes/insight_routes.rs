@@ -0,0 +1,67 @@
+use crate::auth::AuthUser;
+use crate::date_param::DateParam;
+use crate::dtos::insight_dtos::AnomalyReportOutDTO;
+use crate::insights::{detect_anomalies, BASELINE_DAYS};
+use crate::operations::category_ops::fetch_all_categories;
+use crate::operations::subscription_ops::fetch_charges_since;
+use crate::operations::user_preferences_ops::find_user_preferences;
+use chrono::Duration;
+use rocket::http::Status;
+use rocket::response::status;
+use rocket::serde::json::Json;
+use rocket::{get, routes, Route};
+use sqlx::PgPool;
+
+fn insight_failed() -> status::Custom<String> {
+    status::Custom(
+        Status::InternalServerError,
+        "Failed to look for anomalies.".to_string(),
+    )
+}
+
+// Unusual expenses over the last 30 days unless `from` and `to` say
+// otherwise.
+#[get("/insights/anomalies?<from>&<to>")]
+pub async fn get_anomalies(
+    db: &rocket::State<PgPool>,
+    auth: AuthUser,
+    from: Option<DateParam>,
+    to: Option<DateParam>,
+) -> Result<Json<AnomalyReportOutDTO>, status::Custom<String>> {
+    let preferences = find_user_preferences(db, auth.0.id)
+        .await
+        .map_err(|_| insight_failed())?;
+    let to = to.map_or(preferences.today(), |to| to.0);
+    let from = from.map_or(to - Duration::days(29), |from| from.0);
+    if from > to {
+        return Err(status::Custom(
+            Status::BadRequest,
+            "The period must start before it ends.".to_string(),
+        ));
+    }
+    if (to - from).num_days() >= 366 {
+        return Err(status::Custom(
+            Status::BadRequest,
+            "The period can be at most 366 days long.".to_string(),
+        ));
+    }
+
+    let baseline_from = from - Duration::days(BASELINE_DAYS);
+    let data = async {
+        let charges = fetch_charges_since(db, auth.0.id, baseline_from).await?;
+        let categories = fetch_all_categories(db, auth.0.id).await?;
+        Ok::<_, sqlx::Error>((charges, categories))
+    };
+    let (charges, categories) = data.await.map_err(|_| insight_failed())?;
+
+    Ok(Json(AnomalyReportOutDTO {
+        from,
+        to,
+        baseline_from,
+        anomalies: detect_anomalies(&charges, &categories, from, to),
+    }))
+}
+
+pub fn insight_routes() -> Vec<Route> {
+    routes![get_anomalies]
+}
@@ -1,7 +1,23 @@
 pub mod account_routes;
 pub mod achievement_routes;
+pub mod admin_routes;
+pub mod allocation_routes;
+pub mod api_token_routes;
+pub mod auth_routes;
+pub mod badge_routes;
 pub mod budget_routes;
 pub mod category_routes;
+pub mod challenge_routes;
+pub mod exchange_rate_routes;
+pub mod household_routes;
+pub mod insight_routes;
+pub mod oidc_routes;
+pub mod recurring_transaction_routes;
+pub mod report_routes;
+pub mod round_up_routes;
 pub mod saving_goal_routes;
+pub mod subscription_routes;
 pub mod transaction_routes;
+pub mod two_factor_routes;
+pub mod user_preferences_routes;
 pub mod user_routes;
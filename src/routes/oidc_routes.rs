@@ -0,0 +1,214 @@
+use crate::auth::impersonation::Impersonation;
+use crate::auth::oidc::{
+    authorization_url, discover, exchange_code, verify_id_token, AuthorizationFlow, IdTokenClaims,
+    OidcConfig, OidcError, OIDC_FLOW_COOKIE,
+};
+use crate::auth::{session_ttl, set_session_cookie, AuthUser};
+use crate::dtos::user_dtos::UserOutDTO;
+use crate::models::user::User;
+use crate::operations::admin_ops::is_suspended;
+use crate::operations::identity_ops::{
+    create_user_with_identity, find_identity_user, link_identity,
+};
+use crate::operations::session_ops::create_session;
+use crate::operations::user_ops::find_user_by_email;
+use rocket::http::{Cookie, CookieJar, SameSite, Status};
+use rocket::response::{status, Redirect};
+use rocket::serde::json::{serde_json, Json};
+use rocket::{get, routes, Route};
+use sqlx::PgPool;
+
+const FLOW_COOKIE_MINUTES: i64 = 10;
+
+fn oidc_config() -> Result<OidcConfig, status::Custom<String>> {
+    OidcConfig::from_env().ok_or_else(|| {
+        status::Custom(
+            Status::NotFound,
+            "OpenID Connect login is not configured.".to_string(),
+        )
+    })
+}
+
+fn oidc_error(error: OidcError) -> status::Custom<String> {
+    match error {
+        OidcError::Provider(_) => status::Custom(
+            Status::BadGateway,
+            "Identity provider is unavailable.".to_string(),
+        ),
+        OidcError::InvalidToken(_) => status::Custom(
+            Status::Unauthorized,
+            "Identity provider login failed.".to_string(),
+        ),
+    }
+}
+
+#[get("/auth/oidc/login")]
+pub async fn oidc_login(cookies: &CookieJar<'_>) -> Result<Redirect, status::Custom<String>> {
+    let config = oidc_config()?;
+    let metadata = discover(&config).await.map_err(oidc_error)?;
+
+    let flow = AuthorizationFlow::new();
+    let url = authorization_url(&metadata, &config, &flow).map_err(oidc_error)?;
+    let flow_json = serde_json::to_string(&flow).map_err(|_| {
+        status::Custom(
+            Status::InternalServerError,
+            "Failed to start login.".to_string(),
+        )
+    })?;
+    let cookie = Cookie::build(OIDC_FLOW_COOKIE, flow_json)
+        .http_only(true)
+        .same_site(SameSite::Lax)
+        .max_age(rocket::time::Duration::minutes(FLOW_COOKIE_MINUTES))
+        .finish();
+    cookies.add_private(cookie);
+
+    Ok(Redirect::to(url))
+}
+
+// Finds the user linked to the provider's subject. An unlinked subject is
+// linked to the user who is already signed in, if any. Otherwise a new user
+// is created, unless the email is already registered: local emails are never
+// verified, so a matching one proves nothing and the owner has to sign in
+// first to link the provider.
+async fn resolve_user(
+    db: &PgPool,
+    claims: &IdTokenClaims,
+    signed_in: Option<User>,
+) -> Result<User, status::Custom<String>> {
+    let internal_error =
+        || status::Custom(Status::InternalServerError, "Failed to log in.".to_string());
+
+    if let Some(user) = find_identity_user(db, &claims.iss, &claims.sub)
+        .await
+        .map_err(|_| internal_error())?
+    {
+        return match signed_in {
+            Some(signed_in) if signed_in.id != user.id => Err(status::Custom(
+                Status::Conflict,
+                "This identity is linked to another account.".to_string(),
+            )),
+            _ => Ok(user),
+        };
+    }
+
+    if let Some(user) = signed_in {
+        link_identity(db, user.id, &claims.iss, &claims.sub)
+            .await
+            .map_err(|_| internal_error())?;
+        return Ok(user);
+    }
+
+    let email = claims.email.as_deref().ok_or_else(|| {
+        status::Custom(
+            Status::BadRequest,
+            "Identity provider did not share an email address.".to_string(),
+        )
+    })?;
+
+    match find_user_by_email(db, email)
+        .await
+        .map_err(|_| internal_error())?
+    {
+        Some(_) => Err(status::Custom(
+            Status::Conflict,
+            "Email is already registered. Sign in first to link this identity.".to_string(),
+        )),
+        None => {
+            let username = claims
+                .preferred_username
+                .clone()
+                .or_else(|| claims.name.clone())
+                .unwrap_or_else(|| email.split('@').next().unwrap_or(email).to_string());
+            create_user_with_identity(db, &username, email, &claims.iss, &claims.sub)
+                .await
+                .map_err(|_| internal_error())
+        }
+    }
+}
+
+#[get("/auth/oidc/callback?<code>&<state>&<error>")]
+pub async fn oidc_callback(
+    db: &rocket::State<PgPool>,
+    cookies: &CookieJar<'_>,
+    signed_in: Option<AuthUser>,
+    impersonation: Option<Impersonation>,
+    code: Option<String>,
+    state: Option<String>,
+    error: Option<String>,
+) -> Result<Json<UserOutDTO>, status::Custom<String>> {
+    let config = oidc_config()?;
+    if impersonation.is_some() {
+        return Err(status::Custom(
+            Status::Forbidden,
+            "Identities can't be linked while impersonating.".to_string(),
+        ));
+    }
+
+    let flow = cookies
+        .get_private(OIDC_FLOW_COOKIE)
+        .and_then(|cookie| serde_json::from_str::<AuthorizationFlow>(cookie.value()).ok());
+    cookies.remove_private(Cookie::named(OIDC_FLOW_COOKIE));
+
+    let flow = match flow {
+        Some(flow) if error.is_none() && state.as_deref() == Some(flow.state.as_str()) => flow,
+        _ => {
+            return Err(status::Custom(
+                Status::Unauthorized,
+                "Identity provider login failed.".to_string(),
+            ))
+        }
+    };
+    let code = code.ok_or_else(|| {
+        status::Custom(
+            Status::BadRequest,
+            "Missing authorization code.".to_string(),
+        )
+    })?;
+
+    let metadata = discover(&config).await.map_err(oidc_error)?;
+    let id_token = exchange_code(&metadata, &config, &code, &flow.code_verifier)
+        .await
+        .map_err(oidc_error)?;
+    let claims = verify_id_token(&metadata, &config, &id_token, &flow.nonce)
+        .await
+        .map_err(oidc_error)?;
+
+    let linking = signed_in.is_some();
+    let user = resolve_user(db, &claims, signed_in.map(|AuthUser(user)| user)).await?;
+    if linking {
+        // Linking keeps the current session.
+        return Ok(Json(user.to_user_out_dto()));
+    }
+
+    // Users with two-factor enabled still have to confirm it through
+    // `/auth/totp/verify` before this session is accepted.
+    match is_suspended(db, user.id).await {
+        Ok(false) => {}
+        Ok(true) => {
+            return Err(status::Custom(
+                Status::Forbidden,
+                "Account is suspended.".to_string(),
+            ))
+        }
+        Err(_) => {
+            return Err(status::Custom(
+                Status::InternalServerError,
+                "Failed to log in.".to_string(),
+            ))
+        }
+    }
+    match create_session(db, user.id, session_ttl(), false).await {
+        Ok(session) => {
+            set_session_cookie(cookies, session.id);
+            Ok(Json(user.to_user_out_dto()))
+        }
+        Err(_) => Err(status::Custom(
+            Status::InternalServerError,
+            "Failed to log in.".to_string(),
+        )),
+    }
+}
+
+pub fn oidc_routes() -> Vec<Route> {
+    routes![oidc_login, oidc_callback]
+}
@@ -0,0 +1,97 @@
+use crate::auth::AuthUser;
+use crate::dtos::recurring_transaction_dtos::{
+    RecurringTransactionInDTO, RecurringTransactionOutDTO,
+};
+use crate::operations::ownership_ops::{can_edit_references, Owned};
+use crate::operations::recurring_transaction_ops::*;
+use crate::uuid_param::UuidParam;
+use rocket::http::Status;
+use rocket::response::status;
+use rocket::serde::json::Json;
+use rocket::{delete, get, post, routes, Route};
+use sqlx::PgPool;
+
+#[post("/recurring_transactions", data = "<recurring_in>")]
+pub async fn post_recurring_transaction(
+    db: &rocket::State<PgPool>,
+    auth: AuthUser,
+    recurring_in: Json<RecurringTransactionInDTO>,
+) -> Result<Json<RecurringTransactionOutDTO>, status::Custom<String>> {
+    if let Err(message) = recurring_in.validate() {
+        return Err(status::Custom(Status::BadRequest, message.to_string()));
+    }
+
+    let references = [
+        (Owned::Account, recurring_in.account_id),
+        (Owned::Category, recurring_in.category_id),
+    ];
+    match can_edit_references(db, auth.0.id, &references).await {
+        Ok(true) => {}
+        Ok(false) => {
+            return Err(status::Custom(
+                Status::BadRequest,
+                "Account or category not found for this user.".to_string(),
+            ))
+        }
+        Err(_) => {
+            return Err(status::Custom(
+                Status::InternalServerError,
+                "Failed to create recurring transaction.".to_string(),
+            ))
+        }
+    }
+
+    match create_recurring_transaction(db, auth.0.id, &recurring_in.0).await {
+        Ok(recurring) => Ok(Json(recurring.to_recurring_transaction_out_dto())),
+        Err(_) => Err(status::Custom(
+            Status::InternalServerError,
+            "Failed to create recurring transaction.".to_string(),
+        )),
+    }
+}
+
+#[get("/recurring_transactions")]
+pub async fn get_recurring_transactions(
+    db: &rocket::State<PgPool>,
+    auth: AuthUser,
+) -> Result<Json<Vec<RecurringTransactionOutDTO>>, status::Custom<String>> {
+    match fetch_recurring_transactions_for_user(db, auth.0.id).await {
+        Ok(recurring) => Ok(Json(
+            recurring
+                .iter()
+                .map(|recurring| recurring.to_recurring_transaction_out_dto())
+                .collect(),
+        )),
+        Err(_) => Err(status::Custom(
+            Status::InternalServerError,
+            "Failed to fetch recurring transactions.".to_string(),
+        )),
+    }
+}
+
+#[delete("/recurring_transactions/<recurring_id_param>")]
+pub async fn delete_recurring_transaction_by_id(
+    db: &rocket::State<PgPool>,
+    auth: AuthUser,
+    recurring_id_param: UuidParam,
+) -> Result<status::NoContent, status::Custom<String>> {
+    match delete_recurring_transaction(db, auth.0.id, recurring_id_param.0).await {
+        Ok(0) => Err(status::Custom(
+            Status::NotFound,
+            "Recurring transaction not found.".to_string(),
+        )),
+        Ok(_) => Ok(status::NoContent),
+        Err(_) => Err(status::Custom(
+            Status::InternalServerError,
+            "Failed to delete recurring transaction.".to_string(),
+        )),
+    }
+}
+
+pub fn recurring_transaction_routes() -> Vec<Route> {
+    routes![
+        post_recurring_transaction,
+        get_recurring_transactions,
+        delete_recurring_transaction_by_id
+    ]
+}
@@ -0,0 +1,474 @@
+use crate::auth::AuthUser;
+use crate::date_param::DateParam;
+use crate::dtos::report_dtos::{
+    CategoryReportOutDTO, ForecastOutDTO, NetWorthHistoryOutDTO, SummaryReportOutDTO,
+    YearInReviewOutDTO,
+};
+use crate::enums::custom_enums::{ReportBreakdown, ReportGranularity};
+use crate::models::user_preferences::UserPreferences;
+use crate::money::is_currency_code;
+use crate::operations::account_ops::get_all_accounts;
+use crate::operations::admin_ops::is_admin;
+use crate::operations::balance_snapshot_ops::fetch_balance_snapshots;
+use crate::operations::exchange_rate_ops::fetch_rate_table;
+use crate::operations::recurring_transaction_ops::fetch_recurring_transactions_for_user;
+use crate::operations::report_ops::{
+    fetch_budget_results, fetch_category_spending, fetch_completed_goals,
+    fetch_daily_account_flows, fetch_daily_totals, fetch_net_transfers, fetch_visible_charges,
+};
+use crate::operations::user_preferences_ops::find_user_preferences;
+use crate::reports::forecast::{forecast, HISTORY_DAYS};
+use crate::reports::net_worth::net_worth_history;
+use crate::reports::year_in_review::{render_html, year_in_review};
+use crate::reports::{
+    comparison_periods, period_end, period_starts, spending_by_category, summarize, PeriodTotals,
+    MAX_PERIODS,
+};
+use crate::uuid_param::UuidParam;
+use chrono::{Datelike, Duration, NaiveDate};
+use rocket::http::Status;
+use rocket::response::content::RawHtml;
+use rocket::response::status;
+use rocket::serde::json::Json;
+use rocket::{get, routes, Route};
+use sqlx::PgPool;
+use std::str::FromStr;
+use uuid::Uuid;
+
+// Whose transactions a report covers, over which days and in which currency.
+struct ReportScope {
+    user_id: Uuid,
+    preferences: UserPreferences,
+    from: NaiveDate,
+    to: NaiveDate,
+    currency: String,
+}
+
+fn parse_granularity(
+    granularity: Option<&str>,
+    default: ReportGranularity,
+) -> Result<ReportGranularity, status::Custom<String>> {
+    match granularity.map(ReportGranularity::from_str).transpose() {
+        Ok(granularity) => Ok(granularity.unwrap_or(default)),
+        Err(e) => Err(status::Custom(Status::BadRequest, e.to_string())),
+    }
+}
+
+fn check_period_count(
+    from: NaiveDate,
+    to: NaiveDate,
+    granularity: ReportGranularity,
+    preferences: &UserPreferences,
+) -> Result<(), status::Custom<String>> {
+    if period_starts(from, to, granularity, preferences)
+        .nth(MAX_PERIODS)
+        .is_some()
+    {
+        return Err(status::Custom(
+            Status::BadRequest,
+            format!(
+                "A report can cover at most {} periods; use a coarser granularity.",
+                MAX_PERIODS
+            ),
+        ));
+    }
+    Ok(())
+}
+
+fn report_failed() -> status::Custom<String> {
+    status::Custom(
+        Status::InternalServerError,
+        "Failed to build report.".to_string(),
+    )
+}
+
+// Defaults to the current month so far in the user's base currency. Admins
+// can pass `user_id` to report on someone else.
+async fn report_scope(
+    db: &PgPool,
+    auth: &AuthUser,
+    user_id: Option<UuidParam>,
+    from: Option<DateParam>,
+    to: Option<DateParam>,
+    currency: Option<String>,
+) -> Result<ReportScope, status::Custom<String>> {
+    if currency
+        .as_deref()
+        .is_some_and(|currency| !is_currency_code(currency))
+    {
+        return Err(status::Custom(
+            Status::BadRequest,
+            "Currency must be a three letter ISO 4217 code.".to_string(),
+        ));
+    }
+
+    let user_id = match user_id {
+        Some(UuidParam(user_id)) if user_id != auth.0.id => match is_admin(db, auth.0.id).await {
+            Ok(true) => user_id,
+            Ok(false) => {
+                return Err(status::Custom(
+                    Status::Forbidden,
+                    "Only admins can report on other users.".to_string(),
+                ))
+            }
+            Err(_) => return Err(report_failed()),
+        },
+        _ => auth.0.id,
+    };
+
+    let preferences = find_user_preferences(db, user_id)
+        .await
+        .map_err(|_| report_failed())?;
+    let to = to.map_or(preferences.today(), |to| to.0);
+    let from = from.map_or_else(
+        || to.with_day(1).expect("every month has a first day"),
+        |from| from.0,
+    );
+    if from > to {
+        return Err(status::Custom(
+            Status::BadRequest,
+            "The report must start before it ends.".to_string(),
+        ));
+    }
+
+    let currency = currency.unwrap_or(preferences.base_currency.clone());
+    Ok(ReportScope {
+        user_id,
+        preferences,
+        from,
+        to,
+        currency,
+    })
+}
+
+#[get("/reports/summary?<user_id>&<from>&<to>&<granularity>&<breakdown>&<currency>")]
+#[allow(clippy::too_many_arguments)]
+pub async fn get_summary_report(
+    db: &rocket::State<PgPool>,
+    auth: AuthUser,
+    user_id: Option<UuidParam>,
+    from: Option<DateParam>,
+    to: Option<DateParam>,
+    granularity: Option<&str>,
+    breakdown: Option<&str>,
+    currency: Option<String>,
+) -> Result<Json<SummaryReportOutDTO>, status::Custom<String>> {
+    let granularity = parse_granularity(granularity, ReportGranularity::Month)?;
+    let breakdown = match breakdown.map(ReportBreakdown::from_str).transpose() {
+        Ok(breakdown) => breakdown,
+        Err(e) => return Err(status::Custom(Status::BadRequest, e.to_string())),
+    };
+    let scope = report_scope(db, &auth, user_id, from, to, currency).await?;
+
+    check_period_count(scope.from, scope.to, granularity, &scope.preferences)?;
+
+    let data = async {
+        let daily_totals =
+            fetch_daily_totals(db, scope.user_id, scope.from, scope.to, breakdown).await?;
+        let rates = fetch_rate_table(db, scope.from, scope.to).await?;
+        Ok::<_, sqlx::Error>((daily_totals, rates))
+    };
+    let (daily_totals, rates) = data.await.map_err(|_| report_failed())?;
+
+    match summarize(
+        &daily_totals,
+        &rates,
+        &scope.currency,
+        granularity,
+        &scope.preferences,
+        scope.from,
+        scope.to,
+    ) {
+        Ok(report) => Ok(Json(report)),
+        Err(missing) => Err(status::Custom(
+            Status::UnprocessableEntity,
+            missing.to_string(),
+        )),
+    }
+}
+
+// Where the money went, compared with the period before and the same period
+// a year earlier.
+#[get("/reports/categories?<user_id>&<from>&<to>&<currency>")]
+pub async fn get_category_report(
+    db: &rocket::State<PgPool>,
+    auth: AuthUser,
+    user_id: Option<UuidParam>,
+    from: Option<DateParam>,
+    to: Option<DateParam>,
+    currency: Option<String>,
+) -> Result<Json<CategoryReportOutDTO>, status::Custom<String>> {
+    let scope = report_scope(db, &auth, user_id, from, to, currency).await?;
+    let (previous, last_year) = comparison_periods(scope.from, scope.to);
+
+    let data = async {
+        let current = fetch_daily_totals(
+            db,
+            scope.user_id,
+            scope.from,
+            scope.to,
+            Some(ReportBreakdown::Category),
+        )
+        .await?;
+        let before = fetch_daily_totals(
+            db,
+            scope.user_id,
+            previous.0,
+            previous.1,
+            Some(ReportBreakdown::Category),
+        )
+        .await?;
+        let year_before = fetch_daily_totals(
+            db,
+            scope.user_id,
+            last_year.0,
+            last_year.1,
+            Some(ReportBreakdown::Category),
+        )
+        .await?;
+        let rates = fetch_rate_table(db, last_year.0, scope.to).await?;
+        Ok::<_, sqlx::Error>((current, before, year_before, rates))
+    };
+    let (current, before, year_before, rates) = data.await.map_err(|_| report_failed())?;
+
+    match spending_by_category(
+        PeriodTotals {
+            from: scope.from,
+            to: scope.to,
+            daily_totals: &current,
+        },
+        PeriodTotals {
+            from: previous.0,
+            to: previous.1,
+            daily_totals: &before,
+        },
+        PeriodTotals {
+            from: last_year.0,
+            to: last_year.1,
+            daily_totals: &year_before,
+        },
+        &rates,
+        &scope.currency,
+    ) {
+        Ok(report) => Ok(Json(report)),
+        Err(missing) => Err(status::Custom(
+            Status::UnprocessableEntity,
+            missing.to_string(),
+        )),
+    }
+}
+
+// Net worth at the end of every day, week, month or year, taken from balance
+// snapshots where there are some and worked back from transactions where
+// there aren't. Days after today are left out.
+#[get("/reports/net_worth?<user_id>&<from>&<to>&<granularity>&<currency>")]
+pub async fn get_net_worth_history(
+    db: &rocket::State<PgPool>,
+    auth: AuthUser,
+    user_id: Option<UuidParam>,
+    from: Option<DateParam>,
+    to: Option<DateParam>,
+    granularity: Option<&str>,
+    currency: Option<String>,
+) -> Result<Json<NetWorthHistoryOutDTO>, status::Custom<String>> {
+    let granularity = parse_granularity(granularity, ReportGranularity::Day)?;
+    let scope = report_scope(db, &auth, user_id, from, to, currency).await?;
+    check_period_count(scope.from, scope.to, granularity, &scope.preferences)?;
+
+    let today = scope.preferences.today();
+    let dates: Vec<NaiveDate> =
+        period_starts(scope.from, scope.to, granularity, &scope.preferences)
+            .map(|start| period_end(start, granularity).min(scope.to))
+            .filter(|date| *date <= today)
+            .collect();
+
+    let data = async {
+        let accounts = get_all_accounts(db, scope.user_id).await?;
+        let snapshots = fetch_balance_snapshots(db, scope.user_id, scope.from).await?;
+        let flows = fetch_daily_account_flows(db, scope.user_id, scope.from).await?;
+        let rates = fetch_rate_table(db, scope.from, scope.to).await?;
+        Ok::<_, sqlx::Error>((accounts, snapshots, flows, rates))
+    };
+    let (accounts, snapshots, flows, rates) = data.await.map_err(|_| report_failed())?;
+
+    match net_worth_history(
+        &accounts,
+        &snapshots,
+        &flows,
+        &rates,
+        &scope.currency,
+        &dates,
+    ) {
+        Ok(points) => Ok(Json(NetWorthHistoryOutDTO {
+            currency: scope.currency,
+            from: scope.from,
+            to: scope.to,
+            granularity,
+            points,
+        })),
+        Err(missing) => Err(status::Custom(
+            Status::UnprocessableEntity,
+            missing.to_string(),
+        )),
+    }
+}
+
+// Projected balance of every account for each of the next `days` days,
+// defaulting to 90.
+#[get("/reports/forecast?<days>")]
+pub async fn get_forecast(
+    db: &rocket::State<PgPool>,
+    auth: AuthUser,
+    days: Option<i64>,
+) -> Result<Json<ForecastOutDTO>, status::Custom<String>> {
+    let days = days.unwrap_or(90);
+    if !(1..=366).contains(&days) {
+        return Err(status::Custom(
+            Status::BadRequest,
+            "days must be between 1 and 366.".to_string(),
+        ));
+    }
+
+    let data = async {
+        let today = find_user_preferences(db, auth.0.id).await?.today();
+        let accounts = get_all_accounts(db, auth.0.id).await?;
+        let recurring = fetch_recurring_transactions_for_user(db, auth.0.id).await?;
+        let history_from = today - Duration::days(HISTORY_DAYS - 1);
+        let spending = fetch_category_spending(db, auth.0.id, history_from, today).await?;
+        let transfers = fetch_net_transfers(db, auth.0.id, history_from, today).await?;
+        Ok::<_, sqlx::Error>((today, accounts, recurring, spending, transfers))
+    };
+    let (today, accounts, recurring, spending, transfers) =
+        data.await.map_err(|_| report_failed())?;
+
+    Ok(Json(forecast(
+        &accounts, &recurring, &spending, &transfers, today, days,
+    )))
+}
+
+async fn build_year_in_review(
+    db: &PgPool,
+    auth: &AuthUser,
+    user_id: Option<UuidParam>,
+    year: Option<i32>,
+    currency: Option<String>,
+) -> Result<(YearInReviewOutDTO, UserPreferences), status::Custom<String>> {
+    let first_day = |year: i32| NaiveDate::from_ymd_opt(year, 1, 1);
+    let year = match year {
+        Some(year) => year,
+        None => find_user_preferences(db, auth.0.id)
+            .await
+            .map_err(|_| report_failed())?
+            .today()
+            .year(),
+    };
+    let (Some(from), Some(next_year)) = (first_day(year), first_day(year + 1)) else {
+        return Err(status::Custom(
+            Status::BadRequest,
+            "Year is out of range.".to_string(),
+        ));
+    };
+    let to = next_year - Duration::days(1);
+
+    let mut scope = report_scope(
+        db,
+        auth,
+        user_id,
+        Some(DateParam(from)),
+        Some(DateParam(to)),
+        currency,
+    )
+    .await?;
+    let today = scope.preferences.today();
+    if from > today {
+        return Err(status::Custom(
+            Status::BadRequest,
+            "That year hasn't started yet.".to_string(),
+        ));
+    }
+    scope.to = scope.to.min(today);
+    let (_, last_year) = comparison_periods(scope.from, scope.to);
+    let budget_currency = scope.preferences.base_currency.clone();
+
+    let data = async {
+        let current = fetch_daily_totals(
+            db,
+            scope.user_id,
+            scope.from,
+            scope.to,
+            Some(ReportBreakdown::Category),
+        )
+        .await?;
+        let year_before =
+            fetch_daily_totals(db, scope.user_id, last_year.0, last_year.1, None).await?;
+        let charges = fetch_visible_charges(db, scope.user_id, scope.from, scope.to).await?;
+        let goals = fetch_completed_goals(db, scope.user_id, scope.from, scope.to).await?;
+        let budgets =
+            fetch_budget_results(db, scope.user_id, scope.from, scope.to, &budget_currency).await?;
+        let rates = fetch_rate_table(db, last_year.0, scope.to).await?;
+        Ok::<_, sqlx::Error>((current, year_before, charges, goals, budgets, rates))
+    };
+    let (current, year_before, charges, goals, budgets, rates) =
+        data.await.map_err(|_| report_failed())?;
+
+    year_in_review(
+        year,
+        &scope.preferences,
+        PeriodTotals {
+            from: scope.from,
+            to: scope.to,
+            daily_totals: &current,
+        },
+        PeriodTotals {
+            from: last_year.0,
+            to: last_year.1,
+            daily_totals: &year_before,
+        },
+        &charges,
+        &goals,
+        &budgets,
+        &budget_currency,
+        &rates,
+        &scope.currency,
+    )
+    .map(|review| (review, scope.preferences))
+    .map_err(|missing| status::Custom(Status::UnprocessableEntity, missing.to_string()))
+}
+
+// A year at a glance, defaulting to the current year so far.
+#[get("/reports/year_in_review?<user_id>&<year>&<currency>")]
+pub async fn get_year_in_review(
+    db: &rocket::State<PgPool>,
+    auth: AuthUser,
+    user_id: Option<UuidParam>,
+    year: Option<i32>,
+    currency: Option<String>,
+) -> Result<Json<YearInReviewOutDTO>, status::Custom<String>> {
+    build_year_in_review(db, &auth, user_id, year, currency)
+        .await
+        .map(|(review, _)| Json(review))
+}
+
+// The same review rendered as a page.
+#[get("/reports/year_in_review.html?<user_id>&<year>&<currency>")]
+pub async fn get_year_in_review_html(
+    db: &rocket::State<PgPool>,
+    auth: AuthUser,
+    user_id: Option<UuidParam>,
+    year: Option<i32>,
+    currency: Option<String>,
+) -> Result<RawHtml<String>, status::Custom<String>> {
+    let (review, preferences) = build_year_in_review(db, &auth, user_id, year, currency).await?;
+    Ok(RawHtml(render_html(&review, &preferences)))
+}
+
+pub fn report_routes() -> Vec<Route> {
+    routes![
+        get_summary_report,
+        get_category_report,
+        get_net_worth_history,
+        get_forecast,
+        get_year_in_review,
+        get_year_in_review_html
+    ]
+}
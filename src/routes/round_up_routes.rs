@@ -0,0 +1,119 @@
+use crate::auth::AuthUser;
+use crate::dtos::round_up_dtos::{RoundUpRuleInDTO, RoundUpRuleOutDTO, RoundUpSummaryOutDTO};
+use crate::operations::ownership_ops::{can_edit_references, Owned};
+use crate::operations::round_up_ops::*;
+use crate::rules::round_up::is_valid_increment;
+use crate::uuid_param::UuidParam;
+use rocket::http::Status;
+use rocket::response::status;
+use rocket::serde::json::Json;
+use rocket::{delete, get, post, routes, Route};
+use sqlx::PgPool;
+
+#[post("/round_up_rules", data = "<rule_in>")]
+pub async fn post_round_up_rule(
+    db: &rocket::State<PgPool>,
+    auth: AuthUser,
+    rule_in: Json<RoundUpRuleInDTO>,
+) -> Result<Json<RoundUpRuleOutDTO>, status::Custom<String>> {
+    if !is_valid_increment(rule_in.round_to) {
+        return Err(status::Custom(
+            Status::BadRequest,
+            "round_to must be 1, 5 or 10.".to_string(),
+        ));
+    }
+
+    let references = [
+        (Owned::Account, rule_in.account_id),
+        (Owned::SavingGoal, rule_in.goal_id),
+    ];
+    match can_edit_references(db, auth.0.id, &references).await {
+        Ok(true) => {}
+        Ok(false) => {
+            return Err(status::Custom(
+                Status::BadRequest,
+                "Account or saving goal not found for this user.".to_string(),
+            ))
+        }
+        Err(_) => {
+            return Err(status::Custom(
+                Status::InternalServerError,
+                "Failed to create round-up rule.".to_string(),
+            ))
+        }
+    }
+
+    match create_round_up_rule(db, auth.0.id, &rule_in.0).await {
+        Ok(rule) => Ok(Json(rule.to_round_up_rule_out_dto())),
+        Err(_) => Err(status::Custom(
+            Status::InternalServerError,
+            "Failed to create round-up rule.".to_string(),
+        )),
+    }
+}
+
+#[get("/round_up_rules")]
+pub async fn get_round_up_rules(
+    db: &rocket::State<PgPool>,
+    auth: AuthUser,
+) -> Result<Json<Vec<RoundUpRuleOutDTO>>, status::Custom<String>> {
+    match fetch_round_up_rules_for_user(db, auth.0.id).await {
+        Ok(rules) => Ok(Json(
+            rules
+                .iter()
+                .map(|rule| rule.to_round_up_rule_out_dto())
+                .collect(),
+        )),
+        Err(_) => Err(status::Custom(
+            Status::InternalServerError,
+            "Failed to fetch round-up rules.".to_string(),
+        )),
+    }
+}
+
+#[delete("/round_up_rules/<rule_id_param>")]
+pub async fn delete_round_up_rule_by_id(
+    db: &rocket::State<PgPool>,
+    auth: AuthUser,
+    rule_id_param: UuidParam,
+) -> Result<status::NoContent, status::Custom<String>> {
+    match delete_round_up_rule(db, auth.0.id, rule_id_param.0).await {
+        Ok(0) => Err(status::Custom(
+            Status::NotFound,
+            "Round-up rule not found.".to_string(),
+        )),
+        Ok(_) => Ok(status::NoContent),
+        Err(_) => Err(status::Custom(
+            Status::InternalServerError,
+            "Failed to delete round-up rule.".to_string(),
+        )),
+    }
+}
+
+#[get("/round_ups/summary")]
+pub async fn get_round_up_summary(
+    db: &rocket::State<PgPool>,
+    auth: AuthUser,
+) -> Result<Json<Vec<RoundUpSummaryOutDTO>>, status::Custom<String>> {
+    match fetch_round_up_summary(db, auth.0.id).await {
+        Ok(totals) => Ok(Json(
+            totals
+                .iter()
+                .map(|total| total.to_round_up_summary_out_dto())
+                .collect(),
+        )),
+        Err(_) => Err(status::Custom(
+            Status::InternalServerError,
+            "Failed to fetch round-up summary.".to_string(),
+        )),
+    }
+}
+
+pub fn round_up_routes() -> Vec<Route> {
+    routes![
+        post_round_up_rule,
+        get_round_up_rules,
+        delete_round_up_rule_by_id,
+        get_round_up_summary
+    ]
+}
@@ -1,17 +1,25 @@
+use crate::auth::AuthUser;
+use crate::dtos::contribution_dtos::{ContributionInDTO, ContributionOutDTO};
 use crate::dtos::saving_goal_dtos::{SavingGoalInDTO, SavingGoalOutDTO};
+use crate::operations::contribution_ops::*;
+use crate::operations::ownership_ops::{can_edit, Owned};
 use crate::operations::saving_goal_ops::*;
+use crate::operations::user_preferences_ops::find_user_preferences;
+use crate::routes::household_routes::record_access_error;
 use crate::uuid_param::UuidParam;
 use rocket::http::Status;
 use rocket::response::status;
 use rocket::serde::json::Json;
 use rocket::{delete, get, patch, post, routes, Route};
 use sqlx::PgPool;
+use uuid::Uuid;
 
 #[get("/saving_goals")]
 pub async fn get_all_saving_goals(
     db: &rocket::State<PgPool>,
+    auth: AuthUser,
 ) -> Result<Json<Vec<SavingGoalOutDTO>>, status::Custom<String>> {
-    match fetch_all_saving_goals(db).await {
+    match fetch_all_saving_goals(db, auth.0.id).await {
         Ok(saving_goals) => {
             let saving_goals_dto: Vec<SavingGoalOutDTO> = saving_goals
                 .into_iter()
@@ -29,10 +37,11 @@ pub async fn get_all_saving_goals(
 #[get("/saving_goals/<saving_goal_id_param>")]
 pub async fn get_saving_goal_by_id(
     db: &rocket::State<PgPool>,
+    auth: AuthUser,
     saving_goal_id_param: UuidParam,
 ) -> Result<Json<SavingGoalOutDTO>, status::Custom<String>> {
     let saving_goal_id = saving_goal_id_param.0;
-    match find_saving_goal_by_id(db, saving_goal_id).await {
+    match find_saving_goal_by_id(db, auth.0.id, saving_goal_id).await {
         Ok(Some(saving_goal)) => Ok(Json(saving_goal.to_saving_goal_out_dto())),
         Ok(None) => Err(status::Custom(
             Status::NotFound,
@@ -48,9 +57,22 @@ pub async fn get_saving_goal_by_id(
 #[post("/saving_goals", data = "<saving_goal_in>")]
 pub async fn post_saving_goal(
     db: &rocket::State<PgPool>,
+    auth: AuthUser,
     saving_goal_in: Json<SavingGoalInDTO>,
 ) -> Result<Json<SavingGoalOutDTO>, status::Custom<String>> {
-    match create_saving_goal(db.inner(), &saving_goal_in.0).await {
+    let currency = saving_goal_currency(db, auth.0.id, None)
+        .await
+        .map_err(|_| {
+            status::Custom(
+                Status::InternalServerError,
+                "Failed to create saving_goal.".to_string(),
+            )
+        })?;
+    if let Err(message) = saving_goal_in.validate(&currency) {
+        return Err(status::Custom(Status::BadRequest, message.to_string()));
+    }
+
+    match create_saving_goal(db.inner(), auth.0.id, &saving_goal_in.0).await {
         Ok(saving_goal) => Ok(Json(saving_goal.to_saving_goal_out_dto())),
         Err(_) => Err(status::Custom(
             Status::InternalServerError,
@@ -62,12 +84,33 @@ pub async fn post_saving_goal(
 #[patch("/saving_goals/<saving_goal_id_param>", data = "<saving_goal_update>")]
 pub async fn update_saving_goal(
     db: &rocket::State<PgPool>,
+    auth: AuthUser,
     saving_goal_id_param: UuidParam,
     saving_goal_update: Json<SavingGoalInDTO>,
 ) -> Result<Json<SavingGoalOutDTO>, status::Custom<String>> {
     let saving_goal_id = saving_goal_id_param.0;
-    match update_saving_goal_in_db(db, saving_goal_id, &saving_goal_update.0).await {
-        Ok(saving_goal) => Ok(Json(saving_goal.to_saving_goal_out_dto())),
+    let currency = saving_goal_currency(db, auth.0.id, Some(saving_goal_id))
+        .await
+        .map_err(|_| {
+            status::Custom(
+                Status::InternalServerError,
+                "Failed to update saving_goal.".to_string(),
+            )
+        })?;
+    if let Err(message) = saving_goal_update.validate(&currency) {
+        return Err(status::Custom(Status::BadRequest, message.to_string()));
+    }
+
+    match update_saving_goal_in_db(db, auth.0.id, saving_goal_id, &saving_goal_update.0).await {
+        Ok(Some(saving_goal)) => Ok(Json(saving_goal.to_saving_goal_out_dto())),
+        Ok(None) => Err(record_access_error(
+            db,
+            auth.0.id,
+            Owned::SavingGoal,
+            saving_goal_id,
+            "Saving goal",
+        )
+        .await),
         Err(_) => Err(status::Custom(
             Status::InternalServerError,
             "Failed to update saving_goal.".to_string(),
@@ -75,13 +118,37 @@ pub async fn update_saving_goal(
     }
 }
 
+// The base currency of the goal's owner, or of the user for a new goal.
+async fn saving_goal_currency(
+    db: &PgPool,
+    user_id: Uuid,
+    saving_goal_id: Option<Uuid>,
+) -> Result<String, sqlx::Error> {
+    let owner = match saving_goal_id {
+        Some(saving_goal_id) => find_saving_goal_by_id(db, user_id, saving_goal_id)
+            .await?
+            .map_or(user_id, |saving_goal| saving_goal.user_id),
+        None => user_id,
+    };
+    Ok(find_user_preferences(db, owner).await?.base_currency)
+}
+
 #[delete("/saving_goals/<saving_goal_id_param>")]
 pub async fn delete_saving_goal_by_id(
     db: &rocket::State<PgPool>,
+    auth: AuthUser,
     saving_goal_id_param: UuidParam,
 ) -> Result<status::NoContent, status::Custom<String>> {
     let saving_goal_id = saving_goal_id_param.0;
-    match delete_saving_goal(db, saving_goal_id).await {
+    match delete_saving_goal(db, auth.0.id, saving_goal_id).await {
+        Ok(0) => Err(record_access_error(
+            db,
+            auth.0.id,
+            Owned::SavingGoal,
+            saving_goal_id,
+            "Saving goal",
+        )
+        .await),
         Ok(_) => Ok(status::NoContent),
         Err(_) => Err(status::Custom(
             Status::InternalServerError,
@@ -90,12 +157,99 @@ pub async fn delete_saving_goal_by_id(
     }
 }
 
+#[post(
+    "/saving_goals/<saving_goal_id_param>/contributions",
+    data = "<contribution_in>"
+)]
+pub async fn post_contribution(
+    db: &rocket::State<PgPool>,
+    auth: AuthUser,
+    saving_goal_id_param: UuidParam,
+    contribution_in: Json<ContributionInDTO>,
+) -> Result<Json<ContributionOutDTO>, status::Custom<String>> {
+    let saving_goal_id = saving_goal_id_param.0;
+    if !contribution_in.amount.is_positive() {
+        return Err(status::Custom(
+            Status::BadRequest,
+            "Contribution amount must be positive.".to_string(),
+        ));
+    }
+
+    match can_edit(db, auth.0.id, Owned::SavingGoal, saving_goal_id).await {
+        Ok(true) => {}
+        Ok(false) => {
+            return Err(record_access_error(
+                db,
+                auth.0.id,
+                Owned::SavingGoal,
+                saving_goal_id,
+                "Saving goal",
+            )
+            .await)
+        }
+        Err(_) => {
+            return Err(status::Custom(
+                Status::InternalServerError,
+                "Failed to fetch saving_goal.".to_string(),
+            ))
+        }
+    }
+
+    match add_goal_contribution(db, saving_goal_id, &contribution_in.0).await {
+        Ok(contribution) => Ok(Json(contribution.to_contribution_out_dto())),
+        Err(_) => Err(status::Custom(
+            Status::InternalServerError,
+            "Failed to create contribution.".to_string(),
+        )),
+    }
+}
+
+#[get("/saving_goals/<saving_goal_id_param>/contributions")]
+pub async fn get_contributions(
+    db: &rocket::State<PgPool>,
+    auth: AuthUser,
+    saving_goal_id_param: UuidParam,
+) -> Result<Json<Vec<ContributionOutDTO>>, status::Custom<String>> {
+    let saving_goal_id = saving_goal_id_param.0;
+    match find_saving_goal_by_id(db, auth.0.id, saving_goal_id).await {
+        Ok(Some(_)) => {}
+        Ok(None) => {
+            return Err(status::Custom(
+                Status::NotFound,
+                "SavingGoal not found.".to_string(),
+            ))
+        }
+        Err(_) => {
+            return Err(status::Custom(
+                Status::InternalServerError,
+                "Failed to fetch saving_goal.".to_string(),
+            ))
+        }
+    }
+
+    match fetch_goal_contributions(db, saving_goal_id).await {
+        Ok(contributions) => {
+            let contributions_dto: Vec<ContributionOutDTO> = contributions
+                .into_iter()
+                .map(|contribution| contribution.to_contribution_out_dto())
+                .collect();
+            Ok(Json(contributions_dto))
+        }
+        Err(_) => Err(status::Custom(
+            Status::InternalServerError,
+            "Failed to fetch contributions.".to_string(),
+        )),
+    }
+}
+
 pub fn saving_goal_routes() -> Vec<Route> {
     routes![
         post_saving_goal,
         get_all_saving_goals,
         get_saving_goal_by_id,
         update_saving_goal,
-        delete_saving_goal_by_id
+        delete_saving_goal_by_id,
+        post_contribution,
+        get_contributions
     ]
 }
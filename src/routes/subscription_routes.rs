@@ -0,0 +1,134 @@
+use crate::auth::AuthUser;
+use crate::dtos::recurring_transaction_dtos::{
+    RecurringTransactionInDTO, RecurringTransactionOutDTO,
+};
+use crate::dtos::subscription_dtos::{SubscriptionCandidateInDTO, SubscriptionCandidateOutDTO};
+use crate::enums::custom_enums::TransactionType;
+use crate::operations::recurring_transaction_ops::{
+    create_recurring_transaction, fetch_recurring_transactions_for_user,
+};
+use crate::operations::subscription_ops::*;
+use crate::operations::user_preferences_ops::find_user_preferences;
+use crate::subscriptions::{detect_subscriptions, payee_key, LOOKBACK_DAYS};
+use chrono::Duration;
+use rocket::http::Status;
+use rocket::response::status;
+use rocket::serde::json::Json;
+use rocket::{get, post, routes, Route};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+fn detection_failed() -> status::Custom<String> {
+    status::Custom(
+        Status::InternalServerError,
+        "Failed to detect subscriptions.".to_string(),
+    )
+}
+
+// Detected subscriptions that aren't tracked as a recurring transaction yet
+// and haven't been dismissed.
+async fn open_candidates(
+    db: &PgPool,
+    user_id: Uuid,
+) -> Result<Vec<SubscriptionCandidateOutDTO>, sqlx::Error> {
+    let today = find_user_preferences(db, user_id).await?.today();
+    let charges = fetch_charges_since(db, user_id, today - Duration::days(LOOKBACK_DAYS)).await?;
+    let dismissals = fetch_subscription_dismissals(db, user_id).await?;
+    let recurring = fetch_recurring_transactions_for_user(db, user_id).await?;
+
+    let mut candidates = detect_subscriptions(&charges, today);
+    candidates.retain(|candidate| {
+        !dismissals.iter().any(|dismissal| {
+            dismissal.account_id == candidate.account_id && dismissal.payee == candidate.payee
+        }) && !recurring.iter().any(|item| {
+            item.account_id == candidate.account_id && payee_key(&item.title) == candidate.payee
+        })
+    });
+    Ok(candidates)
+}
+
+async fn find_candidate(
+    db: &PgPool,
+    user_id: Uuid,
+    candidate_in: &SubscriptionCandidateInDTO,
+) -> Result<SubscriptionCandidateOutDTO, status::Custom<String>> {
+    let candidates = open_candidates(db, user_id)
+        .await
+        .map_err(|_| detection_failed())?;
+    candidates
+        .into_iter()
+        .find(|candidate| {
+            candidate.account_id == candidate_in.account_id && candidate.payee == candidate_in.payee
+        })
+        .ok_or_else(|| {
+            status::Custom(
+                Status::NotFound,
+                "Subscription candidate not found.".to_string(),
+            )
+        })
+}
+
+#[get("/subscriptions/candidates")]
+pub async fn get_subscription_candidates(
+    db: &rocket::State<PgPool>,
+    auth: AuthUser,
+) -> Result<Json<Vec<SubscriptionCandidateOutDTO>>, status::Custom<String>> {
+    match open_candidates(db, auth.0.id).await {
+        Ok(candidates) => Ok(Json(candidates)),
+        Err(_) => Err(detection_failed()),
+    }
+}
+
+// Tracks a candidate as a recurring expense, continuing from its last charge.
+#[post("/subscriptions/candidates/confirm", data = "<candidate_in>")]
+pub async fn confirm_subscription_candidate(
+    db: &rocket::State<PgPool>,
+    auth: AuthUser,
+    candidate_in: Json<SubscriptionCandidateInDTO>,
+) -> Result<Json<RecurringTransactionOutDTO>, status::Custom<String>> {
+    let candidate = find_candidate(db, auth.0.id, &candidate_in).await?;
+
+    let recurring_in = RecurringTransactionInDTO {
+        title: candidate.name,
+        amount: candidate.amount,
+        transaction_type: TransactionType::Expense,
+        account_id: candidate.account_id,
+        category_id: candidate.category_id,
+        frequency: candidate.frequency,
+        every: Some(candidate.every),
+        start_date: candidate.last_charge,
+        end_date: None,
+    };
+    match create_recurring_transaction(db, auth.0.id, &recurring_in).await {
+        Ok(recurring) => Ok(Json(recurring.to_recurring_transaction_out_dto())),
+        Err(_) => Err(status::Custom(
+            Status::InternalServerError,
+            "Failed to create recurring transaction.".to_string(),
+        )),
+    }
+}
+
+#[post("/subscriptions/candidates/dismiss", data = "<candidate_in>")]
+pub async fn dismiss_subscription_candidate(
+    db: &rocket::State<PgPool>,
+    auth: AuthUser,
+    candidate_in: Json<SubscriptionCandidateInDTO>,
+) -> Result<status::NoContent, status::Custom<String>> {
+    let candidate = find_candidate(db, auth.0.id, &candidate_in).await?;
+
+    match dismiss_subscription(db, auth.0.id, candidate.account_id, &candidate.payee).await {
+        Ok(()) => Ok(status::NoContent),
+        Err(_) => Err(status::Custom(
+            Status::InternalServerError,
+            "Failed to dismiss subscription.".to_string(),
+        )),
+    }
+}
+
+pub fn subscription_routes() -> Vec<Route> {
+    routes![
+        get_subscription_candidates,
+        confirm_subscription_candidate,
+        dismiss_subscription_candidate
+    ]
+}
@@ -1,4 +1,7 @@
+use crate::auth::AuthUser;
 use crate::dtos::transaction_dtos::{TransactionInDTO, TransactionOutDTO};
+use crate::operations::account_ops::find_account_by_id;
+use crate::operations::ownership_ops::{can_edit_references, Owned};
 use crate::operations::transaction_ops::*;
 use crate::uuid_param::UuidParam;
 use rocket::http::Status;
@@ -6,12 +9,14 @@ use rocket::response::status;
 use rocket::serde::json::Json;
 use rocket::{delete, get, patch, post, routes, Route};
 use sqlx::PgPool;
+use uuid::Uuid;
 
 #[get("/transactions")]
 pub async fn get_all_transactions(
     db: &rocket::State<PgPool>,
+    auth: AuthUser,
 ) -> Result<Json<Vec<TransactionOutDTO>>, status::Custom<String>> {
-    match fetch_all_transactions(db).await {
+    match fetch_all_transactions(db, auth.0.id).await {
         Ok(transactions) => {
             let transactions_dto: Vec<TransactionOutDTO> = transactions
                 .into_iter()
@@ -29,10 +34,11 @@ pub async fn get_all_transactions(
 #[get("/transactions/<transaction_id_param>")]
 pub async fn get_transaction_by_id(
     db: &rocket::State<PgPool>,
+    auth: AuthUser,
     transaction_id_param: UuidParam,
 ) -> Result<Json<TransactionOutDTO>, status::Custom<String>> {
     let transaction_id = transaction_id_param.0;
-    match find_transaction_by_id(db, transaction_id).await {
+    match find_transaction_by_id(db, auth.0.id, transaction_id).await {
         Ok(Some(transaction)) => Ok(Json(transaction.to_transaction_out_dto())),
         Ok(None) => Err(status::Custom(
             Status::NotFound,
@@ -45,12 +51,78 @@ pub async fn get_transaction_by_id(
     }
 }
 
+// The amount has to be in the account's currency or come with an exchange
+// rate to convert it.
+async fn check_amount(
+    db: &PgPool,
+    user_id: Uuid,
+    transaction_in: &TransactionInDTO,
+    failure: &str,
+) -> Result<(), status::Custom<String>> {
+    if let Err(message) = transaction_in.validate() {
+        return Err(status::Custom(Status::BadRequest, message.to_string()));
+    }
+    let account = match find_account_by_id(db, user_id, transaction_in.account_id).await {
+        Ok(Some(account)) => account,
+        Ok(None) => {
+            return Err(status::Custom(
+                Status::BadRequest,
+                "Account or category not found for this user.".to_string(),
+            ))
+        }
+        Err(_) => {
+            return Err(status::Custom(
+                Status::InternalServerError,
+                failure.to_string(),
+            ))
+        }
+    };
+    match transaction_in.in_account_currency(&account.currency) {
+        Ok(_) => Ok(()),
+        Err(message) => Err(status::Custom(Status::BadRequest, message.to_string())),
+    }
+}
+
 #[post("/transactions", data = "<transaction_in>")]
 pub async fn post_transaction(
     db: &rocket::State<PgPool>,
+    auth: AuthUser,
     transaction_in: Json<TransactionInDTO>,
 ) -> Result<Json<TransactionOutDTO>, status::Custom<String>> {
-    match create_transaction(db.inner(), &transaction_in.0).await {
+    match can_edit_references(
+        db,
+        auth.0.id,
+        &[
+            (Owned::Account, transaction_in.account_id),
+            (Owned::Category, transaction_in.category_id),
+        ],
+    )
+    .await
+    {
+        Ok(true) => {}
+        Ok(false) => {
+            return Err(status::Custom(
+                Status::BadRequest,
+                "Account or category not found for this user.".to_string(),
+            ))
+        }
+        Err(_) => {
+            return Err(status::Custom(
+                Status::InternalServerError,
+                "Failed to create transaction.".to_string(),
+            ))
+        }
+    }
+
+    check_amount(
+        db,
+        auth.0.id,
+        &transaction_in,
+        "Failed to create transaction.",
+    )
+    .await?;
+
+    match create_transaction(db.inner(), auth.0.id, &transaction_in.0).await {
         Ok(transaction) => Ok(Json(transaction.to_transaction_out_dto())),
         Err(_) => Err(status::Custom(
             Status::InternalServerError,
@@ -59,15 +131,70 @@ pub async fn post_transaction(
     }
 }
 
+// Transactions on an account shared with the caller's household are visible
+// to every member but only editors and owners may change them.
+async fn transaction_access_error(
+    db: &PgPool,
+    user_id: Uuid,
+    transaction_id: Uuid,
+) -> status::Custom<String> {
+    match find_transaction_by_id(db, user_id, transaction_id).await {
+        Ok(Some(_)) => status::Custom(
+            Status::Forbidden,
+            "Not allowed to change this transaction.".to_string(),
+        ),
+        Ok(None) => status::Custom(Status::NotFound, "Transaction not found.".to_string()),
+        Err(_) => status::Custom(
+            Status::InternalServerError,
+            "Failed to check transaction access.".to_string(),
+        ),
+    }
+}
+
 #[patch("/transactions/<transaction_id_param>", data = "<transaction_in>")]
 pub async fn patch_transaction(
     db: &rocket::State<PgPool>,
+    auth: AuthUser,
     transaction_id_param: UuidParam,
     transaction_in: Json<TransactionInDTO>,
 ) -> Result<Json<TransactionOutDTO>, status::Custom<String>> {
     let transaction_id = transaction_id_param.0;
-    match update_transaction(db, transaction_id, &transaction_in.0).await {
-        Ok(transaction) => Ok(Json(transaction.to_transaction_out_dto())),
+    match can_edit_references(
+        db,
+        auth.0.id,
+        &[
+            (Owned::Account, transaction_in.account_id),
+            (Owned::Category, transaction_in.category_id),
+        ],
+    )
+    .await
+    {
+        Ok(true) => {}
+        Ok(false) => {
+            return Err(status::Custom(
+                Status::BadRequest,
+                "Account or category not found for this user.".to_string(),
+            ))
+        }
+        Err(_) => {
+            return Err(status::Custom(
+                Status::InternalServerError,
+                "Failed to update transaction.".to_string(),
+            ))
+        }
+    }
+
+    check_amount(
+        db,
+        auth.0.id,
+        &transaction_in,
+        "Failed to update transaction.",
+    )
+    .await?;
+
+    match update_transaction(db, auth.0.id, transaction_id, &transaction_in.0).await {
+        Ok(Some(transaction)) => Ok(Json(transaction.to_transaction_out_dto())),
+        Ok(None) => Err(transaction_access_error(db, auth.0.id, transaction_id).await),
         Err(_) => Err(status::Custom(
             Status::InternalServerError,
             "Failed to update transaction.".to_string(),
@@ -78,10 +205,12 @@ pub async fn patch_transaction(
 #[delete("/transactions/<transaction_id_param>")]
 pub async fn delete_transaction_route(
     db: &rocket::State<PgPool>,
+    auth: AuthUser,
     transaction_id_param: UuidParam,
 ) -> Result<status::NoContent, status::Custom<String>> {
     let transaction_id = transaction_id_param.0;
-    match delete_transaction(db, transaction_id).await {
+    match delete_transaction(db, auth.0.id, transaction_id).await {
+        Ok(0) => Err(transaction_access_error(db, auth.0.id, transaction_id).await),
         Ok(_) => Ok(status::NoContent),
         Err(_) => Err(status::Custom(
             Status::InternalServerError,
@@ -0,0 +1,245 @@
+use crate::auth::tokens::hash_token;
+use crate::auth::totp::{
+    generate_recovery_codes, generate_secret, normalize_recovery_code, provisioning_uri,
+    verify_code,
+};
+use crate::auth::{session_id_from_cookies, AuthUser};
+use crate::dtos::two_factor_dtos::{
+    RecoveryCodesOutDTO, SecondFactorInDTO, TotpCodeInDTO, TotpEnrollmentOutDTO,
+    TwoFactorStatusOutDTO,
+};
+use crate::dtos::user_dtos::UserOutDTO;
+use crate::operations::two_factor_ops::*;
+use chrono::Utc;
+use rocket::http::{CookieJar, Status};
+use rocket::response::status;
+use rocket::serde::json::Json;
+use rocket::{get, post, routes, Route};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+// Checks an authenticator code or spends a recovery code for the user.
+pub async fn second_factor_accepted(
+    db: &PgPool,
+    user_id: Uuid,
+    totp_code: Option<&str>,
+    recovery_code: Option<&str>,
+) -> Result<bool, sqlx::Error> {
+    if let Some(code) = totp_code {
+        let state = match find_totp_state(db, user_id).await? {
+            Some(state) if state.totp_enabled => state,
+            _ => return Ok(false),
+        };
+        let secret = state.totp_secret.unwrap_or_default();
+        return match verify_code(&secret, code, Utc::now().timestamp(), state.totp_last_step) {
+            Some(step) => record_totp_step(db, user_id, step).await,
+            None => Ok(false),
+        };
+    }
+    if let Some(code) = recovery_code {
+        return use_recovery_code(db, user_id, &hash_token(&normalize_recovery_code(code))).await;
+    }
+
+    Ok(false)
+}
+
+#[get("/auth/totp")]
+pub async fn get_two_factor_status(
+    db: &rocket::State<PgPool>,
+    auth: AuthUser,
+) -> Result<Json<TwoFactorStatusOutDTO>, status::Custom<String>> {
+    let status = async {
+        let enabled = find_totp_state(db, auth.0.id)
+            .await?
+            .is_some_and(|state| state.totp_enabled);
+        let recovery_codes_remaining = count_unused_recovery_codes(db, auth.0.id).await?;
+        Ok::<_, sqlx::Error>(TwoFactorStatusOutDTO {
+            enabled,
+            recovery_codes_remaining,
+        })
+    };
+
+    match status.await {
+        Ok(status) => Ok(Json(status)),
+        Err(_) => Err(status::Custom(
+            Status::InternalServerError,
+            "Failed to fetch two-factor status.".to_string(),
+        )),
+    }
+}
+
+#[post("/auth/totp/enroll")]
+pub async fn enroll_totp(
+    db: &rocket::State<PgPool>,
+    auth: AuthUser,
+) -> Result<Json<TotpEnrollmentOutDTO>, status::Custom<String>> {
+    let secret = generate_secret();
+    match set_pending_totp_secret(db, auth.0.id, &secret).await {
+        Ok(0) => Err(status::Custom(
+            Status::Conflict,
+            "Two-factor authentication is already enabled.".to_string(),
+        )),
+        Ok(_) => Ok(Json(TotpEnrollmentOutDTO {
+            provisioning_uri: provisioning_uri(&secret, &auth.0.email),
+            secret,
+        })),
+        Err(_) => Err(status::Custom(
+            Status::InternalServerError,
+            "Failed to start two-factor enrolment.".to_string(),
+        )),
+    }
+}
+
+#[post("/auth/totp/confirm", data = "<code_in>")]
+pub async fn confirm_totp(
+    db: &rocket::State<PgPool>,
+    cookies: &CookieJar<'_>,
+    auth: AuthUser,
+    code_in: Json<TotpCodeInDTO>,
+) -> Result<Json<RecoveryCodesOutDTO>, status::Custom<String>> {
+    let internal_error = || {
+        status::Custom(
+            Status::InternalServerError,
+            "Failed to enable two-factor authentication.".to_string(),
+        )
+    };
+
+    let state = find_totp_state(db, auth.0.id)
+        .await
+        .map_err(|_| internal_error())?;
+    let (secret, last_step) = match state {
+        Some(state) if state.totp_enabled => {
+            return Err(status::Custom(
+                Status::Conflict,
+                "Two-factor authentication is already enabled.".to_string(),
+            ))
+        }
+        Some(state) if state.totp_secret.is_some() => {
+            (state.totp_secret.unwrap_or_default(), state.totp_last_step)
+        }
+        _ => {
+            return Err(status::Custom(
+                Status::BadRequest,
+                "Start two-factor enrolment first.".to_string(),
+            ))
+        }
+    };
+
+    let step = verify_code(&secret, &code_in.code, Utc::now().timestamp(), last_step);
+    let accepted = match step {
+        Some(step) => record_totp_step(db, auth.0.id, step)
+            .await
+            .map_err(|_| internal_error())?,
+        None => false,
+    };
+    if !accepted {
+        return Err(status::Custom(
+            Status::BadRequest,
+            "Invalid two-factor code.".to_string(),
+        ));
+    }
+
+    let recovery_codes = generate_recovery_codes();
+    let recovery_code_hashes: Vec<String> =
+        recovery_codes.iter().map(|code| hash_token(code)).collect();
+    let session_id = session_id_from_cookies(cookies).unwrap_or_default();
+    enable_totp(db, auth.0.id, session_id, &recovery_code_hashes)
+        .await
+        .map_err(|_| internal_error())?;
+
+    Ok(Json(RecoveryCodesOutDTO { recovery_codes }))
+}
+
+// Completes a session that was opened without the second factor, such as one
+// created by an identity provider login.
+#[post("/auth/totp/verify", data = "<second_factor_in>")]
+pub async fn verify_totp(
+    db: &rocket::State<PgPool>,
+    cookies: &CookieJar<'_>,
+    second_factor_in: Json<SecondFactorInDTO>,
+) -> Result<Json<UserOutDTO>, status::Custom<String>> {
+    let internal_error = || {
+        status::Custom(
+            Status::InternalServerError,
+            "Failed to verify two-factor code.".to_string(),
+        )
+    };
+
+    let session_id = session_id_from_cookies(cookies)
+        .ok_or_else(|| status::Custom(Status::Unauthorized, "Not logged in.".to_string()))?;
+    let user = find_pending_two_factor_user(db, session_id)
+        .await
+        .map_err(|_| internal_error())?
+        .ok_or_else(|| {
+            status::Custom(
+                Status::Unauthorized,
+                "No two-factor verification is pending.".to_string(),
+            )
+        })?;
+
+    let accepted = second_factor_accepted(
+        db,
+        user.id,
+        second_factor_in.totp_code.as_deref(),
+        second_factor_in.recovery_code.as_deref(),
+    )
+    .await
+    .map_err(|_| internal_error())?;
+    if !accepted {
+        return Err(status::Custom(
+            Status::Unauthorized,
+            "Invalid two-factor code.".to_string(),
+        ));
+    }
+
+    mark_session_two_factor_verified(db, session_id)
+        .await
+        .map_err(|_| internal_error())?;
+
+    Ok(Json(user.to_user_out_dto()))
+}
+
+#[post("/auth/totp/disable", data = "<second_factor_in>")]
+pub async fn disable_two_factor(
+    db: &rocket::State<PgPool>,
+    auth: AuthUser,
+    second_factor_in: Json<SecondFactorInDTO>,
+) -> Result<status::NoContent, status::Custom<String>> {
+    let internal_error = || {
+        status::Custom(
+            Status::InternalServerError,
+            "Failed to disable two-factor authentication.".to_string(),
+        )
+    };
+
+    let accepted = second_factor_accepted(
+        db,
+        auth.0.id,
+        second_factor_in.totp_code.as_deref(),
+        second_factor_in.recovery_code.as_deref(),
+    )
+    .await
+    .map_err(|_| internal_error())?;
+    if !accepted {
+        return Err(status::Custom(
+            Status::BadRequest,
+            "Invalid two-factor code.".to_string(),
+        ));
+    }
+
+    disable_totp(db, auth.0.id)
+        .await
+        .map_err(|_| internal_error())?;
+
+    Ok(status::NoContent)
+}
+
+pub fn two_factor_routes() -> Vec<Route> {
+    routes![
+        get_two_factor_status,
+        enroll_totp,
+        confirm_totp,
+        verify_totp,
+        disable_two_factor
+    ]
+}
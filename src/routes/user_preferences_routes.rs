@@ -0,0 +1,49 @@
+use crate::auth::AuthUser;
+use crate::dtos::user_preferences_dtos::{UserPreferencesInDTO, UserPreferencesOutDTO};
+use crate::operations::user_preferences_ops::*;
+use rocket::http::Status;
+use rocket::response::status;
+use rocket::serde::json::Json;
+use rocket::{get, patch, routes, Route};
+use sqlx::PgPool;
+
+#[get("/users/me/preferences")]
+pub async fn get_user_preferences(
+    db: &rocket::State<PgPool>,
+    auth: AuthUser,
+) -> Result<Json<UserPreferencesOutDTO>, status::Custom<String>> {
+    match find_user_preferences(db, auth.0.id).await {
+        Ok(preferences) => Ok(Json(preferences.to_user_preferences_out_dto())),
+        Err(_) => Err(status::Custom(
+            Status::InternalServerError,
+            "Failed to fetch preferences.".to_string(),
+        )),
+    }
+}
+
+#[patch("/users/me/preferences", data = "<preferences_in>")]
+pub async fn patch_user_preferences(
+    db: &rocket::State<PgPool>,
+    auth: AuthUser,
+    preferences_in: Json<UserPreferencesInDTO>,
+) -> Result<Json<UserPreferencesOutDTO>, status::Custom<String>> {
+    let mut preferences_in = preferences_in.into_inner();
+    preferences_in.base_currency = preferences_in
+        .base_currency
+        .map(|currency| currency.trim().to_uppercase());
+    if let Err(message) = preferences_in.validate() {
+        return Err(status::Custom(Status::BadRequest, message.to_string()));
+    }
+
+    match update_user_preferences(db, auth.0.id, &preferences_in).await {
+        Ok(preferences) => Ok(Json(preferences.to_user_preferences_out_dto())),
+        Err(_) => Err(status::Custom(
+            Status::InternalServerError,
+            "Failed to update preferences.".to_string(),
+        )),
+    }
+}
+
+pub fn user_preferences_routes() -> Vec<Route> {
+    routes![get_user_preferences, patch_user_preferences]
+}
@@ -1,4 +1,6 @@
+use crate::auth::{AdminUser, AuthUser};
 use crate::dtos::user_dtos::{UserInDTO, UserOutDTO};
+use crate::operations::admin_ops::is_admin;
 use crate::operations::user_ops::*;
 // use personal_finance_tracker::dtos::user_dtos::{UserInDTO, UserOutDTO};
 use crate::uuid_param::UuidParam;
@@ -7,10 +9,34 @@ use rocket::response::status;
 use rocket::serde::json::Json;
 use rocket::{delete, get, patch, post, routes, Route};
 use sqlx::PgPool;
+use uuid::Uuid;
+
+// Regular users only reach their own profile; admins reach every profile.
+async fn check_profile_access(
+    db: &PgPool,
+    auth: &AuthUser,
+    user_id: Uuid,
+) -> Result<(), status::Custom<String>> {
+    if auth.0.id == user_id {
+        return Ok(());
+    }
+    match is_admin(db, auth.0.id).await {
+        Ok(true) => Ok(()),
+        Ok(false) => Err(status::Custom(
+            Status::NotFound,
+            "User not found.".to_string(),
+        )),
+        Err(_) => Err(status::Custom(
+            Status::InternalServerError,
+            "Failed to fetch user.".to_string(),
+        )),
+    }
+}
 
 #[get("/users")]
 pub async fn get_all_users(
     db: &rocket::State<PgPool>,
+    _admin: AdminUser,
 ) -> Result<Json<Vec<UserOutDTO>>, status::Custom<String>> {
     match fetch_all_users(db).await {
         Ok(users) => {
@@ -30,9 +56,11 @@ pub async fn get_all_users(
 #[get("/users/<user_id_param>")]
 pub async fn get_user_by_id(
     db: &rocket::State<PgPool>,
+    auth: AuthUser,
     user_id_param: UuidParam,
 ) -> Result<Json<UserOutDTO>, status::Custom<String>> {
     let user_id = user_id_param.0;
+    check_profile_access(db, &auth, user_id).await?;
     match find_user_by_id(db, user_id).await {
         Ok(Some(user)) => Ok(Json(user.to_user_out_dto())),
         Ok(None) => Err(status::Custom(
@@ -49,6 +77,7 @@ pub async fn get_user_by_id(
 #[post("/users", data = "<user_in>")]
 pub async fn post_user(
     db: &rocket::State<PgPool>,
+    _admin: AdminUser,
     user_in: Json<UserInDTO>,
 ) -> Result<Json<UserOutDTO>, status::Custom<String>> {
     match create_user(db.inner(), &user_in.0).await {
@@ -63,10 +92,12 @@ pub async fn post_user(
 #[patch("/users/<user_id_param>", data = "<user_update>")]
 pub async fn update_user(
     db: &rocket::State<PgPool>,
+    auth: AuthUser,
     user_id_param: UuidParam,
     user_update: Json<UserInDTO>,
 ) -> Result<Json<UserOutDTO>, status::Custom<String>> {
     let user_id = user_id_param.0;
+    check_profile_access(db, &auth, user_id).await?;
     match update_user_in_db(db, user_id, &user_update.0).await {
         Ok(user) => Ok(Json(user.to_user_out_dto())),
         Err(_) => Err(status::Custom(
@@ -79,6 +110,7 @@ pub async fn update_user(
 #[delete("/users/<user_id_param>")]
 pub async fn delete_user_by_id(
     db: &rocket::State<PgPool>,
+    _admin: AdminUser,
     user_id_param: UuidParam,
 ) -> Result<status::NoContent, status::Custom<String>> {
     let user_id = user_id_param.0;
@@ -0,0 +1,92 @@
+use uuid::Uuid;
+
+use crate::{
+    dtos::allocation_dtos::{AllocationPlanOutDTO, PlannedAllocationOutDTO},
+    enums::custom_enums::{AllocationAmountType, AllocationTargetType},
+    models::{
+        allocation::{AllocationRule, AllocationRuleTarget},
+        transactions::Transaction,
+    },
+    money::Money,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlannedAllocation {
+    pub target_type: AllocationTargetType,
+    pub target_id: Uuid,
+    pub amount: Money,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AllocationPlan {
+    pub allocations: Vec<PlannedAllocation>,
+    pub unallocated: Money,
+}
+
+impl AllocationPlan {
+    pub fn to_allocation_plan_out_dto(
+        &self,
+        transaction: &Transaction,
+        rule: &AllocationRule,
+    ) -> AllocationPlanOutDTO {
+        AllocationPlanOutDTO {
+            transaction_id: transaction.id,
+            rule_id: rule.id,
+            rule_name: rule.name.clone(),
+            income_amount: transaction.amount,
+            allocations: self
+                .allocations
+                .iter()
+                .map(|allocation| PlannedAllocationOutDTO {
+                    target_type: allocation.target_type,
+                    target_id: allocation.target_id,
+                    amount: allocation.amount,
+                })
+                .collect(),
+            unallocated: self.unallocated,
+        }
+    }
+}
+
+// Fixed amounts are taken first, in the rule's order, and percentages are
+// always calculated from the full income and rounded to the currency's minor
+// unit. Once the income runs out later targets are capped, so a plan never
+// hands out more than was received.
+pub fn plan_allocations(
+    income: Money,
+    targets: &[AllocationRuleTarget],
+    currency: &str,
+) -> AllocationPlan {
+    let mut ordered: Vec<&AllocationRuleTarget> = targets.iter().collect();
+    ordered.sort_by_key(|target| {
+        (
+            target.amount_type == AllocationAmountType::Percentage,
+            target.position,
+        )
+    });
+
+    let mut remaining = income.max(Money::ZERO).round_to(currency);
+    let mut allocations = Vec::new();
+    for target in ordered {
+        let wanted = match target.amount_type {
+            AllocationAmountType::Fixed => Money::new(target.value),
+            AllocationAmountType::Percentage => income.percent(target.value),
+        };
+        let amount = wanted.round_to(currency).min(remaining);
+        if !amount.is_positive() {
+            continue;
+        }
+
+        remaining -= amount;
+        allocations.push(PlannedAllocation {
+            target_type: target.target_type,
+            target_id: target.target_id,
+            amount,
+        });
+    }
+
+    AllocationPlan {
+        allocations,
+        unallocated: remaining,
+    }
+}
@@ -0,0 +1,2 @@
+pub mod allocation;
+pub mod round_up;
@@ -0,0 +1,21 @@
+use rust_decimal::Decimal;
+
+use crate::money::Money;
+
+pub const ROUND_UP_INCREMENTS: [i32; 3] = [1, 5, 10];
+
+pub fn is_valid_increment(round_to: i32) -> bool {
+    ROUND_UP_INCREMENTS.contains(&round_to)
+}
+
+// How much is needed to bring `amount` up to the next multiple of `round_to`.
+// An amount that is already a multiple rounds up by nothing.
+pub fn round_up_difference(amount: Money, round_to: i32) -> Money {
+    let step = Decimal::from(round_to);
+    if step <= Decimal::ZERO {
+        return Money::ZERO;
+    }
+
+    let amount = amount.abs().amount();
+    Money::new((step - amount % step) % step)
+}
@@ -0,0 +1,184 @@
+use chrono::{Duration, Months, NaiveDate};
+use rust_decimal::Decimal;
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+use crate::{
+    dtos::subscription_dtos::SubscriptionCandidateOutDTO, enums::custom_enums::RecurrenceFrequency,
+    models::subscription::Charge, money::Money,
+};
+
+// Far enough back to see a yearly charge come round twice.
+pub const LOOKBACK_DAYS: i64 = 800;
+// How far a charge may stray from the typical one, in percent.
+pub const AMOUNT_TOLERANCE: Decimal = Decimal::from_parts(25, 0, 0, false, 0);
+
+// An interval charges can repeat at, with how many days either side of it
+// still count.
+struct Cadence {
+    frequency: RecurrenceFrequency,
+    every: i32,
+    days: i64,
+    tolerance: i64,
+    per_year: i64,
+    min_charges: usize,
+}
+
+const CADENCES: [Cadence; 5] = [
+    Cadence {
+        frequency: RecurrenceFrequency::Weekly,
+        every: 1,
+        days: 7,
+        tolerance: 1,
+        per_year: 52,
+        min_charges: 4,
+    },
+    Cadence {
+        frequency: RecurrenceFrequency::Weekly,
+        every: 2,
+        days: 14,
+        tolerance: 2,
+        per_year: 26,
+        min_charges: 3,
+    },
+    Cadence {
+        frequency: RecurrenceFrequency::Monthly,
+        every: 1,
+        days: 30,
+        tolerance: 4,
+        per_year: 12,
+        min_charges: 3,
+    },
+    Cadence {
+        frequency: RecurrenceFrequency::Monthly,
+        every: 3,
+        days: 91,
+        tolerance: 6,
+        per_year: 4,
+        min_charges: 3,
+    },
+    Cadence {
+        frequency: RecurrenceFrequency::Yearly,
+        every: 1,
+        days: 365,
+        tolerance: 8,
+        per_year: 1,
+        min_charges: 2,
+    },
+];
+
+impl Cadence {
+    fn fits(&self, interval: i64) -> bool {
+        (interval - self.days).abs() <= self.tolerance
+    }
+
+    fn next_after(&self, date: NaiveDate) -> Option<NaiveDate> {
+        match self.frequency {
+            RecurrenceFrequency::Daily => {
+                date.checked_add_signed(Duration::days(i64::from(self.every)))
+            }
+            RecurrenceFrequency::Weekly => {
+                date.checked_add_signed(Duration::weeks(i64::from(self.every)))
+            }
+            RecurrenceFrequency::Monthly => date.checked_add_months(Months::new(self.every as u32)),
+            RecurrenceFrequency::Yearly => {
+                date.checked_add_months(Months::new(self.every as u32 * 12))
+            }
+        }
+    }
+}
+
+// What identifies a payee across charges: the title lowercased, with words
+// that carry digits (order numbers, card endings, dates) left out.
+pub fn payee_key(title: &str) -> String {
+    title
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty() && !word.chars().any(|c| c.is_numeric()))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn median<T: Ord + Copy>(values: &[T]) -> T {
+    let mut sorted = values.to_vec();
+    sorted.sort();
+    sorted[(sorted.len() - 1) / 2]
+}
+
+// Charges to the same payee from the same account that repeat at one of the
+// known cadences for roughly the same amount, and haven't stopped coming.
+// `charges` must be in date order.
+pub fn detect_subscriptions(
+    charges: &[Charge],
+    today: NaiveDate,
+) -> Vec<SubscriptionCandidateOutDTO> {
+    let mut by_payee: BTreeMap<(Uuid, String), Vec<&Charge>> = BTreeMap::new();
+    for charge in charges {
+        let payee = payee_key(&charge.title);
+        if !payee.is_empty() {
+            by_payee
+                .entry((charge.account_id, payee))
+                .or_default()
+                .push(charge);
+        }
+    }
+
+    let mut candidates = Vec::new();
+    for ((account_id, payee), charges) in by_payee {
+        if charges.len() < 2 {
+            continue;
+        }
+        let intervals: Vec<i64> = charges
+            .windows(2)
+            .map(|pair| (pair[1].day - pair[0].day).num_days())
+            .collect();
+        let typical_interval = median(&intervals);
+        let Some(cadence) = CADENCES.iter().find(|cadence| {
+            cadence.fits(typical_interval)
+                && charges.len() >= cadence.min_charges
+                && intervals.iter().all(|interval| cadence.fits(*interval))
+        }) else {
+            continue;
+        };
+
+        let amounts: Vec<Money> = charges.iter().map(|charge| charge.amount).collect();
+        let typical_amount = median(&amounts);
+        let allowed = typical_amount.percent(AMOUNT_TOLERANCE);
+        if amounts
+            .iter()
+            .any(|amount| (*amount - typical_amount).abs() > allowed)
+        {
+            continue;
+        }
+
+        let first = charges[0];
+        let last = charges[charges.len() - 1];
+        let Some(next_expected_charge) = cadence.next_after(last.day) else {
+            continue;
+        };
+        // Overdue by more than the cadence allows: most likely cancelled.
+        if next_expected_charge + Duration::days(cadence.tolerance) < today {
+            continue;
+        }
+
+        candidates.push(SubscriptionCandidateOutDTO {
+            account_id,
+            payee,
+            name: last.title.trim().to_string(),
+            category_id: last.category_id,
+            frequency: cadence.frequency,
+            every: cadence.every,
+            amount: last.amount,
+            currency: last.currency.clone(),
+            charge_count: charges.len(),
+            first_charge: first.day,
+            last_charge: last.day,
+            next_expected_charge,
+            annualized_cost: (last.amount * Decimal::from(cadence.per_year))
+                .round_to(&last.currency),
+        });
+    }
+    candidates.sort_by_key(|candidate| Reverse(candidate.annualized_cost));
+    candidates
+}
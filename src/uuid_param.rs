@@ -1,3 +1,4 @@
+use rocket::form::{self, FromFormField, ValueField};
 use rocket::request::FromParam;
 use std::str::FromStr;
 use uuid::Uuid;
@@ -12,3 +13,11 @@ impl<'r> FromParam<'r> for UuidParam {
             .map_err(|e| e.to_string())
     }
 }
+
+impl<'r> FromFormField<'r> for UuidParam {
+    fn from_value(field: ValueField<'r>) -> form::Result<'r, Self> {
+        Uuid::from_str(field.value)
+            .map(UuidParam)
+            .map_err(|e| form::Error::validation(e.to_string()).into())
+    }
+}
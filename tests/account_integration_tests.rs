@@ -1,11 +1,17 @@
-use personal_finance_tracker::dtos::account_dtos::{AccountInDTO, AccountOutDTO};
+use personal_finance_tracker::dtos::account_dtos::{
+    AccountBalanceOutDTO, AccountInDTO, AccountOutDTO,
+};
+use personal_finance_tracker::dtos::category_dtos::CategoryInDTO;
+use personal_finance_tracker::dtos::transaction_dtos::AccountTransactionOutDTO;
 use personal_finance_tracker::enums::custom_enums::AccountType;
 use personal_finance_tracker::models::user::User;
+use personal_finance_tracker::money::Money;
 use personal_finance_tracker::operations::account_ops::create_account;
+use personal_finance_tracker::operations::category_ops::create_category;
 use personal_finance_tracker::operations::user_ops::create_user;
 use rocket::http::{ContentType, Status};
 use rocket::serde::json::serde_json;
-use serde_json::json;
+use serde_json::{json, Value};
 
 use personal_finance_tracker::dtos::user_dtos::UserInDTO;
 use sqlx::{Error, PgPool};
@@ -21,7 +27,7 @@ async fn before_test(pool: &PgPool, username: &str, email: &str) -> Result<User,
         email: email.to_string(),
     };
 
-    let user = create_user(&pool, &user_dto).await?;
+    let user = create_user(pool, &user_dto).await?;
     Ok(user)
 }
 
@@ -53,12 +59,12 @@ async fn create_account_integration_test() {
 
     let account_dto = AccountInDTO {
         name: "Test".to_string(),
-        balance: 100.0,
+        balance: Money::from(100),
         account_type: AccountType::Bank,
-        user_id: user.id,
+        currency: None,
     };
 
-    let account = create_account(&pool, &account_dto)
+    let account = create_account(&pool, user.id, &account_dto)
         .await
         .expect("Failed to create account");
 
@@ -72,10 +78,23 @@ async fn create_account_integration_test() {
 
 #[rocket::async_test]
 async fn get_all_accounts_test() {
-    let (client, _pool) = setup().await;
+    let (client, pool) = setup().await;
+
+    let user = before_test(&pool, "listuser", "listuser@example.com")
+        .await
+        .expect("Failed to initialize test database");
+    common::login(&client, &pool, user.id).await;
+
     let response = client.get("/accounts").dispatch().await;
 
     assert_eq!(response.status(), Status::Ok);
+
+    let accounts: Vec<AccountOutDTO> =
+        serde_json::from_str(&response.into_string().await.expect("Response has a body"))
+            .expect("Valid list of AccountOutDTO");
+    assert!(accounts.is_empty());
+
+    cleanup_test_user(&pool, "listuser", "listuser@example.com").await;
 }
 
 #[rocket::async_test]
@@ -84,14 +103,16 @@ async fn get_account_by_id_integration_test() {
 
     match before_test(&pool, "testuserid", "testuserid@example.com").await {
         Ok(user) => {
+            common::login(&client, &pool, user.id).await;
+
             let account_dto = AccountInDTO {
                 name: "Test".to_string(),
-                balance: 100.0,
+                balance: Money::from(100),
                 account_type: AccountType::Bank,
-                user_id: user.id,
+                currency: None,
             };
 
-            match create_account(&pool, &account_dto).await {
+            match create_account(&pool, user.id, &account_dto).await {
                 Ok(account) => {
                     assert_eq!(account.name, "Test");
 
@@ -110,16 +131,16 @@ async fn get_account_by_id_integration_test() {
                                 cleanup_test(&pool, account.id).await;
                                 assert_eq!(account.name, founded_account.name.to_string());
                             }
-                            Err(_) => assert!(false, "Failed to deserialize AccountOutDTO"),
+                            Err(_) => panic!("Failed to deserialize AccountOutDTO"),
                         }
                     } else {
-                        assert!(false, "Response does not have a body");
+                        panic!("Response does not have a body");
                     }
                 }
-                Err(_) => assert!(false, "Failed to create account"),
+                Err(_) => panic!("Failed to create account"),
             }
         }
-        Err(_) => assert!(false, "Failed to initialize test database"),
+        Err(_) => panic!("Failed to initialize test database"),
     }
 }
 
@@ -130,12 +151,12 @@ async fn update_account_integration_test() {
     let user = before_test(&pool, "updateuser", "updateuser@example.com")
         .await
         .expect("Failed to initialize test database");
+    common::login(&client, &pool, user.id).await;
 
     let account_data = json!({
         "name": "Test".to_string(),
         "balance": 100.0,
         "account_type": AccountType::Bank,
-        "user_id": user.id,
     });
     let create_response = client
         .post("/accounts")
@@ -155,7 +176,6 @@ async fn update_account_integration_test() {
         "name": "Test updated",
         "balance": 150.0,
         "account_type": AccountType::Bank,
-        "user_id": user.id
     });
     let response = client
         .patch(format!("/accounts/{}", created_account.id))
@@ -171,7 +191,7 @@ async fn update_account_integration_test() {
         serde_json::from_str(&response_body).expect("Valid AccountOutDTO");
 
     assert_eq!(updated_account.name, "Test updated");
-    assert_eq!(updated_account.balance, 150.0);
+    assert_eq!(updated_account.balance, Money::from(150));
 
     cleanup_test_user(&pool, "updateuser", "updateuser@example.com").await;
     cleanup_test(&pool, updated_account.id).await;
@@ -185,14 +205,16 @@ async fn delete_account_integration_test() {
         .await
         .expect("Failed to initialize test database");
 
+    common::login(&client, &pool, user.id).await;
+
     let account_dto = AccountInDTO {
         name: "Test".to_string(),
-        balance: 100.0,
+        balance: Money::from(100),
         account_type: AccountType::Bank,
-        user_id: user.id,
+        currency: None,
     };
 
-    let account = create_account(&pool, &account_dto)
+    let account = create_account(&pool, user.id, &account_dto)
         .await
         .expect("Failed to create account");
     assert_eq!(account.name, "Test");
@@ -211,3 +233,120 @@ async fn delete_account_integration_test() {
     assert_eq!(fetch_response.status(), Status::NotFound);
     cleanup_test_user(&pool, "testuser", "testuser@example.com").await;
 }
+
+#[rocket::async_test]
+async fn account_balance_history_integration_test() {
+    let (client, pool) = setup().await;
+
+    let user = before_test(&pool, "balanceuser", "balanceuser@example.com")
+        .await
+        .expect("Failed to initialize test database");
+    common::login(&client, &pool, user.id).await;
+
+    let account = create_account(
+        &pool,
+        user.id,
+        &AccountInDTO {
+            name: "Checking".to_string(),
+            balance: Money::from(1000),
+            account_type: AccountType::Bank,
+            currency: None,
+        },
+    )
+    .await
+    .expect("Failed to create account");
+    let category = create_category(
+        &pool,
+        user.id,
+        &CategoryInDTO {
+            name: "General".to_string(),
+        },
+    )
+    .await
+    .expect("Failed to create category");
+
+    for (date, amount, transaction_type) in [
+        ("2025-12-20T12:00:00Z", "200", "Income"),
+        ("2026-01-10T12:00:00Z", "50", "Expense"),
+        ("2026-02-01T12:00:00Z", "25", "Expense"),
+    ] {
+        let response = client
+            .post("/transactions")
+            .header(ContentType::JSON)
+            .body(
+                json!({
+                    "title": "Entry",
+                    "amount": amount,
+                    "date": date,
+                    "category_id": category.id,
+                    "transaction_type": transaction_type,
+                    "account_id": account.id,
+                })
+                .to_string(),
+            )
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::Ok);
+    }
+
+    for (query, expected) in [
+        ("?at=2025-12-31", Money::from(1075)),
+        ("?at=2025-12-19", Money::from(875)),
+        ("?at=2026-02-01", Money::from(1000)),
+        ("", Money::from(1000)),
+    ] {
+        let response = client
+            .get(format!("/accounts/{}/balance{}", account.id, query))
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::Ok);
+        let balance: AccountBalanceOutDTO =
+            serde_json::from_str(&response.into_string().await.expect("Response has a body"))
+                .expect("Valid AccountBalanceOutDTO");
+        assert_eq!(balance.balance, expected, "{}", query);
+        assert_eq!(balance.currency, "USD");
+    }
+
+    let response = client
+        .get(format!(
+            "/accounts/{}/transactions?running_balance=true",
+            account.id
+        ))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let transactions: Vec<AccountTransactionOutDTO> =
+        serde_json::from_str(&response.into_string().await.expect("Response has a body"))
+            .expect("Valid list of AccountTransactionOutDTO");
+    let running: Vec<(Money, Option<Money>)> = transactions
+        .iter()
+        .map(|t| (t.transaction.amount, t.running_balance))
+        .collect();
+    assert_eq!(
+        running,
+        vec![
+            (Money::from(25), Some(Money::from(1000))),
+            (Money::from(50), Some(Money::from(1025))),
+            (Money::from(200), Some(Money::from(1075))),
+        ]
+    );
+
+    let response = client
+        .get(format!("/accounts/{}/transactions", account.id))
+        .dispatch()
+        .await;
+    let transactions: Vec<Value> =
+        serde_json::from_str(&response.into_string().await.expect("Response has a body"))
+            .expect("Valid JSON");
+    assert_eq!(transactions.len(), 3);
+    assert!(transactions[0].get("running_balance").is_none());
+    assert_eq!(transactions[0]["title"], json!("Entry"));
+
+    let response = client
+        .get(format!("/accounts/{}/balance", Uuid::new_v4()))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::NotFound);
+
+    cleanup_test_user(&pool, "balanceuser", "balanceuser@example.com").await;
+}
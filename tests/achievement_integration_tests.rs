@@ -1,8 +1,14 @@
-use chrono::Local;
+use chrono::{Local, Utc};
 use personal_finance_tracker::dtos::saving_goal_dtos::SavingGoalInDTO;
 use personal_finance_tracker::dtos::user_dtos::UserInDTO;
-use personal_finance_tracker::operations::achievement_ops::create_achievement;
-use personal_finance_tracker::operations::saving_goal_ops::create_saving_goal;
+use personal_finance_tracker::enums::custom_enums::AchievementKind;
+use personal_finance_tracker::money::Money;
+use personal_finance_tracker::operations::achievement_ops::{
+    create_achievement, fetch_new_achievements_for_user,
+};
+use personal_finance_tracker::operations::saving_goal_ops::{
+    create_saving_goal, update_saving_goal_in_db,
+};
 use personal_finance_tracker::operations::user_ops::create_user;
 use rocket::http::{ContentType, Status};
 use rocket::serde::json::serde_json;
@@ -26,7 +32,7 @@ pub async fn before_test(
         username: username.to_string(),
         email: email.to_string(),
     };
-    let user = create_user(&pool, &user_dto).await?;
+    let user = create_user(pool, &user_dto).await?;
 
     let deadline_option = chrono::NaiveDate::from_ymd_opt(2024, 12, 31);
 
@@ -35,13 +41,12 @@ pub async fn before_test(
     //Create a saving goal
     let saving_goal_dto = SavingGoalInDTO {
         title: "Vacation".to_string(),
-        target_amount: 2000.0,
-        current_amount: 100.0,
+        target_amount: Money::from(2000),
+        current_amount: Money::from(100),
         deadline,
-        user_id: user.id,
     };
 
-    let saving_goal = create_saving_goal(&pool, &saving_goal_dto).await?;
+    let saving_goal = create_saving_goal(pool, user.id, &saving_goal_dto).await?;
 
     Ok((user.id, saving_goal.id))
 }
@@ -75,10 +80,12 @@ async fn create_achievement_integration_test() {
         .await
         .expect("Failed to initialize test database");
 
+    common::login(&client, &pool, user_id).await;
+
     let achievement_data = json!({
         "goal_id": saving_goal_id,
         "amount_saved": 1000.0,
-        "date_achieved": Local::now().naive_local(),
+        "date_achieved": Utc::now(),
     });
 
     let response = client
@@ -108,8 +115,8 @@ async fn create_achievement_integration_test_type_two() {
 
     let achievement_dto = AchievementInDTO {
         goal_id: saving_goal_id,
-        date_achieved: Local::now().naive_local(),
-        amount_saved: 1200.0,
+        date_achieved: Utc::now(),
+        amount_saved: Money::from(1200),
     };
 
     let response = create_achievement(&pool, &achievement_dto).await;
@@ -118,7 +125,7 @@ async fn create_achievement_integration_test_type_two() {
 
     let achievement = response.unwrap();
 
-    assert_eq!(achievement.amount_saved, 1200.0);
+    assert_eq!(achievement.amount_saved, Money::from(1200));
     assert_eq!(achievement.goal_id, saving_goal_id);
 
     cleanup(&pool, user_id, saving_goal_id, Some(achievement.id)).await;
@@ -126,11 +133,23 @@ async fn create_achievement_integration_test_type_two() {
 
 #[rocket::async_test]
 async fn get_all_achievements_integration_test() {
-    let (client, _pool) = setup().await;
+    let (client, pool) = setup().await;
+
+    let (user_id, saving_goal_id) = before_test(&pool, "listuser", "listuser@example.com")
+        .await
+        .expect("Failed to initialize test database");
+    common::login(&client, &pool, user_id).await;
 
     let response = client.get("/achievements").dispatch().await;
 
     assert_eq!(response.status(), Status::Ok);
+
+    let achievements: Vec<AchievementOutDTO> =
+        serde_json::from_str(&response.into_string().await.expect("Response has a body"))
+            .expect("Valid list of AchievementOutDTO");
+    assert!(achievements.is_empty());
+
+    cleanup(&pool, user_id, saving_goal_id, None).await;
 }
 
 #[rocket::async_test]
@@ -141,10 +160,12 @@ async fn get_achievement_by_id_integration_test() {
         .await
         .expect("Failed to initialize test database");
 
+    common::login(&client, &pool, user_id).await;
+
     let achievement_dto = AchievementInDTO {
         goal_id: saving_goal_id,
-        date_achieved: Local::now().naive_local(),
-        amount_saved: 1500.0,
+        date_achieved: Utc::now(),
+        amount_saved: Money::from(1500),
     };
 
     let response = create_achievement(&pool, &achievement_dto).await;
@@ -175,7 +196,9 @@ async fn update_achievement_integration_test() {
         .await
         .expect("Failed to initialize test database");
 
-    let first_date = Local::now().naive_local();
+    common::login(&client, &pool, user_id).await;
+
+    let first_date = Utc::now();
     let achievement_data = json!({
         "goal_id": saving_goal_id,
         "amount_saved": 1600.0,
@@ -195,7 +218,7 @@ async fn update_achievement_integration_test() {
     )
     .expect("Valid AchievementOutDTO");
 
-    let updated_date = Local::now().naive_local();
+    let updated_date = Utc::now();
     let update_data = json!({
         "goal_id": saving_goal_id,
         "amount_saved": 1700.0,
@@ -215,7 +238,7 @@ async fn update_achievement_integration_test() {
         serde_json::from_str(&response_body).expect("Valid AchievementOutDTO");
 
     assert_ne!(updated_achievement.date_achieved, first_date);
-    assert_eq!(updated_achievement.amount_saved, 1700.0);
+    assert_eq!(updated_achievement.amount_saved, Money::from(1700));
 
     cleanup(&pool, user_id, saving_goal_id, Some(updated_achievement.id)).await;
 }
@@ -228,10 +251,12 @@ async fn delete_achievement_integration_test() {
         .await
         .expect("Failed to initialize test database");
 
+    common::login(&client, &pool, user_id).await;
+
     let achievement_dto = AchievementInDTO {
         goal_id: saving_goal_id,
-        date_achieved: Local::now().naive_local(),
-        amount_saved: 1000.0,
+        date_achieved: Utc::now(),
+        amount_saved: Money::from(1000),
     };
 
     let response_saved = create_achievement(&pool, &achievement_dto).await;
@@ -253,3 +278,146 @@ async fn delete_achievement_integration_test() {
 
     cleanup(&pool, user_id, saving_goal_id, None).await;
 }
+
+fn goal_update(current_amount: Money, deadline: chrono::NaiveDate) -> SavingGoalInDTO {
+    SavingGoalInDTO {
+        title: "Vacation".to_string(),
+        target_amount: Money::from(2000),
+        current_amount,
+        deadline,
+    }
+}
+
+#[rocket::async_test]
+async fn milestone_achievements_are_awarded_once_integration_test() {
+    let (_client, pool) = setup().await;
+
+    let (user_id, saving_goal_id) =
+        before_test(&pool, "milestoneuser", "milestoneuser@example.com")
+            .await
+            .expect("Failed to initialize test database");
+    let deadline = chrono::NaiveDate::from_ymd_opt(2024, 12, 31).expect("Invalid date");
+
+    update_saving_goal_in_db(
+        &pool,
+        user_id,
+        saving_goal_id,
+        &goal_update(Money::from(1000), deadline),
+    )
+    .await
+    .expect("Failed to update saving goal");
+    update_saving_goal_in_db(
+        &pool,
+        user_id,
+        saving_goal_id,
+        &goal_update(Money::from(1100), deadline),
+    )
+    .await
+    .expect("Failed to update saving goal");
+
+    let achievements = fetch_new_achievements_for_user(&pool, user_id)
+        .await
+        .expect("Failed to fetch achievements");
+    let milestones: Vec<Option<i32>> = achievements.iter().map(|a| a.milestone).collect();
+
+    assert_eq!(milestones, vec![Some(25), Some(50)]);
+    assert!(achievements
+        .iter()
+        .all(|a| a.kind == AchievementKind::Milestone && a.goal_id == saving_goal_id));
+
+    cleanup(&pool, user_id, saving_goal_id, None).await;
+}
+
+#[rocket::async_test]
+async fn early_completion_achievement_integration_test() {
+    let (_client, pool) = setup().await;
+
+    let (user_id, saving_goal_id) = before_test(&pool, "earlyuser", "earlyuser@example.com")
+        .await
+        .expect("Failed to initialize test database");
+    let deadline = Local::now().date_naive() + chrono::Duration::days(30);
+
+    update_saving_goal_in_db(
+        &pool,
+        user_id,
+        saving_goal_id,
+        &goal_update(Money::from(2000), deadline),
+    )
+    .await
+    .expect("Failed to update saving goal");
+
+    let achievements = fetch_new_achievements_for_user(&pool, user_id)
+        .await
+        .expect("Failed to fetch achievements");
+
+    assert_eq!(
+        achievements
+            .iter()
+            .filter(|a| a.kind == AchievementKind::Milestone)
+            .count(),
+        4
+    );
+    assert_eq!(
+        achievements
+            .iter()
+            .filter(|a| a.kind == AchievementKind::EarlyCompletion)
+            .count(),
+        1
+    );
+
+    cleanup(&pool, user_id, saving_goal_id, None).await;
+}
+
+#[rocket::async_test]
+async fn achievement_feed_integration_test() {
+    let (client, pool) = setup().await;
+
+    let (user_id, saving_goal_id) = before_test(&pool, "feeduser", "feeduser@example.com")
+        .await
+        .expect("Failed to initialize test database");
+    common::login(&client, &pool, user_id).await;
+    let deadline = chrono::NaiveDate::from_ymd_opt(2024, 12, 31).expect("Invalid date");
+
+    update_saving_goal_in_db(
+        &pool,
+        user_id,
+        saving_goal_id,
+        &goal_update(Money::from(600), deadline),
+    )
+    .await
+    .expect("Failed to update saving goal");
+
+    let response = client.get("/achievements/feed").dispatch().await;
+    assert_eq!(response.status(), Status::Ok);
+
+    let response_body = response.into_string().await.expect("Response has a body");
+    let feed: Vec<AchievementOutDTO> =
+        serde_json::from_str(&response_body).expect("Valid AchievementOutDTO list");
+    assert_eq!(feed.len(), 1);
+    assert_eq!(feed[0].milestone, Some(25));
+
+    // Reading the feed again returns the same achievements until they are
+    // acknowledged.
+    let response = client.get("/achievements/feed").dispatch().await;
+    let response_body = response.into_string().await.expect("Response has a body");
+    let again: Vec<AchievementOutDTO> =
+        serde_json::from_str(&response_body).expect("Valid AchievementOutDTO list");
+    assert_eq!(again.len(), 1);
+    assert_eq!(again[0].id, feed[0].id);
+
+    let response = client
+        .post("/achievements/feed/seen")
+        .header(ContentType::JSON)
+        .body(json!({ "achievement_ids": [feed[0].id] }).to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::NoContent);
+
+    let response = client.get("/achievements/feed").dispatch().await;
+    let response_body = response.into_string().await.expect("Response has a body");
+    let feed: Vec<AchievementOutDTO> =
+        serde_json::from_str(&response_body).expect("Valid AchievementOutDTO list");
+    assert!(feed.is_empty());
+
+    cleanup(&pool, user_id, saving_goal_id, None).await;
+}
@@ -0,0 +1,276 @@
+use personal_finance_tracker::dtos::account_dtos::AccountInDTO;
+use personal_finance_tracker::dtos::admin_dtos::{ImpersonationLogEntryOutDTO, UserOverviewOutDTO};
+use personal_finance_tracker::dtos::user_dtos::{UserInDTO, UserOutDTO};
+use personal_finance_tracker::enums::custom_enums::{AccountType, UserRole};
+use personal_finance_tracker::money::Money;
+use personal_finance_tracker::operations::account_ops::create_account;
+use personal_finance_tracker::operations::user_ops::create_user;
+use rocket::http::{ContentType, Status};
+use rocket::local::asynchronous::Client;
+use rocket::serde::json::serde_json;
+use serde_json::json;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::common::{setup, TEST_PASSWORD};
+
+mod common;
+
+pub async fn before_test(pool: &PgPool, username: &str) -> Result<Uuid, sqlx::Error> {
+    let user_dto = UserInDTO {
+        username: username.to_string(),
+        email: format!("{}@example.com", username),
+    };
+    let user = create_user(pool, &user_dto).await?;
+
+    Ok(user.id)
+}
+
+pub async fn cleanup(pool: &PgPool, user_ids: &[Uuid]) {
+    for user_id in user_ids {
+        sqlx::query("DELETE FROM impersonation_log WHERE user_id = $1 OR impersonator_id = $1")
+            .bind(user_id)
+            .execute(pool)
+            .await
+            .unwrap_or_else(|e| panic!("Failed to cleanup impersonation log: {}", e));
+        sqlx::query("DELETE FROM users WHERE id = $1")
+            .bind(user_id)
+            .execute(pool)
+            .await
+            .unwrap_or_else(|e| panic!("Failed to cleanup test users: {}", e));
+    }
+}
+
+async fn password_login(client: &Client, username: &str) -> Status {
+    client
+        .post("/auth/login")
+        .header(ContentType::JSON)
+        .body(
+            json!({
+                "email": format!("{}@example.com", username),
+                "password": TEST_PASSWORD
+            })
+            .to_string(),
+        )
+        .dispatch()
+        .await
+        .status()
+}
+
+async fn me(client: &Client) -> Status {
+    client.get("/auth/me").dispatch().await.status()
+}
+
+#[rocket::async_test]
+async fn admin_endpoints_require_admin_role_integration_test() {
+    let (client, pool) = setup().await;
+    let user_id = before_test(&pool, "adminregular")
+        .await
+        .expect("Failed to initialize test database");
+    common::login(&client, &pool, user_id).await;
+
+    assert_eq!(
+        client.get("/admin/users").dispatch().await.status(),
+        Status::Forbidden
+    );
+    assert_eq!(
+        client
+            .post(format!("/admin/users/{}/suspend", user_id))
+            .dispatch()
+            .await
+            .status(),
+        Status::Forbidden
+    );
+    assert_eq!(
+        client.get("/users").dispatch().await.status(),
+        Status::Forbidden
+    );
+    assert_eq!(
+        client
+            .get(format!("/users/{}", user_id))
+            .dispatch()
+            .await
+            .status(),
+        Status::Ok
+    );
+
+    cleanup(&pool, &[user_id]).await;
+}
+
+#[rocket::async_test]
+async fn suspend_and_force_logout_integration_test() {
+    let (admin, pool) = setup().await;
+    let admin_id = before_test(&pool, "adminsuspender")
+        .await
+        .expect("Failed to initialize test database");
+    common::login_admin(&admin, &pool, admin_id).await;
+    let (client, _) = setup().await;
+    let user_id = before_test(&pool, "adminsuspended")
+        .await
+        .expect("Failed to initialize test database");
+    common::login(&client, &pool, user_id).await;
+    create_account(
+        &pool,
+        user_id,
+        &AccountInDTO {
+            name: "Checking".to_string(),
+            account_type: AccountType::Bank,
+            balance: Money::from(10),
+            currency: None,
+        },
+    )
+    .await
+    .expect("Failed to create account");
+
+    let response = admin.get("/admin/users").dispatch().await;
+    assert_eq!(response.status(), Status::Ok);
+    let overviews: Vec<UserOverviewOutDTO> =
+        serde_json::from_str(&response.into_string().await.expect("Response has a body"))
+            .expect("Valid list of UserOverviewOutDTO");
+    let overview = overviews
+        .iter()
+        .find(|overview| overview.id == user_id)
+        .expect("User is listed");
+    assert_eq!(overview.role, UserRole::User);
+    assert_eq!(overview.account_count, 1);
+    assert_eq!(overview.active_sessions, 1);
+    assert!(overview.last_seen_at.is_some());
+
+    // Force logout ends the session but the user can sign in again.
+    assert_eq!(
+        admin
+            .post(format!("/admin/users/{}/logout", user_id))
+            .dispatch()
+            .await
+            .status(),
+        Status::NoContent
+    );
+    assert_eq!(me(&client).await, Status::Unauthorized);
+    assert_eq!(password_login(&client, "adminsuspended").await, Status::Ok);
+
+    // Suspension ends the session and blocks new ones until reactivated.
+    assert_eq!(
+        admin
+            .post(format!("/admin/users/{}/suspend", user_id))
+            .dispatch()
+            .await
+            .status(),
+        Status::NoContent
+    );
+    assert_eq!(me(&client).await, Status::Unauthorized);
+    assert_eq!(
+        password_login(&client, "adminsuspended").await,
+        Status::Forbidden
+    );
+    assert_eq!(
+        admin
+            .post(format!("/admin/users/{}/reactivate", user_id))
+            .dispatch()
+            .await
+            .status(),
+        Status::NoContent
+    );
+    assert_eq!(password_login(&client, "adminsuspended").await, Status::Ok);
+
+    // Admins can't suspend themselves.
+    assert_eq!(
+        admin
+            .post(format!("/admin/users/{}/suspend", admin_id))
+            .dispatch()
+            .await
+            .status(),
+        Status::BadRequest
+    );
+
+    cleanup(&pool, &[user_id, admin_id]).await;
+}
+
+#[rocket::async_test]
+async fn impersonation_is_recorded_integration_test() {
+    let (admin, pool) = setup().await;
+    let admin_id = before_test(&pool, "adminimpersonator")
+        .await
+        .expect("Failed to initialize test database");
+    common::login_admin(&admin, &pool, admin_id).await;
+    let user_id = before_test(&pool, "adminimpersonated")
+        .await
+        .expect("Failed to initialize test database");
+
+    let response = admin
+        .post(format!("/admin/users/{}/impersonate", user_id))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let user: UserOutDTO =
+        serde_json::from_str(&response.into_string().await.expect("Response has a body"))
+            .expect("Valid UserOutDTO");
+    assert_eq!(user.id, user_id);
+
+    // The admin now acts as the user and loses admin access meanwhile.
+    let me_response = admin.get("/auth/me").dispatch().await;
+    let current: UserOutDTO = serde_json::from_str(
+        &me_response
+            .into_string()
+            .await
+            .expect("Response has a body"),
+    )
+    .expect("Valid UserOutDTO");
+    assert_eq!(current.id, user_id);
+    assert_eq!(admin.get("/accounts").dispatch().await.status(), Status::Ok);
+    assert_eq!(
+        admin.get("/admin/users").dispatch().await.status(),
+        Status::Forbidden
+    );
+
+    assert_eq!(
+        admin
+            .post("/admin/impersonation/stop")
+            .dispatch()
+            .await
+            .status(),
+        Status::NoContent
+    );
+    assert_eq!(
+        admin.get("/admin/users").dispatch().await.status(),
+        Status::Ok
+    );
+
+    let log_response = admin
+        .get(format!("/admin/impersonation_log?user_id={}", user_id))
+        .dispatch()
+        .await;
+    assert_eq!(log_response.status(), Status::Ok);
+    let log: Vec<ImpersonationLogEntryOutDTO> = serde_json::from_str(
+        &log_response
+            .into_string()
+            .await
+            .expect("Response has a body"),
+    )
+    .expect("Valid list of ImpersonationLogEntryOutDTO");
+    let recorded: Vec<(String, String, i32)> = log
+        .iter()
+        .rev()
+        .map(|entry| (entry.method.clone(), entry.path.clone(), entry.status))
+        .collect();
+    assert!(log.iter().all(|entry| entry.impersonator_id == admin_id));
+    assert_eq!(
+        recorded,
+        vec![
+            (
+                "POST".to_string(),
+                format!("/admin/users/{}/impersonate", user_id),
+                200
+            ),
+            ("GET".to_string(), "/auth/me".to_string(), 200),
+            ("GET".to_string(), "/accounts".to_string(), 200),
+            ("GET".to_string(), "/admin/users".to_string(), 403),
+            (
+                "POST".to_string(),
+                "/admin/impersonation/stop".to_string(),
+                204
+            ),
+        ]
+    );
+
+    cleanup(&pool, &[user_id, admin_id]).await;
+}
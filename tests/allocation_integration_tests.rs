@@ -0,0 +1,532 @@
+use chrono::{Duration, Utc};
+use personal_finance_tracker::dtos::account_dtos::AccountInDTO;
+use personal_finance_tracker::dtos::allocation_dtos::{
+    AllocationOutDTO, AllocationPlanOutDTO, AllocationRuleOutDTO,
+};
+use personal_finance_tracker::dtos::category_dtos::CategoryInDTO;
+use personal_finance_tracker::dtos::household_dtos::HouseholdInDTO;
+use personal_finance_tracker::dtos::saving_goal_dtos::SavingGoalInDTO;
+use personal_finance_tracker::dtos::transaction_dtos::{TransactionInDTO, TransactionOutDTO};
+use personal_finance_tracker::dtos::user_dtos::UserInDTO;
+use personal_finance_tracker::enums::custom_enums::{
+    AccountType, AllocationAmountType, AllocationTargetType, HouseholdRole, TransactionType,
+};
+use personal_finance_tracker::models::allocation::AllocationRuleTarget;
+use personal_finance_tracker::money::Money;
+use personal_finance_tracker::operations::account_ops::{
+    account_balance_on, create_account, find_account_by_id,
+};
+use personal_finance_tracker::operations::category_ops::create_category;
+use personal_finance_tracker::operations::household_ops::{
+    add_household_member, create_household, delete_household, share_record,
+};
+use personal_finance_tracker::operations::ownership_ops::Owned;
+use personal_finance_tracker::operations::saving_goal_ops::{
+    create_saving_goal, delete_saving_goal, find_saving_goal_by_id,
+};
+use personal_finance_tracker::operations::transaction_ops::{
+    create_transaction, delete_transaction,
+};
+use personal_finance_tracker::operations::user_ops::create_user;
+use personal_finance_tracker::rules::allocation::plan_allocations;
+use rocket::http::{ContentType, Status};
+use rocket::serde::json::serde_json;
+use rust_decimal::Decimal;
+use serde_json::json;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::common::{money, setup};
+
+mod common;
+
+pub struct Fixture {
+    user_id: Uuid,
+    checking_id: Uuid,
+    savings_id: Uuid,
+    category_id: Uuid,
+    goal_id: Uuid,
+}
+
+pub async fn before_test(
+    pool: &PgPool,
+    username: &str,
+    email: &str,
+) -> Result<Fixture, sqlx::Error> {
+    let user = create_user(
+        pool,
+        &UserInDTO {
+            username: username.to_string(),
+            email: email.to_string(),
+        },
+    )
+    .await?;
+    let checking = create_account(
+        pool,
+        user.id,
+        &AccountInDTO {
+            name: "Checking".to_string(),
+            balance: Money::ZERO,
+            account_type: AccountType::Card,
+            currency: None,
+        },
+    )
+    .await?;
+    let savings = create_account(
+        pool,
+        user.id,
+        &AccountInDTO {
+            name: "Savings".to_string(),
+            balance: Money::ZERO,
+            account_type: AccountType::Bank,
+            currency: None,
+        },
+    )
+    .await?;
+    let category = create_category(
+        pool,
+        user.id,
+        &CategoryInDTO {
+            name: "Salary".to_string(),
+        },
+    )
+    .await?;
+    let goal = create_saving_goal(
+        pool,
+        user.id,
+        &SavingGoalInDTO {
+            title: "Emergency fund".to_string(),
+            target_amount: Money::from(5000),
+            current_amount: Money::ZERO,
+            deadline: chrono::NaiveDate::from_ymd_opt(2030, 12, 31).expect("Valid date"),
+        },
+    )
+    .await?;
+
+    Ok(Fixture {
+        user_id: user.id,
+        checking_id: checking.id,
+        savings_id: savings.id,
+        category_id: category.id,
+        goal_id: goal.id,
+    })
+}
+
+pub async fn cleanup(pool: &PgPool, user_id: Uuid) {
+    sqlx::query("DELETE FROM users WHERE id = $1")
+        .bind(user_id)
+        .execute(pool)
+        .await
+        .unwrap_or_else(|e| panic!("Failed to cleanup test users: {}", e));
+}
+
+fn salary(fixture: &Fixture, amount: Money) -> TransactionInDTO {
+    TransactionInDTO {
+        title: "ACME Corp salary".to_string(),
+        amount,
+        date: Utc::now(),
+        category_id: fixture.category_id,
+        transaction_type: TransactionType::Income,
+        account_id: fixture.checking_id,
+        currency: None,
+        exchange_rate: None,
+    }
+}
+
+fn rule_body(fixture: &Fixture, auto_apply: bool) -> String {
+    json!({
+        "name": "Payday",
+        "match_title": "salary",
+        "auto_apply": auto_apply,
+        "targets": [
+            {
+                "target_type": "saving_goal",
+                "target_id": fixture.goal_id,
+                "amount_type": "fixed",
+                "value": 200.0
+            },
+            {
+                "target_type": "account",
+                "target_id": fixture.savings_id,
+                "amount_type": "percentage",
+                "value": 10.0
+            }
+        ]
+    })
+    .to_string()
+}
+
+async fn goal_amount(pool: &PgPool, user_id: Uuid, goal_id: Uuid) -> Money {
+    find_saving_goal_by_id(pool, user_id, goal_id)
+        .await
+        .expect("Failed to fetch saving goal")
+        .expect("Saving goal exists")
+        .current_amount
+}
+
+async fn account_balance(pool: &PgPool, user_id: Uuid, account_id: Uuid) -> Money {
+    find_account_by_id(pool, user_id, account_id)
+        .await
+        .expect("Failed to fetch account")
+        .expect("Account exists")
+        .balance
+}
+
+fn target(amount_type: AllocationAmountType, value: Decimal) -> AllocationRuleTarget {
+    AllocationRuleTarget {
+        id: Uuid::new_v4(),
+        rule_id: Uuid::nil(),
+        position: 0,
+        target_type: AllocationTargetType::SavingGoal,
+        target_id: Uuid::new_v4(),
+        amount_type,
+        value,
+    }
+}
+
+#[test]
+fn plan_allocations_test() {
+    let plan = plan_allocations(
+        Money::from(1000),
+        &[
+            target(AllocationAmountType::Percentage, Decimal::from(10)),
+            target(AllocationAmountType::Fixed, Decimal::from(200)),
+            target(AllocationAmountType::Percentage, Decimal::from(50)),
+        ],
+        "USD",
+    );
+    let amounts: Vec<Money> = plan.allocations.iter().map(|a| a.amount).collect();
+    assert_eq!(
+        amounts,
+        vec![Money::from(200), Money::from(100), Money::from(500)]
+    );
+    assert_eq!(plan.unallocated, Money::from(200));
+
+    let capped = plan_allocations(
+        Money::from(1000),
+        &[
+            target(AllocationAmountType::Fixed, Decimal::from(900)),
+            target(AllocationAmountType::Percentage, Decimal::from(50)),
+        ],
+        "USD",
+    );
+    let amounts: Vec<Money> = capped.allocations.iter().map(|a| a.amount).collect();
+    assert_eq!(amounts, vec![Money::from(900), Money::from(100)]);
+    assert_eq!(capped.unallocated, Money::ZERO);
+
+    let thirds = plan_allocations(
+        money("100.00"),
+        &[target(
+            AllocationAmountType::Percentage,
+            "33.3333".parse().expect("Valid percentage"),
+        )],
+        "USD",
+    );
+    assert_eq!(thirds.allocations[0].amount, money("33.33"));
+    assert_eq!(thirds.unallocated, money("66.67"));
+
+    let yen = plan_allocations(
+        Money::from(1001),
+        &[target(AllocationAmountType::Percentage, Decimal::from(50))],
+        "JPY",
+    );
+    assert_eq!(yen.allocations[0].amount, Money::from(501));
+}
+
+#[rocket::async_test]
+async fn preview_then_apply_allocation_integration_test() {
+    let (client, pool) = setup().await;
+
+    let fixture = before_test(&pool, "allocationuser", "allocationuser@example.com")
+        .await
+        .expect("Failed to initialize test database");
+    common::login(&client, &pool, fixture.user_id).await;
+
+    let response = client
+        .post("/allocation_rules")
+        .header(ContentType::JSON)
+        .body(rule_body(&fixture, false))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let rule: AllocationRuleOutDTO =
+        serde_json::from_str(&response.into_string().await.expect("Response body"))
+            .expect("Valid JSON");
+    assert_eq!(rule.targets.len(), 2);
+
+    let transaction =
+        create_transaction(&pool, fixture.user_id, &salary(&fixture, Money::from(3000)))
+            .await
+            .expect("Failed to create transaction");
+    assert_eq!(
+        goal_amount(&pool, fixture.user_id, fixture.goal_id).await,
+        Money::from(0)
+    );
+
+    let response = client
+        .get(format!("/transactions/{}/allocation_plan", transaction.id))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let plan: AllocationPlanOutDTO =
+        serde_json::from_str(&response.into_string().await.expect("Response body"))
+            .expect("Valid JSON");
+    assert_eq!(plan.rule_id, rule.id);
+    let amounts: Vec<Money> = plan.allocations.iter().map(|a| a.amount).collect();
+    assert_eq!(amounts, vec![Money::from(200), Money::from(300)]);
+    assert_eq!(plan.unallocated, Money::from(2500));
+
+    let response = client
+        .post(format!("/transactions/{}/allocations", transaction.id))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let allocations: Vec<AllocationOutDTO> =
+        serde_json::from_str(&response.into_string().await.expect("Response body"))
+            .expect("Valid JSON");
+    assert_eq!(allocations.len(), 2);
+    assert_eq!(
+        goal_amount(&pool, fixture.user_id, fixture.goal_id).await,
+        Money::from(200)
+    );
+    assert_eq!(
+        account_balance(&pool, fixture.user_id, fixture.savings_id).await,
+        Money::from(300)
+    );
+
+    let response = client
+        .post(format!("/transactions/{}/allocations", transaction.id))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Conflict);
+
+    cleanup(&pool, fixture.user_id).await;
+}
+
+#[rocket::async_test]
+async fn auto_applied_allocation_reversed_on_delete_integration_test() {
+    let (client, pool) = setup().await;
+
+    let fixture = before_test(&pool, "autoallocuser", "autoallocuser@example.com")
+        .await
+        .expect("Failed to initialize test database");
+    common::login(&client, &pool, fixture.user_id).await;
+
+    let response = client
+        .post("/allocation_rules")
+        .header(ContentType::JSON)
+        .body(rule_body(&fixture, true))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    let transaction =
+        create_transaction(&pool, fixture.user_id, &salary(&fixture, Money::from(1000)))
+            .await
+            .expect("Failed to create transaction");
+    assert_eq!(
+        goal_amount(&pool, fixture.user_id, fixture.goal_id).await,
+        Money::from(200)
+    );
+    assert_eq!(
+        account_balance(&pool, fixture.user_id, fixture.savings_id).await,
+        Money::from(100)
+    );
+    // The transfer is dated with the transaction, so earlier balances don't
+    // include it.
+    let today = Utc::now().date_naive();
+    for (day, expected) in [
+        (today, Money::from(100)),
+        (today - Duration::days(1), Money::ZERO),
+    ] {
+        let balance = account_balance_on(&pool, fixture.user_id, fixture.savings_id, day)
+            .await
+            .expect("Failed to fetch balance");
+        assert_eq!(balance, Some(expected));
+    }
+
+    let response = client
+        .get(format!("/transactions/{}/allocations", transaction.id))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let allocations: Vec<AllocationOutDTO> =
+        serde_json::from_str(&response.into_string().await.expect("Response body"))
+            .expect("Valid JSON");
+    assert_eq!(allocations.len(), 2);
+
+    delete_transaction(&pool, fixture.user_id, transaction.id)
+        .await
+        .expect("Failed to delete transaction");
+    assert_eq!(
+        goal_amount(&pool, fixture.user_id, fixture.goal_id).await,
+        Money::from(0)
+    );
+    assert_eq!(
+        account_balance(&pool, fixture.user_id, fixture.savings_id).await,
+        Money::from(0)
+    );
+
+    cleanup(&pool, fixture.user_id).await;
+}
+
+#[rocket::async_test]
+async fn household_viewer_cannot_apply_allocation_integration_test() {
+    let (client, pool) = setup().await;
+
+    let fixture = before_test(&pool, "allocowner", "allocowner@example.com")
+        .await
+        .expect("Failed to initialize test database");
+    let viewer = create_user(
+        &pool,
+        &UserInDTO {
+            username: "allocviewer".to_string(),
+            email: "allocviewer@example.com".to_string(),
+        },
+    )
+    .await
+    .expect("Failed to create user");
+    let household = create_household(
+        &pool,
+        fixture.user_id,
+        &HouseholdInDTO {
+            name: "Allocation household".to_string(),
+        },
+    )
+    .await
+    .expect("Failed to create household");
+    add_household_member(&pool, household.id, viewer.id, HouseholdRole::Viewer)
+        .await
+        .expect("Failed to add member");
+    share_record(
+        &pool,
+        fixture.user_id,
+        household.id,
+        Owned::Account,
+        fixture.checking_id,
+    )
+    .await
+    .expect("Failed to share account");
+
+    common::login(&client, &pool, fixture.user_id).await;
+    let response = client
+        .post("/allocation_rules")
+        .header(ContentType::JSON)
+        .body(rule_body(&fixture, false))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let transaction =
+        create_transaction(&pool, fixture.user_id, &salary(&fixture, Money::from(1000)))
+            .await
+            .expect("Failed to create transaction");
+
+    // Viewers see the transaction but can't move its money.
+    common::login(&client, &pool, viewer.id).await;
+    let response = client
+        .get(format!("/transactions/{}/allocations", transaction.id))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let response = client
+        .post(format!("/transactions/{}/allocations", transaction.id))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::NotFound);
+    assert_eq!(
+        account_balance(&pool, fixture.user_id, fixture.savings_id).await,
+        Money::ZERO
+    );
+
+    delete_household(&pool, household.id)
+        .await
+        .expect("Failed to delete household");
+    cleanup(&pool, viewer.id).await;
+    cleanup(&pool, fixture.user_id).await;
+}
+
+#[rocket::async_test]
+async fn deleted_target_is_skipped_integration_test() {
+    let (client, pool) = setup().await;
+
+    let fixture = before_test(&pool, "gonetargetuser", "gonetargetuser@example.com")
+        .await
+        .expect("Failed to initialize test database");
+    common::login(&client, &pool, fixture.user_id).await;
+
+    let response = client
+        .post("/allocation_rules")
+        .header(ContentType::JSON)
+        .body(rule_body(&fixture, true))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    delete_saving_goal(&pool, fixture.user_id, fixture.goal_id)
+        .await
+        .expect("Failed to delete saving goal");
+
+    // The income is still saved; the goal's share stays unallocated.
+    let response = client
+        .post("/transactions")
+        .header(ContentType::JSON)
+        .body(serde_json::to_string(&salary(&fixture, Money::from(1000))).expect("Valid JSON"))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let transaction: TransactionOutDTO =
+        serde_json::from_str(&response.into_string().await.expect("Response body"))
+            .expect("Valid JSON");
+
+    let response = client
+        .get(format!("/transactions/{}/allocations", transaction.id))
+        .dispatch()
+        .await;
+    let allocations: Vec<AllocationOutDTO> =
+        serde_json::from_str(&response.into_string().await.expect("Response body"))
+            .expect("Valid JSON");
+    assert_eq!(allocations.len(), 1);
+    assert_eq!(allocations[0].target_id, fixture.savings_id);
+    assert_eq!(
+        account_balance(&pool, fixture.user_id, fixture.savings_id).await,
+        Money::from(100)
+    );
+
+    let response = client
+        .get(format!("/transactions/{}/allocation_plan", transaction.id))
+        .dispatch()
+        .await;
+    let plan: AllocationPlanOutDTO =
+        serde_json::from_str(&response.into_string().await.expect("Response body"))
+            .expect("Valid JSON");
+    assert_eq!(plan.allocations.len(), 1);
+    assert_eq!(plan.unallocated, Money::from(900));
+
+    cleanup(&pool, fixture.user_id).await;
+}
+
+#[rocket::async_test]
+async fn invalid_allocation_rule_integration_test() {
+    let (client, pool) = setup().await;
+
+    let fixture = before_test(&pool, "badallocuser", "badallocuser@example.com")
+        .await
+        .expect("Failed to initialize test database");
+    common::login(&client, &pool, fixture.user_id).await;
+
+    let response = client
+        .post("/allocation_rules")
+        .header(ContentType::JSON)
+        .body(
+            json!({
+                "name": "Nothing to do",
+                "match_title": "salary",
+                "targets": []
+            })
+            .to_string(),
+        )
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::BadRequest);
+
+    cleanup(&pool, fixture.user_id).await;
+}
@@ -0,0 +1,214 @@
+use chrono::{Duration, Local};
+use personal_finance_tracker::auth::tokens::hash_token;
+use personal_finance_tracker::dtos::api_token_dtos::{
+    ApiTokenCreatedOutDTO, ApiTokenInDTO, ApiTokenOutDTO,
+};
+use personal_finance_tracker::dtos::user_dtos::UserInDTO;
+use personal_finance_tracker::operations::api_token_ops::create_api_token;
+use personal_finance_tracker::operations::user_ops::create_user;
+use rocket::http::{ContentType, Header, Status};
+use rocket::local::asynchronous::Client;
+use rocket::serde::json::serde_json;
+use serde_json::json;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::common::setup;
+
+mod common;
+
+pub async fn before_test(pool: &PgPool, username: &str, email: &str) -> Result<Uuid, sqlx::Error> {
+    let user_dto = UserInDTO {
+        username: username.to_string(),
+        email: email.to_string(),
+    };
+    let user = create_user(pool, &user_dto).await?;
+
+    Ok(user.id)
+}
+
+pub async fn cleanup(pool: &PgPool, user_id: Uuid) {
+    sqlx::query("DELETE FROM users WHERE id = $1")
+        .bind(user_id)
+        .execute(pool)
+        .await
+        .unwrap_or_else(|e| panic!("Failed to cleanup test users: {}", e));
+}
+
+fn bearer(token: &str) -> Header<'static> {
+    Header::new("Authorization", format!("Bearer {}", token))
+}
+
+async fn logout(client: &Client) {
+    let response = client.post("/auth/logout").dispatch().await;
+    assert_eq!(response.status(), Status::NoContent);
+}
+
+#[rocket::async_test]
+async fn api_token_lifecycle_integration_test() {
+    let (client, pool) = setup().await;
+
+    let user_id = before_test(&pool, "tokenuser", "tokenuser@example.com")
+        .await
+        .expect("Failed to initialize test database");
+    common::login(&client, &pool, user_id).await;
+
+    let response = client
+        .post("/api_tokens")
+        .header(ContentType::JSON)
+        .body(
+            json!({
+                "name": "Spreadsheet",
+                "scopes": ["transactions:read"],
+                "expires_at": Local::now().naive_local() + Duration::days(30),
+            })
+            .to_string(),
+        )
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let created: ApiTokenCreatedOutDTO =
+        serde_json::from_str(&response.into_string().await.expect("Response has a body"))
+            .expect("Valid ApiTokenCreatedOutDTO");
+    assert!(created.token.starts_with("pft_"));
+    assert!(created.token.starts_with(&created.api_token.token_prefix));
+    assert!(created.api_token.last_used_at.is_none());
+    let token = created.token;
+
+    logout(&client).await;
+    assert_eq!(
+        client.get("/transactions").dispatch().await.status(),
+        Status::Unauthorized
+    );
+
+    let read_response = client
+        .get("/transactions")
+        .header(bearer(&token))
+        .dispatch()
+        .await;
+    assert_eq!(read_response.status(), Status::Ok);
+
+    let write_response = client
+        .post("/transactions")
+        .header(bearer(&token))
+        .header(ContentType::JSON)
+        .body("{}")
+        .dispatch()
+        .await;
+    assert_eq!(write_response.status(), Status::Forbidden);
+
+    let other_resource_response = client
+        .get("/accounts")
+        .header(bearer(&token))
+        .dispatch()
+        .await;
+    assert_eq!(other_resource_response.status(), Status::Forbidden);
+
+    let manage_response = client
+        .get("/api_tokens")
+        .header(bearer(&token))
+        .dispatch()
+        .await;
+    assert_eq!(manage_response.status(), Status::Forbidden);
+
+    common::login(&client, &pool, user_id).await;
+    let list_response = client.get("/api_tokens").dispatch().await;
+    let response_body = list_response
+        .into_string()
+        .await
+        .expect("Response has a body");
+    assert!(!response_body.contains(&token));
+    let api_tokens: Vec<ApiTokenOutDTO> =
+        serde_json::from_str(&response_body).expect("Valid list of ApiTokenOutDTO");
+    assert_eq!(api_tokens.len(), 1);
+    assert!(api_tokens[0].last_used_at.is_some());
+
+    let revoke_response = client
+        .delete(format!("/api_tokens/{}", api_tokens[0].id))
+        .dispatch()
+        .await;
+    assert_eq!(revoke_response.status(), Status::NoContent);
+
+    logout(&client).await;
+    let revoked_response = client
+        .get("/transactions")
+        .header(bearer(&token))
+        .dispatch()
+        .await;
+    assert_eq!(revoked_response.status(), Status::Unauthorized);
+
+    cleanup(&pool, user_id).await;
+}
+
+#[rocket::async_test]
+async fn invalid_or_expired_api_token_integration_test() {
+    let (client, pool) = setup().await;
+
+    let user_id = before_test(&pool, "badtokenuser", "badtokenuser@example.com")
+        .await
+        .expect("Failed to initialize test database");
+    common::login(&client, &pool, user_id).await;
+
+    let unknown_scope = client
+        .post("/api_tokens")
+        .header(ContentType::JSON)
+        .body(
+            json!({
+                "name": "Admin",
+                "scopes": ["users:write"],
+                "expires_at": Local::now().naive_local() + Duration::days(30),
+            })
+            .to_string(),
+        )
+        .dispatch()
+        .await;
+    assert_eq!(unknown_scope.status(), Status::BadRequest);
+
+    let already_expired = client
+        .post("/api_tokens")
+        .header(ContentType::JSON)
+        .body(
+            json!({
+                "name": "Stale",
+                "scopes": ["transactions:read"],
+                "expires_at": Local::now().naive_local() - Duration::days(1),
+            })
+            .to_string(),
+        )
+        .dispatch()
+        .await;
+    assert_eq!(already_expired.status(), Status::BadRequest);
+
+    let expired_token = "pft_expiredtokenfortesting";
+    let api_token_dto = ApiTokenInDTO {
+        name: "Expired".to_string(),
+        scopes: vec!["transactions:read".to_string()],
+        expires_at: Local::now().naive_local() - Duration::minutes(1),
+    };
+    create_api_token(
+        &pool,
+        user_id,
+        &api_token_dto,
+        "pft_expiredt",
+        &hash_token(expired_token),
+    )
+    .await
+    .expect("Failed to create API token");
+
+    logout(&client).await;
+    let expired_response = client
+        .get("/transactions")
+        .header(bearer(expired_token))
+        .dispatch()
+        .await;
+    assert_eq!(expired_response.status(), Status::Unauthorized);
+
+    let garbage_response = client
+        .get("/transactions")
+        .header(bearer("pft_not-a-real-token"))
+        .dispatch()
+        .await;
+    assert_eq!(garbage_response.status(), Status::Unauthorized);
+
+    cleanup(&pool, user_id).await;
+}
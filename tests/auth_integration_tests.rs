@@ -0,0 +1,183 @@
+use personal_finance_tracker::auth::{verify_password, DUMMY_PASSWORD_HASH};
+use personal_finance_tracker::dtos::account_dtos::AccountOutDTO;
+use personal_finance_tracker::dtos::user_dtos::{UserInDTO, UserOutDTO};
+use personal_finance_tracker::enums::custom_enums::AccountType;
+use personal_finance_tracker::operations::user_ops::create_user;
+use rocket::http::{ContentType, Status};
+use rocket::local::asynchronous::Client;
+use rocket::serde::json::serde_json;
+use serde_json::json;
+use sqlx::PgPool;
+
+use crate::common::setup;
+
+mod common;
+
+async fn cleanup(pool: &PgPool, email: &str) {
+    sqlx::query("DELETE FROM users WHERE email = $1")
+        .bind(email)
+        .execute(pool)
+        .await
+        .unwrap_or_else(|e| panic!("Failed to cleanup test users: {}", e));
+}
+
+async fn register(client: &Client, username: &str, email: &str, password: &str) -> Status {
+    client
+        .post("/auth/register")
+        .header(ContentType::JSON)
+        .body(
+            json!({
+                "username": username,
+                "email": email,
+                "password": password
+            })
+            .to_string(),
+        )
+        .dispatch()
+        .await
+        .status()
+}
+
+async fn login(client: &Client, email: &str, password: &str) -> Status {
+    client
+        .post("/auth/login")
+        .header(ContentType::JSON)
+        .body(json!({ "email": email, "password": password }).to_string())
+        .dispatch()
+        .await
+        .status()
+}
+
+#[test]
+fn dummy_password_hash_is_checked_test() {
+    // Unknown emails are verified against this hash; it must parse for the
+    // check to cost as much as a real one.
+    assert!(verify_password("not-a-real-password", DUMMY_PASSWORD_HASH));
+    assert!(!verify_password("password123", DUMMY_PASSWORD_HASH));
+}
+
+#[rocket::async_test]
+async fn register_login_logout_integration_test() {
+    let (client, pool) = setup().await;
+    let email = "authuser@example.com";
+
+    assert_eq!(
+        register(&client, "authuser", email, "hunter2hunter2").await,
+        Status::Ok
+    );
+
+    let response = client.get("/auth/me").dispatch().await;
+    assert_eq!(response.status(), Status::Ok);
+    let me: UserOutDTO =
+        serde_json::from_str(&response.into_string().await.expect("Response body"))
+            .expect("Valid UserOutDTO");
+    assert_eq!(me.email, email);
+
+    let response = client.post("/auth/logout").dispatch().await;
+    assert_eq!(response.status(), Status::NoContent);
+    assert_eq!(
+        client.get("/auth/me").dispatch().await.status(),
+        Status::Unauthorized
+    );
+
+    assert_eq!(
+        login(&client, email, "wrong password").await,
+        Status::Unauthorized
+    );
+    assert_eq!(login(&client, email, "hunter2hunter2").await, Status::Ok);
+    assert_eq!(client.get("/auth/me").dispatch().await.status(), Status::Ok);
+
+    cleanup(&pool, email).await;
+}
+
+#[rocket::async_test]
+async fn register_rejects_weak_or_duplicate_integration_test() {
+    let (client, pool) = setup().await;
+    let email = "dupuser@example.com";
+
+    assert_eq!(
+        register(&client, "dupuser", email, "short").await,
+        Status::BadRequest
+    );
+    assert_eq!(
+        register(&client, "dupuser", email, "long enough password").await,
+        Status::Ok
+    );
+    assert_eq!(
+        register(&client, "dupuser", email, "another long password").await,
+        Status::Conflict
+    );
+
+    cleanup(&pool, email).await;
+}
+
+#[rocket::async_test]
+async fn password_less_user_cannot_log_in_integration_test() {
+    let (client, pool) = setup().await;
+    let email = "nopassword@example.com";
+
+    create_user(
+        &pool,
+        &UserInDTO {
+            username: "nopassword".to_string(),
+            email: email.to_string(),
+        },
+    )
+    .await
+    .expect("Failed to create user");
+
+    assert_eq!(login(&client, email, "").await, Status::Unauthorized);
+
+    cleanup(&pool, email).await;
+}
+
+#[rocket::async_test]
+async fn writes_use_session_user_integration_test() {
+    let (client, pool) = setup().await;
+    let email = "sessionowner@example.com";
+    let other_email = "bodyowner@example.com";
+
+    let other = create_user(
+        &pool,
+        &UserInDTO {
+            username: "bodyowner".to_string(),
+            email: other_email.to_string(),
+        },
+    )
+    .await
+    .expect("Failed to create user");
+
+    let account_data = json!({
+        "name": "Checking",
+        "balance": 10.0,
+        "account_type": AccountType::Bank,
+        "user_id": other.id,
+    });
+
+    let response = client
+        .post("/accounts")
+        .header(ContentType::JSON)
+        .body(account_data.to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Unauthorized);
+
+    assert_eq!(
+        register(&client, "sessionowner", email, "hunter2hunter2").await,
+        Status::Ok
+    );
+    let response = client
+        .post("/accounts")
+        .header(ContentType::JSON)
+        .body(account_data.to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let account: AccountOutDTO =
+        serde_json::from_str(&response.into_string().await.expect("Response body"))
+            .expect("Valid AccountOutDTO");
+    assert_ne!(account.user_id, other.id);
+
+    cleanup(&pool, email).await;
+    cleanup(&pool, other_email).await;
+}
@@ -0,0 +1,309 @@
+use chrono::{DateTime, Duration, NaiveDate, TimeZone, Utc, Weekday};
+use chrono_tz::Tz;
+use personal_finance_tracker::badges::{BadgeContext, WEEKLY_SAVING_STREAK};
+use personal_finance_tracker::dtos::account_dtos::AccountInDTO;
+use personal_finance_tracker::dtos::badge_dtos::UserBadgesOutDTO;
+use personal_finance_tracker::dtos::category_dtos::CategoryInDTO;
+use personal_finance_tracker::dtos::transaction_dtos::TransactionInDTO;
+use personal_finance_tracker::dtos::user_dtos::UserInDTO;
+use personal_finance_tracker::enums::custom_enums::{AccountType, TransactionType};
+use personal_finance_tracker::models::transactions::Transaction;
+use personal_finance_tracker::money::Money;
+use personal_finance_tracker::operations::account_ops::create_account;
+use personal_finance_tracker::operations::badge_ops::evaluate_user_badges;
+use personal_finance_tracker::operations::category_ops::create_category;
+use personal_finance_tracker::operations::transaction_ops::create_transaction;
+use personal_finance_tracker::operations::user_ops::create_user;
+use rocket::http::Status;
+use rocket::serde::json::serde_json;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::common::setup;
+
+mod common;
+
+pub async fn before_test(
+    pool: &PgPool,
+    username: &str,
+    email: &str,
+) -> Result<(Uuid, Uuid, Uuid), sqlx::Error> {
+    let user_dto = UserInDTO {
+        username: username.to_string(),
+        email: email.to_string(),
+    };
+    let user = create_user(pool, &user_dto).await?;
+
+    let account_dto = AccountInDTO {
+        name: "Test".to_string(),
+        balance: Money::from(100),
+        account_type: AccountType::Bank,
+        currency: None,
+    };
+    let account = create_account(pool, user.id, &account_dto).await?;
+
+    let category_dto = CategoryInDTO {
+        name: "TestCategory".to_string(),
+    };
+    let category = create_category(pool, user.id, &category_dto).await?;
+
+    Ok((user.id, account.id, category.id))
+}
+
+pub async fn cleanup(pool: &PgPool, user_id: Uuid) {
+    sqlx::query("DELETE FROM users WHERE id = $1")
+        .bind(user_id)
+        .execute(pool)
+        .await
+        .unwrap_or_else(|e| panic!("Failed to cleanup test users: {}", e));
+}
+
+fn transaction(date: NaiveDate, amount: Money, transaction_type: TransactionType) -> Transaction {
+    transaction_at(
+        Utc.from_utc_datetime(&date.and_hms_opt(12, 0, 0).expect("Valid time")),
+        amount,
+        transaction_type,
+    )
+}
+
+fn transaction_at(
+    date: DateTime<Utc>,
+    amount: Money,
+    transaction_type: TransactionType,
+) -> Transaction {
+    Transaction {
+        id: Uuid::new_v4(),
+        title: "Test".to_string(),
+        amount,
+        date,
+        category_id: Uuid::nil(),
+        transaction_type,
+        user_id: Uuid::nil(),
+        account_id: Uuid::nil(),
+        currency: "USD".to_string(),
+        original_amount: None,
+        original_currency: None,
+    }
+}
+
+#[test]
+fn weekly_saving_streak_test() {
+    // Mondays of four consecutive weeks; the third week spends more than it earns.
+    let monday = NaiveDate::from_ymd_opt(2024, 1, 1).expect("Valid date");
+    let transactions = vec![
+        transaction(monday, Money::from(100), TransactionType::Income),
+        transaction(
+            monday + Duration::days(7),
+            Money::from(100),
+            TransactionType::Income,
+        ),
+        transaction(
+            monday + Duration::days(14),
+            Money::from(50),
+            TransactionType::Income,
+        ),
+        transaction(
+            monday + Duration::days(15),
+            Money::from(80),
+            TransactionType::Expense,
+        ),
+        transaction(
+            monday + Duration::days(21),
+            Money::from(10),
+            TransactionType::Income,
+        ),
+    ];
+    let context = BadgeContext {
+        transactions: &transactions,
+        budgets: &[],
+        saving_goals: &[],
+        today: monday + Duration::days(23),
+        week_start: Weekday::Mon,
+        timezone: Tz::UTC,
+        currency: "USD",
+    };
+
+    let streak = context.weekly_saving_streak();
+
+    assert_eq!(streak.current, 1);
+    assert_eq!(streak.longest, 2);
+}
+
+#[test]
+fn weekly_saving_streak_follows_week_start_test() {
+    // Saturday's income and Sunday's expense share a week only when weeks
+    // start on Monday.
+    let saturday = NaiveDate::from_ymd_opt(2024, 1, 6).expect("Valid date");
+    let transactions = vec![
+        transaction(saturday, Money::from(100), TransactionType::Income),
+        transaction(
+            saturday + Duration::days(1),
+            Money::from(150),
+            TransactionType::Expense,
+        ),
+    ];
+    let context = |week_start| BadgeContext {
+        transactions: &transactions,
+        budgets: &[],
+        saving_goals: &[],
+        today: saturday + Duration::days(4),
+        week_start,
+        timezone: Tz::UTC,
+        currency: "USD",
+    };
+
+    assert_eq!(context(Weekday::Mon).weekly_saving_streak().longest, 0);
+    assert_eq!(context(Weekday::Sun).weekly_saving_streak().longest, 1);
+}
+
+#[test]
+fn no_spend_weekends_test() {
+    // 2024-01-06 and 2024-01-13 are Saturdays; only the second weekend has an expense.
+    let monday = NaiveDate::from_ymd_opt(2024, 1, 1).expect("Valid date");
+    let transactions = vec![
+        transaction(monday, Money::from(100), TransactionType::Income),
+        transaction(
+            monday + Duration::days(13),
+            Money::from(20),
+            TransactionType::Expense,
+        ),
+    ];
+    let context = BadgeContext {
+        transactions: &transactions,
+        budgets: &[],
+        saving_goals: &[],
+        today: monday + Duration::days(15),
+        week_start: Weekday::Mon,
+        timezone: Tz::UTC,
+        currency: "USD",
+    };
+
+    assert_eq!(context.no_spend_weekends(), 1);
+}
+
+#[test]
+fn no_spend_weekends_uses_timezone_test() {
+    // Late on Sunday in UTC is already Monday in Belgrade.
+    let monday = NaiveDate::from_ymd_opt(2024, 1, 1).expect("Valid date");
+    let sunday_night = Utc
+        .with_ymd_and_hms(2024, 1, 7, 23, 30, 0)
+        .single()
+        .expect("Valid time");
+    let transactions = vec![
+        transaction(monday, Money::from(100), TransactionType::Income),
+        transaction_at(sunday_night, Money::from(20), TransactionType::Expense),
+    ];
+    let context = |timezone| BadgeContext {
+        transactions: &transactions,
+        budgets: &[],
+        saving_goals: &[],
+        today: monday + Duration::days(9),
+        week_start: Weekday::Mon,
+        timezone,
+        currency: "USD",
+    };
+
+    assert_eq!(context(Tz::UTC).no_spend_weekends(), 0);
+    assert_eq!(context(Tz::Europe__Belgrade).no_spend_weekends(), 1);
+}
+
+#[rocket::async_test]
+async fn get_user_badges_integration_test() {
+    let (client, pool) = setup().await;
+
+    let (user_id, account_id, category_id) =
+        before_test(&pool, "badgeuser", "badgeuser@example.com")
+            .await
+            .expect("Failed to initialize test database");
+
+    common::login(&client, &pool, user_id).await;
+
+    let now = Utc::now();
+    for week in 0..10 {
+        let transaction_dto = TransactionInDTO {
+            title: "Salary".to_string(),
+            amount: Money::from(100),
+            date: now - Duration::weeks(week),
+            category_id,
+            transaction_type: TransactionType::Income,
+            account_id,
+            currency: None,
+            exchange_rate: None,
+        };
+        create_transaction(&pool, user_id, &transaction_dto)
+            .await
+            .expect("Failed to create transaction");
+    }
+
+    // The evaluator job hasn't reached the user yet, so their first request
+    // evaluates them.
+    let response = client
+        .get(format!("/users/{}/badges", user_id))
+        .dispatch()
+        .await;
+    let response_body = response.into_string().await.expect("Response has a body");
+    let badges: UserBadgesOutDTO =
+        serde_json::from_str(&response_body).expect("Valid UserBadgesOutDTO");
+    assert!(badges
+        .earned
+        .iter()
+        .any(|badge| badge.code == "weekly_saver"));
+
+    evaluate_user_badges(&pool, user_id)
+        .await
+        .expect("Failed to evaluate badges");
+
+    let response = client
+        .get(format!("/users/{}/badges", user_id))
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::Ok);
+
+    let response_body = response.into_string().await.expect("Response has a body");
+    let badges: UserBadgesOutDTO =
+        serde_json::from_str(&response_body).expect("Valid UserBadgesOutDTO");
+
+    let weekly_saver = badges
+        .earned
+        .iter()
+        .find(|badge| badge.code == "weekly_saver")
+        .expect("Weekly saver badge is earned");
+    assert_eq!(weekly_saver.progress_percentage, 100.0);
+
+    let goal_getter = badges
+        .in_progress
+        .iter()
+        .find(|badge| badge.code == "goal_getter")
+        .expect("Goal getter badge is in progress");
+    assert_eq!(goal_getter.progress_percentage, 0.0);
+
+    let streak = badges
+        .streaks
+        .iter()
+        .find(|streak| streak.code == WEEKLY_SAVING_STREAK)
+        .expect("Weekly saving streak is tracked");
+    assert_eq!(streak.current, 10);
+    assert_eq!(streak.longest, 10);
+
+    cleanup(&pool, user_id).await;
+}
+
+#[rocket::async_test]
+async fn get_badges_for_unknown_user_integration_test() {
+    let (client, pool) = setup().await;
+
+    let (user_id, _, _) = before_test(&pool, "otherbadgeuser", "otherbadgeuser@example.com")
+        .await
+        .expect("Failed to initialize test database");
+    common::login(&client, &pool, user_id).await;
+
+    let response = client
+        .get(format!("/users/{}/badges", Uuid::new_v4()))
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::NotFound);
+
+    cleanup(&pool, user_id).await;
+}
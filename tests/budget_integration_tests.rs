@@ -1,5 +1,6 @@
-use chrono::Local;
+use chrono::Utc;
 use personal_finance_tracker::dtos::user_dtos::UserInDTO;
+use personal_finance_tracker::money::Money;
 use personal_finance_tracker::operations::budget_ops::create_budget;
 use personal_finance_tracker::operations::user_ops::create_user;
 use rocket::http::{ContentType, Status};
@@ -20,7 +21,7 @@ pub async fn before_test(pool: &PgPool, username: &str, email: &str) -> Result<U
         username: username.to_string(),
         email: email.to_string(),
     };
-    let user = create_user(&pool, &user_dto).await?;
+    let user = create_user(pool, &user_dto).await?;
 
     Ok(user.id)
 }
@@ -50,13 +51,13 @@ async fn create_budget_integration_test() {
     let user_id = before_test(&pool, "createuser", "createuser@example.com")
         .await
         .expect("Failed to initialize test database");
+    common::login(&client, &pool, user_id).await;
 
     let budget_data = json!({
         "name": "Groceries".to_string(),
-        "user_id": user_id,
         "amount": 100.0,
-        "start_date": Local::now().naive_local(),
-        "end_date": Local::now().naive_local(),
+        "start_date": Utc::now(),
+        "end_date": Utc::now(),
     });
 
     let response = client
@@ -86,14 +87,13 @@ async fn create_budget_integration_test_type_two() {
 
     let budget_dto = BudgetInDTO {
         name: "Groceries".to_string(),
-        user_id: user_id,
-        amount: 100.0,
-        start_date: Local::now().naive_local(),
-        end_date: Local::now().naive_local(),
+        amount: Money::from(100),
+        start_date: Utc::now(),
+        end_date: Utc::now(),
         category_id: None,
     };
 
-    let response = create_budget(&pool, &budget_dto).await;
+    let response = create_budget(&pool, user_id, &budget_dto).await;
 
     assert!(response.is_ok());
 
@@ -107,17 +107,23 @@ async fn create_budget_integration_test_type_two() {
 
 #[rocket::async_test]
 async fn get_all_budgets_integration_test() {
-    let (client, _pool) = setup().await;
+    let (client, pool) = setup().await;
+
+    let user_id = before_test(&pool, "listuser", "listuser@example.com")
+        .await
+        .expect("Failed to initialize test database");
+    common::login(&client, &pool, user_id).await;
 
     let response = client.get("/budgets").dispatch().await;
 
     assert_eq!(response.status(), Status::Ok);
 
-    // let response_body = response.into_string().await.expect("Response has a body");
-    // let budgets: Vec<BudgetOutDTO> =
-    //     serde_json::from_str(&response_body).expect("Valid list of BudgetOutDTO");
+    let budgets: Vec<BudgetOutDTO> =
+        serde_json::from_str(&response.into_string().await.expect("Response has a body"))
+            .expect("Valid list of BudgetOutDTO");
+    assert!(budgets.is_empty());
 
-    // assert!(budgets.len() > 0);
+    cleanup(&pool, user_id, None).await;
 }
 
 #[rocket::async_test]
@@ -128,16 +134,17 @@ async fn get_budget_by_id_integration_test() {
         .await
         .expect("Failed to initialize test database");
 
+    common::login(&client, &pool, user_id).await;
+
     let budget_dto = BudgetInDTO {
         name: "Groceries".to_string(),
-        user_id: user_id,
-        amount: 100.0,
-        start_date: Local::now().naive_local(),
-        end_date: Local::now().naive_local(),
+        amount: Money::from(100),
+        start_date: Utc::now(),
+        end_date: Utc::now(),
         category_id: None,
     };
 
-    let response = create_budget(&pool, &budget_dto).await;
+    let response = create_budget(&pool, user_id, &budget_dto).await;
     let budget = response.unwrap();
 
     let budget_id = budget.id;
@@ -163,13 +170,13 @@ async fn update_budget_integration_test() {
     let user_id = before_test(&pool, "updateuser", "updateuser@example.com")
         .await
         .expect("Failed to initialize test database");
+    common::login(&client, &pool, user_id).await;
 
     let budget_data = json!({
         "name": "Groceries",
-        "user_id": user_id,
         "amount": 100.0,
-        "start_date": Local::now().naive_local(),
-        "end_date": Local::now().naive_local(),
+        "start_date": Utc::now(),
+        "end_date": Utc::now(),
     });
     let create_response = client
         .post("/budgets")
@@ -187,10 +194,9 @@ async fn update_budget_integration_test() {
 
     let update_data = json!({
         "name": "Groceries updated",
-        "user_id": user_id,
         "amount": 150.0,
-        "start_date": Local::now().naive_local(),
-        "end_date": Local::now().naive_local(),
+        "start_date": Utc::now(),
+        "end_date": Utc::now(),
     });
     let response = client
         .patch(format!("/budgets/{}", created_budget.id))
@@ -207,7 +213,7 @@ async fn update_budget_integration_test() {
 
     assert_eq!(updated_budget.name, "Groceries updated");
     assert_eq!(updated_budget.user_id, user_id);
-    assert_eq!(updated_budget.amount, 150.0);
+    assert_eq!(updated_budget.amount, Money::from(150));
 
     cleanup(&pool, user_id, Some(updated_budget.id)).await;
 }
@@ -220,16 +226,17 @@ async fn delete_budget_integration_test() {
         .await
         .expect("Failed to initialize test database");
 
+    common::login(&client, &pool, user_id).await;
+
     let budget_dto = BudgetInDTO {
         name: "Groceries".to_string(),
-        user_id: user_id,
-        amount: 100.0,
-        start_date: Local::now().naive_local(),
-        end_date: Local::now().naive_local(),
+        amount: Money::from(100),
+        start_date: Utc::now(),
+        end_date: Utc::now(),
         category_id: None,
     };
 
-    let response_saved = create_budget(&pool, &budget_dto).await;
+    let response_saved = create_budget(&pool, user_id, &budget_dto).await;
 
     let budget = response_saved.unwrap();
 
@@ -19,7 +19,7 @@ pub async fn before_test(pool: &PgPool, username: &str, email: &str) -> Result<U
         username: username.to_string(),
         email: email.to_string(),
     };
-    let user = create_user(&pool, &user_dto).await?;
+    let user = create_user(pool, &user_dto).await?;
 
     Ok(user.id)
 }
@@ -49,10 +49,10 @@ async fn create_category_integration_test() {
     let user_id = before_test(&pool, "createuser", "createuser@example.com")
         .await
         .expect("Failed to initialize test database");
+    common::login(&client, &pool, user_id).await;
 
     let category_data = json!({
         "name": "Groceries",
-        "user_id": user_id
     });
 
     let response = client
@@ -82,10 +82,9 @@ async fn create_category_integration_test_type_two() {
 
     let category_dto = CategoryInDTO {
         name: "Groceries".to_string(),
-        user_id: user_id,
     };
 
-    let response = create_category(&pool, &category_dto).await;
+    let response = create_category(&pool, user_id, &category_dto).await;
 
     assert!(response.is_ok());
 
@@ -99,17 +98,23 @@ async fn create_category_integration_test_type_two() {
 
 #[rocket::async_test]
 async fn get_all_categories_integration_test() {
-    let (client, _pool) = setup().await;
+    let (client, pool) = setup().await;
+
+    let user_id = before_test(&pool, "listuser", "listuser@example.com")
+        .await
+        .expect("Failed to initialize test database");
+    common::login(&client, &pool, user_id).await;
 
     let response = client.get("/categories").dispatch().await;
 
     assert_eq!(response.status(), Status::Ok);
 
-    // let response_body = response.into_string().await.expect("Response has a body");
-    // let categories: Vec<CategoryOutDTO> =
-    //     serde_json::from_str(&response_body).expect("Valid list of CategoryOutDTO");
+    let categories: Vec<CategoryOutDTO> =
+        serde_json::from_str(&response.into_string().await.expect("Response has a body"))
+            .expect("Valid list of CategoryOutDTO");
+    assert!(categories.is_empty());
 
-    // assert!(categories.len() > 0);
+    cleanup(&pool, user_id, None).await;
 }
 
 #[rocket::async_test]
@@ -120,12 +125,13 @@ async fn get_category_by_id_integration_test() {
         .await
         .expect("Failed to initialize test database");
 
+    common::login(&client, &pool, user_id).await;
+
     let category_dto = CategoryInDTO {
         name: "Groceries".to_string(),
-        user_id: user_id,
     };
 
-    let response = create_category(&pool, &category_dto).await;
+    let response = create_category(&pool, user_id, &category_dto).await;
     let category = response.unwrap();
 
     let category_id = category.id;
@@ -152,10 +158,10 @@ async fn update_category_integration_test() {
     let user_id = before_test(&pool, "updateuser", "updateuser@example.com")
         .await
         .expect("Failed to initialize test database");
+    common::login(&client, &pool, user_id).await;
 
     let category_data = json!({
         "name": "Groceries",
-        "user_id": user_id
     });
     let create_response = client
         .post("/categories")
@@ -173,7 +179,6 @@ async fn update_category_integration_test() {
 
     let update_data = json!({
         "name": "Groceries updated",
-        "user_id": user_id
     });
     let response = client
         .patch(format!("/categories/{}", created_category.id))
@@ -202,12 +207,13 @@ async fn delete_category_integration_test() {
         .await
         .expect("Failed to initialize test database");
 
+    common::login(&client, &pool, user_id).await;
+
     let category_dto = CategoryInDTO {
         name: "Groceries".to_string(),
-        user_id: user_id,
     };
 
-    let response_saved = create_category(&pool, &category_dto).await;
+    let response_saved = create_category(&pool, user_id, &category_dto).await;
 
     let category = response_saved.unwrap();
 
@@ -0,0 +1,423 @@
+use chrono::{Duration, Local, NaiveDate, TimeZone, Utc};
+use chrono_tz::Tz;
+use personal_finance_tracker::challenges::{build_schedule, challenge_end_date};
+use personal_finance_tracker::dtos::account_dtos::AccountInDTO;
+use personal_finance_tracker::dtos::category_dtos::CategoryInDTO;
+use personal_finance_tracker::dtos::challenge_dtos::{ChallengeOutDTO, ChallengeReportOutDTO};
+use personal_finance_tracker::dtos::contribution_dtos::ContributionOutDTO;
+use personal_finance_tracker::dtos::saving_goal_dtos::{SavingGoalInDTO, SavingGoalOutDTO};
+use personal_finance_tracker::dtos::transaction_dtos::TransactionInDTO;
+use personal_finance_tracker::dtos::user_dtos::UserInDTO;
+use personal_finance_tracker::enums::custom_enums::{
+    AccountType, ChallengeStatus, ChallengeType, ContributionSource, TransactionType,
+};
+use personal_finance_tracker::exchange_rates::RateTable;
+use personal_finance_tracker::models::challenge::SavingChallenge;
+use personal_finance_tracker::models::contribution::GoalContribution;
+use personal_finance_tracker::models::exchange_rate::ExchangeRate;
+use personal_finance_tracker::models::transactions::Transaction;
+use personal_finance_tracker::money::Money;
+use personal_finance_tracker::operations::account_ops::create_account;
+use personal_finance_tracker::operations::category_ops::create_category;
+use personal_finance_tracker::operations::saving_goal_ops::create_saving_goal;
+use personal_finance_tracker::operations::transaction_ops::create_transaction;
+use personal_finance_tracker::operations::user_ops::create_user;
+use rocket::http::{ContentType, Status};
+use rocket::local::asynchronous::Client;
+use rocket::serde::json::serde_json;
+use rust_decimal::Decimal;
+use serde_json::json;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::common::setup;
+
+mod common;
+
+pub async fn before_test(
+    pool: &PgPool,
+    username: &str,
+    email: &str,
+) -> Result<(Uuid, Uuid), sqlx::Error> {
+    let user_dto = UserInDTO {
+        username: username.to_string(),
+        email: email.to_string(),
+    };
+    let user = create_user(pool, &user_dto).await?;
+
+    let saving_goal_dto = SavingGoalInDTO {
+        title: "Challenge".to_string(),
+        target_amount: Money::from(10000),
+        current_amount: Money::ZERO,
+        deadline: Local::now().date_naive() + Duration::weeks(60),
+    };
+    let saving_goal = create_saving_goal(pool, user.id, &saving_goal_dto).await?;
+
+    Ok((user.id, saving_goal.id))
+}
+
+pub async fn cleanup(pool: &PgPool, user_id: Uuid) {
+    sqlx::query("DELETE FROM users WHERE id = $1")
+        .bind(user_id)
+        .execute(pool)
+        .await
+        .unwrap_or_else(|e| panic!("Failed to cleanup test users: {}", e));
+}
+
+async fn contribute(client: &Client, goal_id: Uuid, amount: f64, at: chrono::NaiveDateTime) {
+    let response = client
+        .post(format!("/saving_goals/{}/contributions", goal_id))
+        .header(ContentType::JSON)
+        .body(json!({ "amount": amount, "contributed_at": at }).to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+}
+
+#[test]
+fn fifty_two_week_schedule_test() {
+    let start_date = NaiveDate::from_ymd_opt(2024, 1, 1).expect("Valid date");
+    let challenge = SavingChallenge {
+        id: Uuid::new_v4(),
+        challenge_type: ChallengeType::FiftyTwoWeek,
+        goal_id: Uuid::new_v4(),
+        user_id: Uuid::new_v4(),
+        start_date,
+        end_date: challenge_end_date(ChallengeType::FiftyTwoWeek, start_date, None)
+            .expect("Date in range"),
+        unit_amount: Some(Money::from(5)),
+        percentage: None,
+        category_ids: Vec::new(),
+        created_at: Local::now().naive_local(),
+    };
+
+    let schedule = build_schedule(
+        &challenge,
+        &[],
+        &[],
+        &RateTable::new(&[]),
+        start_date,
+        Tz::UTC,
+        "USD",
+    )
+    .expect("No conversions needed");
+
+    assert_eq!(schedule.len(), 52);
+    assert_eq!(schedule[0].expected_amount, Money::from(5));
+    assert_eq!(schedule[51].expected_amount, Money::from(260));
+    assert_eq!(
+        schedule[51].end_date,
+        NaiveDate::from_ymd_opt(2024, 12, 29).expect("Valid date")
+    );
+    assert!(schedule.iter().all(|period| period.met.is_none()));
+}
+
+#[test]
+fn income_percentage_schedule_test() {
+    let start_date = NaiveDate::from_ymd_opt(2024, 1, 1).expect("Valid date");
+    let challenge = SavingChallenge {
+        id: Uuid::new_v4(),
+        challenge_type: ChallengeType::IncomePercentage,
+        goal_id: Uuid::new_v4(),
+        user_id: Uuid::new_v4(),
+        start_date,
+        end_date: challenge_end_date(ChallengeType::IncomePercentage, start_date, Some(2))
+            .expect("Date in range"),
+        unit_amount: None,
+        percentage: Some(Decimal::TEN),
+        category_ids: Vec::new(),
+        created_at: Local::now().naive_local(),
+    };
+    // Late on Sunday the 7th in UTC is already Monday the 8th in Auckland.
+    let late_sunday = Utc.with_ymd_and_hms(2024, 1, 7, 23, 30, 0).unwrap();
+    let contributions = vec![GoalContribution {
+        id: Uuid::new_v4(),
+        goal_id: challenge.goal_id,
+        amount: Money::from(20),
+        contributed_at: late_sunday.with_timezone(&Local).naive_local(),
+        note: None,
+        source: ContributionSource::Manual,
+        transaction_id: None,
+    }];
+    let transactions = vec![Transaction {
+        id: Uuid::new_v4(),
+        title: "Salary".to_string(),
+        amount: Money::from(200),
+        currency: "EUR".to_string(),
+        original_amount: None,
+        original_currency: None,
+        date: late_sunday,
+        category_id: Uuid::new_v4(),
+        transaction_type: TransactionType::Income,
+        user_id: challenge.user_id,
+        account_id: Uuid::new_v4(),
+    }];
+    let rates = RateTable::new(&[ExchangeRate {
+        base_currency: "EUR".to_string(),
+        quote_currency: "USD".to_string(),
+        rate_date: NaiveDate::from_ymd_opt(2024, 1, 8).expect("Valid date"),
+        rate: Decimal::new(11, 1),
+        imported_at: Local::now().naive_local(),
+    }]);
+    let auckland: Tz = "Pacific/Auckland".parse().expect("Valid timezone");
+    let today = NaiveDate::from_ymd_opt(2024, 2, 1).expect("Valid date");
+
+    let schedule = build_schedule(
+        &challenge,
+        &contributions,
+        &transactions,
+        &rates,
+        today,
+        auckland,
+        "USD",
+    )
+    .expect("Rates are available");
+
+    let weeks: Vec<_> = schedule
+        .iter()
+        .map(|p| (p.expected_amount, p.contributed_amount, p.met))
+        .collect();
+    assert_eq!(
+        weeks,
+        vec![
+            (Money::ZERO, Money::ZERO, Some(true)),
+            (Money::from(22), Money::from(20), Some(false)),
+        ]
+    );
+
+    let missing = build_schedule(
+        &challenge,
+        &contributions,
+        &transactions,
+        &RateTable::new(&[]),
+        today,
+        auckland,
+        "USD",
+    );
+    assert!(missing.is_err());
+}
+
+#[test]
+fn challenge_end_date_out_of_range_test() {
+    let late = NaiveDate::MAX - Duration::days(3);
+
+    assert_eq!(
+        challenge_end_date(ChallengeType::FiftyTwoWeek, late, None),
+        None
+    );
+    assert_eq!(
+        challenge_end_date(ChallengeType::IncomePercentage, late, Some(1)),
+        None
+    );
+    assert_eq!(
+        challenge_end_date(ChallengeType::IncomePercentage, late, None),
+        None
+    );
+}
+
+#[rocket::async_test]
+async fn fifty_two_week_challenge_report_integration_test() {
+    let (client, pool) = setup().await;
+
+    let (user_id, goal_id) = before_test(&pool, "challengeuser", "challengeuser@example.com")
+        .await
+        .expect("Failed to initialize test database");
+    common::login(&client, &pool, user_id).await;
+
+    let start_date = Local::now().date_naive() - Duration::weeks(3);
+    let response = client
+        .post("/challenges")
+        .header(ContentType::JSON)
+        .body(
+            json!({
+                "challenge_type": "fifty_two_week",
+                "goal_id": goal_id,
+                "start_date": start_date,
+                "unit_amount": 10.0,
+            })
+            .to_string(),
+        )
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let challenge: ChallengeOutDTO =
+        serde_json::from_str(&response.into_string().await.expect("Response has a body"))
+            .expect("Valid ChallengeOutDTO");
+
+    let start = start_date.and_hms_opt(12, 0, 0).expect("Valid time");
+    contribute(&client, goal_id, 10.0, start).await;
+    contribute(&client, goal_id, 30.0, start + Duration::weeks(2)).await;
+
+    let response = client
+        .get(format!("/challenges/{}", challenge.id))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let report: ChallengeReportOutDTO =
+        serde_json::from_str(&response.into_string().await.expect("Response has a body"))
+            .expect("Valid ChallengeReportOutDTO");
+
+    assert_eq!(report.status, ChallengeStatus::InProgress);
+    assert_eq!(report.missed_weeks, vec![2]);
+    assert_eq!(report.contributed_total, Money::from(40));
+    assert_eq!(report.schedule[0].met, Some(true));
+    assert_eq!(report.schedule[2].met, Some(true));
+
+    let response = client
+        .get(format!("/saving_goals/{}/contributions", goal_id))
+        .dispatch()
+        .await;
+    let contributions: Vec<ContributionOutDTO> =
+        serde_json::from_str(&response.into_string().await.expect("Response has a body"))
+            .expect("Valid ContributionOutDTO list");
+    assert_eq!(contributions.len(), 2);
+
+    let response = client
+        .get(format!("/saving_goals/{}", goal_id))
+        .dispatch()
+        .await;
+    let goal: SavingGoalOutDTO =
+        serde_json::from_str(&response.into_string().await.expect("Response has a body"))
+            .expect("Valid SavingGoalOutDTO");
+    assert_eq!(goal.current_amount, Money::from(40));
+
+    cleanup(&pool, user_id).await;
+}
+
+#[rocket::async_test]
+async fn no_spend_month_challenge_fails_on_spending_integration_test() {
+    let (client, pool) = setup().await;
+
+    let (user_id, goal_id) = before_test(&pool, "nospenduser", "nospenduser@example.com")
+        .await
+        .expect("Failed to initialize test database");
+    common::login(&client, &pool, user_id).await;
+    let account = create_account(
+        &pool,
+        user_id,
+        &AccountInDTO {
+            name: "Test".to_string(),
+            balance: Money::from(100),
+            account_type: AccountType::Bank,
+            currency: None,
+        },
+    )
+    .await
+    .expect("Failed to create account");
+    let category = create_category(
+        &pool,
+        user_id,
+        &CategoryInDTO {
+            name: "Takeaway".to_string(),
+        },
+    )
+    .await
+    .expect("Failed to create category");
+
+    let start_date = Local::now().date_naive() - Duration::days(10);
+    let response = client
+        .post("/challenges")
+        .header(ContentType::JSON)
+        .body(
+            json!({
+                "challenge_type": "no_spend_month",
+                "goal_id": goal_id,
+                "start_date": start_date,
+                "category_ids": [category.id],
+            })
+            .to_string(),
+        )
+        .dispatch()
+        .await;
+    let challenge: ChallengeOutDTO =
+        serde_json::from_str(&response.into_string().await.expect("Response has a body"))
+            .expect("Valid ChallengeOutDTO");
+
+    create_transaction(
+        &pool,
+        user_id,
+        &TransactionInDTO {
+            title: "Pizza".to_string(),
+            amount: Money::from(15),
+            date: Utc.from_utc_datetime(
+                &(start_date + Duration::days(8))
+                    .and_hms_opt(20, 0, 0)
+                    .expect("Valid time"),
+            ),
+            category_id: category.id,
+            transaction_type: TransactionType::Expense,
+            account_id: account.id,
+            currency: None,
+            exchange_rate: None,
+        },
+    )
+    .await
+    .expect("Failed to create transaction");
+
+    let response = client
+        .get(format!("/challenges/{}", challenge.id))
+        .dispatch()
+        .await;
+    let report: ChallengeReportOutDTO =
+        serde_json::from_str(&response.into_string().await.expect("Response has a body"))
+            .expect("Valid ChallengeReportOutDTO");
+
+    assert_eq!(report.status, ChallengeStatus::Failed);
+    assert_eq!(report.missed_weeks, vec![2]);
+    assert_eq!(report.schedule[1].spent_amount, Money::from(15));
+
+    cleanup(&pool, user_id).await;
+}
+
+#[rocket::async_test]
+async fn income_percentage_challenge_requires_percentage_integration_test() {
+    let (client, pool) = setup().await;
+
+    let (user_id, goal_id) = before_test(&pool, "percentuser", "percentuser@example.com")
+        .await
+        .expect("Failed to initialize test database");
+    common::login(&client, &pool, user_id).await;
+
+    let response = client
+        .post("/challenges")
+        .header(ContentType::JSON)
+        .body(
+            json!({
+                "challenge_type": "income_percentage",
+                "goal_id": goal_id,
+                "start_date": Local::now().date_naive(),
+            })
+            .to_string(),
+        )
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::BadRequest);
+
+    for weeks in [0, 521] {
+        let response = client
+            .post("/challenges")
+            .header(ContentType::JSON)
+            .body(
+                json!({
+                    "challenge_type": "income_percentage",
+                    "goal_id": goal_id,
+                    "start_date": Local::now().date_naive(),
+                    "percentage": "10",
+                    "duration_weeks": weeks,
+                })
+                .to_string(),
+            )
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::BadRequest);
+    }
+
+    let response = client.get("/challenges").dispatch().await;
+    let challenges: Vec<ChallengeOutDTO> =
+        serde_json::from_str(&response.into_string().await.expect("Response has a body"))
+            .expect("Valid ChallengeOutDTO list");
+    assert!(challenges.is_empty());
+
+    cleanup(&pool, user_id).await;
+}
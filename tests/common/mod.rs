@@ -32,8 +32,18 @@
 // // }
 
 // // extern crate personal_finance_tracker;
+use personal_finance_tracker::auth::hash_password;
+use personal_finance_tracker::enums::custom_enums::WeekDay;
+use personal_finance_tracker::models::user_preferences::UserPreferences;
+use personal_finance_tracker::money::Money;
+use personal_finance_tracker::operations::user_ops::{find_user_by_id, set_user_password};
 use personal_finance_tracker::{create_rocket, db};
+use rocket::http::{ContentType, Status};
 use rocket::local::asynchronous::Client;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+pub const TEST_PASSWORD: &str = "correct horse battery staple";
 
 pub async fn setup() -> (Client, sqlx::Pool<sqlx::Postgres>) {
     let pool: sqlx::Pool<sqlx::Postgres> = db::conn::establish_connection(true).await;
@@ -43,3 +53,62 @@ pub async fn setup() -> (Client, sqlx::Pool<sqlx::Postgres>) {
         .expect("valid rocket instance");
     (client, pool)
 }
+
+// Gives the user a known password and logs the tracked client in as them,
+// so the session cookie rides along on every following request.
+#[allow(dead_code)]
+pub async fn login(client: &Client, pool: &PgPool, user_id: Uuid) {
+    let user = find_user_by_id(pool, user_id)
+        .await
+        .expect("Failed to fetch user")
+        .expect("User exists");
+    let password_hash = hash_password(TEST_PASSWORD).expect("Failed to hash password");
+    set_user_password(pool, user_id, &password_hash)
+        .await
+        .expect("Failed to set password");
+
+    let response = client
+        .post("/auth/login")
+        .header(ContentType::JSON)
+        .body(
+            rocket::serde::json::serde_json::json!({
+                "email": user.email,
+                "password": TEST_PASSWORD
+            })
+            .to_string(),
+        )
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+}
+
+// Promotes the user to admin before logging the client in as them.
+#[allow(dead_code)]
+pub async fn login_admin(client: &Client, pool: &PgPool, user_id: Uuid) {
+    sqlx::query("UPDATE users SET role = 'admin' WHERE id = $1")
+        .bind(user_id)
+        .execute(pool)
+        .await
+        .expect("Failed to promote user to admin");
+    login(client, pool, user_id).await;
+}
+
+#[allow(dead_code)]
+pub fn money(amount: &str) -> Money {
+    amount.parse().expect("Valid amount")
+}
+
+// Preferences with the defaults a new user gets.
+#[allow(dead_code)]
+pub fn preferences() -> UserPreferences {
+    UserPreferences {
+        user_id: Uuid::nil(),
+        base_currency: "USD".to_string(),
+        locale: "en-US".to_string(),
+        timezone: "UTC".to_string(),
+        week_start: WeekDay::Monday,
+        fiscal_year_start_month: 1,
+        date_format: "%Y-%m-%d".to_string(),
+        updated_at: None,
+    }
+}
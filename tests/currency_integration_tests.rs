@@ -0,0 +1,574 @@
+use chrono::Utc;
+use personal_finance_tracker::dtos::account_dtos::{AccountOutDTO, CurrencyBalanceOutDTO};
+use personal_finance_tracker::dtos::allocation_dtos::AllocationPlanOutDTO;
+use personal_finance_tracker::dtos::category_dtos::CategoryInDTO;
+use personal_finance_tracker::dtos::saving_goal_dtos::{SavingGoalInDTO, SavingGoalOutDTO};
+use personal_finance_tracker::dtos::transaction_dtos::{TransactionInDTO, TransactionOutDTO};
+use personal_finance_tracker::dtos::user_dtos::UserInDTO;
+use personal_finance_tracker::enums::custom_enums::TransactionType;
+use personal_finance_tracker::money::{is_currency_code, minor_units, Money};
+use personal_finance_tracker::operations::category_ops::create_category;
+use personal_finance_tracker::operations::saving_goal_ops::{
+    create_saving_goal, find_saving_goal_by_id,
+};
+use personal_finance_tracker::operations::user_ops::create_user;
+use rocket::http::{ContentType, Status};
+use rocket::local::asynchronous::Client;
+use rocket::serde::json::serde_json;
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::common::{money, setup};
+
+mod common;
+
+pub async fn before_test(pool: &PgPool, username: &str) -> Result<(Uuid, Uuid), sqlx::Error> {
+    let user_dto = UserInDTO {
+        username: username.to_string(),
+        email: format!("{}@example.com", username),
+    };
+    let user = create_user(pool, &user_dto).await?;
+    let category = create_category(
+        pool,
+        user.id,
+        &CategoryInDTO {
+            name: "Travel".to_string(),
+        },
+    )
+    .await?;
+
+    Ok((user.id, category.id))
+}
+
+pub async fn cleanup(pool: &PgPool, user_id: Uuid) {
+    sqlx::query("DELETE FROM users WHERE id = $1")
+        .bind(user_id)
+        .execute(pool)
+        .await
+        .unwrap_or_else(|e| panic!("Failed to cleanup test users: {}", e));
+}
+
+async fn post_account(client: &Client, body: Value) -> (Status, Option<String>) {
+    let response = client
+        .post("/accounts")
+        .header(ContentType::JSON)
+        .body(body.to_string())
+        .dispatch()
+        .await;
+    (response.status(), response.into_string().await)
+}
+
+async fn post_transaction(client: &Client, body: Value) -> (Status, Option<String>) {
+    let response = client
+        .post("/transactions")
+        .header(ContentType::JSON)
+        .body(body.to_string())
+        .dispatch()
+        .await;
+    (response.status(), response.into_string().await)
+}
+
+async fn account(client: &Client, name: &str, balance: &str, currency: Option<&str>) -> Uuid {
+    let (status, body) = post_account(
+        client,
+        json!({
+            "name": name,
+            "balance": balance,
+            "account_type": "Bank",
+            "currency": currency,
+        }),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    let account: AccountOutDTO =
+        serde_json::from_str(&body.expect("Response has a body")).expect("Valid AccountOutDTO");
+    account.id
+}
+
+fn expense(account_id: Uuid, amount: Money, currency: Option<&str>) -> TransactionInDTO {
+    TransactionInDTO {
+        title: "Dinner".to_string(),
+        amount,
+        date: Utc::now(),
+        category_id: Uuid::nil(),
+        transaction_type: TransactionType::Expense,
+        account_id,
+        currency: currency.map(str::to_string),
+        exchange_rate: None,
+    }
+}
+
+#[test]
+fn in_account_currency_test() {
+    let account_id = Uuid::nil();
+
+    let plain = expense(account_id, money("12.50"), None)
+        .in_account_currency("EUR")
+        .expect("Same currency");
+    assert_eq!(plain.amount, money("12.50"));
+    assert_eq!(plain.currency, "EUR");
+    assert_eq!(plain.original_amount, None);
+
+    let mut foreign = expense(account_id, money("10.00"), Some("USD"));
+    assert!(foreign.in_account_currency("EUR").is_err());
+    foreign.exchange_rate = Some("0.9183".parse().expect("Valid rate"));
+    let converted = foreign.in_account_currency("EUR").expect("Converted");
+    assert_eq!(converted.amount, money("9.18"));
+    assert_eq!(converted.currency, "EUR");
+    assert_eq!(converted.original_amount, Some(money("10.00")));
+    assert_eq!(converted.original_currency.as_deref(), Some("USD"));
+
+    let mut same = expense(account_id, money("10.00"), Some("EUR"));
+    same.exchange_rate = Some("1.1".parse().expect("Valid rate"));
+    assert!(same.in_account_currency("EUR").is_err());
+
+    let fractional_yen = expense(account_id, money("100.5"), None);
+    assert!(fractional_yen.in_account_currency("JPY").is_err());
+}
+
+#[test]
+fn minor_units_test() {
+    assert!(is_currency_code("EUR"));
+    for code in ["eur", "EURO", "E1R", ""] {
+        assert!(!is_currency_code(code), "{:?}", code);
+    }
+
+    assert_eq!(minor_units("USD"), 2);
+    assert_eq!(minor_units("JPY"), 0);
+    assert_eq!(minor_units("BHD"), 3);
+    assert_eq!(minor_units("CLF"), 4);
+    assert_eq!(money("2.5").round_to("JPY"), Money::from(3));
+    assert_eq!(money("1.2345").round_to("BHD"), money("1.235"));
+    assert_eq!(money("10.005").round_to("EUR"), money("10.01"));
+}
+
+#[rocket::async_test]
+async fn multi_currency_accounts_integration_test() {
+    let (client, pool) = setup().await;
+
+    let (user_id, category_id) = before_test(&pool, "currencyuser")
+        .await
+        .expect("Failed to initialize test database");
+    common::login(&client, &pool, user_id).await;
+
+    let (status, _) = post_account(
+        &client,
+        json!({ "name": "Bad", "balance": "0", "account_type": "Bank", "currency": "eur" }),
+    )
+    .await;
+    assert_eq!(status, Status::BadRequest);
+
+    let checking = account(&client, "Checking", "100.00", None).await;
+    let euros = account(&client, "Euro account", "250.00", Some("EUR")).await;
+    let more_euros = account(&client, "Euro savings", "50.25", Some("EUR")).await;
+    let dinars = account(&client, "Dinar account", "12000", Some("RSD")).await;
+
+    let response = client
+        .get(format!("/accounts/{}", checking))
+        .dispatch()
+        .await;
+    let checking_out: AccountOutDTO =
+        serde_json::from_str(&response.into_string().await.expect("Response has a body"))
+            .expect("Valid AccountOutDTO");
+    assert_eq!(checking_out.currency, "USD");
+
+    let response = client.get("/accounts/balances").dispatch().await;
+    assert_eq!(response.status(), Status::Ok);
+    let balances: Vec<CurrencyBalanceOutDTO> =
+        serde_json::from_str(&response.into_string().await.expect("Response has a body"))
+            .expect("Valid CurrencyBalanceOutDTO list");
+    let totals: Vec<(&str, Money, i64)> = balances
+        .iter()
+        .map(|b| (b.currency.as_str(), b.balance, b.accounts))
+        .collect();
+    assert_eq!(
+        totals,
+        vec![
+            ("EUR", money("300.25"), 2),
+            ("RSD", Money::from(12000), 1),
+            ("USD", Money::from(100), 1),
+        ]
+    );
+
+    let transaction = |account_id: Uuid, body: Value| {
+        let mut base = json!({
+            "title": "Dinner",
+            "amount": "40.00",
+            "date": "2026-10-19T12:00:00Z",
+            "category_id": category_id,
+            "transaction_type": "Expense",
+            "account_id": account_id,
+        });
+        if let (Value::Object(base), Value::Object(extra)) = (&mut base, body) {
+            base.extend(extra);
+        }
+        base
+    };
+
+    let (status, body) = post_transaction(&client, transaction(euros, json!({}))).await;
+    assert_eq!(status, Status::Ok);
+    let plain: TransactionOutDTO =
+        serde_json::from_str(&body.expect("Response has a body")).expect("Valid TransactionOutDTO");
+    assert_eq!(plain.currency, "EUR");
+    assert_eq!(plain.original_currency, None);
+
+    let (status, _) =
+        post_transaction(&client, transaction(euros, json!({ "currency": "USD" }))).await;
+    assert_eq!(status, Status::BadRequest);
+
+    let (status, body) = post_transaction(
+        &client,
+        transaction(
+            dinars,
+            json!({ "currency": "EUR", "exchange_rate": "117.1234" }),
+        ),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    let converted: TransactionOutDTO =
+        serde_json::from_str(&body.expect("Response has a body")).expect("Valid TransactionOutDTO");
+    assert_eq!(converted.amount, money("4684.94"));
+    assert_eq!(converted.currency, "RSD");
+    assert_eq!(converted.original_amount, Some(money("40.00")));
+    assert_eq!(converted.original_currency.as_deref(), Some("EUR"));
+
+    let response = client
+        .patch(format!("/accounts/{}", euros))
+        .header(ContentType::JSON)
+        .body(
+            json!({ "name": "Euro account", "balance": "250.00", "account_type": "Bank", "currency": "USD" })
+                .to_string(),
+        )
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Conflict);
+
+    let response = client
+        .patch(format!("/accounts/{}", more_euros))
+        .header(ContentType::JSON)
+        .body(
+            json!({ "name": "Dollar savings", "balance": "50.25", "account_type": "Bank", "currency": "USD" })
+                .to_string(),
+        )
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let moved: AccountOutDTO =
+        serde_json::from_str(&response.into_string().await.expect("Response has a body"))
+            .expect("Valid AccountOutDTO");
+    assert_eq!(moved.currency, "USD");
+
+    cleanup(&pool, user_id).await;
+}
+
+#[rocket::async_test]
+async fn savings_rules_skip_goals_in_another_currency_integration_test() {
+    let (client, pool) = setup().await;
+
+    let (user_id, category_id) = before_test(&pool, "rulecurrencyuser")
+        .await
+        .expect("Failed to initialize test database");
+    common::login(&client, &pool, user_id).await;
+
+    // The goal is held in the user's base currency, USD.
+    let goal: SavingGoalOutDTO = create_saving_goal(
+        &pool,
+        user_id,
+        &SavingGoalInDTO {
+            title: "Holiday".to_string(),
+            target_amount: Money::from(1000),
+            current_amount: Money::ZERO,
+            deadline: chrono::NaiveDate::from_ymd_opt(2030, 1, 1).expect("Valid date"),
+        },
+    )
+    .await
+    .expect("Failed to create saving goal")
+    .to_saving_goal_out_dto();
+    let dollars = account(&client, "Checking", "0", None).await;
+    let euros = account(&client, "Euro account", "0", Some("EUR")).await;
+    let euro_savings = account(&client, "Euro savings", "0", Some("EUR")).await;
+
+    for account_id in [dollars, euros] {
+        let response = client
+            .post("/round_up_rules")
+            .header(ContentType::JSON)
+            .body(
+                json!({ "account_id": account_id, "goal_id": goal.id, "round_to": 1 }).to_string(),
+            )
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::Ok);
+    }
+    let response = client
+        .post("/allocation_rules")
+        .header(ContentType::JSON)
+        .body(
+            json!({
+                "name": "Payday",
+                "match_title": "salary",
+                "targets": [
+                    { "target_type": "saving_goal", "target_id": goal.id, "amount_type": "fixed", "value": 100 },
+                    { "target_type": "account", "target_id": euro_savings, "amount_type": "percentage", "value": 10 },
+                ],
+            })
+            .to_string(),
+        )
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    let transaction = |account_id: Uuid, title: &str, amount: &str, transaction_type: &str| {
+        json!({
+            "title": title,
+            "amount": amount,
+            "date": "2026-10-19T12:00:00Z",
+            "category_id": category_id,
+            "transaction_type": transaction_type,
+            "account_id": account_id,
+        })
+    };
+    let goal_amount = || async {
+        find_saving_goal_by_id(&pool, user_id, goal.id)
+            .await
+            .expect("Failed to fetch saving goal")
+            .expect("Saving goal exists")
+            .current_amount
+    };
+
+    // A round-up in euros isn't added to a goal counted in dollars.
+    let (status, _) =
+        post_transaction(&client, transaction(euros, "Coffee", "3.40", "Expense")).await;
+    assert_eq!(status, Status::Ok);
+    assert_eq!(goal_amount().await, Money::ZERO);
+    let (status, _) =
+        post_transaction(&client, transaction(dollars, "Coffee", "3.40", "Expense")).await;
+    assert_eq!(status, Status::Ok);
+    assert_eq!(goal_amount().await, money("0.60"));
+
+    // Euro income only reaches the euro account; the goal's share stays
+    // unallocated.
+    let (status, body) =
+        post_transaction(&client, transaction(euros, "Salary", "1000", "Income")).await;
+    assert_eq!(status, Status::Ok);
+    let salary: TransactionOutDTO =
+        serde_json::from_str(&body.expect("Response has a body")).expect("Valid TransactionOutDTO");
+    assert_eq!(goal_amount().await, money("0.60"));
+    let response = client
+        .get(format!("/transactions/{}/allocation_plan", salary.id))
+        .dispatch()
+        .await;
+    let plan: AllocationPlanOutDTO =
+        serde_json::from_str(&response.into_string().await.expect("Response has a body"))
+            .expect("Valid AllocationPlanOutDTO");
+    let planned: Vec<(Uuid, Money)> = plan
+        .allocations
+        .iter()
+        .map(|a| (a.target_id, a.amount))
+        .collect();
+    assert_eq!(planned, vec![(euro_savings, Money::from(100))]);
+    assert_eq!(plan.unallocated, Money::from(900));
+
+    cleanup(&pool, user_id).await;
+}
+
+#[rocket::async_test]
+async fn malformed_currency_codes_are_rejected_integration_test() {
+    let (client, pool) = setup().await;
+
+    let (user_id, category_id) = before_test(&pool, "currencycodes")
+        .await
+        .expect("Failed to initialize test database");
+    common::login(&client, &pool, user_id).await;
+
+    for code in ["EURO", "E1R", "", "€"] {
+        let (status, _) = post_account(
+            &client,
+            json!({ "name": "Bad", "balance": "0", "account_type": "Bank", "currency": code }),
+        )
+        .await;
+        assert_eq!(status, Status::BadRequest, "{:?}", code);
+    }
+
+    let checking = account(&client, "Checking", "100.00", None).await;
+    let transaction = |extra: Value| {
+        let mut body = json!({
+            "title": "Museum",
+            "amount": "20.00",
+            "date": "2026-10-19T12:00:00Z",
+            "category_id": category_id,
+            "transaction_type": "Expense",
+            "account_id": checking,
+        });
+        if let (Value::Object(body), Value::Object(extra)) = (&mut body, extra) {
+            body.extend(extra);
+        }
+        body
+    };
+    for extra in [
+        json!({ "currency": "eur", "exchange_rate": "1.08" }),
+        json!({ "currency": "EUR", "exchange_rate": "0" }),
+        json!({ "currency": "EUR", "exchange_rate": "-1.08" }),
+    ] {
+        let (status, _) = post_transaction(&client, transaction(extra.clone())).await;
+        assert_eq!(status, Status::BadRequest, "{}", extra);
+    }
+
+    let response = client
+        .get("/accounts/net_worth?currency=usd")
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::BadRequest);
+    let response = client
+        .patch("/users/me/preferences")
+        .header(ContentType::JSON)
+        .body(json!({ "base_currency": "dollars" }).to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::BadRequest);
+
+    // Nothing was booked on the way.
+    let response = client.get("/transactions").dispatch().await;
+    let transactions: Vec<TransactionOutDTO> =
+        serde_json::from_str(&response.into_string().await.expect("Response has a body"))
+            .expect("Valid TransactionOutDTO list");
+    assert!(transactions.is_empty());
+
+    cleanup(&pool, user_id).await;
+}
+
+#[rocket::async_test]
+async fn amounts_follow_currency_minor_units_integration_test() {
+    let (client, pool) = setup().await;
+
+    let (user_id, category_id) = before_test(&pool, "currencyunits")
+        .await
+        .expect("Failed to initialize test database");
+    common::login(&client, &pool, user_id).await;
+
+    for (balance, currency, expected) in [
+        ("100.5", "JPY", Status::BadRequest),
+        ("1.2345", "BHD", Status::BadRequest),
+        ("1.234", "BHD", Status::Ok),
+        ("0.0001", "CLF", Status::Ok),
+    ] {
+        let (status, _) = post_account(
+            &client,
+            json!({ "name": currency, "balance": balance, "account_type": "Bank", "currency": currency }),
+        )
+        .await;
+        assert_eq!(status, expected, "{} {}", balance, currency);
+    }
+
+    let yen = account(&client, "Yen", "10000", Some("JPY")).await;
+    let dollars = account(&client, "Dollars", "100.00", None).await;
+    let transaction = |account_id: Uuid, extra: Value| {
+        let mut body = json!({
+            "title": "Ramen",
+            "date": "2026-10-19T12:00:00Z",
+            "category_id": category_id,
+            "transaction_type": "Expense",
+            "account_id": account_id,
+        });
+        if let (Value::Object(body), Value::Object(extra)) = (&mut body, extra) {
+            body.extend(extra);
+        }
+        body
+    };
+
+    let (status, _) =
+        post_transaction(&client, transaction(yen, json!({ "amount": "12.5" }))).await;
+    assert_eq!(status, Status::BadRequest);
+    let (status, _) = post_transaction(
+        &client,
+        transaction(
+            dollars,
+            json!({ "amount": "1500.5", "currency": "JPY", "exchange_rate": "0.0066" }),
+        ),
+    )
+    .await;
+    assert_eq!(status, Status::BadRequest);
+
+    // Converted amounts are rounded to the account's currency.
+    let (status, body) = post_transaction(
+        &client,
+        transaction(
+            yen,
+            json!({ "amount": "10.00", "currency": "USD", "exchange_rate": "151.237" }),
+        ),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    let converted: TransactionOutDTO =
+        serde_json::from_str(&body.expect("Response has a body")).expect("Valid TransactionOutDTO");
+    assert_eq!(converted.amount, Money::from(1512));
+    assert_eq!(converted.currency, "JPY");
+
+    let (status, body) = post_transaction(
+        &client,
+        transaction(
+            dollars,
+            json!({ "amount": "1500", "currency": "JPY", "exchange_rate": "0.0066" }),
+        ),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    let converted: TransactionOutDTO =
+        serde_json::from_str(&body.expect("Response has a body")).expect("Valid TransactionOutDTO");
+    assert_eq!(converted.amount, money("9.90"));
+    assert_eq!(converted.original_amount, Some(Money::from(1500)));
+
+    cleanup(&pool, user_id).await;
+}
+
+#[rocket::async_test]
+async fn base_currency_sets_new_record_currency_integration_test() {
+    let (client, pool) = setup().await;
+
+    let (user_id, _) = before_test(&pool, "currencybase")
+        .await
+        .expect("Failed to initialize test database");
+    common::login(&client, &pool, user_id).await;
+
+    let response = client
+        .patch("/users/me/preferences")
+        .header(ContentType::JSON)
+        .body(json!({ "base_currency": " jpy " }).to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    let wallet = account(&client, "Wallet", "5000", None).await;
+    let response = client.get(format!("/accounts/{}", wallet)).dispatch().await;
+    let wallet: AccountOutDTO =
+        serde_json::from_str(&response.into_string().await.expect("Response has a body"))
+            .expect("Valid AccountOutDTO");
+    assert_eq!(wallet.currency, "JPY");
+
+    // Goals are kept in the base currency, and so are its minor units.
+    let goal = |target: &str| {
+        json!({
+            "title": "Trip",
+            "target_amount": target,
+            "current_amount": "0",
+            "deadline": "2030-01-01",
+        })
+        .to_string()
+    };
+    let response = client
+        .post("/saving_goals")
+        .header(ContentType::JSON)
+        .body(goal("150000.50"))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::BadRequest);
+    let response = client
+        .post("/saving_goals")
+        .header(ContentType::JSON)
+        .body(goal("150000"))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    cleanup(&pool, user_id).await;
+}
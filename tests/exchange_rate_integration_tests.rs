@@ -0,0 +1,326 @@
+use chrono::{Duration, Local, NaiveDate, Utc};
+use personal_finance_tracker::dtos::account_dtos::{AccountOutDTO, NetWorthOutDTO};
+use personal_finance_tracker::dtos::category_dtos::CategoryInDTO;
+use personal_finance_tracker::dtos::exchange_rate_dtos::{
+    ExchangeRateImportOutDTO, ExchangeRateQuoteOutDTO,
+};
+use personal_finance_tracker::dtos::report_dtos::SummaryReportOutDTO;
+use personal_finance_tracker::dtos::user_dtos::UserInDTO;
+use personal_finance_tracker::exchange_rates::import::{parse_ecb_csv, parse_ecb_xml};
+use personal_finance_tracker::exchange_rates::RateTable;
+use personal_finance_tracker::models::exchange_rate::ExchangeRate;
+use personal_finance_tracker::money::Money;
+use personal_finance_tracker::operations::category_ops::create_category;
+use personal_finance_tracker::operations::user_ops::create_user;
+use rocket::http::{ContentType, Status};
+use rocket::local::asynchronous::Client;
+use rocket::serde::json::serde_json;
+use rust_decimal::Decimal;
+use serde_json::json;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::common::{money, setup};
+
+mod common;
+
+pub async fn before_test(pool: &PgPool, username: &str) -> Result<Uuid, sqlx::Error> {
+    let user_dto = UserInDTO {
+        username: username.to_string(),
+        email: format!("{}@example.com", username),
+    };
+    let user = create_user(pool, &user_dto).await?;
+
+    Ok(user.id)
+}
+
+pub async fn cleanup(pool: &PgPool, user_ids: &[Uuid], from: NaiveDate) {
+    sqlx::query("DELETE FROM users WHERE id = ANY($1)")
+        .bind(user_ids)
+        .execute(pool)
+        .await
+        .unwrap_or_else(|e| panic!("Failed to cleanup test users: {}", e));
+    sqlx::query("DELETE FROM exchange_rates WHERE base_currency = 'EUR' AND rate_date >= $1")
+        .bind(from)
+        .execute(pool)
+        .await
+        .unwrap_or_else(|e| panic!("Failed to cleanup exchange rates: {}", e));
+}
+
+fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).expect("Valid date")
+}
+
+fn decimal(value: &str) -> Decimal {
+    value.parse().expect("Valid decimal")
+}
+
+fn rate(quote: &str, rate_date: NaiveDate, rate: &str) -> ExchangeRate {
+    ExchangeRate {
+        base_currency: "EUR".to_string(),
+        quote_currency: quote.to_string(),
+        rate_date,
+        rate: decimal(rate),
+        imported_at: Local::now().naive_local(),
+    }
+}
+
+async fn account(client: &Client, name: &str, balance: &str, currency: &str) -> Uuid {
+    let response = client
+        .post("/accounts")
+        .header(ContentType::JSON)
+        .body(
+            json!({
+                "name": name,
+                "balance": balance,
+                "account_type": "Bank",
+                "currency": currency,
+            })
+            .to_string(),
+        )
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let account: AccountOutDTO =
+        serde_json::from_str(&response.into_string().await.expect("Response has a body"))
+            .expect("Valid AccountOutDTO");
+    account.id
+}
+
+#[test]
+fn parse_ecb_xml_test() {
+    let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<gesmes:Envelope xmlns:gesmes="http://www.gesmes.org/xml/2002-08-01" xmlns="http://www.ecb.int/vocabulary/2002-08-01/eurofxref">
+    <gesmes:subject>Reference rates</gesmes:subject>
+    <Cube>
+        <Cube time='2024-01-05'>
+            <Cube currency='USD' rate='1.0921'/>
+            <Cube currency='JPY' rate='158.08'/>
+        </Cube>
+        <Cube time="2024-01-04">
+            <Cube currency="USD" rate="1.0953"/>
+        </Cube>
+    </Cube>
+</gesmes:Envelope>"#;
+
+    let rates = parse_ecb_xml(xml).expect("Valid XML");
+    let parsed: Vec<(NaiveDate, &str, Decimal)> = rates
+        .iter()
+        .map(|rate| (rate.date, rate.currency.as_str(), rate.rate))
+        .collect();
+    assert_eq!(
+        parsed,
+        vec![
+            (date(2024, 1, 5), "USD", decimal("1.0921")),
+            (date(2024, 1, 5), "JPY", decimal("158.08")),
+            (date(2024, 1, 4), "USD", decimal("1.0953")),
+        ]
+    );
+
+    assert!(parse_ecb_xml("<Cube><Cube currency='USD' rate='1.1'/></Cube>").is_err());
+    assert!(parse_ecb_xml("<Cube time='2024-01-05'><Cube currency='USD' rate='-1'/>").is_err());
+    assert!(parse_ecb_xml("<Envelope></Envelope>").is_err());
+}
+
+#[test]
+fn parse_ecb_csv_test() {
+    let csv = "Date,USD,JPY,CYP,\n2024-01-05,1.0921,158.08,N/A,\n2024-01-04,1.0953,157.5,N/A,\n";
+
+    let rates = parse_ecb_csv(csv).expect("Valid CSV");
+    assert_eq!(rates.len(), 4);
+    assert_eq!(rates[1].date, date(2024, 1, 5));
+    assert_eq!(rates[1].currency, "JPY");
+    assert_eq!(rates[1].rate, decimal("158.08"));
+    assert!(rates.iter().all(|rate| rate.currency != "CYP"));
+
+    assert!(parse_ecb_csv("Currency,USD\n2024-01-05,1.09\n").is_err());
+    assert!(parse_ecb_csv("Date,USD\n05/01/2024,1.09\n").is_err());
+    assert!(parse_ecb_csv("Date,usd\n2024-01-05,1.09\n").is_err());
+}
+
+#[test]
+fn rate_table_test() {
+    // 2024-01-05 is a Friday; nothing is published over the weekend.
+    let friday = date(2024, 1, 5);
+    let table = RateTable::new(&[
+        rate("USD", friday, "1.1000"),
+        rate("RSD", friday, "117.15"),
+        rate("USD", friday - Duration::days(1), "1.2000"),
+    ]);
+
+    assert_eq!(table.rate("EUR", "USD", friday), Some(decimal("1.1")));
+    assert_eq!(
+        table.rate("EUR", "USD", friday - Duration::days(1)),
+        Some(decimal("1.2"))
+    );
+    assert_eq!(
+        table.rate("EUR", "USD", friday + Duration::days(2)),
+        Some(decimal("1.1"))
+    );
+    assert_eq!(table.rate("EUR", "USD", friday + Duration::days(8)), None);
+    assert_eq!(table.rate("EUR", "USD", friday - Duration::days(2)), None);
+    assert_eq!(table.rate("USD", "USD", friday), Some(Decimal::ONE));
+
+    assert_eq!(
+        table.convert(Money::from(11), "USD", "EUR", friday),
+        Ok(Money::from(10))
+    );
+    assert_eq!(
+        table.convert(Money::from(10), "USD", "RSD", friday),
+        Ok(money("1065"))
+    );
+    assert_eq!(
+        table.convert(Money::from(1000), "RSD", "USD", friday),
+        Ok(money("9.39"))
+    );
+
+    let missing = table
+        .convert(Money::from(10), "EUR", "GBP", friday)
+        .expect_err("No GBP rates");
+    assert_eq!(missing.to, "GBP");
+    assert_eq!(missing.date, friday);
+}
+
+#[rocket::async_test]
+async fn exchange_rates_in_reports_integration_test() {
+    let (client, pool) = setup().await;
+    let admin_client = Client::tracked(personal_finance_tracker::create_rocket(pool.clone()))
+        .await
+        .expect("Valid rocket instance");
+
+    let today = Utc::now().date_naive();
+    let yesterday = today - Duration::days(1);
+    let user_id = before_test(&pool, "ratesuser")
+        .await
+        .expect("Failed to initialize test database");
+    let admin_id = before_test(&pool, "ratesadmin")
+        .await
+        .expect("Failed to initialize test database");
+    common::login(&client, &pool, user_id).await;
+    common::login_admin(&admin_client, &pool, admin_id).await;
+
+    let csv = format!(
+        "Date,USD,CHF,\n{},1.0850,0.9500,\n{},1.0700,N/A,\n",
+        today, yesterday
+    );
+    let response = client
+        .post("/admin/exchange_rates")
+        .header(ContentType::CSV)
+        .body(csv.clone())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Forbidden);
+
+    let response = admin_client
+        .post("/admin/exchange_rates")
+        .header(ContentType::CSV)
+        .body(csv)
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let import: ExchangeRateImportOutDTO =
+        serde_json::from_str(&response.into_string().await.expect("Response has a body"))
+            .expect("Valid ExchangeRateImportOutDTO");
+    assert_eq!(import.imported, 3);
+    assert_eq!(import.first_date, Some(yesterday));
+    assert_eq!(import.last_date, Some(today));
+
+    let response = admin_client
+        .post("/admin/exchange_rates")
+        .body("Date,USD\nyesterday,1.1\n")
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::BadRequest);
+
+    let response = client
+        .get(format!("/exchange_rates/USD/CHF?date={}", today))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let quote: ExchangeRateQuoteOutDTO =
+        serde_json::from_str(&response.into_string().await.expect("Response has a body"))
+            .expect("Valid ExchangeRateQuoteOutDTO");
+    assert_eq!(quote.rate, decimal("0.95") / decimal("1.085"));
+
+    let response = client.get("/exchange_rates/EUR/GBP").dispatch().await;
+    assert_eq!(response.status(), Status::NotFound);
+
+    let euros = account(&client, "Euro account", "100.00", "EUR").await;
+    let francs = account(&client, "Franc account", "50.00", "CHF").await;
+
+    let response = client.get("/accounts/net_worth").dispatch().await;
+    assert_eq!(response.status(), Status::Ok);
+    let net_worth: NetWorthOutDTO =
+        serde_json::from_str(&response.into_string().await.expect("Response has a body"))
+            .expect("Valid NetWorthOutDTO");
+    assert_eq!(net_worth.currency, "USD");
+    assert_eq!(net_worth.date, today);
+    assert_eq!(net_worth.net_worth, money("165.61"));
+
+    let response = client
+        .get("/accounts/net_worth?currency=EUR")
+        .dispatch()
+        .await;
+    let net_worth: NetWorthOutDTO =
+        serde_json::from_str(&response.into_string().await.expect("Response has a body"))
+            .expect("Valid NetWorthOutDTO");
+    let converted: Vec<(&str, Money)> = net_worth
+        .balances
+        .iter()
+        .map(|b| (b.currency.as_str(), b.converted_balance))
+        .collect();
+    assert_eq!(
+        converted,
+        vec![("CHF", money("52.63")), ("EUR", Money::from(100))]
+    );
+    assert_eq!(net_worth.net_worth, money("152.63"));
+
+    let category = create_category(
+        &pool,
+        user_id,
+        &CategoryInDTO {
+            name: "Work".to_string(),
+        },
+    )
+    .await
+    .expect("Failed to create category");
+    for (account_id, amount, transaction_type) in
+        [(francs, "200.00", "Income"), (euros, "20.00", "Expense")]
+    {
+        let response = client
+            .post("/transactions")
+            .header(ContentType::JSON)
+            .body(
+                json!({
+                    "title": "Entry",
+                    "amount": amount,
+                    "date": Utc::now(),
+                    "category_id": category.id,
+                    "transaction_type": transaction_type,
+                    "account_id": account_id,
+                })
+                .to_string(),
+            )
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::Ok);
+    }
+
+    let response = client.get("/reports/summary?currency=EUR").dispatch().await;
+    assert_eq!(response.status(), Status::Ok);
+    let report: SummaryReportOutDTO =
+        serde_json::from_str(&response.into_string().await.expect("Response has a body"))
+            .expect("Valid SummaryReportOutDTO");
+    assert_eq!(report.currency, "EUR");
+    assert_eq!(report.to, today);
+    assert_eq!(report.income, money("210.53"));
+    assert_eq!(report.expenses, Money::from(20));
+    assert_eq!(report.net, money("190.53"));
+    assert_eq!(report.transaction_count, 2);
+
+    account(&client, "Yen account", "1000", "JPY").await;
+    let response = client.get("/accounts/net_worth").dispatch().await;
+    assert_eq!(response.status(), Status::UnprocessableEntity);
+
+    cleanup(&pool, &[user_id, admin_id], yesterday).await;
+}
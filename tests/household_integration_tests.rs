@@ -0,0 +1,828 @@
+use personal_finance_tracker::dtos::account_dtos::{AccountInDTO, AccountOutDTO};
+use personal_finance_tracker::dtos::budget_dtos::{BudgetInDTO, BudgetOutDTO};
+use personal_finance_tracker::dtos::category_dtos::CategoryInDTO;
+use personal_finance_tracker::dtos::household_dtos::{HouseholdDetailOutDTO, HouseholdOutDTO};
+use personal_finance_tracker::dtos::transaction_dtos::TransactionOutDTO;
+use personal_finance_tracker::dtos::user_dtos::UserInDTO;
+use personal_finance_tracker::enums::custom_enums::{AccountType, HouseholdRole};
+use personal_finance_tracker::money::Money;
+use personal_finance_tracker::operations::account_ops::create_account;
+use personal_finance_tracker::operations::budget_ops::create_budget;
+use personal_finance_tracker::operations::category_ops::create_category;
+use personal_finance_tracker::operations::user_ops::create_user;
+use rocket::http::{ContentType, Status};
+use rocket::local::asynchronous::Client;
+use rocket::serde::json::serde_json;
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::common::setup;
+
+mod common;
+
+pub async fn before_test(pool: &PgPool, username: &str, email: &str) -> Result<Uuid, sqlx::Error> {
+    let user_dto = UserInDTO {
+        username: username.to_string(),
+        email: email.to_string(),
+    };
+    let user = create_user(pool, &user_dto).await?;
+
+    Ok(user.id)
+}
+
+pub async fn cleanup(pool: &PgPool, household_id: Uuid, user_ids: &[Uuid]) {
+    sqlx::query("DELETE FROM households WHERE id = $1")
+        .bind(household_id)
+        .execute(pool)
+        .await
+        .unwrap_or_else(|e| panic!("Failed to cleanup test household: {}", e));
+    for user_id in user_ids {
+        for table in ["transactions", "budgets", "accounts", "categories", "users"] {
+            let column = if table == "users" { "id" } else { "user_id" };
+            sqlx::query(&format!("DELETE FROM {} WHERE {} = $1", table, column))
+                .bind(user_id)
+                .execute(pool)
+                .await
+                .unwrap_or_else(|e| panic!("Failed to cleanup test {}: {}", table, e));
+        }
+    }
+}
+
+// Each member gets their own client so their session cookies stay apart.
+async fn member_client(pool: &PgPool, username: &str) -> (Client, Uuid) {
+    let (client, _) = setup().await;
+    let user_id = before_test(pool, username, &format!("{}@example.com", username))
+        .await
+        .expect("Failed to initialize test database");
+    common::login(&client, pool, user_id).await;
+    (client, user_id)
+}
+
+async fn parse<T: DeserializeOwned>(response: rocket::local::asynchronous::LocalResponse<'_>) -> T {
+    assert_eq!(response.status(), Status::Ok);
+    serde_json::from_str(&response.into_string().await.expect("Response has a body"))
+        .expect("Valid response body")
+}
+
+async fn post_json(client: &Client, uri: String, body: Value) -> Status {
+    client
+        .post(uri)
+        .header(ContentType::JSON)
+        .body(body.to_string())
+        .dispatch()
+        .await
+        .status()
+}
+
+async fn create_household(client: &Client, name: &str) -> Uuid {
+    let response = client
+        .post("/households")
+        .header(ContentType::JSON)
+        .body(json!({ "name": name }).to_string())
+        .dispatch()
+        .await;
+    parse::<HouseholdOutDTO>(response).await.id
+}
+
+async fn add_member(client: &Client, household_id: Uuid, email: &str, role: &str) -> Status {
+    post_json(
+        client,
+        format!("/households/{}/members", household_id),
+        json!({ "email": email, "role": role }),
+    )
+    .await
+}
+
+async fn share(client: &Client, household_id: Uuid, record_type: &str, record_id: Uuid) -> Status {
+    post_json(
+        client,
+        format!("/households/{}/shares", household_id),
+        json!({ "record_type": record_type, "record_id": record_id }),
+    )
+    .await
+}
+
+fn account_update(name: &str) -> String {
+    json!({ "name": name, "account_type": "Bank", "balance": 250.0 }).to_string()
+}
+
+#[rocket::async_test]
+async fn household_roles_control_shared_records_integration_test() {
+    let (owner, pool) = setup().await;
+    let owner_id = before_test(&pool, "hhowner", "hhowner@example.com")
+        .await
+        .expect("Failed to initialize test database");
+    common::login(&owner, &pool, owner_id).await;
+    let (editor, editor_id) = member_client(&pool, "hheditor").await;
+    let (viewer, viewer_id) = member_client(&pool, "hhviewer").await;
+    let (outsider, outsider_id) = member_client(&pool, "hhoutsider").await;
+
+    let household_id = create_household(&owner, "Home").await;
+    assert_eq!(
+        add_member(&owner, household_id, "hheditor@example.com", "editor").await,
+        Status::Ok
+    );
+    assert_eq!(
+        add_member(&owner, household_id, "hhviewer@example.com", "viewer").await,
+        Status::Ok
+    );
+    assert_eq!(
+        add_member(&owner, household_id, "hhviewer@example.com", "editor").await,
+        Status::Conflict
+    );
+    // Only owners manage membership.
+    assert_eq!(
+        add_member(&editor, household_id, "hhoutsider@example.com", "viewer").await,
+        Status::Forbidden
+    );
+
+    let details: HouseholdDetailOutDTO = parse(
+        viewer
+            .get(format!("/households/{}", household_id))
+            .dispatch()
+            .await,
+    )
+    .await;
+    assert_eq!(details.members.len(), 3);
+    assert_eq!(details.members[0].role, HouseholdRole::Owner);
+    assert_eq!(
+        outsider
+            .get(format!("/households/{}", household_id))
+            .dispatch()
+            .await
+            .status(),
+        Status::NotFound
+    );
+
+    let account = create_account(
+        &pool,
+        owner_id,
+        &AccountInDTO {
+            name: "Joint".to_string(),
+            account_type: AccountType::Bank,
+            balance: Money::from(100),
+            currency: None,
+        },
+    )
+    .await
+    .expect("Failed to create account");
+    let budget = create_budget(
+        &pool,
+        owner_id,
+        &BudgetInDTO {
+            name: "Groceries".to_string(),
+            amount: Money::from(400),
+            start_date: chrono::Utc::now(),
+            end_date: chrono::Utc::now(),
+            category_id: None,
+        },
+    )
+    .await
+    .expect("Failed to create budget");
+    assert_eq!(
+        share(&owner, household_id, "account", account.id).await,
+        Status::NoContent
+    );
+    assert_eq!(
+        share(&owner, household_id, "budget", budget.id).await,
+        Status::NoContent
+    );
+    // Members can't share records they don't own, and viewers can't share.
+    assert_eq!(
+        share(&editor, household_id, "budget", budget.id).await,
+        Status::NotFound
+    );
+    assert_eq!(
+        share(&viewer, household_id, "budget", budget.id).await,
+        Status::Forbidden
+    );
+
+    // Every member sees the shared records in their lists.
+    for client in [&editor, &viewer] {
+        let accounts: Vec<AccountOutDTO> = parse(client.get("/accounts").dispatch().await).await;
+        assert!(accounts
+            .iter()
+            .any(|a| a.id == account.id && a.household_id == Some(household_id)));
+        let budgets: Vec<BudgetOutDTO> = parse(client.get("/budgets").dispatch().await).await;
+        assert!(budgets.iter().any(|b| b.id == budget.id));
+    }
+    let outsider_accounts: Vec<AccountOutDTO> =
+        parse(outsider.get("/accounts").dispatch().await).await;
+    assert!(outsider_accounts.is_empty());
+
+    // Editors change shared records, viewers only read them.
+    let account_uri = format!("/accounts/{}", account.id);
+    let edited: AccountOutDTO = parse(
+        editor
+            .patch(account_uri.clone())
+            .header(ContentType::JSON)
+            .body(account_update("Joint checking"))
+            .dispatch()
+            .await,
+    )
+    .await;
+    assert_eq!(edited.name, "Joint checking");
+    assert_eq!(
+        viewer
+            .patch(account_uri.clone())
+            .header(ContentType::JSON)
+            .body(account_update("Mine now"))
+            .dispatch()
+            .await
+            .status(),
+        Status::Forbidden
+    );
+    assert_eq!(
+        outsider
+            .patch(account_uri.clone())
+            .header(ContentType::JSON)
+            .body(account_update("Mine now"))
+            .dispatch()
+            .await
+            .status(),
+        Status::NotFound
+    );
+    // Deleting stays with the record's owner.
+    assert_eq!(
+        editor.delete(account_uri.clone()).dispatch().await.status(),
+        Status::Forbidden
+    );
+
+    // An editor can book a transaction on the shared account, and the
+    // viewer sees it without being able to change it.
+    let category = create_category(
+        &pool,
+        editor_id,
+        &CategoryInDTO {
+            name: "Food".to_string(),
+        },
+    )
+    .await
+    .expect("Failed to create category");
+    let transaction: TransactionOutDTO = parse(
+        editor
+            .post("/transactions")
+            .header(ContentType::JSON)
+            .body(
+                json!({
+                    "title": "Weekly shop",
+                    "amount": 42.0,
+                    "date": "2026-10-19T10:00:00+02:00",
+                    "category_id": category.id,
+                    "transaction_type": "Expense",
+                    "account_id": account.id
+                })
+                .to_string(),
+            )
+            .dispatch()
+            .await,
+    )
+    .await;
+    let viewer_transactions: Vec<TransactionOutDTO> =
+        parse(viewer.get("/transactions").dispatch().await).await;
+    assert!(viewer_transactions.iter().any(|t| t.id == transaction.id));
+    assert_eq!(
+        viewer
+            .delete(format!("/transactions/{}", transaction.id))
+            .dispatch()
+            .await
+            .status(),
+        Status::Forbidden
+    );
+
+    cleanup(
+        &pool,
+        household_id,
+        &[editor_id, viewer_id, outsider_id, owner_id],
+    )
+    .await;
+}
+
+#[rocket::async_test]
+async fn removing_member_unshares_their_records_integration_test() {
+    let (owner, pool) = setup().await;
+    let owner_id = before_test(&pool, "hhleaveowner", "hhleaveowner@example.com")
+        .await
+        .expect("Failed to initialize test database");
+    common::login(&owner, &pool, owner_id).await;
+    let (member, member_id) = member_client(&pool, "hhleavemember").await;
+
+    let household_id = create_household(&owner, "Flat").await;
+    assert_eq!(
+        add_member(&owner, household_id, "hhleavemember@example.com", "editor").await,
+        Status::Ok
+    );
+    let account = create_account(
+        &pool,
+        member_id,
+        &AccountInDTO {
+            name: "Shared card".to_string(),
+            account_type: AccountType::Card,
+            balance: Money::ZERO,
+            currency: None,
+        },
+    )
+    .await
+    .expect("Failed to create account");
+    assert_eq!(
+        share(&member, household_id, "account", account.id).await,
+        Status::NoContent
+    );
+    let account_uri = format!("/accounts/{}", account.id);
+    assert_eq!(
+        owner.get(account_uri.clone()).dispatch().await.status(),
+        Status::Ok
+    );
+
+    // The last owner can't leave, and can't demote themselves either.
+    let owner_membership = format!("/households/{}/members/{}", household_id, owner_id);
+    assert_eq!(
+        owner
+            .delete(owner_membership.clone())
+            .dispatch()
+            .await
+            .status(),
+        Status::Conflict
+    );
+    assert_eq!(
+        owner
+            .patch(owner_membership)
+            .header(ContentType::JSON)
+            .body(json!({ "role": "viewer" }).to_string())
+            .dispatch()
+            .await
+            .status(),
+        Status::Conflict
+    );
+
+    assert_eq!(
+        owner
+            .delete(format!(
+                "/households/{}/members/{}",
+                household_id, member_id
+            ))
+            .dispatch()
+            .await
+            .status(),
+        Status::NoContent
+    );
+    assert_eq!(
+        owner.get(account_uri.clone()).dispatch().await.status(),
+        Status::NotFound
+    );
+    let kept: AccountOutDTO = parse(member.get(account_uri).dispatch().await).await;
+    assert_eq!(kept.household_id, None);
+
+    cleanup(&pool, household_id, &[member_id, owner_id]).await;
+}
+
+// An owner's household with one account shared into it, plus a transaction
+// and a budget on it.
+async fn shared_account(
+    pool: &PgPool,
+    owner: &Client,
+    owner_id: Uuid,
+    household_id: Uuid,
+) -> (Uuid, Uuid, Uuid) {
+    let account = create_account(
+        pool,
+        owner_id,
+        &AccountInDTO {
+            name: "Joint".to_string(),
+            account_type: AccountType::Bank,
+            balance: Money::from(100),
+            currency: None,
+        },
+    )
+    .await
+    .expect("Failed to create account");
+    let category = create_category(
+        pool,
+        owner_id,
+        &CategoryInDTO {
+            name: "Bills".to_string(),
+        },
+    )
+    .await
+    .expect("Failed to create category");
+    let budget = create_budget(
+        pool,
+        owner_id,
+        &BudgetInDTO {
+            name: "Bills".to_string(),
+            amount: Money::from(300),
+            start_date: chrono::Utc::now(),
+            end_date: chrono::Utc::now(),
+            category_id: Some(category.id),
+        },
+    )
+    .await
+    .expect("Failed to create budget");
+    for (record_type, record_id) in [
+        ("account", account.id),
+        ("category", category.id),
+        ("budget", budget.id),
+    ] {
+        assert_eq!(
+            share(owner, household_id, record_type, record_id).await,
+            Status::NoContent
+        );
+    }
+    let transaction: TransactionOutDTO = parse(
+        owner
+            .post("/transactions")
+            .header(ContentType::JSON)
+            .body(transaction_body(account.id, category.id))
+            .dispatch()
+            .await,
+    )
+    .await;
+
+    (account.id, budget.id, transaction.id)
+}
+
+fn transaction_body(account_id: Uuid, category_id: Uuid) -> String {
+    json!({
+        "title": "Electricity",
+        "amount": 60.0,
+        "date": "2026-10-19T10:00:00Z",
+        "category_id": category_id,
+        "transaction_type": "Expense",
+        "account_id": account_id
+    })
+    .to_string()
+}
+
+fn budget_update(name: &str) -> String {
+    json!({
+        "name": name,
+        "amount": 500.0,
+        "start_date": "2026-10-01T00:00:00Z",
+        "end_date": "2026-10-31T00:00:00Z",
+        "category_id": null
+    })
+    .to_string()
+}
+
+#[rocket::async_test]
+async fn non_member_cannot_reach_household_records_integration_test() {
+    let (owner, pool) = setup().await;
+    let owner_id = before_test(&pool, "hhprivateowner", "hhprivateowner@example.com")
+        .await
+        .expect("Failed to initialize test database");
+    common::login(&owner, &pool, owner_id).await;
+    let (outsider, outsider_id) = member_client(&pool, "hhprivateoutsider").await;
+
+    let household_id = create_household(&owner, "Private").await;
+    let (account_id, budget_id, transaction_id) =
+        shared_account(&pool, &owner, owner_id, household_id).await;
+
+    // Shared records look just like records that don't exist.
+    for uri in [
+        format!("/accounts/{}", account_id),
+        format!("/accounts/{}/transactions", account_id),
+        format!("/budgets/{}", budget_id),
+        format!("/transactions/{}", transaction_id),
+        format!("/households/{}", household_id),
+    ] {
+        assert_eq!(
+            outsider.get(uri.clone()).dispatch().await.status(),
+            Status::NotFound,
+            "GET {}",
+            uri
+        );
+    }
+    let transactions: Vec<TransactionOutDTO> =
+        parse(outsider.get("/transactions").dispatch().await).await;
+    assert!(transactions.is_empty());
+    assert_eq!(
+        outsider
+            .patch(format!("/budgets/{}", budget_id))
+            .header(ContentType::JSON)
+            .body(budget_update("Taken"))
+            .dispatch()
+            .await
+            .status(),
+        Status::NotFound
+    );
+    assert_eq!(
+        outsider
+            .delete(format!("/transactions/{}", transaction_id))
+            .dispatch()
+            .await
+            .status(),
+        Status::NotFound
+    );
+
+    // Nor can they join, share into or manage the household.
+    let own_account = create_account(
+        &pool,
+        outsider_id,
+        &AccountInDTO {
+            name: "Mine".to_string(),
+            account_type: AccountType::Cash,
+            balance: Money::ZERO,
+            currency: None,
+        },
+    )
+    .await
+    .expect("Failed to create account");
+    assert_eq!(
+        share(&outsider, household_id, "account", own_account.id).await,
+        Status::NotFound
+    );
+    assert_eq!(
+        add_member(
+            &outsider,
+            household_id,
+            "hhprivateoutsider@example.com",
+            "owner"
+        )
+        .await,
+        Status::NotFound
+    );
+    assert_eq!(
+        outsider
+            .delete(format!(
+                "/households/{}/shares/account/{}",
+                household_id, account_id
+            ))
+            .dispatch()
+            .await
+            .status(),
+        Status::NotFound
+    );
+    assert_eq!(
+        outsider
+            .delete(format!("/households/{}", household_id))
+            .dispatch()
+            .await
+            .status(),
+        Status::NotFound
+    );
+
+    cleanup(&pool, household_id, &[outsider_id, owner_id]).await;
+}
+
+#[rocket::async_test]
+async fn viewer_cannot_change_shared_records_integration_test() {
+    let (owner, pool) = setup().await;
+    let owner_id = before_test(&pool, "hhviewowner", "hhviewowner@example.com")
+        .await
+        .expect("Failed to initialize test database");
+    common::login(&owner, &pool, owner_id).await;
+    let (viewer, viewer_id) = member_client(&pool, "hhviewonly").await;
+
+    let household_id = create_household(&owner, "Read only").await;
+    assert_eq!(
+        add_member(&owner, household_id, "hhviewonly@example.com", "viewer").await,
+        Status::Ok
+    );
+    let (account_id, budget_id, transaction_id) =
+        shared_account(&pool, &owner, owner_id, household_id).await;
+
+    let budget_uri = format!("/budgets/{}", budget_id);
+    assert_eq!(
+        viewer.get(budget_uri.clone()).dispatch().await.status(),
+        Status::Ok
+    );
+    assert_eq!(
+        viewer
+            .patch(budget_uri.clone())
+            .header(ContentType::JSON)
+            .body(budget_update("Mine now"))
+            .dispatch()
+            .await
+            .status(),
+        Status::Forbidden
+    );
+    assert_eq!(
+        viewer.delete(budget_uri.clone()).dispatch().await.status(),
+        Status::Forbidden
+    );
+    let category = create_category(
+        &pool,
+        viewer_id,
+        &CategoryInDTO {
+            name: "Snacks".to_string(),
+        },
+    )
+    .await
+    .expect("Failed to create category");
+    // Viewers can't book on a shared account, so they can neither move a
+    // transaction there nor add one.
+    assert_eq!(
+        viewer
+            .patch(format!("/transactions/{}", transaction_id))
+            .header(ContentType::JSON)
+            .body(transaction_body(account_id, category.id))
+            .dispatch()
+            .await
+            .status(),
+        Status::BadRequest
+    );
+    assert_eq!(
+        viewer
+            .delete(format!("/transactions/{}", transaction_id))
+            .dispatch()
+            .await
+            .status(),
+        Status::Forbidden
+    );
+    assert_eq!(
+        viewer
+            .post("/transactions")
+            .header(ContentType::JSON)
+            .body(transaction_body(account_id, category.id))
+            .dispatch()
+            .await
+            .status(),
+        Status::BadRequest
+    );
+    // Only the record's owner or a household owner can unshare it.
+    assert_eq!(
+        viewer
+            .delete(format!(
+                "/households/{}/shares/budget/{}",
+                household_id, budget_id
+            ))
+            .dispatch()
+            .await
+            .status(),
+        Status::NotFound
+    );
+    let unchanged: BudgetOutDTO = parse(owner.get(budget_uri.clone()).dispatch().await).await;
+    assert_eq!(unchanged.name, "Bills");
+    assert_eq!(unchanged.household_id, Some(household_id));
+
+    // A viewer who leaves loses sight of the household's records.
+    assert_eq!(
+        viewer
+            .delete(format!(
+                "/households/{}/members/{}",
+                household_id, viewer_id
+            ))
+            .dispatch()
+            .await
+            .status(),
+        Status::NoContent
+    );
+    assert_eq!(
+        viewer.get(budget_uri).dispatch().await.status(),
+        Status::NotFound
+    );
+
+    cleanup(&pool, household_id, &[viewer_id, owner_id]).await;
+}
+
+#[rocket::async_test]
+async fn household_membership_edge_cases_integration_test() {
+    let (owner, pool) = setup().await;
+    let owner_id = before_test(&pool, "hhedgeowner", "hhedgeowner@example.com")
+        .await
+        .expect("Failed to initialize test database");
+    common::login(&owner, &pool, owner_id).await;
+    let (member, member_id) = member_client(&pool, "hhedgemember").await;
+
+    assert_eq!(
+        post_json(&owner, "/households".to_string(), json!({ "name": "  " })).await,
+        Status::BadRequest
+    );
+    let household_id = create_household(&owner, "Edges").await;
+    assert_eq!(
+        add_member(&owner, household_id, "nobody@example.com", "viewer").await,
+        Status::NotFound
+    );
+    assert_eq!(
+        add_member(&owner, household_id, "hhedgemember@example.com", "admin").await,
+        Status::UnprocessableEntity
+    );
+    assert_eq!(
+        add_member(&owner, household_id, "hhedgemember@example.com", "viewer").await,
+        Status::Ok
+    );
+
+    let stranger = Uuid::new_v4();
+    assert_eq!(
+        owner
+            .patch(format!("/households/{}/members/{}", household_id, stranger))
+            .header(ContentType::JSON)
+            .body(json!({ "role": "editor" }).to_string())
+            .dispatch()
+            .await
+            .status(),
+        Status::NotFound
+    );
+    assert_eq!(
+        owner
+            .delete(format!("/households/{}/members/{}", household_id, stranger))
+            .dispatch()
+            .await
+            .status(),
+        Status::NotFound
+    );
+    // Members can leave but can't remove anyone else.
+    assert_eq!(
+        member
+            .delete(format!("/households/{}/members/{}", household_id, owner_id))
+            .dispatch()
+            .await
+            .status(),
+        Status::Forbidden
+    );
+
+    // Promoting a viewer lets them edit shared records.
+    let (account_id, _, _) = shared_account(&pool, &owner, owner_id, household_id).await;
+    let account_uri = format!("/accounts/{}", account_id);
+    assert_eq!(
+        member
+            .patch(account_uri.clone())
+            .header(ContentType::JSON)
+            .body(account_update("Renamed"))
+            .dispatch()
+            .await
+            .status(),
+        Status::Forbidden
+    );
+    assert_eq!(
+        owner
+            .patch(format!(
+                "/households/{}/members/{}",
+                household_id, member_id
+            ))
+            .header(ContentType::JSON)
+            .body(json!({ "role": "editor" }).to_string())
+            .dispatch()
+            .await
+            .status(),
+        Status::NoContent
+    );
+    let renamed: AccountOutDTO = parse(
+        member
+            .patch(account_uri)
+            .header(ContentType::JSON)
+            .body(account_update("Renamed"))
+            .dispatch()
+            .await,
+    )
+    .await;
+    assert_eq!(renamed.name, "Renamed");
+
+    cleanup(&pool, household_id, &[member_id, owner_id]).await;
+}
+
+#[rocket::async_test]
+async fn deleting_household_unshares_records_integration_test() {
+    let (owner, pool) = setup().await;
+    let owner_id = before_test(&pool, "hhdeleteowner", "hhdeleteowner@example.com")
+        .await
+        .expect("Failed to initialize test database");
+    common::login(&owner, &pool, owner_id).await;
+    let (editor, editor_id) = member_client(&pool, "hhdeleteeditor").await;
+
+    let household_id = create_household(&owner, "Short lived").await;
+    assert_eq!(
+        add_member(&owner, household_id, "hhdeleteeditor@example.com", "editor").await,
+        Status::Ok
+    );
+    let (account_id, _, _) = shared_account(&pool, &owner, owner_id, household_id).await;
+    let account_uri = format!("/accounts/{}", account_id);
+    assert_eq!(
+        editor.get(account_uri.clone()).dispatch().await.status(),
+        Status::Ok
+    );
+
+    let household_uri = format!("/households/{}", household_id);
+    assert_eq!(
+        editor
+            .delete(household_uri.clone())
+            .dispatch()
+            .await
+            .status(),
+        Status::Forbidden
+    );
+    assert_eq!(
+        owner
+            .delete(household_uri.clone())
+            .dispatch()
+            .await
+            .status(),
+        Status::NoContent
+    );
+    assert_eq!(
+        owner.get(household_uri).dispatch().await.status(),
+        Status::NotFound
+    );
+
+    // The records go back to being their owner's alone.
+    assert_eq!(
+        editor.get(account_uri.clone()).dispatch().await.status(),
+        Status::NotFound
+    );
+    let kept: AccountOutDTO = parse(owner.get(account_uri).dispatch().await).await;
+    assert_eq!(kept.household_id, None);
+
+    cleanup(&pool, household_id, &[editor_id, owner_id]).await;
+}
@@ -0,0 +1,399 @@
+use chrono::{Duration, NaiveDate, Utc};
+use personal_finance_tracker::dtos::account_dtos::AccountOutDTO;
+use personal_finance_tracker::dtos::category_dtos::CategoryInDTO;
+use personal_finance_tracker::dtos::insight_dtos::AnomalyReportOutDTO;
+use personal_finance_tracker::dtos::user_dtos::UserInDTO;
+use personal_finance_tracker::enums::custom_enums::AnomalyKind;
+use personal_finance_tracker::insights::detect_anomalies;
+use personal_finance_tracker::models::categories::Category;
+use personal_finance_tracker::models::subscription::Charge;
+use personal_finance_tracker::operations::category_ops::create_category;
+use personal_finance_tracker::operations::user_ops::create_user;
+use rocket::http::{ContentType, Status};
+use rocket::serde::json::serde_json;
+use serde_json::json;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::common::{money, setup};
+
+mod common;
+
+pub async fn before_test(pool: &PgPool, username: &str) -> Result<(Uuid, Uuid), sqlx::Error> {
+    let user_dto = UserInDTO {
+        username: username.to_string(),
+        email: format!("{}@example.com", username),
+    };
+    let user = create_user(pool, &user_dto).await?;
+    let category = create_category(
+        pool,
+        user.id,
+        &CategoryInDTO {
+            name: "Groceries".to_string(),
+        },
+    )
+    .await?;
+
+    Ok((user.id, category.id))
+}
+
+pub async fn cleanup(pool: &PgPool, user_id: Uuid) {
+    sqlx::query("DELETE FROM users WHERE id = $1")
+        .bind(user_id)
+        .execute(pool)
+        .await
+        .unwrap_or_else(|e| panic!("Failed to cleanup test users: {}", e));
+}
+
+fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).expect("Valid date")
+}
+
+fn category(name: &str) -> Category {
+    Category {
+        id: Uuid::new_v4(),
+        name: name.to_string(),
+        user_id: Uuid::nil(),
+        household_id: None,
+    }
+}
+
+fn charge(category: &Category, title: &str, day: NaiveDate, amount: &str) -> Charge {
+    Charge {
+        transaction_id: Uuid::new_v4(),
+        account_id: Uuid::nil(),
+        category_id: category.id,
+        title: title.to_string(),
+        day,
+        amount: money(amount),
+        currency: "USD".to_string(),
+    }
+}
+
+#[test]
+fn detect_anomalies_test() {
+    let groceries = category("Groceries");
+    let dining = category("Dining");
+    let gifts = category("Gifts");
+    let mut charges = vec![
+        charge(&dining, "Cafe", date(2026, 1, 5), "5"),
+        charge(&dining, "Cafe", date(2026, 2, 5), "5"),
+        charge(&dining, "Cafe", date(2026, 3, 5), "5"),
+        charge(&dining, "Cafe", date(2026, 4, 5), "5"),
+        charge(&groceries, "Supermarket", date(2026, 1, 10), "40"),
+        charge(&groceries, "Supermarket", date(2026, 2, 10), "50"),
+        charge(&groceries, "Supermarket", date(2026, 3, 10), "45"),
+        charge(&groceries, "Supermarket", date(2026, 4, 10), "55"),
+        charge(&groceries, "Supermarket", date(2026, 5, 10), "60"),
+        charge(&groceries, "Supermarket", date(2026, 5, 20), "50"),
+        // A new payee, but a small amount.
+        charge(&dining, "Bakery", date(2026, 6, 3), "10"),
+        charge(&groceries, "Supermarket", date(2026, 6, 5), "200"),
+        charge(&gifts, "Jeweller 0612", date(2026, 6, 12), "300"),
+        // After the period.
+        charge(&gifts, "Jeweller", date(2026, 7, 2), "900"),
+    ];
+    charges.sort_by_key(|charge| charge.day);
+    let groceries_id = groceries.id;
+
+    let anomalies = detect_anomalies(
+        &charges,
+        &[groceries, dining, gifts],
+        date(2026, 6, 1),
+        date(2026, 6, 30),
+    );
+
+    let kinds: Vec<_> = anomalies
+        .iter()
+        .map(|a| (a.kind, a.category_name.as_str(), a.amount))
+        .collect();
+    assert_eq!(
+        kinds,
+        vec![
+            (AnomalyKind::CategoryOutlier, "Groceries", money("200")),
+            (AnomalyKind::PayeeOutlier, "Groceries", money("200")),
+            (AnomalyKind::NewPayee, "Gifts", money("300")),
+            (AnomalyKind::CategorySpike, "Dining", money("10")),
+            (AnomalyKind::CategorySpike, "Groceries", money("200")),
+        ]
+    );
+
+    let outlier = &anomalies[0];
+    assert_eq!(outlier.date, Some(date(2026, 6, 5)));
+    assert_eq!(outlier.statistics.sample_size, 6);
+    assert_eq!(outlier.statistics.median, Some(money("50")));
+    assert_eq!(outlier.statistics.threshold, money("150"));
+    assert_eq!(
+        outlier.explanation,
+        "200.00 USD is 4 times the usual 50.00 USD spent on Groceries, the median of 6 earlier expenses."
+    );
+    assert_eq!(anomalies[1].payee.as_deref(), Some("supermarket"));
+
+    let new_payee = &anomalies[2];
+    assert_eq!(new_payee.payee.as_deref(), Some("jeweller"));
+    assert_eq!(new_payee.statistics.percentile_90, Some(money("55")));
+
+    let spike = &anomalies[4];
+    assert_eq!(spike.transaction_id, None);
+    assert_eq!(spike.statistics.expected, Some(money("61.22")));
+    assert_eq!(spike.statistics.threshold, money("91.83"));
+    assert_eq!(spike.category_id, groceries_id);
+}
+
+#[test]
+fn too_little_history_flags_nothing_test() {
+    let groceries = category("Groceries");
+    let charges = vec![
+        charge(&groceries, "Supermarket", date(2026, 5, 20), "50"),
+        charge(&groceries, "Supermarket", date(2026, 5, 25), "50"),
+        charge(&groceries, "Supermarket", date(2026, 6, 10), "900"),
+        charge(&groceries, "Caviar bar", date(2026, 6, 11), "900"),
+    ];
+
+    // Too few earlier expenses for a median, a payee history, a large first
+    // payment or a pace; and less history than the period is long.
+    let anomalies = detect_anomalies(&charges, &[groceries], date(2026, 6, 1), date(2026, 6, 30));
+    assert!(anomalies.is_empty());
+}
+
+#[test]
+fn outlier_has_to_beat_the_largest_earlier_expense_test() {
+    let fuel = category("Fuel");
+    let mut charges: Vec<Charge> = [10, 12, 14, 16, 18]
+        .into_iter()
+        .map(|day| charge(&fuel, "Station", date(2026, 1, day), "10"))
+        .collect();
+    charges.push(charge(&fuel, "Station", date(2026, 1, 20), "100"));
+    charges.push(charge(&fuel, "Station", date(2026, 6, 2), "90"));
+    let categories = std::slice::from_ref(&fuel);
+
+    // Nine times the median, but no more than was spent once before.
+    let anomalies = detect_anomalies(&charges, categories, date(2026, 6, 1), date(2026, 6, 5));
+    assert!(anomalies
+        .iter()
+        .all(|anomaly| anomaly.kind == AnomalyKind::CategorySpike));
+
+    charges.push(charge(&fuel, "Station", date(2026, 6, 3), "101"));
+    let anomalies = detect_anomalies(&charges, categories, date(2026, 6, 1), date(2026, 6, 5));
+    let outlier = anomalies
+        .iter()
+        .find(|anomaly| anomaly.kind == AnomalyKind::CategoryOutlier)
+        .expect("The larger expense stands out");
+    assert_eq!(outlier.amount, money("101"));
+    assert_eq!(outlier.statistics.median, Some(money("10")));
+    assert_eq!(outlier.statistics.maximum, Some(money("100")));
+    assert_eq!(outlier.statistics.threshold, money("100"));
+}
+
+#[test]
+fn currencies_are_compared_separately_test() {
+    let groceries = category("Groceries");
+    let in_currency = |day: NaiveDate, amount: &str, currency: &str| Charge {
+        currency: currency.to_string(),
+        ..charge(&groceries, "Supermarket", day, amount)
+    };
+    let mut charges: Vec<Charge> = (1..=6)
+        .map(|month| in_currency(date(2026, month, 10), "50", "EUR"))
+        .collect();
+    charges.push(in_currency(date(2026, 7, 3), "6000", "JPY"));
+    charges.push(in_currency(date(2026, 7, 4), "400", "EUR"));
+
+    // Yen are never measured against the euro history.
+    let anomalies = detect_anomalies(&charges, &[groceries], date(2026, 7, 1), date(2026, 7, 7));
+    assert!(!anomalies.is_empty());
+    assert!(anomalies.iter().all(|anomaly| anomaly.currency == "EUR"));
+    assert_eq!(anomalies[0].kind, AnomalyKind::CategoryOutlier);
+    assert_eq!(anomalies[0].amount, money("400"));
+    assert_eq!(
+        anomalies[0].explanation,
+        "400.00 EUR is 8 times the usual 50.00 EUR spent on Groceries, the median of 6 earlier expenses."
+    );
+}
+
+#[rocket::async_test]
+async fn anomalies_integration_test() {
+    let (client, pool) = setup().await;
+
+    let (user_id, category_id) = before_test(&pool, "anomalyuser")
+        .await
+        .expect("Failed to initialize test database");
+    common::login(&client, &pool, user_id).await;
+
+    let response = client
+        .post("/accounts")
+        .header(ContentType::JSON)
+        .body(json!({ "name": "Card", "balance": "500", "account_type": "Card" }).to_string())
+        .dispatch()
+        .await;
+    let card: AccountOutDTO =
+        serde_json::from_str(&response.into_string().await.expect("Response has a body"))
+            .expect("Valid AccountOutDTO");
+
+    let now = Utc::now();
+    let mut spike_id = None;
+    for (days_ago, amount) in [
+        (170, "50"),
+        (140, "50"),
+        (110, "50"),
+        (80, "50"),
+        (60, "50"),
+        (40, "50"),
+        (2, "400"),
+    ] {
+        let response = client
+            .post("/transactions")
+            .header(ContentType::JSON)
+            .body(
+                json!({
+                    "title": "Supermarket",
+                    "amount": amount,
+                    "date": now - Duration::days(days_ago),
+                    "category_id": category_id,
+                    "transaction_type": "Expense",
+                    "account_id": card.id,
+                })
+                .to_string(),
+            )
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::Ok);
+        let created: serde_json::Value =
+            serde_json::from_str(&response.into_string().await.expect("Response has a body"))
+                .expect("Valid JSON");
+        spike_id = created["id"].as_str().map(str::to_string);
+    }
+
+    let response = client.get("/insights/anomalies").dispatch().await;
+    assert_eq!(response.status(), Status::Ok);
+    let report: AnomalyReportOutDTO =
+        serde_json::from_str(&response.into_string().await.expect("Response has a body"))
+            .expect("Valid AnomalyReportOutDTO");
+    let today = now.date_naive();
+    assert_eq!(report.from, today - Duration::days(29));
+    assert_eq!(report.baseline_from, today - Duration::days(209));
+    let kinds: Vec<_> = report.anomalies.iter().map(|a| a.kind).collect();
+    assert_eq!(
+        kinds,
+        vec![
+            AnomalyKind::CategoryOutlier,
+            AnomalyKind::PayeeOutlier,
+            AnomalyKind::CategorySpike
+        ]
+    );
+    assert_eq!(
+        report.anomalies[0].transaction_id.map(|id| id.to_string()),
+        spike_id
+    );
+    assert_eq!(report.anomalies[0].statistics.ratio, Some(8.into()));
+
+    let response = client
+        .get(format!(
+            "/insights/anomalies?to={}",
+            today - Duration::days(3)
+        ))
+        .dispatch()
+        .await;
+    let report: AnomalyReportOutDTO =
+        serde_json::from_str(&response.into_string().await.expect("Response has a body"))
+            .expect("Valid AnomalyReportOutDTO");
+    assert!(report.anomalies.is_empty());
+
+    for query in [
+        "from=2026-02-01&to=2026-01-01",
+        "from=2024-01-01&to=2026-01-01",
+    ] {
+        let response = client
+            .get(format!("/insights/anomalies?{}", query))
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::BadRequest);
+    }
+
+    cleanup(&pool, user_id).await;
+}
+
+#[rocket::async_test]
+async fn anomalies_only_measure_own_expenses_integration_test() {
+    let (client, pool) = setup().await;
+
+    let (user_id, category_id) = before_test(&pool, "anomalyowner")
+        .await
+        .expect("Failed to initialize test database");
+    let (other_id, other_category_id) = before_test(&pool, "anomalyother")
+        .await
+        .expect("Failed to initialize test database");
+
+    let response = client.get("/insights/anomalies").dispatch().await;
+    assert_eq!(response.status(), Status::Unauthorized);
+
+    let now = Utc::now();
+    let book = |days_ago: i64,
+                amount: &str,
+                transaction_type: &str,
+                category_id: Uuid,
+                account_id: Uuid| {
+        json!({
+            "title": "Supermarket",
+            "amount": amount,
+            "date": now - Duration::days(days_ago),
+            "category_id": category_id,
+            "transaction_type": transaction_type,
+            "account_id": account_id,
+        })
+        .to_string()
+    };
+    let mut accounts = Vec::new();
+    for user in [other_id, user_id] {
+        common::login(&client, &pool, user).await;
+        let response = client
+            .post("/accounts")
+            .header(ContentType::JSON)
+            .body(json!({ "name": "Card", "balance": "0", "account_type": "Card" }).to_string())
+            .dispatch()
+            .await;
+        let card: AccountOutDTO =
+            serde_json::from_str(&response.into_string().await.expect("Response has a body"))
+                .expect("Valid AccountOutDTO");
+        accounts.push(card.id);
+    }
+    let (other_card, card) = (accounts[0], accounts[1]);
+
+    // Someone else's steady history doesn't make this user's first big
+    // expense an outlier, and neither does a large income.
+    common::login(&client, &pool, other_id).await;
+    for days_ago in [170, 140, 110, 80, 60, 40] {
+        let response = client
+            .post("/transactions")
+            .header(ContentType::JSON)
+            .body(book(
+                days_ago,
+                "50",
+                "Expense",
+                other_category_id,
+                other_card,
+            ))
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::Ok);
+    }
+    common::login(&client, &pool, user_id).await;
+    for (amount, transaction_type) in [("400", "Expense"), ("5000", "Income")] {
+        let response = client
+            .post("/transactions")
+            .header(ContentType::JSON)
+            .body(book(2, amount, transaction_type, category_id, card))
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::Ok);
+    }
+
+    let response = client.get("/insights/anomalies").dispatch().await;
+    assert_eq!(response.status(), Status::Ok);
+    let report: AnomalyReportOutDTO =
+        serde_json::from_str(&response.into_string().await.expect("Response has a body"))
+            .expect("Valid AnomalyReportOutDTO");
+    assert!(report.anomalies.is_empty());
+
+    cleanup(&pool, user_id).await;
+    cleanup(&pool, other_id).await;
+}
@@ -0,0 +1,213 @@
+use personal_finance_tracker::dtos::account_dtos::AccountOutDTO;
+use personal_finance_tracker::dtos::user_dtos::UserInDTO;
+use personal_finance_tracker::money::{minor_units, Money};
+use personal_finance_tracker::operations::user_ops::create_user;
+use rocket::http::{ContentType, Status};
+use rocket::serde::json::serde_json;
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::common::{money, setup};
+
+mod common;
+
+pub async fn before_test(pool: &PgPool, username: &str) -> Result<Uuid, sqlx::Error> {
+    let user_dto = UserInDTO {
+        username: username.to_string(),
+        email: format!("{}@example.com", username),
+    };
+    let user = create_user(pool, &user_dto).await?;
+
+    Ok(user.id)
+}
+
+pub async fn cleanup(pool: &PgPool, user_id: Uuid) {
+    sqlx::query("DELETE FROM users WHERE id = $1")
+        .bind(user_id)
+        .execute(pool)
+        .await
+        .unwrap_or_else(|e| panic!("Failed to cleanup test users: {}", e));
+}
+
+#[test]
+fn money_sums_exactly_test() {
+    let total: Money = (0..10_000).map(|_| money("0.01")).sum();
+    assert_eq!(total, Money::from(100));
+
+    assert_eq!(money("0.10") + money("0.20"), money("0.30"));
+}
+
+#[test]
+fn money_rounds_to_currency_test() {
+    assert_eq!(minor_units("USD"), 2);
+    assert_eq!(minor_units("JPY"), 0);
+    assert_eq!(minor_units("KWD"), 3);
+
+    assert_eq!(money("2.345").round_to("USD"), money("2.35"));
+    assert_eq!(money("-2.345").round_to("USD"), money("-2.35"));
+    assert_eq!(money("1234.5").round_to("JPY"), Money::from(1235));
+    assert_eq!(money("1.2345").round_to("KWD"), money("1.235"));
+}
+
+#[test]
+fn money_json_test() {
+    assert_eq!(
+        serde_json::to_string(&money("12.30")).expect("Serializable"),
+        "\"12.30\""
+    );
+    assert_eq!(
+        serde_json::from_str::<Money>("12.3").expect("Valid amount"),
+        money("12.30")
+    );
+    assert_eq!(
+        serde_json::from_str::<Money>("\"0.1\"").expect("Valid amount"),
+        money("0.10")
+    );
+    assert!(serde_json::from_str::<Money>("\"0.00001\"").is_err());
+    assert!(serde_json::from_str::<Money>("\"1000000000000000\"").is_err());
+    assert!(serde_json::from_str::<Money>("\"999999999999999.9999\"").is_ok());
+}
+
+#[rocket::async_test]
+async fn account_balance_round_trips_integration_test() {
+    let (client, pool) = setup().await;
+
+    let user_id = before_test(&pool, "moneyuser")
+        .await
+        .expect("Failed to initialize test database");
+    common::login(&client, &pool, user_id).await;
+
+    let response = client
+        .post("/accounts")
+        .header(ContentType::JSON)
+        .body(
+            json!({
+                "name": "Savings",
+                "balance": "1234567890.1234",
+                "account_type": "Bank",
+                "currency": "CLF",
+            })
+            .to_string(),
+        )
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let body: Value =
+        serde_json::from_str(&response.into_string().await.expect("Response has a body"))
+            .expect("Valid JSON");
+    assert_eq!(body["balance"], json!("1234567890.1234"));
+    let account: AccountOutDTO = serde_json::from_value(body).expect("Valid AccountOutDTO");
+    assert_eq!(account.balance, money("1234567890.1234"));
+
+    let response = client
+        .post("/accounts")
+        .header(ContentType::JSON)
+        .body(
+            json!({
+                "name": "Too precise",
+                "balance": "0.00001",
+                "account_type": "Bank",
+            })
+            .to_string(),
+        )
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::UnprocessableEntity);
+
+    cleanup(&pool, user_id).await;
+}
+
+#[rocket::async_test]
+async fn amounts_follow_currency_minor_units_integration_test() {
+    let (client, pool) = setup().await;
+
+    let user_id = before_test(&pool, "minorunituser")
+        .await
+        .expect("Failed to initialize test database");
+    common::login(&client, &pool, user_id).await;
+
+    let post = |path: &'static str, body: Value| {
+        let client = &client;
+        async move {
+            client
+                .post(path)
+                .header(ContentType::JSON)
+                .body(body.to_string())
+                .dispatch()
+                .await
+        }
+    };
+    let account = |balance: &str, currency: Option<&str>| {
+        json!({
+            "name": "Wallet",
+            "balance": balance,
+            "account_type": "Cash",
+            "currency": currency,
+        })
+    };
+
+    // Without a currency the account is kept in the base currency, USD.
+    let response = post("/accounts", account("10.001", None)).await;
+    assert_eq!(response.status(), Status::BadRequest);
+    let response = post("/accounts", account("10.5", Some("JPY"))).await;
+    assert_eq!(response.status(), Status::BadRequest);
+    let response = post("/accounts", account("10.001", Some("KWD"))).await;
+    assert_eq!(response.status(), Status::Ok);
+    let wallet: AccountOutDTO =
+        serde_json::from_str(&response.into_string().await.expect("Response has a body"))
+            .expect("Valid AccountOutDTO");
+
+    // An update is checked against the account's currency unless it changes it.
+    let response = client
+        .patch(format!("/accounts/{}", wallet.id))
+        .header(ContentType::JSON)
+        .body(account("10.0001", None).to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::BadRequest);
+    let response = client
+        .patch(format!("/accounts/{}", wallet.id))
+        .header(ContentType::JSON)
+        .body(account("10.001", None).to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let response = client
+        .patch(format!("/accounts/{}", wallet.id))
+        .header(ContentType::JSON)
+        .body(account("10.001", Some("USD")).to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::BadRequest);
+
+    let budget = |amount: &str| {
+        json!({
+            "name": "Food",
+            "amount": amount,
+            "start_date": "2026-01-01T00:00:00Z",
+            "end_date": "2026-01-31T00:00:00Z",
+        })
+    };
+    let response = post("/budgets", budget("100.005")).await;
+    assert_eq!(response.status(), Status::BadRequest);
+    let response = post("/budgets", budget("100.05")).await;
+    assert_eq!(response.status(), Status::Ok);
+
+    let goal = |target: &str, current: &str| {
+        json!({
+            "title": "Bike",
+            "target_amount": target,
+            "current_amount": current,
+            "deadline": "2027-01-01",
+        })
+    };
+    let response = post("/saving_goals", goal("300.001", "0")).await;
+    assert_eq!(response.status(), Status::BadRequest);
+    let response = post("/saving_goals", goal("300", "0.001")).await;
+    assert_eq!(response.status(), Status::BadRequest);
+    let response = post("/saving_goals", goal("300", "0.01")).await;
+    assert_eq!(response.status(), Status::Ok);
+
+    cleanup(&pool, user_id).await;
+}
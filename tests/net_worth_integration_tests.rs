@@ -0,0 +1,448 @@
+use chrono::{Duration, Local, NaiveDate, Utc};
+use personal_finance_tracker::dtos::account_dtos::{AccountOutDTO, NetWorthOutDTO};
+use personal_finance_tracker::dtos::category_dtos::CategoryInDTO;
+use personal_finance_tracker::dtos::report_dtos::NetWorthHistoryOutDTO;
+use personal_finance_tracker::dtos::user_dtos::UserInDTO;
+use personal_finance_tracker::enums::custom_enums::AccountType;
+use personal_finance_tracker::exchange_rates::RateTable;
+use personal_finance_tracker::models::account::Account;
+use personal_finance_tracker::models::balance_snapshot::BalanceSnapshot;
+use personal_finance_tracker::models::exchange_rate::ExchangeRate;
+use personal_finance_tracker::models::report::DailyAccountFlow;
+use personal_finance_tracker::money::Money;
+use personal_finance_tracker::operations::balance_snapshot_ops::take_balance_snapshots;
+use personal_finance_tracker::operations::category_ops::create_category;
+use personal_finance_tracker::operations::user_ops::create_user;
+use personal_finance_tracker::reports::net_worth::net_worth_history;
+use rocket::http::{ContentType, Status};
+use rocket::local::asynchronous::Client;
+use rocket::serde::json::serde_json;
+use rust_decimal::Decimal;
+use serde_json::json;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::common::{money, setup};
+
+mod common;
+
+pub async fn before_test(pool: &PgPool, username: &str) -> Result<(Uuid, Uuid), sqlx::Error> {
+    let user_dto = UserInDTO {
+        username: username.to_string(),
+        email: format!("{}@example.com", username),
+    };
+    let user = create_user(pool, &user_dto).await?;
+    let category = create_category(
+        pool,
+        user.id,
+        &CategoryInDTO {
+            name: "General".to_string(),
+        },
+    )
+    .await?;
+
+    Ok((user.id, category.id))
+}
+
+pub async fn cleanup(pool: &PgPool, user_id: Uuid) {
+    sqlx::query("DELETE FROM users WHERE id = $1")
+        .bind(user_id)
+        .execute(pool)
+        .await
+        .unwrap_or_else(|e| panic!("Failed to cleanup test users: {}", e));
+}
+
+fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).expect("Valid date")
+}
+
+fn account(account_type: AccountType, balance: i64) -> Account {
+    Account {
+        id: Uuid::new_v4(),
+        name: "Account".to_string(),
+        account_type,
+        balance: Money::from(balance),
+        currency: "USD".to_string(),
+        user_id: Uuid::nil(),
+        household_id: None,
+    }
+}
+
+fn rate(base: &str, quote: &str, rate_date: NaiveDate, rate: &str) -> ExchangeRate {
+    ExchangeRate {
+        base_currency: base.to_string(),
+        quote_currency: quote.to_string(),
+        rate_date,
+        rate: rate.parse::<Decimal>().expect("Valid rate"),
+        imported_at: Local::now().naive_local(),
+    }
+}
+
+async fn open_account(client: &Client, name: &str, balance: &str, currency: &str) -> AccountOutDTO {
+    let response = client
+        .post("/accounts")
+        .header(ContentType::JSON)
+        .body(
+            json!({ "name": name, "balance": balance, "account_type": "Bank", "currency": currency })
+                .to_string(),
+        )
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    serde_json::from_str(&response.into_string().await.expect("Response has a body"))
+        .expect("Valid AccountOutDTO")
+}
+
+fn flow(account_id: Uuid, day: NaiveDate, net: i64) -> DailyAccountFlow {
+    DailyAccountFlow {
+        account_id,
+        day,
+        net: Money::from(net),
+    }
+}
+
+#[test]
+fn net_worth_history_test() {
+    let bank = account(AccountType::Bank, 1000);
+    let cash = account(AccountType::Cash, 50);
+    let snapshots = vec![BalanceSnapshot {
+        account_id: bank.id,
+        snapshot_date: date(2026, 3, 10),
+        account_type: AccountType::Bank,
+        balance: Money::from(800),
+        currency: "USD".to_string(),
+        taken_at: Local::now().naive_local(),
+    }];
+    let flows = vec![
+        flow(bank.id, date(2026, 3, 5), 100),
+        flow(cash.id, date(2026, 3, 8), -20),
+        flow(bank.id, date(2026, 3, 12), 150),
+    ];
+    let dates = [
+        date(2026, 3, 4),
+        date(2026, 3, 5),
+        date(2026, 3, 10),
+        date(2026, 3, 11),
+        date(2026, 3, 12),
+    ];
+
+    let points = net_worth_history(
+        &[bank, cash],
+        &snapshots,
+        &flows,
+        &RateTable::default(),
+        "USD",
+        &dates,
+    )
+    .expect("Everything is in USD");
+
+    let series: Vec<(NaiveDate, Money)> = points.iter().map(|p| (p.date, p.net_worth)).collect();
+    assert_eq!(
+        series,
+        vec![
+            (date(2026, 3, 4), Money::from(770)),
+            (date(2026, 3, 5), Money::from(870)),
+            (date(2026, 3, 10), Money::from(850)),
+            (date(2026, 3, 11), Money::from(900)),
+            (date(2026, 3, 12), Money::from(1050)),
+        ]
+    );
+    let split: Vec<(AccountType, Money)> = points[0]
+        .account_types
+        .iter()
+        .map(|t| (t.account_type, t.balance))
+        .collect();
+    assert_eq!(
+        split,
+        vec![
+            (AccountType::Bank, Money::from(700)),
+            (AccountType::Cash, Money::from(70))
+        ]
+    );
+    assert!(points.iter().all(|p| p.reconstructed));
+
+    let missing = net_worth_history(
+        &[account(AccountType::Card, 10)],
+        &[],
+        &[],
+        &RateTable::default(),
+        "EUR",
+        &dates,
+    );
+    assert!(missing.is_err());
+}
+
+#[rocket::async_test]
+async fn net_worth_history_integration_test() {
+    let (client, pool) = setup().await;
+
+    let (user_id, category_id) = before_test(&pool, "networthuser")
+        .await
+        .expect("Failed to initialize test database");
+    common::login(&client, &pool, user_id).await;
+
+    let response = client
+        .post("/accounts")
+        .header(ContentType::JSON)
+        .body(json!({ "name": "Checking", "balance": "500", "account_type": "Bank" }).to_string())
+        .dispatch()
+        .await;
+    let checking: AccountOutDTO =
+        serde_json::from_str(&response.into_string().await.expect("Response has a body"))
+            .expect("Valid AccountOutDTO");
+
+    let now = Utc::now();
+    for (days_ago, amount, transaction_type) in [(2, "100", "Income"), (1, "30", "Expense")] {
+        let response = client
+            .post("/transactions")
+            .header(ContentType::JSON)
+            .body(
+                json!({
+                    "title": "Entry",
+                    "amount": amount,
+                    "date": now - Duration::days(days_ago),
+                    "category_id": category_id,
+                    "transaction_type": transaction_type,
+                    "account_id": checking.id,
+                })
+                .to_string(),
+            )
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::Ok);
+    }
+
+    assert!(
+        take_balance_snapshots(&pool)
+            .await
+            .expect("Snapshots taken")
+            >= 1
+    );
+    // Taking them again the same day replaces today's snapshot.
+    take_balance_snapshots(&pool)
+        .await
+        .expect("Snapshots taken");
+
+    let today = now.date_naive();
+    let response = client
+        .get(format!(
+            "/reports/net_worth?from={}&to={}",
+            today - Duration::days(3),
+            today + Duration::days(5)
+        ))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let history: NetWorthHistoryOutDTO =
+        serde_json::from_str(&response.into_string().await.expect("Response has a body"))
+            .expect("Valid NetWorthHistoryOutDTO");
+    let series: Vec<(NaiveDate, Money, bool)> = history
+        .points
+        .iter()
+        .map(|p| (p.date, p.net_worth, p.reconstructed))
+        .collect();
+    assert_eq!(
+        series,
+        vec![
+            (today - Duration::days(3), Money::from(430), true),
+            (today - Duration::days(2), Money::from(530), true),
+            (today - Duration::days(1), Money::from(500), true),
+            (today, Money::from(500), false),
+        ]
+    );
+    assert_eq!(
+        history.points[3].account_types[0].account_type,
+        AccountType::Bank
+    );
+
+    let response = client
+        .get(format!(
+            "/reports/net_worth?from={}&granularity=month",
+            today - Duration::days(3)
+        ))
+        .dispatch()
+        .await;
+    let history: NetWorthHistoryOutDTO =
+        serde_json::from_str(&response.into_string().await.expect("Response has a body"))
+            .expect("Valid NetWorthHistoryOutDTO");
+    assert_eq!(history.points.last().map(|p| p.date), Some(today));
+    assert_eq!(
+        history.points.last().map(|p| p.net_worth),
+        Some(Money::from(500))
+    );
+
+    let response = client
+        .get("/reports/net_worth?from=2000-01-01&granularity=day")
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::BadRequest);
+
+    cleanup(&pool, user_id).await;
+}
+
+#[test]
+fn net_worth_history_converts_currencies_test() {
+    let mut euros = account(AccountType::Bank, 100);
+    euros.currency = "EUR".to_string();
+    let accounts = [euros, account(AccountType::Cash, 50)];
+    // Friday's rate still applies over the weekend.
+    let rates = RateTable::new(&[rate("EUR", "USD", date(2026, 3, 6), "1.10")]);
+
+    let points = net_worth_history(
+        &accounts,
+        &[],
+        &[],
+        &rates,
+        "USD",
+        &[date(2026, 3, 6), date(2026, 3, 8)],
+    )
+    .expect("Rates are published");
+    let series: Vec<(NaiveDate, Money)> = points.iter().map(|p| (p.date, p.net_worth)).collect();
+    assert_eq!(
+        series,
+        vec![
+            (date(2026, 3, 6), Money::from(160)),
+            (date(2026, 3, 8), Money::from(160)),
+        ]
+    );
+
+    let in_euros = net_worth_history(&accounts, &[], &[], &rates, "EUR", &[date(2026, 3, 6)])
+        .expect("Inverted rate is used");
+    assert_eq!(in_euros[0].net_worth, money("145.45"));
+
+    // A week later the rate is too old to fall back on.
+    let stale = net_worth_history(&accounts, &[], &[], &rates, "USD", &[date(2026, 3, 16)])
+        .expect_err("Rate is too old");
+    assert_eq!(
+        (stale.from.as_str(), stale.to.as_str(), stale.date),
+        ("EUR", "USD", date(2026, 3, 16))
+    );
+}
+
+#[rocket::async_test]
+async fn net_worth_converts_balances_integration_test() {
+    let (client, pool) = setup().await;
+
+    let (user_id, _) = before_test(&pool, "networthconvertuser")
+        .await
+        .expect("Failed to initialize test database");
+    common::login(&client, &pool, user_id).await;
+
+    open_account(&client, "Checking", "100", "USD").await;
+    open_account(&client, "Krona savings", "2000", "SEK").await;
+
+    let today = Utc::now().date_naive();
+    for day in [today - Duration::days(1), today, today + Duration::days(1)] {
+        sqlx::query(
+            "INSERT INTO exchange_rates (base_currency, quote_currency, rate_date, rate, imported_at)
+             VALUES ('SEK', 'USD', $1, 0.10, NOW())
+             ON CONFLICT (base_currency, quote_currency, rate_date) DO UPDATE SET rate = EXCLUDED.rate",
+        )
+        .bind(day)
+        .execute(&pool)
+        .await
+        .expect("Rate stored");
+    }
+
+    let response = client.get("/accounts/net_worth").dispatch().await;
+    assert_eq!(response.status(), Status::Ok);
+    let net_worth: NetWorthOutDTO =
+        serde_json::from_str(&response.into_string().await.expect("Response has a body"))
+            .expect("Valid NetWorthOutDTO");
+    assert_eq!(net_worth.currency, "USD");
+    assert_eq!(net_worth.net_worth, Money::from(300));
+    let converted: Vec<(String, Money)> = net_worth
+        .balances
+        .iter()
+        .map(|b| (b.currency.clone(), b.converted_balance))
+        .collect();
+    assert_eq!(
+        converted,
+        vec![
+            ("SEK".to_string(), Money::from(200)),
+            ("USD".to_string(), Money::from(100))
+        ]
+    );
+
+    let response = client
+        .get("/accounts/net_worth?currency=SEK")
+        .dispatch()
+        .await;
+    let net_worth: NetWorthOutDTO =
+        serde_json::from_str(&response.into_string().await.expect("Response has a body"))
+            .expect("Valid NetWorthOutDTO");
+    assert_eq!(net_worth.net_worth, Money::from(3000));
+
+    let response = client
+        .get(format!("/reports/net_worth?from={}&to={}", today, today))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let history: NetWorthHistoryOutDTO =
+        serde_json::from_str(&response.into_string().await.expect("Response has a body"))
+            .expect("Valid NetWorthHistoryOutDTO");
+    assert_eq!(
+        history
+            .points
+            .iter()
+            .map(|p| p.net_worth)
+            .collect::<Vec<_>>(),
+        vec![Money::from(300)]
+    );
+
+    sqlx::query(
+        "DELETE FROM exchange_rates WHERE base_currency = 'SEK' AND quote_currency = 'USD'",
+    )
+    .execute(&pool)
+    .await
+    .expect("Rates removed");
+    cleanup(&pool, user_id).await;
+}
+
+#[rocket::async_test]
+async fn net_worth_rejects_unusable_currencies_integration_test() {
+    let (client, pool) = setup().await;
+
+    let (user_id, _) = before_test(&pool, "networthcurrencyuser")
+        .await
+        .expect("Failed to initialize test database");
+    common::login(&client, &pool, user_id).await;
+    open_account(&client, "Checking", "100", "USD").await;
+
+    for currency in ["usd", "DOLLARS", "U1D"] {
+        let response = client
+            .get(format!("/accounts/net_worth?currency={}", currency))
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::BadRequest);
+        let response = client
+            .get(format!("/reports/net_worth?currency={}", currency))
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::BadRequest);
+    }
+
+    // XTS is reserved for testing, so no rate is ever published for it.
+    let response = client
+        .get("/accounts/net_worth?currency=XTS")
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::UnprocessableEntity);
+    let response = client
+        .get("/reports/net_worth?currency=XTS")
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::UnprocessableEntity);
+
+    cleanup(&pool, user_id).await;
+}
+
+#[rocket::async_test]
+async fn net_worth_requires_login_integration_test() {
+    let (client, _) = setup().await;
+
+    let response = client.get("/accounts/net_worth").dispatch().await;
+    assert_eq!(response.status(), Status::Unauthorized);
+    let response = client.get("/reports/net_worth").dispatch().await;
+    assert_eq!(response.status(), Status::Unauthorized);
+}
@@ -0,0 +1,580 @@
+use jsonwebtoken::{encode, EncodingKey, Header};
+use personal_finance_tracker::auth::oidc::code_challenge;
+use personal_finance_tracker::dtos::user_dtos::{UserInDTO, UserOutDTO};
+use personal_finance_tracker::operations::admin_ops::suspend_user;
+use personal_finance_tracker::operations::user_ops::create_user;
+use rocket::form::Form;
+use rocket::http::{ContentType, Status};
+use rocket::local::asynchronous::Client;
+use rocket::serde::json::{serde_json, Json, Value};
+use rocket::{get, post, routes, State};
+use serde_json::json;
+use sqlx::PgPool;
+use std::collections::HashMap;
+use std::net::{Ipv4Addr, TcpListener, TcpStream};
+use std::sync::{Arc, Mutex, OnceLock};
+
+use crate::common::setup;
+
+mod common;
+
+const CLIENT_ID: &str = "finance-tracker";
+const REDIRECT_URI: &str = "http://localhost:8000/auth/oidc/callback";
+const SIGNING_KEY: &[u8] = include_bytes!("fixtures/oidc_test_key.pem");
+const JWKS: &str = include_str!("fixtures/oidc_test_jwks.json");
+
+// What the mock provider knows about a user who just approved the login.
+#[derive(Clone)]
+struct PendingLogin {
+    code_challenge: String,
+    nonce: String,
+    subject: String,
+    email: String,
+    email_verified: bool,
+}
+
+struct MockIdp {
+    issuer: String,
+    pending: Mutex<HashMap<String, PendingLogin>>,
+}
+
+#[get("/.well-known/openid-configuration")]
+fn discovery(idp: &State<Arc<MockIdp>>) -> Json<Value> {
+    Json(json!({
+        "issuer": idp.issuer,
+        "authorization_endpoint": format!("{}/authorize", idp.issuer),
+        "token_endpoint": format!("{}/token", idp.issuer),
+        "jwks_uri": format!("{}/jwks", idp.issuer),
+    }))
+}
+
+#[get("/jwks")]
+fn jwks() -> Json<Value> {
+    Json(serde_json::from_str(JWKS).expect("Valid JWKS fixture"))
+}
+
+#[post("/token", data = "<request>")]
+fn token(
+    idp: &State<Arc<MockIdp>>,
+    request: Form<HashMap<String, String>>,
+) -> Result<Json<Value>, Status> {
+    let field = |name: &str| request.get(name).map(String::as_str).unwrap_or_default();
+    let login = idp
+        .pending
+        .lock()
+        .unwrap()
+        .remove(field("code"))
+        .ok_or(Status::BadRequest)?;
+    if field("grant_type") != "authorization_code"
+        || field("client_id") != CLIENT_ID
+        || field("redirect_uri") != REDIRECT_URI
+        || code_challenge(field("code_verifier")) != login.code_challenge
+    {
+        return Err(Status::BadRequest);
+    }
+
+    let now = chrono::Utc::now().timestamp();
+    let claims = json!({
+        "iss": idp.issuer,
+        "sub": login.subject,
+        "aud": CLIENT_ID,
+        "iat": now,
+        "exp": now + 300,
+        "nonce": login.nonce,
+        "email": login.email,
+        "email_verified": login.email_verified,
+        "preferred_username": login.subject,
+    });
+    let mut header = Header::new(jsonwebtoken::Algorithm::RS256);
+    header.kid = Some("mock-idp-key".to_string());
+    let key = EncodingKey::from_rsa_pem(SIGNING_KEY).expect("Valid signing key fixture");
+    let id_token = encode(&header, &claims, &key).map_err(|_| Status::InternalServerError)?;
+
+    Ok(Json(json!({
+        "access_token": "mock-access-token",
+        "token_type": "Bearer",
+        "id_token": id_token,
+    })))
+}
+
+// One provider serves every test in this file, on a thread of its own so it
+// outlives each test's runtime. The app reads the provider from the
+// environment, which the tests share.
+fn mock_idp() -> Arc<MockIdp> {
+    static IDP: OnceLock<Arc<MockIdp>> = OnceLock::new();
+    IDP.get_or_init(|| {
+        let port = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))
+            .and_then(|listener| listener.local_addr())
+            .expect("Free port")
+            .port();
+        let idp = Arc::new(MockIdp {
+            issuer: format!("http://127.0.0.1:{}", port),
+            pending: Mutex::new(HashMap::new()),
+        });
+
+        let mut config = rocket::Config::debug_default();
+        config.address = Ipv4Addr::LOCALHOST.into();
+        config.port = port;
+        config.log_level = rocket::config::LogLevel::Off;
+        config.shutdown.ctrlc = false;
+        let server = rocket::custom(config)
+            .manage(idp.clone())
+            .mount("/", routes![discovery, jwks, token]);
+        std::thread::spawn(move || {
+            let runtime = tokio::runtime::Runtime::new().expect("Tokio runtime");
+            let _ = runtime.block_on(server.launch());
+        });
+
+        for _ in 0..100 {
+            if TcpStream::connect((Ipv4Addr::LOCALHOST, port)).is_ok() {
+                std::env::set_var("OIDC_ISSUER", &idp.issuer);
+                std::env::set_var("OIDC_CLIENT_ID", CLIENT_ID);
+                std::env::set_var("OIDC_REDIRECT_URI", REDIRECT_URI);
+                return idp;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(20));
+        }
+        panic!("Mock identity provider did not start");
+    })
+    .clone()
+}
+
+// Follows the app's redirect to the provider and returns the query it sent.
+async fn start_login(client: &Client) -> HashMap<String, String> {
+    let response = client.get("/auth/oidc/login").dispatch().await;
+    assert_eq!(response.status(), Status::SeeOther);
+    let location = response
+        .headers()
+        .get_one("Location")
+        .expect("Redirect has a location");
+    let url = reqwest::Url::parse(location).expect("Valid authorization URL");
+
+    url.query_pairs().into_owned().collect()
+}
+
+// Plays the provider's part of a successful approval and returns the
+// callback response from the app.
+async fn approve_login(
+    client: &Client,
+    idp: &MockIdp,
+    subject: &str,
+    email: &str,
+    email_verified: bool,
+) -> Status {
+    let query = start_login(client).await;
+    let code = format!("code-{}", subject);
+    idp.pending.lock().unwrap().insert(
+        code.clone(),
+        PendingLogin {
+            code_challenge: query["code_challenge"].clone(),
+            nonce: query["nonce"].clone(),
+            subject: subject.to_string(),
+            email: email.to_string(),
+            email_verified,
+        },
+    );
+
+    client
+        .get(format!(
+            "/auth/oidc/callback?code={}&state={}",
+            code, query["state"]
+        ))
+        .dispatch()
+        .await
+        .status()
+}
+
+async fn callback(client: &Client, query: &str) -> Status {
+    client
+        .get(format!("/auth/oidc/callback?{}", query))
+        .dispatch()
+        .await
+        .status()
+}
+
+async fn linked_identities(pool: &PgPool, email: &str) -> i64 {
+    sqlx::query_scalar(
+        "SELECT COUNT(*) FROM user_identities i JOIN users u ON u.id = i.user_id WHERE u.email = $1",
+    )
+    .bind(email)
+    .fetch_one(pool)
+    .await
+    .expect("Failed to count identities")
+}
+
+async fn me(client: &Client) -> UserOutDTO {
+    let response = client.get("/auth/me").dispatch().await;
+    assert_eq!(response.status(), Status::Ok);
+    serde_json::from_str(&response.into_string().await.expect("Response has a body"))
+        .expect("Valid UserOutDTO")
+}
+
+async fn logout(client: &Client) {
+    let response = client.post("/auth/logout").dispatch().await;
+    assert_eq!(response.status(), Status::NoContent);
+}
+
+async fn cleanup(pool: &PgPool, emails: &[&str]) {
+    for email in emails {
+        sqlx::query("DELETE FROM users WHERE email = $1")
+            .bind(email)
+            .execute(pool)
+            .await
+            .unwrap_or_else(|e| panic!("Failed to cleanup test users: {}", e));
+    }
+}
+
+#[test]
+fn code_challenge_matches_rfc_7636_example() {
+    assert_eq!(
+        code_challenge("dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk"),
+        "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM"
+    );
+}
+
+#[rocket::async_test]
+async fn oidc_login_with_mock_provider_integration_test() {
+    let idp = mock_idp();
+    let (client, pool) = setup().await;
+    let new_email = "oidcnew@example.com";
+    let existing_email = "oidcexisting@example.com";
+
+    // The authorization request carries everything PKCE and OIDC require.
+    let query = start_login(&client).await;
+    assert_eq!(query["response_type"], "code");
+    assert_eq!(query["client_id"], CLIENT_ID);
+    assert_eq!(query["code_challenge_method"], "S256");
+    assert!(query["scope"].split(' ').any(|scope| scope == "openid"));
+
+    // First login creates the user, the second finds them again.
+    assert_eq!(
+        approve_login(&client, &idp, "oidcnew", new_email, false).await,
+        Status::Ok
+    );
+    let created = me(&client).await;
+    assert_eq!(created.email, new_email);
+    assert_eq!(created.username, "oidcnew");
+    logout(&client).await;
+
+    assert_eq!(
+        approve_login(&client, &idp, "oidcnew", new_email, false).await,
+        Status::Ok
+    );
+    assert_eq!(me(&client).await.id, created.id);
+    logout(&client).await;
+
+    // A matching email never links an existing account, verified or not; its
+    // owner signs in and links the provider from their session.
+    let existing = create_user(
+        &pool,
+        &UserInDTO {
+            username: "oidcexisting".to_string(),
+            email: existing_email.to_string(),
+        },
+    )
+    .await
+    .expect("Failed to create user");
+    assert_eq!(
+        approve_login(&client, &idp, "oidcexisting", existing_email, false).await,
+        Status::Conflict
+    );
+    assert_eq!(
+        approve_login(&client, &idp, "oidcexisting", existing_email, true).await,
+        Status::Conflict
+    );
+    common::login(&client, &pool, existing.id).await;
+    assert_eq!(
+        approve_login(&client, &idp, "oidcexisting", existing_email, true).await,
+        Status::Ok
+    );
+    logout(&client).await;
+    assert_eq!(
+        approve_login(&client, &idp, "oidcexisting", existing_email, true).await,
+        Status::Ok
+    );
+    assert_eq!(me(&client).await.id, existing.id);
+    logout(&client).await;
+
+    // A callback whose state doesn't match the flow cookie is rejected.
+    start_login(&client).await;
+    let forged = client
+        .get("/auth/oidc/callback?code=anything&state=forged")
+        .dispatch()
+        .await;
+    assert_eq!(forged.status(), Status::Unauthorized);
+
+    // The provider refuses a code verifier that doesn't match the challenge.
+    let query = start_login(&client).await;
+    idp.pending.lock().unwrap().insert(
+        "code-pkce".to_string(),
+        PendingLogin {
+            code_challenge: code_challenge("some other verifier"),
+            nonce: query["nonce"].clone(),
+            subject: "oidcnew".to_string(),
+            email: new_email.to_string(),
+            email_verified: false,
+        },
+    );
+    let pkce_mismatch = client
+        .get(format!(
+            "/auth/oidc/callback?code=code-pkce&state={}",
+            query["state"]
+        ))
+        .dispatch()
+        .await;
+    assert_eq!(pkce_mismatch.status(), Status::Unauthorized);
+
+    // An ID token minted for another login attempt is rejected by its nonce.
+    let query = start_login(&client).await;
+    idp.pending.lock().unwrap().insert(
+        "code-nonce".to_string(),
+        PendingLogin {
+            code_challenge: query["code_challenge"].clone(),
+            nonce: "replayed-nonce".to_string(),
+            subject: "oidcnew".to_string(),
+            email: new_email.to_string(),
+            email_verified: false,
+        },
+    );
+    let nonce_mismatch = client
+        .get(format!(
+            "/auth/oidc/callback?code=code-nonce&state={}",
+            query["state"]
+        ))
+        .dispatch()
+        .await;
+    assert_eq!(nonce_mismatch.status(), Status::Unauthorized);
+    assert_eq!(
+        client.get("/auth/me").dispatch().await.status(),
+        Status::Unauthorized
+    );
+
+    cleanup(&pool, &[new_email, existing_email]).await;
+}
+
+#[rocket::async_test]
+async fn registered_email_does_not_capture_provider_login_integration_test() {
+    let idp = mock_idp();
+    let (client, pool) = setup().await;
+    let victim_email = "oidcvictim@example.com";
+
+    // Someone registers a password account with an email that isn't theirs.
+    let response = client
+        .post("/auth/register")
+        .header(ContentType::JSON)
+        .body(
+            json!({
+                "username": "oidcsquatter",
+                "email": victim_email,
+                "password": common::TEST_PASSWORD,
+            })
+            .to_string(),
+        )
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    logout(&client).await;
+
+    // The real owner of the email signs in through the provider, which has
+    // verified it. The login is refused instead of linking the two.
+    assert_eq!(
+        approve_login(&client, &idp, "oidcvictim", victim_email, true).await,
+        Status::Conflict
+    );
+    assert_eq!(
+        client.get("/auth/me").dispatch().await.status(),
+        Status::Unauthorized
+    );
+    assert_eq!(linked_identities(&pool, victim_email).await, 0);
+
+    cleanup(&pool, &[victim_email]).await;
+}
+
+#[rocket::async_test]
+async fn callback_without_a_usable_flow_is_rejected_integration_test() {
+    let idp = mock_idp();
+    let (client, pool) = setup().await;
+    let email = "oidcreplay@example.com";
+
+    // No login was started from this browser.
+    assert_eq!(
+        callback(&client, "code=code-oidcreplay&state=guessed").await,
+        Status::Unauthorized
+    );
+
+    // The user declined at the provider.
+    let query = start_login(&client).await;
+    assert_eq!(
+        callback(
+            &client,
+            &format!("error=access_denied&state={}", query["state"])
+        )
+        .await,
+        Status::Unauthorized
+    );
+
+    // The provider came back without a code.
+    let query = start_login(&client).await;
+    assert_eq!(
+        callback(&client, &format!("state={}", query["state"])).await,
+        Status::BadRequest
+    );
+
+    // A code the provider never issued.
+    let query = start_login(&client).await;
+    assert_eq!(
+        callback(&client, &format!("code=unknown&state={}", query["state"])).await,
+        Status::Unauthorized
+    );
+
+    // A completed callback can't be replayed: its flow is used up.
+    let query = start_login(&client).await;
+    idp.pending.lock().unwrap().insert(
+        "code-oidcreplay".to_string(),
+        PendingLogin {
+            code_challenge: query["code_challenge"].clone(),
+            nonce: query["nonce"].clone(),
+            subject: "oidcreplay".to_string(),
+            email: email.to_string(),
+            email_verified: true,
+        },
+    );
+    let completed = format!("code=code-oidcreplay&state={}", query["state"]);
+    assert_eq!(callback(&client, &completed).await, Status::Ok);
+    logout(&client).await;
+    assert_eq!(callback(&client, &completed).await, Status::Unauthorized);
+    assert_eq!(
+        client.get("/auth/me").dispatch().await.status(),
+        Status::Unauthorized
+    );
+
+    cleanup(&pool, &[email]).await;
+}
+
+#[rocket::async_test]
+async fn identity_linked_to_another_account_integration_test() {
+    let idp = mock_idp();
+    let (client, pool) = setup().await;
+    let owner_email = "oidclinkowner@example.com";
+    let other_email = "oidclinkother@example.com";
+
+    assert_eq!(
+        approve_login(&client, &idp, "oidclinkowner", owner_email, true).await,
+        Status::Ok
+    );
+    let owner = me(&client).await;
+    logout(&client).await;
+
+    // Someone else signed in can't take the identity over, and stays signed
+    // in as themselves.
+    let other = create_user(
+        &pool,
+        &UserInDTO {
+            username: "oidclinkother".to_string(),
+            email: other_email.to_string(),
+        },
+    )
+    .await
+    .expect("Failed to create user");
+    common::login(&client, &pool, other.id).await;
+    assert_eq!(
+        approve_login(&client, &idp, "oidclinkowner", owner_email, true).await,
+        Status::Conflict
+    );
+    assert_eq!(me(&client).await.id, other.id);
+    assert_eq!(linked_identities(&pool, other_email).await, 0);
+
+    // Signing in again with the identity it's already linked to is fine.
+    logout(&client).await;
+    assert_eq!(
+        approve_login(&client, &idp, "oidclinkowner", owner_email, true).await,
+        Status::Ok
+    );
+    assert_eq!(
+        approve_login(&client, &idp, "oidclinkowner", owner_email, true).await,
+        Status::Ok
+    );
+    assert_eq!(me(&client).await.id, owner.id);
+    assert_eq!(linked_identities(&pool, owner_email).await, 1);
+
+    cleanup(&pool, &[owner_email, other_email]).await;
+}
+
+#[rocket::async_test]
+async fn suspended_user_cannot_log_in_with_provider_integration_test() {
+    let idp = mock_idp();
+    let (client, pool) = setup().await;
+    let email = "oidcsuspended@example.com";
+
+    assert_eq!(
+        approve_login(&client, &idp, "oidcsuspended", email, true).await,
+        Status::Ok
+    );
+    let user = me(&client).await;
+    suspend_user(&pool, user.id)
+        .await
+        .expect("Failed to suspend user");
+    assert_eq!(
+        client.get("/auth/me").dispatch().await.status(),
+        Status::Unauthorized
+    );
+
+    assert_eq!(
+        approve_login(&client, &idp, "oidcsuspended", email, true).await,
+        Status::Forbidden
+    );
+    assert_eq!(
+        client.get("/auth/me").dispatch().await.status(),
+        Status::Unauthorized
+    );
+
+    cleanup(&pool, &[email]).await;
+}
+
+#[rocket::async_test]
+async fn identity_cannot_be_linked_while_impersonating_integration_test() {
+    let idp = mock_idp();
+    let (client, pool) = setup().await;
+    let admin_email = "oidcadmin@example.com";
+    let user_email = "oidcimpersonated@example.com";
+
+    let admin = create_user(
+        &pool,
+        &UserInDTO {
+            username: "oidcadmin".to_string(),
+            email: admin_email.to_string(),
+        },
+    )
+    .await
+    .expect("Failed to create user");
+    let user = create_user(
+        &pool,
+        &UserInDTO {
+            username: "oidcimpersonated".to_string(),
+            email: user_email.to_string(),
+        },
+    )
+    .await
+    .expect("Failed to create user");
+    common::login_admin(&client, &pool, admin.id).await;
+    let response = client
+        .post(format!("/admin/users/{}/impersonate", user.id))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    // An admin's provider login must not end up on the user's account.
+    assert_eq!(
+        approve_login(&client, &idp, "oidcadmin", admin_email, true).await,
+        Status::Forbidden
+    );
+    assert_eq!(me(&client).await.id, user.id);
+    assert_eq!(linked_identities(&pool, user_email).await, 0);
+
+    sqlx::query("DELETE FROM impersonation_log WHERE impersonator_id = $1")
+        .bind(admin.id)
+        .execute(&pool)
+        .await
+        .expect("Failed to cleanup impersonation log");
+    cleanup(&pool, &[user_email, admin_email]).await;
+}
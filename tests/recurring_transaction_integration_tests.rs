@@ -0,0 +1,424 @@
+use chrono::{Duration, Local, NaiveDate, Utc};
+use personal_finance_tracker::dtos::account_dtos::AccountOutDTO;
+use personal_finance_tracker::dtos::category_dtos::CategoryInDTO;
+use personal_finance_tracker::dtos::recurring_transaction_dtos::RecurringTransactionOutDTO;
+use personal_finance_tracker::dtos::report_dtos::ForecastOutDTO;
+use personal_finance_tracker::dtos::user_dtos::UserInDTO;
+use personal_finance_tracker::enums::custom_enums::{
+    AccountType, RecurrenceFrequency, TransactionType,
+};
+use personal_finance_tracker::models::account::Account;
+use personal_finance_tracker::models::recurring_transaction::RecurringTransaction;
+use personal_finance_tracker::models::report::{AccountTransferTotal, CategorySpending};
+use personal_finance_tracker::money::Money;
+use personal_finance_tracker::operations::category_ops::create_category;
+use personal_finance_tracker::operations::user_ops::create_user;
+use personal_finance_tracker::reports::forecast::forecast;
+use rocket::http::{ContentType, Status};
+use rocket::serde::json::serde_json;
+use serde_json::json;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::common::{money, setup};
+
+mod common;
+
+pub async fn before_test(pool: &PgPool, username: &str) -> Result<(Uuid, Uuid, Uuid), sqlx::Error> {
+    let user_dto = UserInDTO {
+        username: username.to_string(),
+        email: format!("{}@example.com", username),
+    };
+    let user = create_user(pool, &user_dto).await?;
+    let groceries = create_category(
+        pool,
+        user.id,
+        &CategoryInDTO {
+            name: "Groceries".to_string(),
+        },
+    )
+    .await?;
+    let rent = create_category(
+        pool,
+        user.id,
+        &CategoryInDTO {
+            name: "Rent".to_string(),
+        },
+    )
+    .await?;
+
+    Ok((user.id, groceries.id, rent.id))
+}
+
+pub async fn cleanup(pool: &PgPool, user_ids: &[Uuid]) {
+    sqlx::query("DELETE FROM users WHERE id = ANY($1)")
+        .bind(user_ids)
+        .execute(pool)
+        .await
+        .unwrap_or_else(|e| panic!("Failed to cleanup test users: {}", e));
+}
+
+fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).expect("Valid date")
+}
+
+#[allow(clippy::too_many_arguments)]
+fn recurring(
+    account_id: Uuid,
+    category_id: Uuid,
+    amount: i64,
+    transaction_type: TransactionType,
+    frequency: RecurrenceFrequency,
+    every: i32,
+    start_date: NaiveDate,
+    end_date: Option<NaiveDate>,
+) -> RecurringTransaction {
+    RecurringTransaction {
+        id: Uuid::new_v4(),
+        user_id: Uuid::nil(),
+        account_id,
+        category_id,
+        title: "Item".to_string(),
+        amount: Money::from(amount),
+        transaction_type,
+        frequency,
+        every,
+        start_date,
+        end_date,
+        created_at: Local::now().naive_local(),
+    }
+}
+
+#[test]
+fn occurrences_between_test() {
+    let id = Uuid::new_v4();
+    let monthly = recurring(
+        id,
+        id,
+        10,
+        TransactionType::Expense,
+        RecurrenceFrequency::Monthly,
+        1,
+        date(2026, 1, 31),
+        None,
+    );
+    assert_eq!(
+        monthly.occurrences_between(date(2026, 1, 1), date(2026, 4, 30)),
+        vec![
+            date(2026, 1, 31),
+            date(2026, 2, 28),
+            date(2026, 3, 31),
+            date(2026, 4, 30)
+        ]
+    );
+
+    let fortnightly = recurring(
+        id,
+        id,
+        10,
+        TransactionType::Income,
+        RecurrenceFrequency::Weekly,
+        2,
+        date(2026, 3, 2),
+        Some(date(2026, 4, 12)),
+    );
+    assert_eq!(
+        fortnightly.occurrences_between(date(2026, 3, 10), date(2026, 12, 31)),
+        vec![date(2026, 3, 16), date(2026, 3, 30)]
+    );
+
+    let leap_day = recurring(
+        id,
+        id,
+        10,
+        TransactionType::Expense,
+        RecurrenceFrequency::Yearly,
+        1,
+        date(2024, 2, 29),
+        None,
+    );
+    assert_eq!(
+        leap_day.occurrences_between(date(2025, 1, 1), date(2028, 12, 31)),
+        vec![
+            date(2025, 2, 28),
+            date(2026, 2, 28),
+            date(2027, 2, 28),
+            date(2028, 2, 29)
+        ]
+    );
+
+    let daily = recurring(
+        id,
+        id,
+        10,
+        TransactionType::Expense,
+        RecurrenceFrequency::Daily,
+        3,
+        date(2026, 5, 1),
+        None,
+    );
+    assert!(daily
+        .occurrences_between(date(2026, 4, 1), date(2026, 4, 30))
+        .is_empty());
+}
+
+#[test]
+fn forecast_test() {
+    let account = Account {
+        id: Uuid::new_v4(),
+        name: "Checking".to_string(),
+        account_type: AccountType::Bank,
+        balance: Money::from(100),
+        currency: "USD".to_string(),
+        user_id: Uuid::nil(),
+        household_id: None,
+    };
+    let groceries = Uuid::new_v4();
+    let rent = Uuid::new_v4();
+    let today = date(2026, 3, 27);
+    let items = vec![
+        recurring(
+            account.id,
+            rent,
+            80,
+            TransactionType::Expense,
+            RecurrenceFrequency::Monthly,
+            1,
+            date(2026, 1, 1),
+            None,
+        ),
+        recurring(
+            account.id,
+            groceries,
+            20,
+            TransactionType::Income,
+            RecurrenceFrequency::Weekly,
+            1,
+            date(2026, 3, 30),
+            None,
+        ),
+    ];
+    let spending = vec![
+        // Already covered by the rent.
+        CategorySpending {
+            account_id: account.id,
+            category_id: rent,
+            amount: Money::from(240),
+        },
+        CategorySpending {
+            account_id: account.id,
+            category_id: groceries,
+            amount: money("30"),
+        },
+    ];
+
+    let report = forecast(&[account], &items, &spending, &[], today, 7);
+
+    assert_eq!(report.from, date(2026, 3, 28));
+    assert_eq!(report.to, date(2026, 4, 3));
+    let projection = &report.accounts[0];
+    assert_eq!(projection.daily_discretionary, money("0.33"));
+    let balances: Vec<_> = projection.days.iter().map(|day| day.balance).collect();
+    assert_eq!(
+        balances,
+        vec![
+            money("99.67"),
+            money("99.33"),
+            money("119.00"),
+            money("118.67"),
+            money("38.33"),
+            money("38.00"),
+            money("37.67"),
+        ]
+    );
+    assert_eq!(projection.ending_balance, money("37.67"));
+    assert_eq!(projection.lowest_balance_date, date(2026, 4, 3));
+    assert_eq!(projection.first_negative_date, None);
+    assert!(report.negative_dates.is_empty());
+    let upcoming: Vec<_> = report
+        .upcoming
+        .iter()
+        .map(|o| (o.date, o.transaction_type))
+        .collect();
+    assert_eq!(
+        upcoming,
+        vec![
+            (date(2026, 3, 30), TransactionType::Income),
+            (date(2026, 4, 1), TransactionType::Expense),
+        ]
+    );
+}
+
+#[test]
+fn forecast_transfers_test() {
+    let account = Account {
+        id: Uuid::new_v4(),
+        name: "Checking".to_string(),
+        account_type: AccountType::Bank,
+        balance: Money::from(100),
+        currency: "USD".to_string(),
+        user_id: Uuid::nil(),
+        household_id: None,
+    };
+    let transfers = vec![
+        AccountTransferTotal {
+            account_id: account.id,
+            net: Money::from(-45),
+        },
+        AccountTransferTotal {
+            account_id: Uuid::new_v4(),
+            net: Money::from(45),
+        },
+    ];
+
+    let report = forecast(&[account], &[], &[], &transfers, date(2026, 3, 27), 3);
+
+    let projection = &report.accounts[0];
+    assert_eq!(projection.daily_transfers, money("-0.50"));
+    let balances: Vec<_> = projection.days.iter().map(|day| day.balance).collect();
+    assert_eq!(
+        balances,
+        vec![money("99.50"), money("99.00"), money("98.50")]
+    );
+}
+
+#[rocket::async_test]
+async fn recurring_transaction_forecast_integration_test() {
+    let (client, pool) = setup().await;
+
+    let (user_id, groceries_id, rent_id) = before_test(&pool, "forecastuser")
+        .await
+        .expect("Failed to initialize test database");
+    let (other_id, _, _) = before_test(&pool, "forecastother")
+        .await
+        .expect("Failed to initialize test database");
+    common::login(&client, &pool, user_id).await;
+
+    let response = client
+        .post("/accounts")
+        .header(ContentType::JSON)
+        .body(json!({ "name": "Checking", "balance": "100", "account_type": "Bank" }).to_string())
+        .dispatch()
+        .await;
+    let checking: AccountOutDTO =
+        serde_json::from_str(&response.into_string().await.expect("Response has a body"))
+            .expect("Valid AccountOutDTO");
+
+    let now = Utc::now();
+    let response = client
+        .post("/transactions")
+        .header(ContentType::JSON)
+        .body(
+            json!({
+                "title": "Groceries",
+                "amount": "45",
+                "date": now - Duration::days(10),
+                "category_id": groceries_id,
+                "transaction_type": "Expense",
+                "account_id": checking.id,
+            })
+            .to_string(),
+        )
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    let today = now.date_naive();
+    let rent = json!({
+        "title": "Rent",
+        "amount": "60",
+        "transaction_type": "Expense",
+        "account_id": checking.id,
+        "category_id": rent_id,
+        "frequency": "weekly",
+        "start_date": today + Duration::days(1),
+    });
+
+    let mut invalid = rent.clone();
+    invalid["amount"] = json!("0");
+    let response = client
+        .post("/recurring_transactions")
+        .header(ContentType::JSON)
+        .body(invalid.to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::BadRequest);
+
+    let mut not_mine = rent.clone();
+    not_mine["category_id"] = json!(Uuid::new_v4());
+    let response = client
+        .post("/recurring_transactions")
+        .header(ContentType::JSON)
+        .body(not_mine.to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::BadRequest);
+
+    let response = client
+        .post("/recurring_transactions")
+        .header(ContentType::JSON)
+        .body(rent.to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let created: RecurringTransactionOutDTO =
+        serde_json::from_str(&response.into_string().await.expect("Response has a body"))
+            .expect("Valid RecurringTransactionOutDTO");
+    assert_eq!(created.every, 1);
+    assert_eq!(created.frequency, RecurrenceFrequency::Weekly);
+
+    let response = client.get("/recurring_transactions").dispatch().await;
+    let listed: Vec<RecurringTransactionOutDTO> =
+        serde_json::from_str(&response.into_string().await.expect("Response has a body"))
+            .expect("Valid RecurringTransactionOutDTO list");
+    assert_eq!(listed.len(), 1);
+
+    let response = client.get("/reports/forecast?days=10").dispatch().await;
+    assert_eq!(response.status(), Status::Ok);
+    let report: ForecastOutDTO =
+        serde_json::from_str(&response.into_string().await.expect("Response has a body"))
+            .expect("Valid ForecastOutDTO");
+    let projection = &report.accounts[0];
+    assert_eq!(projection.daily_discretionary, money("0.50"));
+    assert_eq!(projection.days.len(), 10);
+    assert_eq!(projection.days[0].balance, money("39.50"));
+    assert_eq!(projection.days[7].balance, money("-24.00"));
+    assert_eq!(projection.ending_balance, money("-25.00"));
+    assert_eq!(
+        projection.first_negative_date,
+        Some(today + Duration::days(8))
+    );
+    assert_eq!(
+        report.negative_dates,
+        vec![
+            today + Duration::days(8),
+            today + Duration::days(9),
+            today + Duration::days(10)
+        ]
+    );
+    assert_eq!(report.upcoming.len(), 2);
+
+    for days in ["0", "367"] {
+        let response = client
+            .get(format!("/reports/forecast?days={}", days))
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::BadRequest);
+    }
+
+    common::login(&client, &pool, other_id).await;
+    let response = client
+        .delete(format!("/recurring_transactions/{}", created.id))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::NotFound);
+
+    common::login(&client, &pool, user_id).await;
+    let response = client
+        .delete(format!("/recurring_transactions/{}", created.id))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::NoContent);
+
+    cleanup(&pool, &[user_id, other_id]).await;
+}
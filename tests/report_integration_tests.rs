@@ -0,0 +1,571 @@
+use chrono::{Local, NaiveDate};
+use personal_finance_tracker::dtos::account_dtos::AccountOutDTO;
+use personal_finance_tracker::dtos::category_dtos::CategoryInDTO;
+use personal_finance_tracker::dtos::household_dtos::HouseholdInDTO;
+use personal_finance_tracker::dtos::report_dtos::{CategoryReportOutDTO, SummaryReportOutDTO};
+use personal_finance_tracker::dtos::user_dtos::UserInDTO;
+use personal_finance_tracker::enums::custom_enums::{
+    HouseholdRole, ReportGranularity, TransactionType, WeekDay,
+};
+use personal_finance_tracker::exchange_rates::RateTable;
+use personal_finance_tracker::models::exchange_rate::ExchangeRate;
+use personal_finance_tracker::models::report::DailyTotal;
+use personal_finance_tracker::models::user_preferences::UserPreferences;
+use personal_finance_tracker::money::Money;
+use personal_finance_tracker::operations::category_ops::create_category;
+use personal_finance_tracker::operations::household_ops::{
+    add_household_member, create_household, delete_household, share_record,
+};
+use personal_finance_tracker::operations::ownership_ops::Owned;
+use personal_finance_tracker::operations::user_ops::create_user;
+use personal_finance_tracker::reports::{comparison_periods, summarize};
+use rocket::http::{ContentType, Status};
+use rocket::local::asynchronous::Client;
+use rocket::serde::json::serde_json;
+use rust_decimal::Decimal;
+use serde_json::json;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::common::{money, preferences, setup};
+
+mod common;
+
+pub async fn before_test(pool: &PgPool, username: &str) -> Result<Uuid, sqlx::Error> {
+    let user_dto = UserInDTO {
+        username: username.to_string(),
+        email: format!("{}@example.com", username),
+    };
+    let user = create_user(pool, &user_dto).await?;
+
+    Ok(user.id)
+}
+
+pub async fn cleanup(pool: &PgPool, user_ids: &[Uuid]) {
+    sqlx::query("DELETE FROM users WHERE id = ANY($1)")
+        .bind(user_ids)
+        .execute(pool)
+        .await
+        .unwrap_or_else(|e| panic!("Failed to cleanup test users: {}", e));
+}
+
+fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).expect("Valid date")
+}
+
+fn daily(
+    day: NaiveDate,
+    transaction_type: TransactionType,
+    amount: &str,
+    currency: &str,
+) -> DailyTotal {
+    DailyTotal {
+        day,
+        transaction_type,
+        currency: currency.to_string(),
+        group_id: None,
+        group_name: None,
+        amount: money(amount),
+        transaction_count: 1,
+    }
+}
+
+async fn get_report(client: &Client, query: &str) -> SummaryReportOutDTO {
+    let response = client
+        .get(format!("/reports/summary?{}", query))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    serde_json::from_str(&response.into_string().await.expect("Response has a body"))
+        .expect("Valid SummaryReportOutDTO")
+}
+
+async fn post_transactions(
+    client: &Client,
+    account_id: Uuid,
+    transactions: &[(&str, &str, Uuid, &str)],
+) {
+    for (date, amount, category_id, transaction_type) in transactions {
+        let response = client
+            .post("/transactions")
+            .header(ContentType::JSON)
+            .body(
+                json!({
+                    "title": "Entry",
+                    "amount": amount,
+                    "date": date,
+                    "category_id": category_id,
+                    "transaction_type": transaction_type,
+                    "account_id": account_id,
+                })
+                .to_string(),
+            )
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::Ok);
+    }
+}
+
+#[test]
+fn summarize_by_week_test() {
+    let rates = RateTable::new(&[ExchangeRate {
+        base_currency: "EUR".to_string(),
+        quote_currency: "USD".to_string(),
+        rate_date: date(2024, 1, 5),
+        rate: "1.1".parse().expect("Valid rate"),
+        imported_at: Local::now().naive_local(),
+    }]);
+    let daily_totals = vec![
+        daily(date(2024, 1, 8), TransactionType::Income, "100", "USD"),
+        daily(date(2024, 1, 9), TransactionType::Expense, "25", "EUR"),
+    ];
+
+    // 2024-01-03 is a Wednesday, so the first week started on the Sunday
+    // before and only its last days are reported.
+    let sunday_first = UserPreferences {
+        week_start: WeekDay::Sunday,
+        ..preferences()
+    };
+    let report = summarize(
+        &daily_totals,
+        &rates,
+        "USD",
+        ReportGranularity::Week,
+        &sunday_first,
+        date(2024, 1, 3),
+        date(2024, 1, 16),
+    )
+    .expect("Rates are available");
+
+    let periods: Vec<(NaiveDate, NaiveDate, i64)> = report
+        .periods
+        .iter()
+        .map(|p| (p.start, p.end, p.transaction_count))
+        .collect();
+    assert_eq!(
+        periods,
+        vec![
+            (date(2024, 1, 3), date(2024, 1, 6), 0),
+            (date(2024, 1, 7), date(2024, 1, 13), 2),
+            (date(2024, 1, 14), date(2024, 1, 16), 0),
+        ]
+    );
+    assert_eq!(report.periods[1].expenses, money("27.50"));
+    assert_eq!(report.periods[1].savings_rate, Some(Decimal::new(725, 1)));
+    assert_eq!(report.periods[0].savings_rate, None);
+    assert_eq!(report.net, money("72.50"));
+
+    let missing = summarize(
+        &daily_totals,
+        &rates,
+        "GBP",
+        ReportGranularity::Week,
+        &sunday_first,
+        date(2024, 1, 3),
+        date(2024, 1, 16),
+    );
+    assert!(missing.is_err());
+}
+
+#[test]
+fn summarize_by_fiscal_year_test() {
+    let rates = RateTable::new(&[]);
+    let daily_totals = vec![
+        daily(date(2024, 3, 31), TransactionType::Expense, "10", "USD"),
+        daily(date(2024, 4, 1), TransactionType::Expense, "20", "USD"),
+    ];
+    let april_start = UserPreferences {
+        fiscal_year_start_month: 4,
+        ..preferences()
+    };
+
+    let report = summarize(
+        &daily_totals,
+        &rates,
+        "USD",
+        ReportGranularity::Year,
+        &april_start,
+        date(2024, 1, 1),
+        date(2024, 12, 31),
+    )
+    .expect("Rates are available");
+
+    let periods: Vec<(NaiveDate, NaiveDate, Money)> = report
+        .periods
+        .iter()
+        .map(|p| (p.start, p.end, p.expenses))
+        .collect();
+    assert_eq!(
+        periods,
+        vec![
+            (date(2024, 1, 1), date(2024, 3, 31), money("10")),
+            (date(2024, 4, 1), date(2024, 12, 31), money("20")),
+        ]
+    );
+}
+
+#[rocket::async_test]
+async fn shared_account_summary_integration_test() {
+    let (client, pool) = setup().await;
+
+    let owner_id = before_test(&pool, "reportsharer")
+        .await
+        .expect("Failed to initialize test database");
+    let member_id = before_test(&pool, "reportmember")
+        .await
+        .expect("Failed to initialize test database");
+    common::login(&client, &pool, owner_id).await;
+
+    let response = client
+        .post("/accounts")
+        .header(ContentType::JSON)
+        .body(json!({ "name": "Joint", "balance": "0", "account_type": "Bank" }).to_string())
+        .dispatch()
+        .await;
+    let account: AccountOutDTO =
+        serde_json::from_str(&response.into_string().await.expect("Response has a body"))
+            .expect("Valid AccountOutDTO");
+    let category = create_category(
+        &pool,
+        owner_id,
+        &CategoryInDTO {
+            name: "Groceries".to_string(),
+        },
+    )
+    .await
+    .expect("Failed to create category");
+    post_transactions(
+        &client,
+        account.id,
+        &[("2026-01-15T12:00:00Z", "80.00", category.id, "Expense")],
+    )
+    .await;
+
+    let household = create_household(
+        &pool,
+        owner_id,
+        &HouseholdInDTO {
+            name: "Report household".to_string(),
+        },
+    )
+    .await
+    .expect("Failed to create household");
+    add_household_member(&pool, household.id, member_id, HouseholdRole::Viewer)
+        .await
+        .expect("Failed to add member");
+    share_record(&pool, owner_id, household.id, Owned::Account, account.id)
+        .await
+        .expect("Failed to share account");
+
+    // Spending on a shared account shows up in every member's reports.
+    common::login(&client, &pool, member_id).await;
+    let report = get_report(&client, "from=2026-01-01&to=2026-01-31").await;
+    assert_eq!(report.expenses, Money::from(80));
+    assert_eq!(report.transaction_count, 1);
+
+    delete_household(&pool, household.id)
+        .await
+        .expect("Failed to delete household");
+    cleanup(&pool, &[owner_id, member_id]).await;
+}
+
+#[rocket::async_test]
+async fn monthly_summary_integration_test() {
+    let (client, pool) = setup().await;
+    let admin_client = Client::tracked(personal_finance_tracker::create_rocket(pool.clone()))
+        .await
+        .expect("Valid rocket instance");
+
+    let user_id = before_test(&pool, "reportuser")
+        .await
+        .expect("Failed to initialize test database");
+    let admin_id = before_test(&pool, "reportadmin")
+        .await
+        .expect("Failed to initialize test database");
+    common::login(&client, &pool, user_id).await;
+    common::login_admin(&admin_client, &pool, admin_id).await;
+
+    let response = client
+        .post("/accounts")
+        .header(ContentType::JSON)
+        .body(json!({ "name": "Checking", "balance": "0", "account_type": "Bank" }).to_string())
+        .dispatch()
+        .await;
+    let account: AccountOutDTO =
+        serde_json::from_str(&response.into_string().await.expect("Response has a body"))
+            .expect("Valid AccountOutDTO");
+
+    let mut categories = Vec::new();
+    for name in ["Salary", "Food", "Fun"] {
+        let category = create_category(
+            &pool,
+            user_id,
+            &CategoryInDTO {
+                name: name.to_string(),
+            },
+        )
+        .await
+        .expect("Failed to create category");
+        categories.push(category.id);
+    }
+    let (salary, food, fun) = (categories[0], categories[1], categories[2]);
+
+    post_transactions(
+        &client,
+        account.id,
+        &[
+            ("2026-01-15T12:00:00Z", "1000.00", salary, "Income"),
+            ("2026-01-20T12:00:00Z", "250.00", food, "Expense"),
+            ("2026-02-03T12:00:00Z", "100.00", food, "Expense"),
+            ("2026-02-20T12:00:00Z", "50.50", fun, "Expense"),
+            ("2026-04-01T12:00:00Z", "999.00", fun, "Expense"),
+        ],
+    )
+    .await;
+
+    let report = get_report(&client, "from=2026-01-01&to=2026-03-31&granularity=month").await;
+    assert_eq!(report.currency, "USD");
+    assert_eq!(report.granularity, ReportGranularity::Month);
+    assert_eq!(report.income, Money::from(1000));
+    assert_eq!(report.expenses, money("400.50"));
+    assert_eq!(report.net, money("599.50"));
+    assert_eq!(report.savings_rate, Some(Decimal::new(5995, 2)));
+    assert_eq!(report.transaction_count, 4);
+
+    let periods: Vec<(NaiveDate, Money, Money, Option<Decimal>)> = report
+        .periods
+        .iter()
+        .map(|p| (p.start, p.income, p.expenses, p.savings_rate))
+        .collect();
+    assert_eq!(
+        periods,
+        vec![
+            (
+                date(2026, 1, 1),
+                Money::from(1000),
+                Money::from(250),
+                Some(Decimal::from(75))
+            ),
+            (date(2026, 2, 1), Money::ZERO, money("150.50"), None),
+            (date(2026, 3, 1), Money::ZERO, Money::ZERO, None),
+        ]
+    );
+    assert_eq!(report.periods[1].end, date(2026, 2, 28));
+    assert!(report.periods.iter().all(|p| p.breakdown.is_empty()));
+
+    let report = get_report(
+        &client,
+        "from=2026-01-01&to=2026-02-28&granularity=month&breakdown=category",
+    )
+    .await;
+    let breakdown: Vec<Vec<(&str, Money, Money)>> = report
+        .periods
+        .iter()
+        .map(|p| {
+            p.breakdown
+                .iter()
+                .map(|b| (b.name.as_str(), b.income, b.expenses))
+                .collect()
+        })
+        .collect();
+    assert_eq!(
+        breakdown,
+        vec![
+            vec![
+                ("Food", Money::ZERO, Money::from(250)),
+                ("Salary", Money::from(1000), Money::ZERO),
+            ],
+            vec![
+                ("Food", Money::ZERO, Money::from(100)),
+                ("Fun", Money::ZERO, money("50.50")),
+            ],
+        ]
+    );
+    assert_eq!(report.periods[1].breakdown[0].id, food);
+
+    let report = get_report(
+        &client,
+        "from=2026-01-01&to=2026-12-31&granularity=year&breakdown=account",
+    )
+    .await;
+    assert_eq!(report.periods.len(), 1);
+    assert_eq!(report.periods[0].breakdown[0].name, "Checking");
+    assert_eq!(report.periods[0].breakdown[0].expenses, money("1399.50"));
+
+    for query in [
+        "granularity=fortnight",
+        "breakdown=payee",
+        "from=2026-02-01&to=2026-01-01",
+        "from=2000-01-01&to=2026-01-01&granularity=day",
+    ] {
+        let response = client
+            .get(format!("/reports/summary?{}", query))
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::BadRequest, "{}", query);
+    }
+
+    let response = client
+        .get(format!("/reports/summary?user_id={}", admin_id))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Forbidden);
+
+    let report = get_report(
+        &admin_client,
+        &format!("user_id={}&from=2026-01-01&to=2026-01-31", user_id),
+    )
+    .await;
+    assert_eq!(report.net, Money::from(750));
+
+    cleanup(&pool, &[user_id, admin_id]).await;
+}
+
+#[test]
+fn comparison_periods_test() {
+    let cases = [
+        // This month so far against the same days of last month.
+        (
+            (date(2026, 10, 1), date(2026, 10, 19)),
+            (date(2026, 9, 1), date(2026, 9, 19)),
+            (date(2025, 10, 1), date(2025, 10, 19)),
+        ),
+        (
+            (date(2026, 9, 1), date(2026, 9, 30)),
+            (date(2026, 8, 1), date(2026, 8, 31)),
+            (date(2025, 9, 1), date(2025, 9, 30)),
+        ),
+        (
+            (date(2025, 2, 1), date(2025, 2, 28)),
+            (date(2025, 1, 1), date(2025, 1, 31)),
+            (date(2024, 2, 1), date(2024, 2, 29)),
+        ),
+        (
+            (date(2026, 1, 1), date(2026, 3, 31)),
+            (date(2025, 10, 1), date(2025, 12, 31)),
+            (date(2025, 1, 1), date(2025, 3, 31)),
+        ),
+        (
+            (date(2026, 10, 10), date(2026, 10, 16)),
+            (date(2026, 10, 3), date(2026, 10, 9)),
+            (date(2025, 10, 10), date(2025, 10, 16)),
+        ),
+    ];
+
+    for ((from, to), previous, last_year) in cases {
+        assert_eq!(comparison_periods(from, to), (previous, last_year));
+    }
+}
+
+#[rocket::async_test]
+async fn category_report_integration_test() {
+    let (client, pool) = setup().await;
+
+    let user_id = before_test(&pool, "categoryreportuser")
+        .await
+        .expect("Failed to initialize test database");
+    common::login(&client, &pool, user_id).await;
+
+    let response = client
+        .post("/accounts")
+        .header(ContentType::JSON)
+        .body(json!({ "name": "Checking", "balance": "0", "account_type": "Bank" }).to_string())
+        .dispatch()
+        .await;
+    let account: AccountOutDTO =
+        serde_json::from_str(&response.into_string().await.expect("Response has a body"))
+            .expect("Valid AccountOutDTO");
+
+    let mut categories = Vec::new();
+    for name in ["Food", "Fun", "Rent", "Salary"] {
+        let category = create_category(
+            &pool,
+            user_id,
+            &CategoryInDTO {
+                name: name.to_string(),
+            },
+        )
+        .await
+        .expect("Failed to create category");
+        categories.push(category.id);
+    }
+    let (food, fun, rent, salary) = (categories[0], categories[1], categories[2], categories[3]);
+
+    post_transactions(
+        &client,
+        account.id,
+        &[
+            ("2026-03-01T12:00:00Z", "5000.00", salary, "Income"),
+            ("2026-03-05T12:00:00Z", "300.00", food, "Expense"),
+            ("2026-03-10T12:00:00Z", "700.00", rent, "Expense"),
+            ("2026-02-07T12:00:00Z", "200.00", food, "Expense"),
+            ("2026-02-14T12:00:00Z", "100.00", fun, "Expense"),
+            ("2025-03-20T12:00:00Z", "400.00", food, "Expense"),
+        ],
+    )
+    .await;
+
+    let response = client
+        .get("/reports/categories?from=2026-03-01&to=2026-03-31")
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let report: CategoryReportOutDTO =
+        serde_json::from_str(&response.into_string().await.expect("Response has a body"))
+            .expect("Valid CategoryReportOutDTO");
+    assert_eq!(report.total, Money::from(1000));
+    assert_eq!(report.previous_period.from, date(2026, 2, 1));
+    assert_eq!(report.previous_period.to, date(2026, 2, 28));
+    assert_eq!(report.previous_period.total, Money::from(300));
+    assert_eq!(report.last_year.total, Money::from(400));
+
+    let rows: Vec<_> = report
+        .categories
+        .iter()
+        .map(|c| {
+            (
+                c.name.as_str(),
+                c.amount,
+                c.share,
+                c.change_from_previous,
+                c.change_from_last_year,
+            )
+        })
+        .collect();
+    assert_eq!(
+        rows,
+        vec![
+            (
+                "Rent",
+                Money::from(700),
+                Some(Decimal::from(70)),
+                None,
+                None
+            ),
+            (
+                "Food",
+                Money::from(300),
+                Some(Decimal::from(30)),
+                Some(Decimal::from(50)),
+                Some(Decimal::from(-25))
+            ),
+            (
+                "Fun",
+                Money::ZERO,
+                Some(Decimal::ZERO),
+                Some(Decimal::from(-100)),
+                None
+            ),
+        ]
+    );
+    assert_eq!(report.categories[2].category_id, fun);
+    assert_eq!(report.categories[2].previous_amount, Money::from(100));
+
+    let response = client
+        .get("/reports/categories?from=2026-03-01&to=2026-03-31&currency=GBP")
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::UnprocessableEntity);
+
+    cleanup(&pool, &[user_id]).await;
+}
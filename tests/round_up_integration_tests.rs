@@ -0,0 +1,226 @@
+use chrono::Utc;
+use personal_finance_tracker::dtos::account_dtos::AccountInDTO;
+use personal_finance_tracker::dtos::category_dtos::CategoryInDTO;
+use personal_finance_tracker::dtos::round_up_dtos::RoundUpSummaryOutDTO;
+use personal_finance_tracker::dtos::saving_goal_dtos::SavingGoalInDTO;
+use personal_finance_tracker::dtos::transaction_dtos::TransactionInDTO;
+use personal_finance_tracker::dtos::user_dtos::UserInDTO;
+use personal_finance_tracker::enums::custom_enums::{AccountType, TransactionType};
+use personal_finance_tracker::money::Money;
+use personal_finance_tracker::operations::account_ops::create_account;
+use personal_finance_tracker::operations::category_ops::create_category;
+use personal_finance_tracker::operations::saving_goal_ops::{
+    create_saving_goal, find_saving_goal_by_id,
+};
+use personal_finance_tracker::operations::transaction_ops::{
+    create_transaction, delete_transaction, update_transaction,
+};
+use personal_finance_tracker::operations::user_ops::create_user;
+use personal_finance_tracker::rules::round_up::round_up_difference;
+use rocket::http::{ContentType, Status};
+use rocket::serde::json::serde_json;
+use serde_json::json;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::common::{money, setup};
+
+mod common;
+
+pub struct Fixture {
+    user_id: Uuid,
+    account_id: Uuid,
+    category_id: Uuid,
+    goal_id: Uuid,
+}
+
+pub async fn before_test(
+    pool: &PgPool,
+    username: &str,
+    email: &str,
+) -> Result<Fixture, sqlx::Error> {
+    let user = create_user(
+        pool,
+        &UserInDTO {
+            username: username.to_string(),
+            email: email.to_string(),
+        },
+    )
+    .await?;
+    let account = create_account(
+        pool,
+        user.id,
+        &AccountInDTO {
+            name: "Card".to_string(),
+            balance: Money::from(100),
+            account_type: AccountType::Card,
+            currency: None,
+        },
+    )
+    .await?;
+    let category = create_category(
+        pool,
+        user.id,
+        &CategoryInDTO {
+            name: "Coffee".to_string(),
+        },
+    )
+    .await?;
+    let goal = create_saving_goal(
+        pool,
+        user.id,
+        &SavingGoalInDTO {
+            title: "Round-ups".to_string(),
+            target_amount: Money::from(500),
+            current_amount: Money::ZERO,
+            deadline: chrono::NaiveDate::from_ymd_opt(2030, 12, 31).expect("Valid date"),
+        },
+    )
+    .await?;
+
+    Ok(Fixture {
+        user_id: user.id,
+        account_id: account.id,
+        category_id: category.id,
+        goal_id: goal.id,
+    })
+}
+
+pub async fn cleanup(pool: &PgPool, user_id: Uuid) {
+    sqlx::query("DELETE FROM users WHERE id = $1")
+        .bind(user_id)
+        .execute(pool)
+        .await
+        .unwrap_or_else(|e| panic!("Failed to cleanup test users: {}", e));
+}
+
+fn expense(fixture: &Fixture, amount: Money) -> TransactionInDTO {
+    TransactionInDTO {
+        title: "Coffee".to_string(),
+        amount,
+        date: Utc::now(),
+        category_id: fixture.category_id,
+        transaction_type: TransactionType::Expense,
+        account_id: fixture.account_id,
+        currency: None,
+        exchange_rate: None,
+    }
+}
+
+async fn goal_amount(pool: &PgPool, user_id: Uuid, goal_id: Uuid) -> Money {
+    find_saving_goal_by_id(pool, user_id, goal_id)
+        .await
+        .expect("Failed to fetch saving goal")
+        .expect("Saving goal exists")
+        .current_amount
+}
+
+#[test]
+fn round_up_difference_test() {
+    assert_eq!(round_up_difference(money("12.30"), 1), money("0.70"));
+    assert_eq!(round_up_difference(money("12.30"), 5), money("2.70"));
+    assert_eq!(round_up_difference(money("3.99"), 10), money("6.01"));
+    assert_eq!(round_up_difference(Money::from(20), 10), Money::ZERO);
+}
+
+#[rocket::async_test]
+async fn round_up_follows_transaction_lifecycle_integration_test() {
+    let (client, pool) = setup().await;
+
+    let fixture = before_test(&pool, "roundupuser", "roundupuser@example.com")
+        .await
+        .expect("Failed to initialize test database");
+    common::login(&client, &pool, fixture.user_id).await;
+
+    let response = client
+        .post("/round_up_rules")
+        .header(ContentType::JSON)
+        .body(
+            json!({
+                "account_id": fixture.account_id,
+                "goal_id": fixture.goal_id,
+                "round_to": 1,
+            })
+            .to_string(),
+        )
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    let first = create_transaction(&pool, fixture.user_id, &expense(&fixture, money("12.30")))
+        .await
+        .expect("Failed to create transaction");
+    assert_eq!(
+        goal_amount(&pool, fixture.user_id, fixture.goal_id).await,
+        money("0.70"),
+    );
+
+    update_transaction(
+        &pool,
+        fixture.user_id,
+        first.id,
+        &expense(&fixture, money("12.80")),
+    )
+    .await
+    .expect("Failed to update transaction");
+    assert_eq!(
+        goal_amount(&pool, fixture.user_id, fixture.goal_id).await,
+        money("0.20"),
+    );
+
+    create_transaction(&pool, fixture.user_id, &expense(&fixture, money("4.50")))
+        .await
+        .expect("Failed to create transaction");
+    assert_eq!(
+        goal_amount(&pool, fixture.user_id, fixture.goal_id).await,
+        money("0.70"),
+    );
+
+    delete_transaction(&pool, fixture.user_id, first.id)
+        .await
+        .expect("Failed to delete transaction");
+    assert_eq!(
+        goal_amount(&pool, fixture.user_id, fixture.goal_id).await,
+        money("0.50"),
+    );
+
+    let response = client.get("/round_ups/summary").dispatch().await;
+    assert_eq!(response.status(), Status::Ok);
+    let summary: Vec<RoundUpSummaryOutDTO> =
+        serde_json::from_str(&response.into_string().await.expect("Response has a body"))
+            .expect("Valid RoundUpSummaryOutDTO list");
+
+    assert_eq!(summary.len(), 1);
+    assert_eq!(summary[0].goal_id, fixture.goal_id);
+    assert_eq!(summary[0].total, money("0.50"));
+
+    cleanup(&pool, fixture.user_id).await;
+}
+
+#[rocket::async_test]
+async fn round_up_rule_rejects_unknown_increment_integration_test() {
+    let (client, pool) = setup().await;
+
+    let fixture = before_test(&pool, "roundupbad", "roundupbad@example.com")
+        .await
+        .expect("Failed to initialize test database");
+    common::login(&client, &pool, fixture.user_id).await;
+
+    let response = client
+        .post("/round_up_rules")
+        .header(ContentType::JSON)
+        .body(
+            json!({
+                "account_id": fixture.account_id,
+                "goal_id": fixture.goal_id,
+                "round_to": 3,
+            })
+            .to_string(),
+        )
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::BadRequest);
+
+    cleanup(&pool, fixture.user_id).await;
+}
@@ -1,5 +1,6 @@
 use chrono::{Local, NaiveDate};
 use personal_finance_tracker::dtos::user_dtos::UserInDTO;
+use personal_finance_tracker::money::Money;
 use personal_finance_tracker::operations::saving_goal_ops::create_saving_goal;
 use personal_finance_tracker::operations::user_ops::create_user;
 use rocket::http::{ContentType, Status};
@@ -20,7 +21,7 @@ pub async fn before_test(pool: &PgPool, username: &str, email: &str) -> Result<U
         username: username.to_string(),
         email: email.to_string(),
     };
-    let user = create_user(&pool, &user_dto).await?;
+    let user = create_user(pool, &user_dto).await?;
 
     Ok(user.id)
 }
@@ -50,13 +51,13 @@ async fn create_saving_goal_integration_test() {
     let user_id = before_test(&pool, "createuser", "createuser@example.com")
         .await
         .expect("Failed to initialize test database");
+    common::login(&client, &pool, user_id).await;
 
     let saving_goal_data = json!({
         "title": "Vacation",
         "target_amount": 2000.0,
         "current_amount": 100.0,
         "deadline": NaiveDate::from_ymd_opt(2024, 12, 31),
-        "user_id": user_id,
     });
 
     let response = client
@@ -90,13 +91,12 @@ async fn create_saving_goal_integration_test_type_two() {
 
     let saving_goal_dto = SavingGoalInDTO {
         title: "Wedding".to_string(),
-        target_amount: 1000.0,
-        current_amount: 100.0,
+        target_amount: Money::from(1000),
+        current_amount: Money::from(100),
         deadline,
-        user_id: user_id,
     };
 
-    let response = create_saving_goal(&pool, &saving_goal_dto).await;
+    let response = create_saving_goal(&pool, user_id, &saving_goal_dto).await;
 
     assert!(response.is_ok());
 
@@ -110,11 +110,23 @@ async fn create_saving_goal_integration_test_type_two() {
 
 #[rocket::async_test]
 async fn get_all_saving_goals_integration_test() {
-    let (client, _pool) = setup().await;
+    let (client, pool) = setup().await;
+
+    let user_id = before_test(&pool, "listuser", "listuser@example.com")
+        .await
+        .expect("Failed to initialize test database");
+    common::login(&client, &pool, user_id).await;
 
     let response = client.get("/saving_goals").dispatch().await;
 
     assert_eq!(response.status(), Status::Ok);
+
+    let saving_goals: Vec<SavingGoalOutDTO> =
+        serde_json::from_str(&response.into_string().await.expect("Response has a body"))
+            .expect("Valid list of SavingGoalOutDTO");
+    assert!(saving_goals.is_empty());
+
+    cleanup(&pool, user_id, None).await;
 }
 
 #[rocket::async_test]
@@ -125,19 +137,20 @@ async fn get_saving_goal_by_id_integration_test() {
         .await
         .expect("Failed to initialize test database");
 
+    common::login(&client, &pool, user_id).await;
+
     let deadline_option = chrono::NaiveDate::from_ymd_opt(2024, 12, 31);
 
     let deadline = deadline_option.expect("Invalid date");
 
     let saving_goal_dto = SavingGoalInDTO {
         title: "Wedding".to_string(),
-        target_amount: 1000.0,
-        current_amount: 100.0,
-        deadline: deadline,
-        user_id: user_id,
+        target_amount: Money::from(1000),
+        current_amount: Money::from(100),
+        deadline,
     };
 
-    let response = create_saving_goal(&pool, &saving_goal_dto).await;
+    let response = create_saving_goal(&pool, user_id, &saving_goal_dto).await;
     let saving_goal = response.unwrap();
 
     let saving_goal_id = saving_goal.id;
@@ -164,13 +177,13 @@ async fn update_saving_goal_integration_test() {
     let user_id = before_test(&pool, "updateuser", "updateuser@example.com")
         .await
         .expect("Failed to initialize test database");
+    common::login(&client, &pool, user_id).await;
 
     let saving_goal_data = json!({
         "title": "Vacation",
         "target_amount": 2000.0,
         "current_amount": 100.0,
         "deadline": NaiveDate::from_ymd_opt(2024, 12, 31),
-        "user_id": user_id,
     });
     let create_response = client
         .post("/saving_goals")
@@ -191,7 +204,6 @@ async fn update_saving_goal_integration_test() {
         "target_amount": 2000.0,
         "current_amount": 300.0,
         "deadline": NaiveDate::from_ymd_opt(2024, 12, 31),
-        "user_id": user_id,
         "updated_at": Local::now().naive_local()
     });
     let response = client
@@ -209,7 +221,7 @@ async fn update_saving_goal_integration_test() {
 
     assert_eq!(updated_saving_goal.title, "Vacation updated");
     assert_eq!(updated_saving_goal.user_id, user_id);
-    assert_eq!(updated_saving_goal.current_amount, 300.0);
+    assert_eq!(updated_saving_goal.current_amount, Money::from(300));
 
     cleanup(&pool, user_id, Some(updated_saving_goal.id)).await;
 }
@@ -222,19 +234,20 @@ async fn delete_saving_goal_integration_test() {
         .await
         .expect("Failed to initialize test database");
 
+    common::login(&client, &pool, user_id).await;
+
     let deadline_option = chrono::NaiveDate::from_ymd_opt(2024, 12, 31);
 
     let deadline = deadline_option.expect("Invalid date");
 
     let saving_goal_dto = SavingGoalInDTO {
         title: "Wedding".to_string(),
-        target_amount: 1000.0,
-        current_amount: 100.0,
-        deadline: deadline,
-        user_id: user_id,
+        target_amount: Money::from(1000),
+        current_amount: Money::from(100),
+        deadline,
     };
 
-    let response_saved = create_saving_goal(&pool, &saving_goal_dto).await;
+    let response_saved = create_saving_goal(&pool, user_id, &saving_goal_dto).await;
 
     let saving_goal = response_saved.unwrap();
 
@@ -0,0 +1,240 @@
+use chrono::{Months, NaiveDate, Utc};
+use personal_finance_tracker::dtos::account_dtos::AccountOutDTO;
+use personal_finance_tracker::dtos::category_dtos::CategoryInDTO;
+use personal_finance_tracker::dtos::recurring_transaction_dtos::RecurringTransactionOutDTO;
+use personal_finance_tracker::dtos::subscription_dtos::SubscriptionCandidateOutDTO;
+use personal_finance_tracker::dtos::user_dtos::UserInDTO;
+use personal_finance_tracker::enums::custom_enums::RecurrenceFrequency;
+use personal_finance_tracker::models::subscription::Charge;
+use personal_finance_tracker::operations::category_ops::create_category;
+use personal_finance_tracker::operations::user_ops::create_user;
+use personal_finance_tracker::subscriptions::{detect_subscriptions, payee_key};
+use rocket::http::{ContentType, Status};
+use rocket::serde::json::serde_json;
+use serde_json::json;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::common::{money, setup};
+
+mod common;
+
+pub async fn before_test(pool: &PgPool, username: &str) -> Result<(Uuid, Uuid), sqlx::Error> {
+    let user_dto = UserInDTO {
+        username: username.to_string(),
+        email: format!("{}@example.com", username),
+    };
+    let user = create_user(pool, &user_dto).await?;
+    let category = create_category(
+        pool,
+        user.id,
+        &CategoryInDTO {
+            name: "Entertainment".to_string(),
+        },
+    )
+    .await?;
+
+    Ok((user.id, category.id))
+}
+
+pub async fn cleanup(pool: &PgPool, user_id: Uuid) {
+    sqlx::query("DELETE FROM users WHERE id = $1")
+        .bind(user_id)
+        .execute(pool)
+        .await
+        .unwrap_or_else(|e| panic!("Failed to cleanup test users: {}", e));
+}
+
+fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).expect("Valid date")
+}
+
+fn charge(account_id: Uuid, title: &str, day: NaiveDate, amount: &str) -> Charge {
+    Charge {
+        transaction_id: Uuid::new_v4(),
+        account_id,
+        category_id: Uuid::nil(),
+        title: title.to_string(),
+        day,
+        amount: money(amount),
+        currency: "USD".to_string(),
+    }
+}
+
+#[test]
+fn payee_key_test() {
+    assert_eq!(payee_key("NETFLIX.COM 866-579-7172"), "netflix com");
+    assert_eq!(payee_key("  Spotify P0A1B2C3 "), "spotify");
+    assert_eq!(payee_key("12345"), "");
+}
+
+#[test]
+fn detect_subscriptions_test() {
+    let account = Uuid::new_v4();
+    let mut charges = vec![
+        charge(account, "Netflix 0131", date(2026, 1, 31), "15.49"),
+        charge(account, "Netflix 0228", date(2026, 2, 28), "15.49"),
+        charge(account, "Netflix 0331", date(2026, 3, 31), "17.99"),
+        // Every other week.
+        charge(account, "Gym", date(2026, 3, 2), "20"),
+        charge(account, "Gym", date(2026, 3, 16), "20"),
+        charge(account, "Gym", date(2026, 3, 30), "20"),
+        // Regular, but the amounts are all over the place.
+        charge(account, "Grocer", date(2026, 1, 15), "40"),
+        charge(account, "Grocer", date(2026, 2, 15), "120"),
+        charge(account, "Grocer", date(2026, 3, 15), "75"),
+        // Stopped after February.
+        charge(account, "Magazine", date(2025, 12, 5), "5"),
+        charge(account, "Magazine", date(2026, 1, 5), "5"),
+        charge(account, "Magazine", date(2026, 2, 5), "5"),
+        // Irregular.
+        charge(account, "Cafe", date(2026, 1, 3), "4"),
+        charge(account, "Cafe", date(2026, 1, 9), "4"),
+        charge(account, "Cafe", date(2026, 3, 20), "4"),
+        // Yearly.
+        charge(account, "Domain renewal", date(2025, 4, 10), "12"),
+        charge(account, "Domain renewal", date(2026, 4, 8), "12"),
+    ];
+    charges.sort_by_key(|charge| charge.day);
+
+    let candidates = detect_subscriptions(&charges, date(2026, 4, 10));
+    let found: Vec<_> = candidates
+        .iter()
+        .map(|c| {
+            (
+                c.payee.as_str(),
+                c.frequency,
+                c.every,
+                c.next_expected_charge,
+                c.annualized_cost,
+            )
+        })
+        .collect();
+    assert_eq!(
+        found,
+        vec![
+            (
+                "gym",
+                RecurrenceFrequency::Weekly,
+                2,
+                date(2026, 4, 13),
+                money("520")
+            ),
+            (
+                "netflix",
+                RecurrenceFrequency::Monthly,
+                1,
+                date(2026, 4, 30),
+                money("215.88")
+            ),
+            (
+                "domain renewal",
+                RecurrenceFrequency::Yearly,
+                1,
+                date(2027, 4, 8),
+                money("12")
+            ),
+        ]
+    );
+    assert_eq!(candidates[1].name, "Netflix 0331");
+    assert_eq!(candidates[1].amount, money("17.99"));
+    assert_eq!(candidates[1].charge_count, 3);
+    assert_eq!(candidates[1].first_charge, date(2026, 1, 31));
+}
+
+#[rocket::async_test]
+async fn subscription_integration_test() {
+    let (client, pool) = setup().await;
+
+    let (user_id, category_id) = before_test(&pool, "subscriptionuser")
+        .await
+        .expect("Failed to initialize test database");
+    common::login(&client, &pool, user_id).await;
+
+    let response = client
+        .post("/accounts")
+        .header(ContentType::JSON)
+        .body(json!({ "name": "Card", "balance": "500", "account_type": "Card" }).to_string())
+        .dispatch()
+        .await;
+    let card: AccountOutDTO =
+        serde_json::from_str(&response.into_string().await.expect("Response has a body"))
+            .expect("Valid AccountOutDTO");
+
+    let now = Utc::now();
+    for months_ago in 0..4 {
+        let date = now
+            .checked_sub_months(Months::new(months_ago))
+            .expect("Valid date");
+        for (title, amount) in [("Streamly", "9.99"), ("Fitness club", "30")] {
+            let response = client
+                .post("/transactions")
+                .header(ContentType::JSON)
+                .body(
+                    json!({
+                        "title": title,
+                        "amount": amount,
+                        "date": date,
+                        "category_id": category_id,
+                        "transaction_type": "Expense",
+                        "account_id": card.id,
+                    })
+                    .to_string(),
+                )
+                .dispatch()
+                .await;
+            assert_eq!(response.status(), Status::Ok);
+        }
+    }
+
+    let response = client.get("/subscriptions/candidates").dispatch().await;
+    assert_eq!(response.status(), Status::Ok);
+    let candidates: Vec<SubscriptionCandidateOutDTO> =
+        serde_json::from_str(&response.into_string().await.expect("Response has a body"))
+            .expect("Valid SubscriptionCandidateOutDTO list");
+    let payees: Vec<_> = candidates.iter().map(|c| c.payee.as_str()).collect();
+    assert_eq!(payees, vec!["fitness club", "streamly"]);
+    assert_eq!(candidates[1].annualized_cost, money("119.88"));
+    assert_eq!(candidates[1].last_charge, now.date_naive());
+
+    let response = client
+        .post("/subscriptions/candidates/confirm")
+        .header(ContentType::JSON)
+        .body(json!({ "account_id": card.id, "payee": "streamly" }).to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let recurring: RecurringTransactionOutDTO =
+        serde_json::from_str(&response.into_string().await.expect("Response has a body"))
+            .expect("Valid RecurringTransactionOutDTO");
+    assert_eq!(recurring.title, "Streamly");
+    assert_eq!(recurring.amount, money("9.99"));
+    assert_eq!(recurring.frequency, RecurrenceFrequency::Monthly);
+    assert_eq!(recurring.start_date, now.date_naive());
+
+    let response = client
+        .post("/subscriptions/candidates/dismiss")
+        .header(ContentType::JSON)
+        .body(json!({ "account_id": card.id, "payee": "fitness club" }).to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::NoContent);
+
+    let response = client.get("/subscriptions/candidates").dispatch().await;
+    let candidates: Vec<SubscriptionCandidateOutDTO> =
+        serde_json::from_str(&response.into_string().await.expect("Response has a body"))
+            .expect("Valid SubscriptionCandidateOutDTO list");
+    assert!(candidates.is_empty());
+
+    for payee in ["fitness club", "streamly"] {
+        let response = client
+            .post("/subscriptions/candidates/dismiss")
+            .header(ContentType::JSON)
+            .body(json!({ "account_id": card.id, "payee": payee }).to_string())
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::NotFound);
+    }
+
+    cleanup(&pool, user_id).await;
+}
@@ -1,11 +1,14 @@
-use chrono::Local;
+use chrono::Utc;
 use personal_finance_tracker::dtos::account_dtos::AccountInDTO;
 use personal_finance_tracker::dtos::category_dtos::CategoryInDTO;
 use personal_finance_tracker::dtos::transaction_dtos::{TransactionInDTO, TransactionOutDTO};
 use personal_finance_tracker::enums::custom_enums::{AccountType, TransactionType};
+use personal_finance_tracker::money::Money;
 use personal_finance_tracker::operations::account_ops::create_account;
 use personal_finance_tracker::operations::category_ops::create_category;
-use personal_finance_tracker::operations::transaction_ops::create_transaction;
+use personal_finance_tracker::operations::transaction_ops::{
+    create_transaction, find_transaction_by_id,
+};
 use personal_finance_tracker::operations::user_ops::create_user;
 use rocket::http::{ContentType, Status};
 use rocket::serde::json::serde_json;
@@ -29,23 +32,22 @@ pub async fn before_test(
         username: username.to_string(),
         email: email.to_string(),
     };
-    let user = create_user(&pool, &user_dto).await?;
+    let user = create_user(pool, &user_dto).await?;
 
     // Create an account
     let account_dto = AccountInDTO {
         name: "Test".to_string(),
-        balance: 100.0,
+        balance: Money::from(100),
         account_type: AccountType::Bank,
-        user_id: user.id,
+        currency: None,
     };
-    let account = create_account(&pool, &account_dto).await?;
+    let account = create_account(pool, user.id, &account_dto).await?;
 
     // Create a category
     let category_dto = CategoryInDTO {
         name: "TestCategory".to_string(),
-        user_id: user.id,
     };
-    let category = create_category(&pool, &category_dto).await?;
+    let category = create_category(pool, user.id, &category_dto).await?;
 
     Ok((user.id, account.id, category.id))
 }
@@ -87,15 +89,16 @@ async fn create_transaction_integration_test() {
 
     let transaction_dto = TransactionInDTO {
         title: "Test transaction".to_string(),
-        amount: 100.0,
+        amount: Money::from(100),
         transaction_type: TransactionType::Income,
-        user_id: user_id,
-        date: Local::now().naive_local(),
+        date: Utc::now(),
         category_id,
-        account_id: account_id,
+        account_id,
+        currency: None,
+        exchange_rate: None,
     };
 
-    let transaction = create_transaction(&pool, &transaction_dto)
+    let transaction = create_transaction(&pool, user_id, &transaction_dto)
         .await
         .expect("Failed to create transaction");
 
@@ -115,10 +118,23 @@ async fn create_transaction_integration_test() {
 
 #[rocket::async_test]
 async fn get_all_transactions_test() {
-    let (client, _pool) = setup().await;
+    let (client, pool) = setup().await;
+
+    let (user_id, account_id, category_id) = before_test(&pool, "listuser", "listuser@example.com")
+        .await
+        .expect("Failed to initialize test database");
+    common::login(&client, &pool, user_id).await;
+
     let response = client.get("/transactions").dispatch().await;
 
     assert_eq!(response.status(), Status::Ok);
+
+    let transactions: Vec<TransactionOutDTO> =
+        serde_json::from_str(&response.into_string().await.expect("Response has a body"))
+            .expect("Valid list of TransactionOutDTO");
+    assert!(transactions.is_empty());
+
+    cleanup(&pool, user_id, account_id, category_id, None).await;
 }
 
 #[rocket::async_test]
@@ -127,17 +143,20 @@ async fn get_transaction_by_id_integration_test() {
 
     match before_test(&pool, "testuserid", "testuserid@example.com").await {
         Ok((user_id, account_id, category_id)) => {
+            common::login(&client, &pool, user_id).await;
+
             let transaction_dto = TransactionInDTO {
                 title: "Test transaction".to_string(),
-                amount: 100.0,
+                amount: Money::from(100),
                 transaction_type: TransactionType::Income,
-                user_id: user_id,
-                date: Local::now().naive_local(),
+                date: Utc::now(),
                 category_id,
-                account_id: account_id,
+                account_id,
+                currency: None,
+                exchange_rate: None,
             };
 
-            match create_transaction(&pool, &transaction_dto).await {
+            match create_transaction(&pool, user_id, &transaction_dto).await {
                 Ok(transaction) => {
                     assert_eq!(transaction.title, "Test transaction");
 
@@ -164,16 +183,16 @@ async fn get_transaction_by_id_integration_test() {
                                     founded_transaction.title.to_string()
                                 );
                             }
-                            Err(_) => assert!(false, "Failed to deserialize TransactionOutDTO"),
+                            Err(_) => panic!("Failed to deserialize TransactionOutDTO"),
                         }
                     } else {
-                        assert!(false, "Response does not have a body");
+                        panic!("Response does not have a body");
                     }
                 }
-                Err(_) => assert!(false, "Failed to create transaction"),
+                Err(_) => panic!("Failed to create transaction"),
             }
         }
-        Err(_) => assert!(false, "Failed to initialize test database"),
+        Err(_) => panic!("Failed to initialize test database"),
     }
 }
 
@@ -185,13 +204,13 @@ async fn update_transaction_integration_test() {
         before_test(&pool, "updateuser", "updateuser@example.com")
             .await
             .expect("Failed to initialize test database");
+    common::login(&client, &pool, user_id).await;
 
     let transaction_data = json!({
         "title": "Test transaction".to_string(),
         "amount": 100.0,
         "transaction_type": TransactionType::Income,
-        "user_id": user_id,
-        "date": Local::now().naive_local(),
+        "date": Utc::now(),
         "category_id": category_id,
         "account_id": account_id,
     });
@@ -215,8 +234,7 @@ async fn update_transaction_integration_test() {
         "title": "Test transaction updated".to_string(),
         "amount": 150.0,
         "transaction_type": TransactionType::Income,
-        "user_id": user_id,
-        "date": Local::now().naive_local(),
+        "date": Utc::now(),
         "category_id": category_id,
         "account_id": account_id,
     });
@@ -235,7 +253,7 @@ async fn update_transaction_integration_test() {
         serde_json::from_str(&response_body).expect("Valid TransactionOutDTO");
 
     assert_eq!(updated_transaction.title, "Test transaction updated");
-    assert_eq!(updated_transaction.amount, 150.0);
+    assert_eq!(updated_transaction.amount, Money::from(150));
 
     cleanup(
         &pool,
@@ -255,17 +273,20 @@ async fn delete_transaction_integration_test() {
         .await
         .expect("Failed to initialize test database");
 
+    common::login(&client, &pool, user_id).await;
+
     let transaction_dto = TransactionInDTO {
         title: "Test transaction".to_string(),
-        amount: 100.0,
+        amount: Money::from(100),
         transaction_type: TransactionType::Income,
-        user_id: user_id,
-        date: Local::now().naive_local(),
+        date: Utc::now(),
         category_id,
-        account_id: account_id,
+        account_id,
+        currency: None,
+        exchange_rate: None,
     };
 
-    let transaction = create_transaction(&pool, &transaction_dto)
+    let transaction = create_transaction(&pool, user_id, &transaction_dto)
         .await
         .expect("Failed to create transaction");
     assert_eq!(transaction.title, "Test transaction");
@@ -284,3 +305,161 @@ async fn delete_transaction_integration_test() {
     assert_eq!(fetch_response.status(), Status::NotFound);
     cleanup(&pool, user_id, account_id, category_id, None).await;
 }
+
+#[rocket::async_test]
+async fn cross_user_transaction_access_integration_test() {
+    let (client, pool) = setup().await;
+
+    let (owner_id, owner_account_id, owner_category_id) =
+        before_test(&pool, "owneruser", "owneruser@example.com")
+            .await
+            .expect("Failed to initialize test database");
+    let (intruder_id, intruder_account_id, intruder_category_id) =
+        before_test(&pool, "intruderuser", "intruderuser@example.com")
+            .await
+            .expect("Failed to initialize test database");
+
+    let transaction_dto = TransactionInDTO {
+        title: "Rent".to_string(),
+        amount: Money::from(900),
+        transaction_type: TransactionType::Expense,
+        date: Utc::now(),
+        category_id: owner_category_id,
+        account_id: owner_account_id,
+        currency: None,
+        exchange_rate: None,
+    };
+    let transaction = create_transaction(&pool, owner_id, &transaction_dto)
+        .await
+        .expect("Failed to create transaction");
+
+    common::login(&client, &pool, intruder_id).await;
+
+    let fetch_response = client
+        .get(format!("/transactions/{}", transaction.id))
+        .dispatch()
+        .await;
+    assert_eq!(fetch_response.status(), Status::NotFound);
+
+    let update_response = client
+        .patch(format!("/transactions/{}", transaction.id))
+        .header(ContentType::JSON)
+        .body(
+            json!({
+                "title": "Hijacked",
+                "amount": 1.0,
+                "transaction_type": TransactionType::Expense,
+                "date": Utc::now(),
+                "category_id": intruder_category_id,
+                "account_id": intruder_account_id,
+            })
+            .to_string(),
+        )
+        .dispatch()
+        .await;
+    assert_eq!(update_response.status(), Status::NotFound);
+
+    let delete_response = client
+        .delete(format!("/transactions/{}", transaction.id))
+        .dispatch()
+        .await;
+    assert_eq!(delete_response.status(), Status::NotFound);
+
+    let list_response = client.get("/transactions").dispatch().await;
+    let transactions: Vec<TransactionOutDTO> = serde_json::from_str(
+        &list_response
+            .into_string()
+            .await
+            .expect("Response has a body"),
+    )
+    .expect("Valid list of TransactionOutDTO");
+    assert!(transactions.is_empty());
+
+    let create_response = client
+        .post("/transactions")
+        .header(ContentType::JSON)
+        .body(
+            json!({
+                "title": "Someone else's account",
+                "amount": 10.0,
+                "transaction_type": TransactionType::Expense,
+                "date": Utc::now(),
+                "category_id": intruder_category_id,
+                "account_id": owner_account_id,
+            })
+            .to_string(),
+        )
+        .dispatch()
+        .await;
+    assert_eq!(create_response.status(), Status::BadRequest);
+
+    let untouched = find_transaction_by_id(&pool, owner_id, transaction.id)
+        .await
+        .expect("Failed to fetch transaction")
+        .expect("Transaction still exists");
+    assert_eq!(untouched.title, "Rent");
+
+    cleanup(
+        &pool,
+        owner_id,
+        owner_account_id,
+        owner_category_id,
+        Some(transaction.id),
+    )
+    .await;
+    cleanup(
+        &pool,
+        intruder_id,
+        intruder_account_id,
+        intruder_category_id,
+        None,
+    )
+    .await;
+}
+
+#[rocket::async_test]
+async fn transaction_date_offset_integration_test() {
+    let (client, pool) = setup().await;
+
+    let (user_id, account_id, category_id) =
+        before_test(&pool, "offsetuser", "offsetuser@example.com")
+            .await
+            .expect("Failed to initialize test database");
+    common::login(&client, &pool, user_id).await;
+
+    let transaction_data = |date: &str| {
+        json!({
+            "title": "Late dinner",
+            "amount": 40.0,
+            "transaction_type": TransactionType::Expense,
+            "date": date,
+            "category_id": category_id,
+            "account_id": account_id,
+        })
+        .to_string()
+    };
+
+    // Offsets are accepted and the instant is stored in UTC.
+    let response = client
+        .post("/transactions")
+        .header(ContentType::JSON)
+        .body(transaction_data("2026-01-31T23:30:00+01:00"))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let created: TransactionOutDTO =
+        serde_json::from_str(&response.into_string().await.expect("Response has a body"))
+            .expect("Valid TransactionOutDTO");
+    assert_eq!(created.date.to_rfc3339(), "2026-01-31T22:30:00+00:00");
+
+    // A date without an offset is ambiguous.
+    let response = client
+        .post("/transactions")
+        .header(ContentType::JSON)
+        .body(transaction_data("2026-01-31T23:30:00"))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::UnprocessableEntity);
+
+    cleanup(&pool, user_id, account_id, category_id, Some(created.id)).await;
+}
@@ -0,0 +1,276 @@
+use chrono::{Duration, Utc};
+use personal_finance_tracker::auth::totp::{base32_decode, base32_encode, hotp, time_step};
+use personal_finance_tracker::auth::SESSION_COOKIE;
+use personal_finance_tracker::dtos::two_factor_dtos::{
+    RecoveryCodesOutDTO, TotpEnrollmentOutDTO, TwoFactorStatusOutDTO,
+};
+use personal_finance_tracker::dtos::user_dtos::UserInDTO;
+use personal_finance_tracker::operations::session_ops::create_session;
+use personal_finance_tracker::operations::user_ops::create_user;
+use rocket::http::{ContentType, Cookie, Status};
+use rocket::local::asynchronous::Client;
+use rocket::serde::json::serde_json;
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::common::{setup, TEST_PASSWORD};
+
+mod common;
+
+pub async fn before_test(pool: &PgPool, username: &str, email: &str) -> Result<Uuid, sqlx::Error> {
+    let user_dto = UserInDTO {
+        username: username.to_string(),
+        email: email.to_string(),
+    };
+    let user = create_user(pool, &user_dto).await?;
+
+    Ok(user.id)
+}
+
+pub async fn cleanup(pool: &PgPool, user_id: Uuid) {
+    sqlx::query("DELETE FROM users WHERE id = $1")
+        .bind(user_id)
+        .execute(pool)
+        .await
+        .unwrap_or_else(|e| panic!("Failed to cleanup test users: {}", e));
+}
+
+fn code_for_step(secret: &str, step: i64) -> String {
+    let secret = base32_decode(secret).expect("Valid base32 secret");
+    format!("{:06}", hotp(&secret, step as u64))
+}
+
+async fn login(client: &Client, email: &str, second_factor: Value) -> Status {
+    let mut body = json!({ "email": email, "password": TEST_PASSWORD });
+    if let (Some(body), Some(second_factor)) = (body.as_object_mut(), second_factor.as_object()) {
+        body.extend(second_factor.clone());
+    }
+    client
+        .post("/auth/login")
+        .header(ContentType::JSON)
+        .body(body.to_string())
+        .dispatch()
+        .await
+        .status()
+}
+
+async fn logout(client: &Client) {
+    let response = client.post("/auth/logout").dispatch().await;
+    assert_eq!(response.status(), Status::NoContent);
+}
+
+#[test]
+fn hotp_matches_rfc_6238_sha1_vectors() {
+    let secret = b"12345678901234567890";
+
+    assert_eq!(hotp(secret, time_step(59) as u64), 287082);
+    assert_eq!(hotp(secret, time_step(1111111109) as u64), 81804);
+    assert_eq!(hotp(secret, time_step(2000000000) as u64), 279037);
+}
+
+#[test]
+fn base32_round_trips() {
+    assert_eq!(
+        base32_encode(b"12345678901234567890"),
+        "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ"
+    );
+    assert_eq!(
+        base32_decode("GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ").as_deref(),
+        Some(&b"12345678901234567890"[..])
+    );
+    assert_eq!(base32_decode("not base32!"), None);
+}
+
+#[rocket::async_test]
+async fn totp_enrolment_and_login_integration_test() {
+    let (client, pool) = setup().await;
+    let email = "totpuser@example.com";
+
+    let user_id = before_test(&pool, "totpuser", email)
+        .await
+        .expect("Failed to initialize test database");
+    common::login(&client, &pool, user_id).await;
+    let other_session = create_session(&pool, user_id, Duration::hours(1), true)
+        .await
+        .expect("Failed to create session");
+
+    let enroll_response = client.post("/auth/totp/enroll").dispatch().await;
+    assert_eq!(enroll_response.status(), Status::Ok);
+    let enrollment: TotpEnrollmentOutDTO = serde_json::from_str(
+        &enroll_response
+            .into_string()
+            .await
+            .expect("Response has a body"),
+    )
+    .expect("Valid TotpEnrollmentOutDTO");
+    assert!(enrollment
+        .provisioning_uri
+        .starts_with("otpauth://totp/Personal%20Finance%20Tracker:totpuser@example.com?"));
+    assert!(enrollment
+        .provisioning_uri
+        .contains(&format!("secret={}", enrollment.secret)));
+
+    // Enrolment only takes effect once a correct code confirms it.
+    let wrong_code = client
+        .post("/auth/totp/confirm")
+        .header(ContentType::JSON)
+        .body(json!({ "code": "000000" }).to_string())
+        .dispatch()
+        .await;
+    assert_eq!(wrong_code.status(), Status::BadRequest);
+
+    let step = time_step(Utc::now().timestamp());
+    let confirm_response = client
+        .post("/auth/totp/confirm")
+        .header(ContentType::JSON)
+        .body(json!({ "code": code_for_step(&enrollment.secret, step) }).to_string())
+        .dispatch()
+        .await;
+    assert_eq!(confirm_response.status(), Status::Ok);
+    let recovery: RecoveryCodesOutDTO = serde_json::from_str(
+        &confirm_response
+            .into_string()
+            .await
+            .expect("Response has a body"),
+    )
+    .expect("Valid RecoveryCodesOutDTO");
+    assert_eq!(recovery.recovery_codes.len(), 10);
+
+    // The session that confirmed enrolment stays signed in; any other session
+    // needs the second factor again.
+    assert_eq!(client.get("/auth/me").dispatch().await.status(), Status::Ok);
+    let other = client
+        .get("/transactions")
+        .private_cookie(Cookie::new(SESSION_COOKIE, other_session.id.to_string()))
+        .dispatch()
+        .await;
+    assert_eq!(other.status(), Status::Unauthorized);
+    logout(&client).await;
+
+    assert_eq!(login(&client, email, json!({})).await, Status::Unauthorized);
+    // A code is only good once.
+    assert_eq!(
+        login(
+            &client,
+            email,
+            json!({ "totp_code": code_for_step(&enrollment.secret, step) })
+        )
+        .await,
+        Status::Unauthorized
+    );
+    assert_eq!(
+        login(
+            &client,
+            email,
+            json!({ "totp_code": code_for_step(&enrollment.secret, step + 1) })
+        )
+        .await,
+        Status::Ok
+    );
+    logout(&client).await;
+
+    let recovery_code = &recovery.recovery_codes[0];
+    assert_eq!(
+        login(&client, email, json!({ "recovery_code": recovery_code })).await,
+        Status::Ok
+    );
+    let status_response = client.get("/auth/totp").dispatch().await;
+    let status: TwoFactorStatusOutDTO = serde_json::from_str(
+        &status_response
+            .into_string()
+            .await
+            .expect("Response has a body"),
+    )
+    .expect("Valid TwoFactorStatusOutDTO");
+    assert!(status.enabled);
+    assert_eq!(status.recovery_codes_remaining, 9);
+    logout(&client).await;
+
+    assert_eq!(
+        login(&client, email, json!({ "recovery_code": recovery_code })).await,
+        Status::Unauthorized
+    );
+
+    // Disabling needs a second factor too; afterwards the password is enough.
+    assert_eq!(
+        login(
+            &client,
+            email,
+            json!({ "recovery_code": recovery.recovery_codes[1] })
+        )
+        .await,
+        Status::Ok
+    );
+    let disable_response = client
+        .post("/auth/totp/disable")
+        .header(ContentType::JSON)
+        .body(json!({ "recovery_code": recovery.recovery_codes[2] }).to_string())
+        .dispatch()
+        .await;
+    assert_eq!(disable_response.status(), Status::NoContent);
+    logout(&client).await;
+    assert_eq!(login(&client, email, json!({})).await, Status::Ok);
+
+    cleanup(&pool, user_id).await;
+}
+
+#[rocket::async_test]
+async fn guard_rejects_session_without_second_factor_integration_test() {
+    let (client, pool) = setup().await;
+
+    let user_id = before_test(&pool, "totppending", "totppending@example.com")
+        .await
+        .expect("Failed to initialize test database");
+    common::login(&client, &pool, user_id).await;
+
+    let enroll_response = client.post("/auth/totp/enroll").dispatch().await;
+    let enrollment: TotpEnrollmentOutDTO = serde_json::from_str(
+        &enroll_response
+            .into_string()
+            .await
+            .expect("Response has a body"),
+    )
+    .expect("Valid TotpEnrollmentOutDTO");
+    let step = time_step(Utc::now().timestamp());
+    let confirm_response = client
+        .post("/auth/totp/confirm")
+        .header(ContentType::JSON)
+        .body(json!({ "code": code_for_step(&enrollment.secret, step) }).to_string())
+        .dispatch()
+        .await;
+    assert_eq!(confirm_response.status(), Status::Ok);
+    logout(&client).await;
+
+    // A session opened without the second factor, as an identity provider
+    // login would, is refused until the code is supplied.
+    let session = create_session(&pool, user_id, Duration::hours(1), false)
+        .await
+        .expect("Failed to create session");
+    let session_cookie = || Cookie::new(SESSION_COOKIE, session.id.to_string());
+
+    let pending = client
+        .get("/transactions")
+        .private_cookie(session_cookie())
+        .dispatch()
+        .await;
+    assert_eq!(pending.status(), Status::Unauthorized);
+
+    let verify_response = client
+        .post("/auth/totp/verify")
+        .private_cookie(session_cookie())
+        .header(ContentType::JSON)
+        .body(json!({ "totp_code": code_for_step(&enrollment.secret, step + 1) }).to_string())
+        .dispatch()
+        .await;
+    assert_eq!(verify_response.status(), Status::Ok);
+
+    let verified = client
+        .get("/transactions")
+        .private_cookie(session_cookie())
+        .dispatch()
+        .await;
+    assert_eq!(verified.status(), Status::Ok);
+
+    cleanup(&pool, user_id).await;
+}
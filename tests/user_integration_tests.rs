@@ -7,9 +7,26 @@ use personal_finance_tracker::dtos::user_dtos::{UserInDTO, UserOutDTO};
 use sqlx::PgPool;
 
 use crate::common::setup;
+use rocket::local::asynchronous::Client;
+use uuid::Uuid;
 
 mod common;
 
+// Creates an admin and logs the client in as them.
+async fn login_as_admin(client: &Client, pool: &PgPool, username: &str) -> Uuid {
+    let admin = create_user(
+        pool,
+        &UserInDTO {
+            username: username.to_string(),
+            email: format!("{}@example.com", username),
+        },
+    )
+    .await
+    .expect("Failed to create admin");
+    common::login_admin(client, pool, admin.id).await;
+    admin.id
+}
+
 async fn cleanup_test_user(pool: &PgPool, username: &str, email: &str) {
     sqlx::query("DELETE FROM users WHERE username = $1 AND email = $2")
         .bind(username)
@@ -22,6 +39,7 @@ async fn cleanup_test_user(pool: &PgPool, username: &str, email: &str) {
 #[rocket::async_test]
 async fn create_user_integration_test() {
     let (client, pool) = setup().await;
+    login_as_admin(&client, &pool, "usercreateadmin").await;
 
     let user_data = json!({
         "username": "test_username",
@@ -36,6 +54,7 @@ async fn create_user_integration_test() {
         .await;
 
     cleanup_test_user(&pool, "test_username", "test_email@example.com").await;
+    cleanup_test_user(&pool, "usercreateadmin", "usercreateadmin@example.com").await;
 
     assert_eq!(response.status(), Status::Ok);
 
@@ -77,6 +96,13 @@ async fn get_all_users_integration_test() {
 
     let _user_response = create_user(&pool, &user_dto).await;
 
+    // Listing every user is reserved for admins.
+    assert_eq!(
+        client.get("/users").dispatch().await.status(),
+        Status::Unauthorized
+    );
+    login_as_admin(&client, &pool, "usergetalladmin").await;
+
     let response = client.get("/users").dispatch().await;
 
     assert_eq!(response.status(), Status::Ok);
@@ -85,9 +111,10 @@ async fn get_all_users_integration_test() {
     let users: Vec<UserOutDTO> =
         serde_json::from_str(&response_body).expect("Valid list of UserOutDTO");
 
-    assert!(users.len() > 0);
+    assert!(!users.is_empty());
 
     cleanup_test_user(&pool, "testusergetall", "testusergetall@example.com").await;
+    cleanup_test_user(&pool, "usergetalladmin", "usergetalladmin@example.com").await;
 }
 
 #[rocket::async_test]
@@ -103,6 +130,21 @@ async fn get_user_by_id_integration_test() {
     let user = response.unwrap();
 
     let user_id = user.id;
+    let other = create_user(
+        &pool,
+        &UserInDTO {
+            username: "testuseridother".to_string(),
+            email: "testuseridother@example.com".to_string(),
+        },
+    )
+    .await
+    .expect("Failed to create user");
+    common::login(&client, &pool, user_id).await;
+
+    // Regular users only see their own profile.
+    let other_response = client.get(format!("/users/{}", other.id)).dispatch().await;
+    assert_eq!(other_response.status(), Status::NotFound);
+    cleanup_test_user(&pool, "testuseridother", "testuseridother@example.com").await;
 
     let response = client.get(format!("/users/{}", user_id)).dispatch().await;
 
@@ -120,23 +162,16 @@ async fn get_user_by_id_integration_test() {
 async fn update_user_integration_test() {
     let (client, pool) = setup().await;
 
-    let user_data = json!({
-        "username": "update_test_username",
-        "email": "update_test_email@example.com"
-    });
-    let create_response = client
-        .post("/users")
-        .header(ContentType::JSON)
-        .body(user_data.to_string())
-        .dispatch()
-        .await;
-    let created_user: UserOutDTO = serde_json::from_str(
-        &create_response
-            .into_string()
-            .await
-            .expect("Response has a body"),
+    let created_user = create_user(
+        &pool,
+        &UserInDTO {
+            username: "update_test_username".to_string(),
+            email: "update_test_email@example.com".to_string(),
+        },
     )
-    .expect("Valid UserOutDTO");
+    .await
+    .expect("Failed to create user");
+    common::login(&client, &pool, created_user.id).await;
 
     let update_data = json!({
         "username": "updated_username",
@@ -192,6 +227,7 @@ async fn delete_user_integration_test() {
     let response_saved = create_user(&pool, &user_dto).await;
 
     let user = response_saved.unwrap();
+    login_as_admin(&client, &pool, "userdeleteadmin").await;
 
     let response = client
         .delete(format!("/users/{}", user.id))
@@ -202,4 +238,5 @@ async fn delete_user_integration_test() {
 
     let fetch_response = client.get(format!("/users/{}", user.id)).dispatch().await;
     assert_eq!(fetch_response.status(), Status::NotFound);
+    cleanup_test_user(&pool, "userdeleteadmin", "userdeleteadmin@example.com").await;
 }
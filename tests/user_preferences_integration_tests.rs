@@ -0,0 +1,149 @@
+use chrono::{NaiveDate, Weekday};
+use personal_finance_tracker::calendar::{fiscal_year_start, week_start};
+use personal_finance_tracker::dtos::user_dtos::UserInDTO;
+use personal_finance_tracker::dtos::user_preferences_dtos::UserPreferencesOutDTO;
+use personal_finance_tracker::enums::custom_enums::WeekDay;
+use personal_finance_tracker::models::user_preferences::UserPreferences;
+use personal_finance_tracker::operations::user_ops::create_user;
+use rocket::http::{ContentType, Status};
+use rocket::local::asynchronous::Client;
+use rocket::serde::json::serde_json;
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::common::{preferences, setup};
+
+mod common;
+
+pub async fn before_test(pool: &PgPool, username: &str) -> Result<Uuid, sqlx::Error> {
+    let user_dto = UserInDTO {
+        username: username.to_string(),
+        email: format!("{}@example.com", username),
+    };
+    let user = create_user(pool, &user_dto).await?;
+
+    Ok(user.id)
+}
+
+pub async fn cleanup(pool: &PgPool, user_id: Uuid) {
+    sqlx::query("DELETE FROM users WHERE id = $1")
+        .bind(user_id)
+        .execute(pool)
+        .await
+        .unwrap_or_else(|e| panic!("Failed to cleanup test users: {}", e));
+}
+
+async fn patch_preferences(client: &Client, body: Value) -> (Status, Option<String>) {
+    let response = client
+        .patch("/users/me/preferences")
+        .header(ContentType::JSON)
+        .body(body.to_string())
+        .dispatch()
+        .await;
+    (response.status(), response.into_string().await)
+}
+
+fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).expect("Valid date")
+}
+
+#[test]
+fn week_start_test() {
+    // 2024-01-10 is a Wednesday.
+    let wednesday = date(2024, 1, 10);
+
+    assert_eq!(week_start(wednesday, Weekday::Mon), date(2024, 1, 8));
+    assert_eq!(week_start(wednesday, Weekday::Sun), date(2024, 1, 7));
+    assert_eq!(week_start(wednesday, Weekday::Wed), wednesday);
+    assert_eq!(week_start(wednesday, Weekday::Thu), date(2024, 1, 4));
+}
+
+#[test]
+fn fiscal_year_start_test() {
+    assert_eq!(fiscal_year_start(date(2024, 5, 20), 1), date(2024, 1, 1));
+    assert_eq!(fiscal_year_start(date(2024, 5, 20), 4), date(2024, 4, 1));
+    assert_eq!(fiscal_year_start(date(2024, 3, 31), 4), date(2023, 4, 1));
+}
+
+#[test]
+fn preference_helpers_test() {
+    let preferences = UserPreferences {
+        week_start: WeekDay::Sunday,
+        fiscal_year_start_month: 7,
+        date_format: "%d.%m.%Y".to_string(),
+        ..preferences()
+    };
+    // 2024-01-10 is a Wednesday.
+    let wednesday = date(2024, 1, 10);
+
+    assert_eq!(preferences.first_day_of_week(), Weekday::Sun);
+    assert_eq!(preferences.week_start_of(wednesday), date(2024, 1, 7));
+    assert_eq!(
+        preferences.fiscal_year_start_of(wednesday),
+        date(2023, 7, 1)
+    );
+    assert_eq!(preferences.format_date(wednesday), "10.01.2024");
+}
+
+#[rocket::async_test]
+async fn user_preferences_integration_test() {
+    let (client, pool) = setup().await;
+    let user_id = before_test(&pool, "prefsuser")
+        .await
+        .expect("Failed to initialize test database");
+    common::login(&client, &pool, user_id).await;
+
+    let response = client.get("/users/me/preferences").dispatch().await;
+    assert_eq!(response.status(), Status::Ok);
+    let defaults: UserPreferencesOutDTO =
+        serde_json::from_str(&response.into_string().await.expect("Response has a body"))
+            .expect("Valid UserPreferencesOutDTO");
+    assert_eq!(defaults.base_currency, "USD");
+    assert_eq!(defaults.timezone, "UTC");
+    assert_eq!(defaults.week_start, WeekDay::Monday);
+    assert_eq!(defaults.fiscal_year_start_month, 1);
+
+    // Only the fields sent change; currency codes are normalized.
+    let (status, body) = patch_preferences(
+        &client,
+        json!({
+            "base_currency": "eur",
+            "timezone": "Europe/Berlin",
+            "week_start": "sunday",
+            "fiscal_year_start_month": 4
+        }),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    let updated: UserPreferencesOutDTO =
+        serde_json::from_str(&body.expect("Response has a body")).expect("Valid DTO");
+    assert_eq!(updated.base_currency, "EUR");
+    assert_eq!(updated.timezone, "Europe/Berlin");
+    assert_eq!(updated.week_start, WeekDay::Sunday);
+    assert_eq!(updated.fiscal_year_start_month, 4);
+    assert_eq!(updated.locale, defaults.locale);
+    assert_eq!(updated.date_format, defaults.date_format);
+    assert!(updated.updated_at.is_some());
+
+    for invalid in [
+        json!({ "base_currency": "EURO" }),
+        json!({ "locale": "english" }),
+        json!({ "timezone": "Mars/Olympus_Mons" }),
+        json!({ "fiscal_year_start_month": 13 }),
+        json!({ "date_format": "%Q" }),
+    ] {
+        assert_eq!(
+            patch_preferences(&client, invalid).await.0,
+            Status::BadRequest
+        );
+    }
+
+    let response = client.get("/users/me/preferences").dispatch().await;
+    let current: UserPreferencesOutDTO =
+        serde_json::from_str(&response.into_string().await.expect("Response has a body"))
+            .expect("Valid UserPreferencesOutDTO");
+    assert_eq!(current.timezone, "Europe/Berlin");
+
+    cleanup(&pool, user_id).await;
+}
@@ -0,0 +1,492 @@
+use chrono::{Datelike, NaiveDate, Utc};
+use personal_finance_tracker::dtos::account_dtos::{AccountInDTO, AccountOutDTO};
+use personal_finance_tracker::dtos::budget_dtos::BudgetInDTO;
+use personal_finance_tracker::dtos::category_dtos::CategoryInDTO;
+use personal_finance_tracker::dtos::household_dtos::HouseholdInDTO;
+use personal_finance_tracker::dtos::report_dtos::YearInReviewOutDTO;
+use personal_finance_tracker::dtos::saving_goal_dtos::SavingGoalOutDTO;
+use personal_finance_tracker::dtos::transaction_dtos::TransactionInDTO;
+use personal_finance_tracker::dtos::user_dtos::UserInDTO;
+use personal_finance_tracker::enums::custom_enums::{AccountType, HouseholdRole, TransactionType};
+use personal_finance_tracker::exchange_rates::RateTable;
+use personal_finance_tracker::models::report::{BudgetResult, DailyTotal};
+use personal_finance_tracker::models::subscription::Charge;
+use personal_finance_tracker::models::user_preferences::UserPreferences;
+use personal_finance_tracker::money::Money;
+use personal_finance_tracker::operations::account_ops::create_account;
+use personal_finance_tracker::operations::budget_ops::create_budget;
+use personal_finance_tracker::operations::category_ops::create_category;
+use personal_finance_tracker::operations::household_ops::{
+    add_household_member, create_household, delete_household, share_record,
+};
+use personal_finance_tracker::operations::ownership_ops::Owned;
+use personal_finance_tracker::operations::transaction_ops::create_transaction;
+use personal_finance_tracker::operations::user_ops::create_user;
+use personal_finance_tracker::reports::year_in_review::{render_html, year_in_review};
+use personal_finance_tracker::reports::PeriodTotals;
+use rocket::http::{ContentType, Status};
+use rocket::serde::json::serde_json;
+use rust_decimal::Decimal;
+use serde_json::json;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::common::{money, preferences, setup};
+
+mod common;
+
+pub async fn before_test(pool: &PgPool, username: &str) -> Result<(Uuid, Uuid), sqlx::Error> {
+    let user_dto = UserInDTO {
+        username: username.to_string(),
+        email: format!("{}@example.com", username),
+    };
+    let user = create_user(pool, &user_dto).await?;
+    let category = create_category(
+        pool,
+        user.id,
+        &CategoryInDTO {
+            name: "Groceries".to_string(),
+        },
+    )
+    .await?;
+
+    Ok((user.id, category.id))
+}
+
+pub async fn cleanup(pool: &PgPool, user_id: Uuid) {
+    sqlx::query("DELETE FROM users WHERE id = $1")
+        .bind(user_id)
+        .execute(pool)
+        .await
+        .unwrap_or_else(|e| panic!("Failed to cleanup test users: {}", e));
+}
+
+fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).expect("Valid date")
+}
+
+fn daily(day: NaiveDate, transaction_type: TransactionType, amount: i64) -> DailyTotal {
+    DailyTotal {
+        day,
+        transaction_type,
+        currency: "USD".to_string(),
+        group_id: None,
+        group_name: None,
+        amount: amount.into(),
+        transaction_count: 1,
+    }
+}
+
+#[test]
+fn year_in_review_test() {
+    let current = vec![
+        daily(date(2026, 1, 10), TransactionType::Income, 500),
+        daily(date(2026, 1, 20), TransactionType::Expense, 100),
+        daily(date(2026, 2, 2), TransactionType::Expense, 150),
+    ];
+    let previous = vec![daily(date(2025, 1, 3), TransactionType::Expense, 200)];
+    let charges = vec![Charge {
+        transaction_id: Uuid::new_v4(),
+        account_id: Uuid::nil(),
+        category_id: Uuid::nil(),
+        title: "<b>Fish & Chips</b>".to_string(),
+        day: date(2026, 2, 2),
+        amount: 150.into(),
+        currency: "USD".to_string(),
+    }];
+    let budgets = vec![BudgetResult {
+        budget_id: Uuid::new_v4(),
+        name: "January".to_string(),
+        amount: 120.into(),
+        spent: 100.into(),
+    }];
+
+    let review = year_in_review(
+        2026,
+        &preferences(),
+        PeriodTotals {
+            from: date(2026, 1, 1),
+            to: date(2026, 2, 28),
+            daily_totals: &current,
+        },
+        PeriodTotals {
+            from: date(2025, 1, 1),
+            to: date(2025, 2, 28),
+            daily_totals: &previous,
+        },
+        &charges,
+        &[],
+        &budgets,
+        "USD",
+        &RateTable::default(),
+        "USD",
+    )
+    .expect("Everything is in USD");
+
+    assert_eq!(review.net, 250.into());
+    assert_eq!(review.months.len(), 2);
+    assert_eq!(review.biggest_spending_month, Some(date(2026, 2, 1)));
+    assert_eq!(review.biggest_income_month, Some(date(2026, 1, 1)));
+    assert_eq!(review.top_payees[0].payee, "b fish chips b");
+    assert_eq!(review.top_payees[0].share, Some(Decimal::new(60, 0)));
+    assert_eq!(review.longest_no_spend_streak.days, 26);
+    assert_eq!(review.longest_no_spend_streak.from, Some(date(2026, 2, 3)));
+    assert_eq!(review.budgets.kept, 1);
+    assert_eq!(
+        review.year_over_year.expenses_change,
+        Some(Decimal::new(25, 0))
+    );
+    assert_eq!(review.year_over_year.net_change, Some(Decimal::new(225, 0)));
+
+    let british = UserPreferences {
+        locale: "en-GB".to_string(),
+        date_format: "%d/%m/%Y".to_string(),
+        ..preferences()
+    };
+    let html = render_html(&review, &british);
+    assert!(html.contains("<html lang=\"en-GB\">"));
+    assert!(html.contains("<p>01/01/2026 to 28/02/2026</p>"));
+    assert!(html.contains("<h1>2026 in review</h1>"));
+    assert!(html.contains("&lt;b&gt;Fish &amp; Chips&lt;/b&gt;"));
+    assert!(!html.contains("<b>Fish"));
+    assert!(html.contains("<td>January</td>"));
+
+    let german = UserPreferences {
+        locale: "de-AT".to_string(),
+        ..preferences()
+    };
+    let html = render_html(&review, &german);
+    assert!(html.contains("<td>Januar</td>"));
+    assert!(html.contains("Biggest spending month: Februar."));
+    let japanese = UserPreferences {
+        locale: "ja-JP".to_string(),
+        ..preferences()
+    };
+    assert!(render_html(&review, &japanese).contains("<td>February</td>"));
+}
+
+#[rocket::async_test]
+async fn year_in_review_integration_test() {
+    let (client, pool) = setup().await;
+
+    let (user_id, category_id) = before_test(&pool, "yearreviewuser")
+        .await
+        .expect("Failed to initialize test database");
+    common::login(&client, &pool, user_id).await;
+
+    let response = client
+        .post("/accounts")
+        .header(ContentType::JSON)
+        .body(json!({ "name": "Checking", "balance": "0", "account_type": "Bank" }).to_string())
+        .dispatch()
+        .await;
+    let checking: AccountOutDTO =
+        serde_json::from_str(&response.into_string().await.expect("Response has a body"))
+            .expect("Valid AccountOutDTO");
+
+    let year = Utc::now().year() - 1;
+    let at_noon = |year: i32, month: u32, day: u32| format!("{}T12:00:00Z", date(year, month, day));
+    for (title, amount, on, transaction_type) in [
+        ("Salary", "1000", at_noon(year, 3, 1), "Income"),
+        ("Landlord", "200", at_noon(year, 3, 2), "Expense"),
+        ("Corner shop 12", "50", at_noon(year, 7, 10), "Expense"),
+        ("Corner shop 98", "30", at_noon(year, 7, 20), "Expense"),
+        ("Salary", "800", at_noon(year - 1, 5, 1), "Income"),
+        ("Landlord", "100", at_noon(year - 1, 5, 2), "Expense"),
+    ] {
+        let response = client
+            .post("/transactions")
+            .header(ContentType::JSON)
+            .body(
+                json!({
+                    "title": title,
+                    "amount": amount,
+                    "date": on,
+                    "category_id": category_id,
+                    "transaction_type": transaction_type,
+                    "account_id": checking.id,
+                })
+                .to_string(),
+            )
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::Ok);
+    }
+
+    for (name, amount, start, end) in [
+        (
+            "July groceries",
+            "100",
+            at_noon(year, 7, 1),
+            at_noon(year, 7, 31),
+        ),
+        ("March", "150", at_noon(year, 3, 1), at_noon(year, 3, 31)),
+    ] {
+        let response = client
+            .post("/budgets")
+            .header(ContentType::JSON)
+            .body(
+                json!({
+                    "name": name,
+                    "amount": amount,
+                    "start_date": start,
+                    "end_date": end,
+                    "category_id": category_id,
+                })
+                .to_string(),
+            )
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::Ok);
+    }
+
+    let response = client
+        .get(format!("/reports/year_in_review?year={}", year))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let review: YearInReviewOutDTO =
+        serde_json::from_str(&response.into_string().await.expect("Response has a body"))
+            .expect("Valid YearInReviewOutDTO");
+    assert_eq!(review.to, date(year, 12, 31));
+    assert_eq!(review.income, money("1000"));
+    assert_eq!(review.expenses, money("280"));
+    assert_eq!(review.months.len(), 12);
+    assert_eq!(review.biggest_spending_month, Some(date(year, 3, 1)));
+    assert_eq!(review.top_categories[0].amount, money("280"));
+    let payees: Vec<_> = review
+        .top_payees
+        .iter()
+        .map(|p| (p.payee.as_str(), p.amount, p.transaction_count))
+        .collect();
+    assert_eq!(
+        payees,
+        vec![
+            ("landlord", money("200"), 1),
+            ("corner shop", money("80"), 2)
+        ]
+    );
+    assert_eq!(review.longest_no_spend_streak.from, Some(date(year, 7, 21)));
+    assert_eq!(review.longest_no_spend_streak.to, Some(date(year, 12, 31)));
+    assert_eq!(review.longest_no_spend_streak.days, 164);
+    assert_eq!((review.budgets.kept, review.budgets.total), (1, 2));
+    assert!(review.goals_completed.is_empty());
+    assert_eq!(review.year_over_year.income, money("800"));
+    assert_eq!(
+        review.year_over_year.income_change,
+        Some(Decimal::new(25, 0))
+    );
+    assert_eq!(review.year_over_year.net_change, Some(Decimal::new(286, 2)));
+
+    let response = client
+        .get(format!("/reports/year_in_review.html?year={}", year))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.content_type(), Some(ContentType::HTML));
+    let html = response.into_string().await.expect("Response has a body");
+    assert!(html.contains(&format!("<h1>{} in review</h1>", year)));
+    assert!(html.contains("Corner shop 98"));
+
+    let response = client
+        .post("/saving_goals")
+        .header(ContentType::JSON)
+        .body(
+            json!({
+                "title": "Bike",
+                "target_amount": "300",
+                "current_amount": "300",
+                "deadline": date(year + 2, 1, 1),
+            })
+            .to_string(),
+        )
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let response = client.get("/reports/year_in_review").dispatch().await;
+    let review: YearInReviewOutDTO =
+        serde_json::from_str(&response.into_string().await.expect("Response has a body"))
+            .expect("Valid YearInReviewOutDTO");
+    assert_eq!(review.year, year + 1);
+    assert_eq!(review.goals_completed.len(), 1);
+    assert_eq!(review.goals_completed[0].title, "Bike");
+
+    let response = client
+        .get(format!("/reports/year_in_review?year={}", year + 2))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::BadRequest);
+
+    cleanup(&pool, user_id).await;
+}
+
+#[rocket::async_test]
+async fn goal_completion_date_survives_later_edits_integration_test() {
+    let (client, pool) = setup().await;
+
+    let (user_id, _) = before_test(&pool, "yearreviewgoal")
+        .await
+        .expect("Failed to initialize test database");
+    common::login(&client, &pool, user_id).await;
+
+    let year = Utc::now().year() - 1;
+    let goal = json!({
+        "title": "Bike",
+        "target_amount": "300",
+        "current_amount": "300",
+        "deadline": date(year + 2, 1, 1),
+    });
+    let response = client
+        .post("/saving_goals")
+        .header(ContentType::JSON)
+        .body(goal.to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let created: SavingGoalOutDTO =
+        serde_json::from_str(&response.into_string().await.expect("Response has a body"))
+            .expect("Valid SavingGoalOutDTO");
+
+    // The goal was finished last year and only renamed this year.
+    sqlx::query("UPDATE saving_goals SET completed_at = $1 WHERE id = $2")
+        .bind(
+            date(year, 6, 15)
+                .and_hms_opt(12, 0, 0)
+                .expect("Valid time")
+                .and_utc(),
+        )
+        .bind(created.id)
+        .execute(&pool)
+        .await
+        .expect("Failed to backdate completion");
+    let mut renamed = goal.clone();
+    renamed["title"] = json!("Road bike");
+    let response = client
+        .patch(format!("/saving_goals/{}", created.id))
+        .header(ContentType::JSON)
+        .body(renamed.to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    let review = |year: i32| {
+        let client = &client;
+        async move {
+            let response = client
+                .get(format!("/reports/year_in_review?year={}", year))
+                .dispatch()
+                .await;
+            serde_json::from_str::<YearInReviewOutDTO>(
+                &response.into_string().await.expect("Response has a body"),
+            )
+            .expect("Valid YearInReviewOutDTO")
+        }
+    };
+    let last_year = review(year).await;
+    assert_eq!(last_year.goals_completed.len(), 1);
+    assert_eq!(last_year.goals_completed[0].title, "Road bike");
+    assert!(review(year + 1).await.goals_completed.is_empty());
+
+    cleanup(&pool, user_id).await;
+}
+
+#[rocket::async_test]
+async fn shared_spending_counts_in_year_in_review_integration_test() {
+    let (client, pool) = setup().await;
+
+    let (owner_id, category_id) = before_test(&pool, "yearreviewowner")
+        .await
+        .expect("Failed to initialize test database");
+    let (member_id, _) = before_test(&pool, "yearreviewmember")
+        .await
+        .expect("Failed to initialize test database");
+
+    let year = Utc::now().year() - 1;
+    let at_noon = |month: u32, day: u32| {
+        date(year, month, day)
+            .and_hms_opt(12, 0, 0)
+            .expect("Valid time")
+            .and_utc()
+    };
+    let account = create_account(
+        &pool,
+        owner_id,
+        &AccountInDTO {
+            name: "Joint".to_string(),
+            balance: Money::ZERO,
+            account_type: AccountType::Bank,
+            currency: None,
+        },
+    )
+    .await
+    .expect("Failed to create account");
+    create_transaction(
+        &pool,
+        owner_id,
+        &TransactionInDTO {
+            title: "Bakery".to_string(),
+            amount: money("40"),
+            date: at_noon(7, 10),
+            category_id,
+            transaction_type: TransactionType::Expense,
+            account_id: account.id,
+            currency: None,
+            exchange_rate: None,
+        },
+    )
+    .await
+    .expect("Failed to create transaction");
+    create_budget(
+        &pool,
+        member_id,
+        &BudgetInDTO {
+            name: "July".to_string(),
+            amount: money("30"),
+            start_date: at_noon(7, 1),
+            end_date: at_noon(7, 31),
+            category_id: None,
+        },
+    )
+    .await
+    .expect("Failed to create budget");
+
+    let household = create_household(
+        &pool,
+        owner_id,
+        &HouseholdInDTO {
+            name: "Review household".to_string(),
+        },
+    )
+    .await
+    .expect("Failed to create household");
+    add_household_member(&pool, household.id, member_id, HouseholdRole::Viewer)
+        .await
+        .expect("Failed to add member");
+    share_record(&pool, owner_id, household.id, Owned::Account, account.id)
+        .await
+        .expect("Failed to share account");
+
+    // The shared account's spending counts in the member's totals, payees and
+    // budgets alike.
+    common::login(&client, &pool, member_id).await;
+    let response = client
+        .get(format!("/reports/year_in_review?year={}", year))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let review: YearInReviewOutDTO =
+        serde_json::from_str(&response.into_string().await.expect("Response has a body"))
+            .expect("Valid YearInReviewOutDTO");
+    assert_eq!(review.expenses, money("40"));
+    assert_eq!(review.top_payees.len(), 1);
+    assert_eq!(review.top_payees[0].payee, "bakery");
+    assert_eq!(review.top_payees[0].amount, money("40"));
+    assert_eq!(review.budgets.budgets[0].spent, money("40"));
+    assert_eq!((review.budgets.kept, review.budgets.total), (0, 1));
+
+    delete_household(&pool, household.id)
+        .await
+        .expect("Failed to delete household");
+    cleanup(&pool, owner_id).await;
+    cleanup(&pool, member_id).await;
+}